@@ -0,0 +1,11 @@
+/// Configuration option for how to highlight the changes within a changed line when displaying diffs.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[non_exhaustive]
+pub enum DiffWordHighlightSetting {
+	/// Do not highlight the changes within a line.
+	None,
+	/// Highlight the words that changed within a line.
+	Word,
+	/// Highlight the characters that changed within a line.
+	Character,
+}
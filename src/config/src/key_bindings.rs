@@ -23,12 +23,20 @@ pub struct KeyBindings {
 	pub action_reword: Vec<String>,
 	/// Key bindings for the squash action.
 	pub action_squash: Vec<String>,
+	/// Key bindings for applying fixup and squash commits.
+	pub autosquash: Vec<String>,
 	/// Key bindings for negative confirmation.
 	pub confirm_no: Vec<String>,
 	/// Key bindings for positive confirmation.
 	pub confirm_yes: Vec<String>,
+	/// Key bindings for cycling the fixup message option.
+	pub cycle_fixup_option: Vec<String>,
+	/// Key bindings for dropping the commits that are already applied upstream.
+	pub drop_applied_upstream: Vec<String>,
 	/// Key bindings for editing.
 	pub edit: Vec<String>,
+	/// Key bindings for filtering the list.
+	pub filter: Vec<String>,
 	/// Key bindings for forcing a abort.
 	pub force_abort: Vec<String>,
 	/// Key bindings for forcing a rebase.
@@ -57,18 +65,42 @@ pub struct KeyBindings {
 	pub move_up: Vec<String>,
 	/// Key bindings for moving up a step.
 	pub move_up_step: Vec<String>,
+	/// Key bindings for moving to the next file of a diff.
+	pub next_file: Vec<String>,
+	/// Key bindings for moving to the next hunk of a diff.
+	pub next_hunk: Vec<String>,
 	/// Key bindings for opening the external editor.
 	pub open_in_external_editor: Vec<String>,
+	/// Key bindings for moving to the previous file of a diff.
+	pub previous_file: Vec<String>,
+	/// Key bindings for moving to the previous hunk of a diff.
+	pub previous_hunk: Vec<String>,
 	/// Key bindings for rebasing.
 	pub rebase: Vec<String>,
 	/// Key bindings for redoing a change.
 	pub redo: Vec<String>,
 	/// Key bindings for removing a line.
 	pub remove_line: Vec<String>,
+	/// Key bindings for starting a search.
+	pub search: Vec<String>,
+	/// Key bindings for moving to the next search match.
+	pub search_next: Vec<String>,
+	/// Key bindings for moving to the previous search match.
+	pub search_previous: Vec<String>,
+	/// Key bindings for showing the combined diff of a squash or fixup group.
+	pub show_combined_diff: Vec<String>,
 	/// Key bindings for showing a commit.
 	pub show_commit: Vec<String>,
 	/// Key bindings for showing a diff.
 	pub show_diff: Vec<String>,
+	/// Key bindings for showing the list of files of a diff.
+	pub show_file_list: Vec<String>,
+	/// Key bindings for collapsing or expanding a file of a diff.
+	pub toggle_fold: Vec<String>,
+	/// Key bindings for toggling the graph view.
+	pub toggle_graph: Vec<String>,
+	/// Key bindings for toggling a side by side diff.
+	pub toggle_side_by_side: Vec<String>,
 	/// Key bindings for toggling visual mode.
 	pub toggle_visual_mode: Vec<String>,
 	/// Key bindings for undoing a change.
@@ -101,9 +133,13 @@ impl KeyBindings {
 			action_pick: get_input(git_config, "interactive-rebase-tool.inputActionPick", "p")?,
 			action_reword: get_input(git_config, "interactive-rebase-tool.inputActionReword", "r")?,
 			action_squash: get_input(git_config, "interactive-rebase-tool.inputActionSquash", "s")?,
+			autosquash: get_input(git_config, "interactive-rebase-tool.inputAutosquash", "A")?,
 			confirm_no,
 			confirm_yes,
+			cycle_fixup_option: get_input(git_config, "interactive-rebase-tool.inputCycleFixupOption", "u")?,
+			drop_applied_upstream: get_input(git_config, "interactive-rebase-tool.inputDropAppliedUpstream", "D")?,
 			edit: get_input(git_config, "interactive-rebase-tool.inputEdit", "E")?,
+			filter: get_input(git_config, "interactive-rebase-tool.inputFilter", "F")?,
 			force_abort: get_input(git_config, "interactive-rebase-tool.inputForceAbort", "Q")?,
 			force_rebase: get_input(git_config, "interactive-rebase-tool.inputForceRebase", "W")?,
 			help: get_input(git_config, "interactive-rebase-tool.inputHelp", "?")?,
@@ -118,12 +154,24 @@ impl KeyBindings {
 			move_selection_up: get_input(git_config, "interactive-rebase-tool.inputMoveSelectionUp", "k")?,
 			move_up_step: get_input(git_config, "interactive-rebase-tool.inputMoveStepUp", "PageUp")?,
 			move_up: get_input(git_config, "interactive-rebase-tool.inputMoveUp", "Up")?,
+			next_file: get_input(git_config, "interactive-rebase-tool.inputNextFile", "]")?,
+			next_hunk: get_input(git_config, "interactive-rebase-tool.inputNextHunk", "}")?,
 			open_in_external_editor: get_input(git_config, "interactive-rebase-tool.inputOpenInExternalEditor", "!")?,
+			previous_file: get_input(git_config, "interactive-rebase-tool.inputPreviousFile", "[")?,
+			previous_hunk: get_input(git_config, "interactive-rebase-tool.inputPreviousHunk", "{")?,
 			rebase: get_input(git_config, "interactive-rebase-tool.inputRebase", "w")?,
 			redo: get_input(git_config, "interactive-rebase-tool.inputRedo", "control+y")?,
 			remove_line: get_input(git_config, "interactive-rebase-tool.removeLine", "delete")?,
+			search: get_input(git_config, "interactive-rebase-tool.inputSearch", "/")?,
+			search_next: get_input(git_config, "interactive-rebase-tool.inputSearchNext", "n")?,
+			search_previous: get_input(git_config, "interactive-rebase-tool.inputSearchPrevious", "N")?,
+			show_combined_diff: get_input(git_config, "interactive-rebase-tool.inputShowCombinedDiff", "s")?,
 			show_commit: get_input(git_config, "interactive-rebase-tool.inputShowCommit", "c")?,
 			show_diff: get_input(git_config, "interactive-rebase-tool.inputShowDiff", "d")?,
+			show_file_list: get_input(git_config, "interactive-rebase-tool.inputShowFileList", "f")?,
+			toggle_fold: get_input(git_config, "interactive-rebase-tool.inputToggleFold", "z")?,
+			toggle_graph: get_input(git_config, "interactive-rebase-tool.inputToggleGraph", "G")?,
+			toggle_side_by_side: get_input(git_config, "interactive-rebase-tool.inputToggleSideBySide", "S")?,
 			toggle_visual_mode: get_input(git_config, "interactive-rebase-tool.inputToggleVisualMode", "v")?,
 			undo: get_input(git_config, "interactive-rebase-tool.inputUndo", "control+z")?,
 		})
@@ -269,9 +317,17 @@ mod tests {
 	#[case::action_pick("inputActionPick", "p", |bindings: KeyBindings| bindings.action_pick)]
 	#[case::action_reword("inputActionReword", "r", |bindings: KeyBindings| bindings.action_reword)]
 	#[case::action_squash("inputActionSquash", "s", |bindings: KeyBindings| bindings.action_squash)]
+	#[case::autosquash("inputAutosquash", "A", |bindings: KeyBindings| bindings.autosquash)]
 	#[case::confirm_no("inputConfirmNo", "n", |bindings: KeyBindings| bindings.confirm_no)]
 	#[case::confirm_yes("inputConfirmYes", "y", |bindings: KeyBindings| bindings.confirm_yes)]
+	#[case::cycle_fixup_option("inputCycleFixupOption", "u", |bindings: KeyBindings| bindings.cycle_fixup_option)]
+	#[case::drop_applied_upstream(
+		"inputDropAppliedUpstream",
+		"D",
+		|bindings: KeyBindings| bindings.drop_applied_upstream
+	)]
 	#[case::edit("inputEdit", "E", |bindings: KeyBindings| bindings.edit)]
+	#[case::filter("inputFilter", "F", |bindings: KeyBindings| bindings.filter)]
 	#[case::force_abort("inputForceAbort", "Q", |bindings: KeyBindings| bindings.force_abort)]
 	#[case::force_rebase("inputForceRebase", "W", |bindings: KeyBindings| bindings.force_rebase)]
 	#[case::help("inputHelp", "?", |bindings: KeyBindings| bindings.help)]
@@ -291,11 +347,23 @@ mod tests {
 		"!",
 		|bindings: KeyBindings| bindings.open_in_external_editor)
 	]
+	#[case::next_file("inputNextFile", "]", |bindings: KeyBindings| bindings.next_file)]
+	#[case::next_hunk("inputNextHunk", "}", |bindings: KeyBindings| bindings.next_hunk)]
+	#[case::previous_file("inputPreviousFile", "[", |bindings: KeyBindings| bindings.previous_file)]
+	#[case::previous_hunk("inputPreviousHunk", "{", |bindings: KeyBindings| bindings.previous_hunk)]
 	#[case::rebase("inputRebase", "w", |bindings: KeyBindings| bindings.rebase)]
 	#[case::redo("inputRedo", "Controly", |bindings: KeyBindings| bindings.redo)]
 	#[case::remove_line("removeLine", "Delete", |bindings: KeyBindings| bindings.remove_line)]
+	#[case::search("inputSearch", "/", |bindings: KeyBindings| bindings.search)]
+	#[case::search_next("inputSearchNext", "n", |bindings: KeyBindings| bindings.search_next)]
+	#[case::search_previous("inputSearchPrevious", "N", |bindings: KeyBindings| bindings.search_previous)]
+	#[case::show_combined_diff("inputShowCombinedDiff", "s", |bindings: KeyBindings| bindings.show_combined_diff)]
 	#[case::show_commit("inputShowCommit", "c", |bindings: KeyBindings| bindings.show_commit)]
 	#[case::show_diff("inputShowDiff", "d", |bindings: KeyBindings| bindings.show_diff)]
+	#[case::show_file_list("inputShowFileList", "f", |bindings: KeyBindings| bindings.show_file_list)]
+	#[case::toggle_fold("inputToggleFold", "z", |bindings: KeyBindings| bindings.toggle_fold)]
+	#[case::toggle_graph("inputToggleGraph", "G", |bindings: KeyBindings| bindings.toggle_graph)]
+	#[case::toggle_side_by_side("inputToggleSideBySide", "S", |bindings: KeyBindings| bindings.toggle_side_by_side)]
 	#[case::toggle_visual_mode("inputToggleVisualMode", "v", |bindings: KeyBindings| bindings.toggle_visual_mode)]
 	#[case::undo("inputUndo", "Controlz", |bindings: KeyBindings| bindings.undo)]
 	pub(crate) fn test_binding<F: 'static>(#[case] config_name: &str, #[case] default: &str, #[case] access: F)
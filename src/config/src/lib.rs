@@ -97,6 +97,7 @@
 mod color;
 mod diff_ignore_whitespace_setting;
 mod diff_show_whitespace_setting;
+mod diff_word_highlight_setting;
 mod git_config;
 mod key_bindings;
 mod theme;
@@ -108,11 +109,19 @@ mod testutils;
 use anyhow::{Error, Result};
 use git::Repository;
 
-use self::utils::{get_bool, get_diff_ignore_whitespace, get_diff_show_whitespace, get_string, get_unsigned_integer};
+use self::utils::{
+	get_bool,
+	get_diff_ignore_whitespace,
+	get_diff_show_whitespace,
+	get_diff_word_highlight,
+	get_string,
+	get_unsigned_integer,
+};
 pub use self::{
 	color::Color,
 	diff_ignore_whitespace_setting::DiffIgnoreWhitespaceSetting,
 	diff_show_whitespace_setting::DiffShowWhitespaceSetting,
+	diff_word_highlight_setting::DiffWordHighlightSetting,
 	git_config::GitConfig,
 	key_bindings::KeyBindings,
 	theme::Theme,
@@ -131,8 +140,12 @@ pub struct Config {
 	pub diff_space_symbol: String,
 	/// The symbol used to replace tab characters.
 	pub diff_tab_symbol: String,
+	/// If to highlight the syntax of the lines in diffs.
+	pub diff_syntax_highlight: bool,
 	/// The display width of the tab character.
 	pub diff_tab_width: u32,
+	/// How to highlight the changes within changed lines in diffs.
+	pub diff_word_highlight: DiffWordHighlightSetting,
 	/// The maximum number of undo steps.
 	pub undo_limit: u32,
 	/// Configuration options loaded directly from Git.
@@ -157,8 +170,10 @@ impl Config {
 			diff_ignore_whitespace: get_diff_ignore_whitespace(git_config)?,
 			diff_show_whitespace: get_diff_show_whitespace(git_config)?,
 			diff_space_symbol: get_string(git_config, "interactive-rebase-tool.diffSpaceSymbol", "·")?,
+			diff_syntax_highlight: get_bool(git_config, "interactive-rebase-tool.diffSyntaxHighlight", true)?,
 			diff_tab_symbol: get_string(git_config, "interactive-rebase-tool.diffTabSymbol", "→")?,
 			diff_tab_width: get_unsigned_integer(git_config, "interactive-rebase-tool.diffTabWidth", 4)?,
+			diff_word_highlight: get_diff_word_highlight(git_config)?,
 			undo_limit: get_unsigned_integer(git_config, "interactive-rebase-tool.undoLimit", 5000)?,
 			git: GitConfig::new_with_config(git_config)?,
 			key_bindings: KeyBindings::new_with_config(git_config)?,
@@ -361,6 +376,18 @@ mod tests {
 		DiffShowWhitespaceSetting::Trailing,
 		|config: Config| config.diff_show_whitespace)
 	]
+	#[case::diff_syntax_highlight_default(
+		"diffSyntaxHighlight",
+		"",
+		true,
+		|config: Config| config.diff_syntax_highlight)
+	]
+	#[case::diff_syntax_highlight(
+		"diffSyntaxHighlight",
+		"false",
+		false,
+		|config: Config| config.diff_syntax_highlight)
+	]
 	#[case::diff_tab_width_default("diffTabWidth", "", 4, |config: Config| config.diff_tab_width)]
 	#[case::diff_tab_width("diffTabWidth", "42", 42, |config: Config| config.diff_tab_width)]
 	#[case::diff_tab_symbol_default("diffTabSymbol", "", String::from("→"), |config: Config| config.diff_tab_symbol)]
@@ -373,6 +400,60 @@ mod tests {
 		|config: Config| config.diff_space_symbol)
 	]
 	#[case::diff_space_symbol("diffSpaceSymbol", "-", String::from("-"), |config: Config| config.diff_space_symbol)]
+	#[case::diff_word_highlight_default(
+		"diffWordHighlight",
+		"",
+		DiffWordHighlightSetting::None,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_true(
+		"diffWordHighlight",
+		"true",
+		DiffWordHighlightSetting::Word,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_on(
+		"diffWordHighlight",
+		"on",
+		DiffWordHighlightSetting::Word,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_word(
+		"diffWordHighlight",
+		"word",
+		DiffWordHighlightSetting::Word,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_character(
+		"diffWordHighlight",
+		"character",
+		DiffWordHighlightSetting::Character,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_false(
+		"diffWordHighlight",
+		"false",
+		DiffWordHighlightSetting::None,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_off(
+		"diffWordHighlight",
+		"off",
+		DiffWordHighlightSetting::None,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_none(
+		"diffWordHighlight",
+		"none",
+		DiffWordHighlightSetting::None,
+		|config: Config| config.diff_word_highlight)
+	]
+	#[case::diff_word_highlight_mixed_case(
+		"diffWordHighlight",
+		"wOrD",
+		DiffWordHighlightSetting::Word,
+		|config: Config| config.diff_word_highlight)
+	]
 	#[case::undo_limit_default("undoLimit", "", 5000, |config: Config| config.undo_limit)]
 	#[case::undo_limit_default("undoLimit", "42", 42, |config: Config| config.undo_limit)]
 	pub(crate) fn theme_color<F: 'static, T: Debug + PartialEq>(
@@ -414,6 +495,11 @@ mod tests {
 		"\"interactive-rebase-tool.diffShowWhitespace\" is not valid: \"invalid\" does not match one of \"true\", \
 		 \"on\", \"both\", \"trailing\", \"leading\", \"false\", \"off\" or \"none\""
 	)]
+	#[case::diff_syntax_highlight(
+		"diffSyntaxHighlight",
+		"invalid",
+		"\"interactive-rebase-tool.diffSyntaxHighlight\" is not valid: failed to parse \'invalid\' as a boolean value"
+	)]
 	#[case::diff_tab_width_non_integer(
 		"diffTabWidth",
 		"invalid",
@@ -435,6 +521,12 @@ mod tests {
 		invalid_utf(),
 		"\"interactive-rebase-tool.diffSpaceSymbol\" is not valid: configuration value is not valid utf8"
 	)]
+	#[case::diff_word_highlight(
+		"diffWordHighlight",
+		"invalid",
+		"\"interactive-rebase-tool.diffWordHighlight\" is not valid: \"invalid\" does not match one of \"true\", \
+		 \"on\", \"word\", \"character\", \"false\", \"off\" or \"none\""
+	)]
 	#[case::undo_limit_non_integer(
 		"undoLimit",
 		"invalid",
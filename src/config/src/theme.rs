@@ -33,16 +33,32 @@ pub struct Theme {
 	pub color_action_reset: Color,
 	/// The color for the merge action.
 	pub color_action_merge: Color,
+	/// The color for the update-ref action.
+	pub color_action_update_ref: Color,
+	/// The color for the revert action.
+	pub color_action_revert: Color,
 	/// The color for the background.
 	pub color_background: Color,
 	/// The color for added lines in a diff.
 	pub color_diff_add: Color,
+	/// The color for the highlighted changes within added lines in a diff.
+	pub color_diff_add_highlight: Color,
 	/// The color for changed lines in a diff.
 	pub color_diff_change: Color,
 	/// The color for context lines in a diff.
 	pub color_diff_context: Color,
 	/// The color for removed lines in a diff.
 	pub color_diff_remove: Color,
+	/// The color for the highlighted changes within removed lines in a diff.
+	pub color_diff_remove_highlight: Color,
+	/// The color for comments in the syntax highlighting of a diff.
+	pub color_diff_syntax_comment: Color,
+	/// The color for keywords in the syntax highlighting of a diff.
+	pub color_diff_syntax_keyword: Color,
+	/// The color for numbers in the syntax highlighting of a diff.
+	pub color_diff_syntax_number: Color,
+	/// The color for strings in the syntax highlighting of a diff.
+	pub color_diff_syntax_string: Color,
 	/// The color for whitespace characters in a diff.
 	pub color_diff_whitespace: Color,
 	/// The color for the standard text.
@@ -51,6 +67,8 @@ pub struct Theme {
 	pub color_indicator: Color,
 	/// The background color for selected lines.
 	pub color_selected_background: Color,
+	/// The color for warnings about problems with the rebase file.
+	pub color_warning: Color,
 }
 
 impl Theme {
@@ -80,8 +98,19 @@ impl Theme {
 			color_action_label: get_color(git_config, "interactive-rebase-tool.labelColor", Color::DarkYellow)?,
 			color_action_reset: get_color(git_config, "interactive-rebase-tool.resetColor", Color::DarkYellow)?,
 			color_action_merge: get_color(git_config, "interactive-rebase-tool.mergeColor", Color::DarkYellow)?,
+			color_action_update_ref: get_color(
+				git_config,
+				"interactive-rebase-tool.updateRefColor",
+				Color::DarkMagenta,
+			)?,
+			color_action_revert: get_color(git_config, "interactive-rebase-tool.revertColor", Color::DarkRed)?,
 			color_background: get_color(git_config, "interactive-rebase-tool.backgroundColor", Color::Default)?,
 			color_diff_add: get_color(git_config, "interactive-rebase-tool.diffAddColor", Color::LightGreen)?,
+			color_diff_add_highlight: get_color(
+				git_config,
+				"interactive-rebase-tool.diffAddHighlightColor",
+				Color::LightGreen,
+			)?,
 			color_diff_change: get_color(
 				git_config,
 				"interactive-rebase-tool.diffChangeColor",
@@ -93,6 +122,31 @@ impl Theme {
 				Color::LightWhite,
 			)?,
 			color_diff_remove: get_color(git_config, "interactive-rebase-tool.diffRemoveColor", Color::LightRed)?,
+			color_diff_remove_highlight: get_color(
+				git_config,
+				"interactive-rebase-tool.diffRemoveHighlightColor",
+				Color::LightRed,
+			)?,
+			color_diff_syntax_comment: get_color(
+				git_config,
+				"interactive-rebase-tool.diffSyntaxCommentColor",
+				Color::LightBlack,
+			)?,
+			color_diff_syntax_keyword: get_color(
+				git_config,
+				"interactive-rebase-tool.diffSyntaxKeywordColor",
+				Color::LightMagenta,
+			)?,
+			color_diff_syntax_number: get_color(
+				git_config,
+				"interactive-rebase-tool.diffSyntaxNumberColor",
+				Color::LightCyan,
+			)?,
+			color_diff_syntax_string: get_color(
+				git_config,
+				"interactive-rebase-tool.diffSyntaxStringColor",
+				Color::LightYellow,
+			)?,
 			color_diff_whitespace: get_color(git_config, "interactive-rebase-tool.diffWhitespace", Color::LightBlack)?,
 			color_foreground: get_color(git_config, "interactive-rebase-tool.foregroundColor", Color::Default)?,
 			color_indicator: get_color(git_config, "interactive-rebase-tool.indicatorColor", Color::LightCyan)?,
@@ -101,6 +155,7 @@ impl Theme {
 				"interactive-rebase-tool.selectedBackgroundColor",
 				Color::Index(237),
 			)?,
+			color_warning: get_color(git_config, "interactive-rebase-tool.warningColor", Color::LightRed)?,
 		})
 	}
 }
@@ -176,11 +231,43 @@ mod tests {
 	#[case::color_action_label("labelColor", Color::DarkYellow, |theme: Theme| theme.color_action_label)]
 	#[case::color_action_reset("resetColor", Color::DarkYellow, |theme: Theme| theme.color_action_reset)]
 	#[case::color_action_merge("mergeColor", Color::DarkYellow, |theme: Theme| theme.color_action_merge)]
+	#[case::color_action_update_ref("updateRefColor", Color::DarkMagenta, |theme: Theme| theme.color_action_update_ref)]
+	#[case::color_action_revert("revertColor", Color::DarkRed, |theme: Theme| theme.color_action_revert)]
 	#[case::color_background("backgroundColor", Color::Default, |theme: Theme| theme.color_background)]
 	#[case::color_diff_add("diffAddColor", Color::LightGreen, |theme: Theme| theme.color_diff_add)]
+	#[case::color_diff_add_highlight(
+		"diffAddHighlightColor",
+		Color::LightGreen,
+		|theme: Theme| theme.color_diff_add_highlight)
+	]
 	#[case::color_diff_change("diffChangeColor", Color::LightYellow, |theme: Theme| theme.color_diff_change)]
 	#[case::color_diff_context("diffContextColor", Color::LightWhite, |theme: Theme| theme.color_diff_context)]
 	#[case::color_diff_remove("diffRemoveColor", Color::LightRed, |theme: Theme| theme.color_diff_remove)]
+	#[case::color_diff_remove_highlight(
+		"diffRemoveHighlightColor",
+		Color::LightRed,
+		|theme: Theme| theme.color_diff_remove_highlight)
+	]
+	#[case::color_diff_syntax_comment(
+		"diffSyntaxCommentColor",
+		Color::LightBlack,
+		|theme: Theme| theme.color_diff_syntax_comment)
+	]
+	#[case::color_diff_syntax_keyword(
+		"diffSyntaxKeywordColor",
+		Color::LightMagenta,
+		|theme: Theme| theme.color_diff_syntax_keyword)
+	]
+	#[case::color_diff_syntax_number(
+		"diffSyntaxNumberColor",
+		Color::LightCyan,
+		|theme: Theme| theme.color_diff_syntax_number)
+	]
+	#[case::color_diff_syntax_string(
+		"diffSyntaxStringColor",
+		Color::LightYellow,
+		|theme: Theme| theme.color_diff_syntax_string)
+	]
 	#[case::color_diff_whitespace("diffWhitespace", Color::LightBlack, |theme: Theme| theme.color_diff_whitespace)]
 	#[case::color_foreground("foregroundColor", Color::Default, |theme: Theme| theme.color_foreground)]
 	#[case::color_indicator("indicatorColor", Color::LightCyan, |theme: Theme| theme.color_indicator)]
@@ -189,6 +276,7 @@ mod tests {
 		Color::Index(237),
 		|theme: Theme| theme.color_selected_background)
 	]
+	#[case::color_warning("warningColor", Color::LightRed, |theme: Theme| theme.color_warning)]
 	pub(crate) fn theme_color<F: 'static>(#[case] config_name: &str, #[case] default: Color, #[case] access: F)
 	where F: Fn(Theme) -> Color {
 		let default_theme = Theme::new();
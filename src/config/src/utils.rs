@@ -6,6 +6,7 @@ use git::{Config, ErrorCode};
 use super::{
 	diff_ignore_whitespace_setting::DiffIgnoreWhitespaceSetting,
 	diff_show_whitespace_setting::DiffShowWhitespaceSetting,
+	diff_word_highlight_setting::DiffWordHighlightSetting,
 	Color,
 };
 
@@ -147,6 +148,24 @@ pub(super) fn get_diff_show_whitespace(git_config: Option<&Config>) -> Result<Di
 	}
 }
 
+pub(super) fn get_diff_word_highlight(git_config: Option<&Config>) -> Result<DiffWordHighlightSetting> {
+	let diff_word_highlight = get_string(git_config, "interactive-rebase-tool.diffWordHighlight", "none")?;
+
+	match diff_word_highlight.to_lowercase().as_str() {
+		"true" | "on" | "word" => Ok(DiffWordHighlightSetting::Word),
+		"character" => Ok(DiffWordHighlightSetting::Character),
+		"false" | "off" | "none" => Ok(DiffWordHighlightSetting::None),
+		_ => {
+			Err(anyhow!(
+				"\"{}\" does not match one of \"true\", \"on\", \"word\", \"character\", \"false\", \"off\" or \
+				 \"none\"",
+				diff_word_highlight
+			)
+			.context("\"interactive-rebase-tool.diffWordHighlight\" is not valid"))
+		},
+	}
+}
+
 pub(super) fn get_diff_ignore_whitespace(git_config: Option<&Config>) -> Result<DiffIgnoreWhitespaceSetting> {
 	let diff_ignore_whitespace = get_string(git_config, "interactive-rebase-tool.diffIgnoreWhitespace", "none")?;
 
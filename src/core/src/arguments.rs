@@ -12,10 +12,38 @@ pub(crate) enum Mode {
 	License,
 }
 
+/// The options for starting a rebase, rather than editing the todo file of a rebase started by Git.
+#[derive(Debug, PartialEq)]
+pub(crate) struct RebaseOptions {
+	upstream: String,
+	onto: Option<String>,
+	autosquash: bool,
+	rebase_merges: bool,
+}
+
+impl RebaseOptions {
+	pub(crate) fn upstream(&self) -> &str {
+		self.upstream.as_str()
+	}
+
+	pub(crate) fn onto(&self) -> Option<&str> {
+		self.onto.as_deref()
+	}
+
+	pub(crate) const fn autosquash(&self) -> bool {
+		self.autosquash
+	}
+
+	pub(crate) const fn rebase_merges(&self) -> bool {
+		self.rebase_merges
+	}
+}
+
 #[derive(Debug)]
 pub(crate) struct Args {
 	mode: Mode,
 	todo_file_path: Option<String>,
+	rebase_options: Option<RebaseOptions>,
 }
 
 impl Args {
@@ -26,6 +54,14 @@ impl Args {
 	pub(crate) const fn todo_file_path(&self) -> &Option<String> {
 		&self.todo_file_path
 	}
+
+	pub(crate) const fn rebase_options(&self) -> &Option<RebaseOptions> {
+		&self.rebase_options
+	}
+}
+
+fn state_error(message: &str) -> Exit {
+	Exit::new(ExitStatus::StateError, message)
 }
 
 impl TryFrom<Vec<OsString>> for Args {
@@ -47,10 +83,108 @@ impl TryFrom<Vec<OsString>> for Args {
 			Mode::Normal
 		};
 
+		let upstream: Option<String> = pargs
+			.opt_value_from_str("--rebase")
+			.map_err(|err| state_error(err.to_string().as_str()))?;
+		let onto: Option<String> = pargs
+			.opt_value_from_str("--onto")
+			.map_err(|err| state_error(err.to_string().as_str()))?;
+		let autosquash = pargs.contains("--autosquash");
+		let rebase_merges = pargs.contains("--rebase-merges");
+
 		let todo_file_path = pargs
 			.opt_free_from_str()
-			.map_err(|err| Exit::new(ExitStatus::StateError, err.to_string().as_str()))?;
+			.map_err(|err| state_error(err.to_string().as_str()))?;
+
+		let rebase_options = if let Some(upstream) = upstream {
+			if todo_file_path.is_some() {
+				return Err(state_error("A todo file path cannot be used with --rebase"));
+			}
+			Some(RebaseOptions {
+				upstream,
+				onto,
+				autosquash,
+				rebase_merges,
+			})
+		}
+		else if onto.is_some() || autosquash || rebase_merges {
+			return Err(state_error(
+				"The --onto, --autosquash and --rebase-merges flags can only be used with --rebase",
+			));
+		}
+		else {
+			None
+		};
+
+		Ok(Self {
+			mode,
+			todo_file_path,
+			rebase_options,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Args, Exit> {
+		Args::try_from(args.iter().map(OsString::from).collect::<Vec<OsString>>())
+	}
+
+	#[test]
+	fn todo_file_path() {
+		let args = parse(&["todo"]).unwrap();
+		assert_eq!(args.todo_file_path(), &Some(String::from("todo")));
+		assert!(args.rebase_options().is_none());
+	}
+
+	#[test]
+	fn rebase() {
+		let args = parse(&["--rebase", "main"]).unwrap();
+		assert!(args.todo_file_path().is_none());
+		assert_eq!(
+			args.rebase_options(),
+			&Some(RebaseOptions {
+				upstream: String::from("main"),
+				onto: None,
+				autosquash: false,
+				rebase_merges: false,
+			})
+		);
+	}
+
+	#[test]
+	fn rebase_with_flags() {
+		let args = parse(&["--autosquash", "--rebase", "main", "--onto", "next", "--rebase-merges"]).unwrap();
+		let rebase_options = args.rebase_options().as_ref().unwrap();
+		assert_eq!(rebase_options.upstream(), "main");
+		assert_eq!(rebase_options.onto(), Some("next"));
+		assert!(rebase_options.autosquash());
+		assert!(rebase_options.rebase_merges());
+	}
+
+	#[test]
+	fn rebase_missing_upstream() {
+		assert_eq!(parse(&["--rebase"]).unwrap_err().get_status(), &ExitStatus::StateError);
+	}
+
+	#[test]
+	fn rebase_with_todo_file_path() {
+		assert_eq!(
+			parse(&["--rebase", "main", "todo"]).unwrap_err(),
+			Exit::new(ExitStatus::StateError, "A todo file path cannot be used with --rebase")
+		);
+	}
 
-		Ok(Self { mode, todo_file_path })
+	#[test]
+	fn rebase_flag_without_rebase() {
+		assert_eq!(
+			parse(&["--autosquash", "todo"]).unwrap_err(),
+			Exit::new(
+				ExitStatus::StateError,
+				"The --onto, --autosquash and --rebase-merges flags can only be used with --rebase"
+			)
+		);
 	}
 }
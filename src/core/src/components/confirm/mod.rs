@@ -13,23 +13,36 @@ lazy_static! {
 }
 
 pub(crate) struct Confirm {
+	prompt: String,
 	view_data: ViewData,
 }
 
 impl Confirm {
 	pub(crate) fn new(prompt: &str, confirm_yes: &[String], confirm_no: &[String]) -> Self {
+		let prompt = format!("{} ({}/{})? ", prompt, confirm_yes.join(","), confirm_no.join(","));
 		let view_data = ViewData::new(|updater| {
-			capture!(confirm_yes, confirm_no);
+			capture!(prompt);
 			updater.set_show_title(true);
 			updater.set_retain_scroll_position(false);
-			updater.push_line(ViewLine::from(format!(
-				"{} ({}/{})? ",
-				prompt,
-				confirm_yes.join(","),
-				confirm_no.join(",")
-			)));
+			updater.push_line(ViewLine::from(prompt.as_str()));
+		});
+		Self { prompt, view_data }
+	}
+
+	/// Set the lines that are shown before the prompt, separated from the prompt by an empty line.
+	pub(crate) fn set_details(&mut self, details: Vec<ViewLine>) {
+		let prompt = self.prompt.as_str();
+		self.view_data.update_view_data(|updater| {
+			updater.clear();
+			let has_details = !details.is_empty();
+			for line in details {
+				updater.push_line(line);
+			}
+			if has_details {
+				updater.push_line(ViewLine::new_empty_line());
+			}
+			updater.push_line(ViewLine::from(prompt));
 		});
-		Self { view_data }
 	}
 
 	pub(crate) fn get_view_data(&mut self) -> &ViewData {
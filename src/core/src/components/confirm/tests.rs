@@ -21,6 +21,39 @@ fn render() {
 	);
 }
 
+#[test]
+fn render_with_details() {
+	let mut module = Confirm::new("Prompt message", &[String::from("y")], &[String::from("n")]);
+	module.set_details(vec![ViewLine::from("Detail 1"), ViewLine::from("Detail 2")]);
+	assert_rendered_output!(
+		Options AssertRenderOptions {
+			ignore_trailing_whitespace: false
+		},
+		module.get_view_data(),
+		"{TITLE}",
+		"{BODY}",
+		"{Normal}Detail 1",
+		"{Normal}Detail 2",
+		"",
+		"{Normal}Prompt message (y/n)? "
+	);
+}
+
+#[test]
+fn render_with_empty_details() {
+	let mut module = Confirm::new("Prompt message", &[String::from("y")], &[String::from("n")]);
+	module.set_details(vec![]);
+	assert_rendered_output!(
+		Options AssertRenderOptions {
+			ignore_trailing_whitespace: false
+		},
+		module.get_view_data(),
+		"{TITLE}",
+		"{BODY}",
+		"{Normal}Prompt message (y/n)? "
+	);
+}
+
 #[test]
 fn handle_event_yes_uppercase() {
 	with_event_handler(&[Event::from('Y')], |context| {
@@ -1,6 +1,7 @@
 #[cfg(test)]
 mod tests;
 
+use anyhow::Result;
 use display::DisplayColor;
 use input::{Event, EventHandler, InputOptions, KeyCode, KeyEvent, KeyModifiers};
 use lazy_static::lazy_static;
@@ -15,8 +16,10 @@ pub(crate) struct Edit {
 	content: String,
 	cursor_position: usize,
 	description: Option<String>,
+	error: Option<String>,
 	finished: bool,
 	label: Option<String>,
+	validator: Option<fn(&str) -> Result<()>>,
 	view_data: ViewData,
 }
 
@@ -29,13 +32,46 @@ impl Edit {
 			content: String::from(""),
 			cursor_position: 0,
 			description: None,
+			error: None,
 			finished: false,
 			label: None,
+			validator: None,
 			view_data,
 		}
 	}
 
 	pub(crate) fn get_view_data(&mut self) -> &ViewData {
+		let segments = self.get_line_segments();
+		let pointer = self.cursor_position;
+		let description = self.description.as_ref();
+		let error = self.error.as_ref();
+		self.view_data.update_view_data(|updater| {
+			updater.clear();
+			if let Some(desc) = description {
+				updater.push_leading_line(ViewLine::from(vec![LineSegment::new_with_color(
+					desc.as_str(),
+					DisplayColor::IndicatorColor,
+				)]));
+				updater.push_leading_line(ViewLine::new_empty_line());
+			}
+			updater.push_line(ViewLine::from(segments));
+			if let Some(err) = error {
+				updater.push_trailing_line(ViewLine::new_pinned(vec![LineSegment::new_with_color(
+					err.as_str(),
+					DisplayColor::IndicatorColor,
+				)]));
+			}
+			updater.push_trailing_line(ViewLine::new_pinned(vec![LineSegment::new_with_color(
+				"Enter to finish",
+				DisplayColor::IndicatorColor,
+			)]));
+			updater.ensure_column_visible(pointer);
+			updater.ensure_line_visible(0);
+		});
+		&self.view_data
+	}
+
+	pub(crate) fn get_line_segments(&self) -> Vec<LineSegment> {
 		let line = self.content.as_str();
 		let pointer = self.cursor_position;
 
@@ -69,29 +105,14 @@ impl Edit {
 		if !end.is_empty() {
 			segments.push(LineSegment::new(end.as_str()));
 		}
-		let description = self.description.as_ref();
-		self.view_data.update_view_data(|updater| {
-			updater.clear();
-			if let Some(desc) = description {
-				updater.push_leading_line(ViewLine::from(vec![LineSegment::new_with_color(
-					desc.as_str(),
-					DisplayColor::IndicatorColor,
-				)]));
-				updater.push_leading_line(ViewLine::new_empty_line());
-			}
-			updater.push_line(ViewLine::from(segments));
-			updater.push_trailing_line(ViewLine::new_pinned(vec![LineSegment::new_with_color(
-				"Enter to finish",
-				DisplayColor::IndicatorColor,
-			)]));
-			updater.ensure_column_visible(pointer);
-			updater.ensure_line_visible(0);
-		});
-		&self.view_data
+		segments
 	}
 
 	pub(crate) fn handle_event(&mut self, event_handler: &EventHandler) -> Event {
 		let event = event_handler.read_event(&INPUT_OPTIONS, |event, _| event);
+		if let Event::Key(_) = event {
+			self.error = None;
+		}
 
 		match event {
 			Event::Key(KeyEvent {
@@ -152,7 +173,15 @@ impl Edit {
 			Event::Key(KeyEvent {
 				code: KeyCode::Enter,
 				modifiers: KeyModifiers::NONE,
-			}) => self.finished = true,
+			}) => {
+				match self
+					.validator
+					.map_or(Ok(()), |validator| validator(self.content.as_str()))
+				{
+					Ok(_) => self.finished = true,
+					Err(err) => self.error = Some(format!("{:#}", err)),
+				}
+			},
 			Event::Key(KeyEvent {
 				code: KeyCode::Char(c),
 				modifiers: KeyModifiers::NONE | KeyModifiers::SHIFT,
@@ -180,6 +209,10 @@ impl Edit {
 		self.label = Some(String::from(label));
 	}
 
+	pub(crate) fn set_validator(&mut self, validator: fn(&str) -> Result<()>) {
+		self.validator = Some(validator);
+	}
+
 	pub(crate) fn set_content(&mut self, content: &str) {
 		self.content = String::from(content);
 		self.cursor_position = UnicodeSegmentation::graphemes(content, true).count();
@@ -188,7 +221,9 @@ impl Edit {
 	pub(crate) fn clear(&mut self) {
 		self.content.clear();
 		self.cursor_position = 0;
+		self.error = None;
 		self.finished = false;
+		self.validator = None;
 	}
 
 	pub(crate) const fn is_finished(&self) -> bool {
@@ -198,4 +233,8 @@ impl Edit {
 	pub(crate) fn get_content(&self) -> String {
 		self.content.clone()
 	}
+
+	pub(crate) fn get_error(&self) -> Option<&str> {
+		self.error.as_deref()
+	}
 }
@@ -1,3 +1,4 @@
+use anyhow::anyhow;
 use input::testutil::with_event_handler;
 use view::assert_rendered_output;
 
@@ -643,3 +644,54 @@ fn clear_content() {
 	assert_eq!(module.cursor_position, 0);
 	assert_eq!(module.get_content(), "");
 }
+
+#[test]
+fn validator_valid() {
+	with_event_handler(&[Event::from(KeyCode::Enter)], |context| {
+		let mut module = Edit::new();
+		module.set_content("abcd");
+		module.set_validator(|_| Ok(()));
+		let _ = module.handle_event(&context.event_handler);
+		assert!(module.is_finished());
+	});
+}
+
+#[test]
+fn validator_invalid() {
+	with_event_handler(&[Event::from(KeyCode::Enter)], |context| {
+		let mut module = Edit::new();
+		module.set_content("abcd");
+		module.set_validator(|_| Err(anyhow!("Invalid content")));
+		let _ = module.handle_event(&context.event_handler);
+		assert!(!module.is_finished());
+		let view_data = module.get_view_data();
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}",
+			"{BODY}",
+			"{Normal}abcd{Normal,Underline}",
+			"{TRAILING}",
+			"{IndicatorColor}Invalid content",
+			"{IndicatorColor}Enter to finish"
+		);
+	});
+}
+
+#[test]
+fn validator_error_cleared_on_input() {
+	with_event_handler(&[Event::from(KeyCode::Enter), Event::from('a')], |context| {
+		let mut module = Edit::new();
+		module.set_validator(|_| Err(anyhow!("Invalid content")));
+		let _ = module.handle_event(&context.event_handler);
+		let _ = module.handle_event(&context.event_handler);
+		assert!(module.error.is_none());
+	});
+}
+
+#[test]
+fn clear_validator() {
+	let mut module = Edit::new();
+	module.set_validator(|_| Err(anyhow!("Invalid content")));
+	module.clear();
+	assert!(module.validator.is_none());
+}
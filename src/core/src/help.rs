@@ -6,12 +6,19 @@ Full feature terminal based sequence editor for git interactive rebase.
 
 USAGE:
   interactive-rebase-tool [FLAGS] [REBASE-TODO-FILE]
+  interactive-rebase-tool [FLAGS] --rebase <UPSTREAM> [REBASE-OPTIONS]
 
 FLAGS:
   -v, --version       Prints versioning information
   -h, --help          Prints help information
   --license           Prints Open Source Software licensing
 
+REBASE-OPTIONS:
+  --rebase <UPSTREAM> Start a rebase of the commits that are not in upstream
+  --onto <NEWBASE>    Rebase the commits onto newbase, instead of upstream
+  --autosquash        Move fixup!, squash! and amend! commits below their targets
+  --rebase-merges     Recreate merge commits instead of flattening the history
+
 ARGS:
   <REBASE-TODO-FILE>  The path to the Git rebase todo file
 "#;
@@ -1,25 +1,105 @@
+use display::DisplayColor;
 use input::EventHandler;
 use todo_file::TodoFile;
-use view::{RenderContext, ViewData, ViewSender};
+use view::{LineSegment, RenderContext, ViewData, ViewLine, ViewSender};
 
 use crate::{
 	components::confirm::{Confirm, Confirmed},
 	module::{ExitStatus, Module, ProcessResult, State},
 };
 
+// the commits that the rebase rewrites, that either others may have based work on, or that lose their signature
+fn get_rewritten_commit_details(rebase_todo: &TodoFile) -> Vec<ViewLine> {
+	let mut details = vec![];
+	for (index, line) in rebase_todo.iter().enumerate() {
+		if !rebase_todo.is_rewritten(index) {
+			continue;
+		}
+		let mut badges = vec![];
+		if rebase_todo.is_published(line) {
+			badges.push("published");
+		}
+		if rebase_todo.is_signed(line) {
+			badges.push("signed");
+		}
+		if badges.is_empty() {
+			continue;
+		}
+		if details.is_empty() {
+			details.push(ViewLine::from(LineSegment::new_with_color(
+				"The rebase rewrites the following published or signed commits:",
+				DisplayColor::WarningColor,
+			)));
+		}
+		details.push(ViewLine::from(vec![
+			LineSegment::new_with_color(
+				format!(" Line {}: ", index + 1).as_str(),
+				DisplayColor::IndicatorColor,
+			),
+			LineSegment::new(format!("{} {}", line.get_hash(), line.get_content()).as_str()),
+			LineSegment::new_with_color(format!(" [{}]", badges.join(", ")).as_str(), DisplayColor::WarningColor),
+		]));
+	}
+	details
+}
+
 pub(crate) struct ConfirmRebase {
 	dialog: Confirm,
+	has_rewritten_commits: bool,
+	is_confirming_rewrite: bool,
+	rewrite_dialog: Confirm,
 }
 
 impl Module for ConfirmRebase {
+	fn activate(&mut self, rebase_todo: &TodoFile, _: State) -> ProcessResult {
+		let diagnostics = rebase_todo.lint();
+		let mut details = vec![];
+		if !diagnostics.is_empty() {
+			details.push(ViewLine::from(LineSegment::new_with_color(
+				"The rebase todo file has the following problems:",
+				DisplayColor::WarningColor,
+			)));
+			for diagnostic in diagnostics {
+				details.push(ViewLine::from(vec![
+					LineSegment::new_with_color(
+						format!(" Line {}: ", diagnostic.get_line_index() + 1).as_str(),
+						DisplayColor::IndicatorColor,
+					),
+					LineSegment::new(diagnostic.to_string().as_str()),
+				]));
+			}
+		}
+		self.dialog.set_details(details);
+
+		let rewritten_commit_details = get_rewritten_commit_details(rebase_todo);
+		self.has_rewritten_commits = !rewritten_commit_details.is_empty();
+		self.is_confirming_rewrite = false;
+		self.rewrite_dialog.set_details(rewritten_commit_details);
+		ProcessResult::new()
+	}
+
 	fn build_view_data(&mut self, _: &RenderContext, _: &TodoFile) -> &ViewData {
-		self.dialog.get_view_data()
+		if self.is_confirming_rewrite {
+			self.rewrite_dialog.get_view_data()
+		}
+		else {
+			self.dialog.get_view_data()
+		}
 	}
 
 	fn handle_events(&mut self, event_handler: &EventHandler, _: &ViewSender, _: &mut TodoFile) -> ProcessResult {
-		let (confirmed, event) = self.dialog.handle_event(event_handler);
+		let (confirmed, event) = if self.is_confirming_rewrite {
+			self.rewrite_dialog.handle_event(event_handler)
+		}
+		else {
+			self.dialog.handle_event(event_handler)
+		};
 		let mut result = ProcessResult::from(event);
 		match confirmed {
+			// rewriting published or signed commits requires a second confirmation
+			Confirmed::Yes if self.has_rewritten_commits && !self.is_confirming_rewrite => {
+				self.is_confirming_rewrite = true;
+			},
 			Confirmed::Yes => {
 				result = result.exit_status(ExitStatus::Good);
 			},
@@ -36,6 +116,13 @@ impl ConfirmRebase {
 	pub(crate) fn new(confirm_yes: &[String], confirm_no: &[String]) -> Self {
 		Self {
 			dialog: Confirm::new("Are you sure you want to rebase", confirm_yes, confirm_no),
+			has_rewritten_commits: false,
+			is_confirming_rewrite: false,
+			rewrite_dialog: Confirm::new(
+				"Are you sure you want to rewrite published or signed commits",
+				confirm_yes,
+				confirm_no,
+			),
 		}
 	}
 }
@@ -69,6 +156,56 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn activate_with_problems() {
+		module_test(
+			&[
+				"fixup aaa comment",
+				"pick bbb comment",
+				"reset onto",
+				"pick aaa comment",
+			],
+			&[],
+			|test_context| {
+				let mut module = create_confirm_rebase();
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+					Options AssertRenderOptions {
+						ignore_trailing_whitespace: false
+					},
+					view_data,
+					"{TITLE}",
+					"{BODY}",
+					"{WarningColor}The rebase todo file has the following problems:",
+					"{IndicatorColor} Line 1: {Normal}Cannot fixup without a previous commit",
+					"{IndicatorColor} Line 3: {Normal}Reset to undefined label: onto",
+					"{IndicatorColor} Line 4: {Normal}Commit aaa appears more than once",
+					"",
+					"{Normal}Are you sure you want to rebase (y/n)? "
+				);
+			},
+		);
+	}
+
+	#[test]
+	fn activate_without_problems() {
+		module_test(&["pick aaa comment"], &[], |test_context| {
+			let mut module = create_confirm_rebase();
+			assert_process_result!(test_context.activate(&mut module, State::List));
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				Options AssertRenderOptions {
+					ignore_trailing_whitespace: false
+				},
+				view_data,
+				"{TITLE}",
+				"{BODY}",
+				"{Normal}Are you sure you want to rebase (y/n)? "
+			);
+		});
+	}
+
 	#[test]
 	fn handle_event_yes() {
 		module_test(
@@ -102,6 +239,145 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn handle_event_yes_with_rewritten_commits() {
+		module_test(
+			&["pick aaa comment 1", "pick bbb comment 2", "pick ccc comment 3"],
+			&[Event::from(MetaEvent::Yes)],
+			|mut test_context| {
+				test_context.rebase_todo_file.set_published(vec![String::from("aaa")]);
+				test_context
+					.rebase_todo_file
+					.set_signed(vec![String::from("aaa"), String::from("ccc")]);
+				let mut module = create_confirm_rebase();
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				assert_process_result!(
+					test_context.handle_event(&mut module),
+					event = Event::from(MetaEvent::Yes)
+				);
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+					Options AssertRenderOptions {
+						ignore_trailing_whitespace: false
+					},
+					view_data,
+					"{TITLE}",
+					"{BODY}",
+					"{WarningColor}The rebase rewrites the following published or signed commits:",
+					"{IndicatorColor} Line 1: {Normal}aaa comment 1{WarningColor} [published, signed]",
+					"{IndicatorColor} Line 3: {Normal}ccc comment 3{WarningColor} [signed]",
+					"",
+					"{Normal}Are you sure you want to rewrite published or signed commits (y/n)? "
+				);
+			},
+		);
+	}
+
+	#[test]
+	fn handle_event_yes_with_unchanged_and_dropped_commits() {
+		module_test(
+			&["pick aaa comment 1", "drop bbb comment 2", "pick ccc comment 3"],
+			&[Event::from(MetaEvent::Yes)],
+			|mut test_context| {
+				test_context.rebase_todo_file.set_published(vec![
+					String::from("aaa"),
+					String::from("bbb"),
+					String::from("ccc"),
+				]);
+				test_context.rebase_todo_file.set_unchanged(vec![String::from("aaa")]);
+				let mut module = create_confirm_rebase();
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				assert_process_result!(
+					test_context.handle_event(&mut module),
+					event = Event::from(MetaEvent::Yes)
+				);
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+					Options AssertRenderOptions {
+						ignore_trailing_whitespace: false
+					},
+					view_data,
+					"{TITLE}",
+					"{BODY}",
+					"{WarningColor}The rebase rewrites the following published or signed commits:",
+					"{IndicatorColor} Line 3: {Normal}ccc comment 3{WarningColor} [published]",
+					"",
+					"{Normal}Are you sure you want to rewrite published or signed commits (y/n)? "
+				);
+			},
+		);
+	}
+
+	#[test]
+	fn handle_event_yes_without_rewritten_commits() {
+		module_test(
+			&["pick aaa comment 1", "drop bbb comment 2"],
+			&[Event::from(MetaEvent::Yes)],
+			|mut test_context| {
+				test_context
+					.rebase_todo_file
+					.set_published(vec![String::from("aaa"), String::from("bbb")]);
+				test_context.rebase_todo_file.set_unchanged(vec![String::from("aaa")]);
+				let mut module = create_confirm_rebase();
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				assert_process_result!(
+					test_context.handle_event(&mut module),
+					event = Event::from(MetaEvent::Yes),
+					exit_status = ExitStatus::Good
+				);
+			},
+		);
+	}
+
+	#[test]
+	fn handle_event_yes_confirm_rewritten_commits() {
+		module_test(
+			&["pick aaa comment"],
+			&[Event::from(MetaEvent::Yes), Event::from(MetaEvent::Yes)],
+			|mut test_context| {
+				test_context.rebase_todo_file.set_published(vec![String::from("aaa")]);
+				let mut module = create_confirm_rebase();
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				let _ = test_context.handle_event(&mut module);
+				assert_process_result!(
+					test_context.handle_event(&mut module),
+					event = Event::from(MetaEvent::Yes),
+					exit_status = ExitStatus::Good
+				);
+			},
+		);
+	}
+
+	#[test]
+	fn handle_event_no_confirm_rewritten_commits() {
+		module_test(
+			&["pick aaa comment"],
+			&[Event::from(MetaEvent::Yes), Event::from(MetaEvent::No)],
+			|mut test_context| {
+				test_context.rebase_todo_file.set_signed(vec![String::from("aaa")]);
+				let mut module = create_confirm_rebase();
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				let _ = test_context.handle_event(&mut module);
+				assert_process_result!(
+					test_context.handle_event(&mut module),
+					event = Event::from(MetaEvent::No),
+					state = State::List
+				);
+				assert_process_result!(test_context.activate(&mut module, State::List));
+				let view_data = test_context.build_view_data(&mut module);
+				assert_rendered_output!(
+					Options AssertRenderOptions {
+						ignore_trailing_whitespace: false
+					},
+					view_data,
+					"{TITLE}",
+					"{BODY}",
+					"{Normal}Are you sure you want to rebase (y/n)? "
+				);
+			},
+		);
+	}
+
 	#[test]
 	fn handle_event_no_match_key() {
 		module_test(
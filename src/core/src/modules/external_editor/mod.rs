@@ -27,6 +27,7 @@ pub(crate) struct ExternalEditor {
 	error_choice: Choice<Action>,
 	external_command: (String, Vec<String>),
 	lines: Vec<Line>,
+	reloads: usize,
 	state: ExternalEditorState,
 	view_data: ViewData,
 }
@@ -51,6 +52,7 @@ impl Module for ExternalEditor {
 
 	fn deactivate(&mut self) {
 		self.lines.clear();
+		self.reloads = 0;
 		self.view_data.update_view_data(|updater| updater.clear());
 	}
 
@@ -85,8 +87,11 @@ impl Module for ExternalEditor {
 				result = result.event(event);
 				match event {
 					Event::Meta(MetaEvent::ExternalCommandSuccess) => {
-						match todo_file.load_file() {
-							Ok(_) => {
+						match todo_file.reload_file() {
+							Ok(changed) => {
+								if changed {
+									self.reloads += 1;
+								}
 								if todo_file.is_empty() || todo_file.is_noop() {
 									result = self.set_state(result, ExternalEditorState::Empty);
 								}
@@ -113,10 +118,7 @@ impl Module for ExternalEditor {
 					match *action {
 						Action::AbortRebase => result = result.exit_status(ExitStatus::Good),
 						Action::EditRebase => result = self.set_state(result, ExternalEditorState::Active),
-						Action::UndoAndEdit => {
-							todo_file.set_lines(self.lines.clone());
-							result = self.undo_and_edit(result, todo_file);
-						},
+						Action::UndoAndEdit => result = self.undo_and_edit(result, todo_file),
 						Action::RestoreAndAbortEdit => {},
 					}
 				}
@@ -132,16 +134,13 @@ impl Module for ExternalEditor {
 						},
 						Action::EditRebase => result = self.set_state(result, ExternalEditorState::Active),
 						Action::RestoreAndAbortEdit => {
-							todo_file.set_lines(self.lines.clone());
+							self.restore_lines(todo_file);
 							result = result.state(State::List);
 							if let Err(err) = todo_file.write_file() {
 								result = result.error(err);
 							}
 						},
-						Action::UndoAndEdit => {
-							todo_file.set_lines(self.lines.clone());
-							result = self.undo_and_edit(result, todo_file);
-						},
+						Action::UndoAndEdit => result = self.undo_and_edit(result, todo_file),
 					}
 				}
 			},
@@ -188,6 +187,7 @@ impl ExternalEditor {
 			error_choice,
 			external_command: (String::from(""), vec![]),
 			lines: vec![],
+			reloads: 0,
 			state: ExternalEditorState::Active,
 			view_data,
 		}
@@ -203,8 +203,20 @@ impl ExternalEditor {
 		}
 	}
 
+	// the changes loaded from the editor are undone, rather than replaced, so restoring does not add another undo step
+	fn restore_lines(&mut self, todo_file: &mut TodoFile) {
+		for _ in 0..self.reloads {
+			let _ = todo_file.undo();
+		}
+		self.reloads = 0;
+		// the undo history may no longer hold every change, when the undo limit was reached
+		if todo_file.iter().ne(self.lines.iter()) {
+			todo_file.replace_lines(self.lines.clone());
+		}
+	}
+
 	fn undo_and_edit(&mut self, result: ProcessResult, todo_file: &mut TodoFile) -> ProcessResult {
-		todo_file.set_lines(self.lines.clone());
+		self.restore_lines(todo_file);
 		if let Err(err) = todo_file.write_file() {
 			return result.error(err).state(State::List);
 		}
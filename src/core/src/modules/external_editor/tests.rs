@@ -125,6 +125,33 @@ fn edit_success() {
 	});
 }
 
+#[test]
+fn edit_success_undo() {
+	module_test(&["pick aaa comment"], &[], |mut test_context| {
+		test_context
+			.event_handler_context
+			.event_handler
+			.push_event(Event::from(MetaEvent::ExternalCommandSuccess));
+		let mut module = ExternalEditor::new("editor");
+		let _ = test_context.activate(&mut module, State::List);
+		std::fs::write(
+			test_context.get_todo_file_path(),
+			"drop aaa comment\npick bbb comment\n",
+		)
+		.unwrap();
+		let _ = test_context.handle_event(&mut module);
+		assert_eq!(test_context.rebase_todo_file.get_lines_owned(), vec![
+			Line::new("drop aaa comment").unwrap(),
+			Line::new("pick bbb comment").unwrap()
+		]);
+		assert_eq!(test_context.rebase_todo_file.undo(), Some((0, 0)));
+		assert_eq!(test_context.rebase_todo_file.get_lines_owned(), vec![Line::new(
+			"pick aaa comment"
+		)
+		.unwrap()]);
+	});
+}
+
 #[test]
 fn empty_edit_error() {
 	module_test(&["pick aaa comment"], &[Event::from('1')], |mut test_context| {
@@ -214,6 +241,31 @@ fn empty_edit_undo_and_edit() {
 	);
 }
 
+#[test]
+fn empty_edit_undo_and_edit_undo() {
+	module_test(
+		&["pick aaa comment", "drop bbb comment"],
+		&[Event::from('3')],
+		|mut test_context| {
+			test_context
+				.event_handler_context
+				.event_handler
+				.push_event(Event::from(MetaEvent::ExternalCommandSuccess));
+			let mut module = ExternalEditor::new("editor");
+			let _ = test_context.activate(&mut module, State::List);
+			std::fs::write(test_context.get_todo_file_path(), "").unwrap();
+			let _ = test_context.handle_event(&mut module);
+			assert_external_editor_state_eq!(module.state, ExternalEditorState::Empty);
+			let _ = test_context.handle_event(&mut module);
+			let _ = test_context.rebase_todo_file.undo();
+			assert_eq!(test_context.rebase_todo_file.get_lines_owned(), vec![
+				Line::new("pick aaa comment").unwrap(),
+				Line::new("drop bbb comment").unwrap()
+			]);
+		},
+	);
+}
+
 #[test]
 fn empty_edit_noop() {
 	module_test(&["pick aaa comment"], &[], |mut test_context| {
@@ -379,6 +431,31 @@ fn error_restore_and_abort() {
 	});
 }
 
+#[test]
+fn error_restore_and_abort_undo() {
+	module_test(&["pick aaa comment"], &[], |mut test_context| {
+		for event in [
+			Event::from(MetaEvent::ExternalCommandSuccess),
+			Event::from('2'),
+			Event::from(MetaEvent::ExternalCommandError),
+			Event::from('3'),
+		] {
+			test_context.event_handler_context.event_handler.push_event(event);
+		}
+		let mut module = ExternalEditor::new("editor");
+		let _ = test_context.activate(&mut module, State::List);
+		std::fs::write(test_context.get_todo_file_path(), "").unwrap();
+		for _ in 0..4 {
+			let _ = test_context.handle_event(&mut module);
+		}
+		assert!(test_context.rebase_todo_file.undo().is_none());
+		assert_eq!(test_context.rebase_todo_file.get_lines_owned(), vec![Line::new(
+			"pick aaa comment"
+		)
+		.unwrap()]);
+	});
+}
+
 #[test]
 fn error_undo_modifications_and_reedit() {
 	module_test(&["pick aaa comment"], &[Event::from('4')], |mut test_context| {
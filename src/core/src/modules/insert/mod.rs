@@ -4,6 +4,7 @@ mod line_type;
 #[cfg(all(unix, test))]
 mod tests;
 
+use anyhow::Result;
 use input::EventHandler;
 use todo_file::{Line, TodoFile};
 use view::{RenderContext, ViewData, ViewLine, ViewSender};
@@ -52,6 +53,9 @@ impl Module for Insert {
 					else {
 						self.line_type = action.clone();
 						self.edit.set_label(format!("{} ", action.to_string()).as_str());
+						if action == &LineType::Merge {
+							self.edit.set_validator(Self::validate_merge);
+						}
 						self.state = InsertState::Edit;
 					}
 				}
@@ -69,6 +73,7 @@ impl Module for Insert {
 							LineType::Label => Line::new_label(content.as_str()),
 							LineType::Reset => Line::new_reset(content.as_str()),
 							LineType::Merge => Line::new_merge(content.as_str()),
+							LineType::UpdateRef => Line::new_update_ref(content.as_str()),
 							// this should exit in the prompt state and never get here
 							LineType::Cancel => unreachable!(),
 						};
@@ -98,6 +103,7 @@ impl Insert {
 				'm',
 				String::from("merge [-C <commit> | -c <commit>] <label> [# <oneline>]"),
 			),
+			(LineType::UpdateRef, 'u', String::from("update-ref <reference>")),
 			(LineType::Cancel, 'q', String::from("Cancel add line")),
 		]);
 		action_choices.set_prompt(vec![ViewLine::from("Select the type of line to insert:")]);
@@ -109,4 +115,14 @@ impl Insert {
 			line_type: LineType::Exec,
 		}
 	}
+
+	fn validate_merge(content: &str) -> Result<()> {
+		// empty content cancels the creation of the line, so is always valid
+		if content.is_empty() {
+			Ok(())
+		}
+		else {
+			Line::validate_merge(content)
+		}
+	}
 }
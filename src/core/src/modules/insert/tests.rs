@@ -29,6 +29,7 @@ fn render_prompt() {
 			"{Normal}l) label <label>",
 			"{Normal}r) reset <label>",
 			"{Normal}m) merge [-C <commit> | -c <commit>] <label> [# <oneline>]",
+			"{Normal}u) update-ref <reference>",
 			"{Normal}q) Cancel add line",
 			"",
 			"{IndicatorColor}Please choose an option."
@@ -240,6 +241,45 @@ fn edit_render_merge() {
 	);
 }
 
+#[test]
+fn edit_render_update_ref() {
+	module_test(
+		&[],
+		&[
+			Event::from('u'),
+			Event::from('f'),
+			Event::from('o'),
+			Event::from('o'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = Insert::new();
+			let _ = test_context.handle_n_events(&mut module, 4);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Enter contents of the new line. Empty content cancels creation of a new line.",
+				"",
+				"{BODY}",
+				"{Normal,Dimmed}update-ref {Normal}foo{Normal,Underline}",
+				"{TRAILING}",
+				"{IndicatorColor}Enter to finish"
+			);
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(KeyCode::Enter),
+				state = State::List
+			);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"update-ref foo"
+			);
+		},
+	);
+}
+
 #[test]
 fn edit_select_next_index() {
 	module_test(
@@ -271,3 +311,40 @@ fn cancel_edit() {
 		},
 	);
 }
+
+#[test]
+fn edit_merge_invalid() {
+	module_test(
+		&[],
+		&[
+			Event::from('m'),
+			Event::from('-'),
+			Event::from('C'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = Insert::new();
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, InsertState::Edit);
+			assert!(test_context.rebase_todo_file.is_empty());
+		},
+	);
+}
+
+#[test]
+fn edit_merge_empty_cancels() {
+	module_test(
+		&[],
+		&[Event::from('m'), Event::from(KeyCode::Enter)],
+		|mut test_context| {
+			let mut module = Insert::new();
+			let _ = test_context.handle_event(&mut module);
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(KeyCode::Enter),
+				state = State::List
+			);
+			assert!(test_context.rebase_todo_file.is_empty());
+		},
+	);
+}
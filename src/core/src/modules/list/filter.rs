@@ -0,0 +1,120 @@
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+use todo_file::{Action, Line, TodoFile};
+
+use super::search::is_hash_match;
+
+const ACTION_PREFIX: &str = "action:";
+
+enum FilterKind {
+	Actions(Vec<Action>),
+	Pattern(Regex),
+}
+
+/// A query that limits the list to the lines that match it. A query of `action:` followed by a comma separated list
+/// of actions, such as `action:fixup,squash`, matches lines with those actions, any other query matches lines where
+/// the query is a prefix of the commit hash, or a case-insensitive regular expression that matches the content.
+pub(super) struct Filter {
+	kind: FilterKind,
+	query: String,
+}
+
+impl Filter {
+	pub(super) fn new(query: &str) -> Result<Self> {
+		let kind = if let Some(actions) = query.strip_prefix(ACTION_PREFIX) {
+			let actions = actions
+				.split(',')
+				.map(str::trim)
+				.filter(|action| !action.is_empty())
+				.map(Action::try_from)
+				.collect::<Result<Vec<Action>>>()?;
+			if actions.is_empty() {
+				return Err(anyhow!("Expected at least one action"));
+			}
+			FilterKind::Actions(actions)
+		}
+		else {
+			FilterKind::Pattern(
+				RegexBuilder::new(query)
+					.case_insensitive(true)
+					.build()
+					.map_err(|err| anyhow!("Invalid pattern: {}", err))?,
+			)
+		};
+		Ok(Self {
+			kind,
+			query: String::from(query),
+		})
+	}
+
+	pub(super) fn validate(query: &str) -> Result<()> {
+		Self::new(query).map(|_| ())
+	}
+
+	pub(super) fn get_query(&self) -> &str {
+		self.query.as_str()
+	}
+
+	pub(super) fn is_match(&self, line: &Line) -> bool {
+		match self.kind {
+			FilterKind::Actions(ref actions) => actions.contains(line.get_action()),
+			FilterKind::Pattern(ref pattern) => {
+				is_hash_match(line.get_hash(), self.query.as_str()) || pattern.is_match(line.get_content())
+			},
+		}
+	}
+}
+
+/// Get the indices of the lines in the todo file that are shown, which is every line when there is no filter.
+pub(super) fn get_visible_indices(todo_file: &TodoFile, filter: Option<&Filter>) -> Vec<usize> {
+	todo_file
+		.iter()
+		.enumerate()
+		.filter(|&(_, line)| filter.map_or(true, |f| f.is_match(line)))
+		.map(|(index, _)| index)
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+
+	#[rstest]
+	#[case::content("pick aaa Some summary", "summ", true)]
+	#[case::content_ignore_case("pick aaa Some summary", "SOME", true)]
+	#[case::content_regex("pick aaa Some summary", "^some s.*y$", true)]
+	#[case::content_no_match("pick aaa Some summary", "^summary", false)]
+	#[case::hash_prefix("pick abcdef c1", "abc", true)]
+	#[case::hash_not_prefix("pick abcdef c1", "def", false)]
+	#[case::exec_command("exec make test", "make", true)]
+	#[case::action("fixup aaa c1", "action:fixup", true)]
+	#[case::action_abbreviation("fixup aaa c1", "action:f", true)]
+	#[case::action_list("squash aaa c1", "action:fixup, squash", true)]
+	#[case::action_no_match("pick aaa c1", "action:fixup,squash", false)]
+	fn is_match(#[case] line: &str, #[case] query: &str, #[case] expected: bool) {
+		let filter = Filter::new(query).unwrap();
+		assert_eq!(filter.is_match(&Line::new(line).unwrap()), expected);
+	}
+
+	#[rstest]
+	#[case::invalid_action("action:foo", "Invalid action: foo")]
+	#[case::missing_action("action:", "Expected at least one action")]
+	fn validate_invalid(#[case] query: &str, #[case] expected: &str) {
+		assert_eq!(Filter::validate(query).unwrap_err().to_string(), expected);
+	}
+
+	#[test]
+	fn validate_invalid_pattern() {
+		assert!(Filter::validate("(")
+			.unwrap_err()
+			.to_string()
+			.starts_with("Invalid pattern: "));
+	}
+
+	#[test]
+	fn validate_valid() {
+		assert!(Filter::validate("c[0-9]").is_ok());
+	}
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+
+use todo_file::{Action, Line, TodoFile};
+
+/// A run of lines that starts at a `reset` line, or at the start of the file, and represents a single branch.
+struct Segment {
+	start: usize,
+	end: usize,
+	lane: usize,
+	merged_at: Option<usize>,
+}
+
+impl Segment {
+	const fn last_active_index(&self) -> usize {
+		match self.merged_at {
+			Some(index) => index,
+			None => self.end,
+		}
+	}
+
+	const fn is_active(&self, index: usize) -> bool {
+		index >= self.start && index <= self.last_active_index()
+	}
+
+	const fn overlaps(&self, other: &Self) -> bool {
+		self.start <= other.last_active_index() && other.start <= self.last_active_index()
+	}
+}
+
+fn get_segments(lines: &[&Line]) -> (Vec<Segment>, Vec<usize>) {
+	let mut segments: Vec<Segment> = vec![];
+	let mut line_segments = vec![];
+	for (index, line) in lines.iter().enumerate() {
+		if segments.is_empty() || line.get_action() == &Action::Reset {
+			segments.push(Segment {
+				start: index,
+				end: index,
+				lane: 0,
+				merged_at: None,
+			});
+		}
+		let segment_index = segments.len() - 1;
+		segments[segment_index].end = index;
+		line_segments.push(segment_index);
+	}
+	(segments, line_segments)
+}
+
+fn assign_lanes(lines: &[&Line], segments: &mut [Segment], line_segments: &[usize]) {
+	let mut label_segments = HashMap::new();
+	for (index, line) in lines.iter().enumerate() {
+		if line.get_action() == &Action::Label {
+			let _previous = label_segments.insert(line.get_content(), line_segments[index]);
+		}
+	}
+
+	let mut parents = vec![None; segments.len()];
+	for (index, line) in lines.iter().enumerate() {
+		if line.get_action() != &Action::Merge {
+			continue;
+		}
+		let segment_index = line_segments[index];
+		for label in line.get_labels() {
+			if let Some(&merged_segment) = label_segments.get(label) {
+				if merged_segment < segment_index && parents[merged_segment].is_none() {
+					parents[merged_segment] = Some(segment_index);
+					segments[merged_segment].merged_at = Some(index);
+				}
+			}
+		}
+	}
+
+	// a branch is always merged into a segment later in the file, so assigning the lanes from the end of the file
+	// guarantees that the lane of the parent is known
+	for segment_index in (0..segments.len()).rev() {
+		if let Some(parent) = parents[segment_index] {
+			let mut lane = segments[parent].lane + 1;
+			let segment = &segments[segment_index];
+			while segments[segment_index + 1..]
+				.iter()
+				.any(|other| other.lane == lane && other.overlaps(segment))
+			{
+				lane += 1;
+			}
+			segments[segment_index].lane = lane;
+		}
+	}
+}
+
+/// Get the graph gutter for each line of the todo file, where each branch created by `reset` and joined by `merge`
+/// is drawn in its own lane to the right of the branch that it is merged into.
+pub(super) fn get_graph_lines(todo_file: &TodoFile) -> Vec<String> {
+	let lines = todo_file.iter().collect::<Vec<&Line>>();
+	let (mut segments, line_segments) = get_segments(&lines);
+	assign_lanes(&lines, &mut segments, &line_segments);
+	let max_lane = segments.iter().map(|segment| segment.lane).max().unwrap_or(0);
+
+	lines
+		.iter()
+		.enumerate()
+		.map(|(index, line)| {
+			let own_segment = &segments[line_segments[index]];
+			let mut graph = String::new();
+			for lane in 0..=max_lane {
+				let rail = if lane == own_segment.lane {
+					if line.has_reference() {
+						'*'
+					}
+					else if lane > 0 && index == own_segment.start {
+						'\\'
+					}
+					else {
+						'|'
+					}
+				}
+				else if lane == 0 {
+					'|'
+				}
+				else if let Some(segment) = segments.iter().find(|s| s.lane == lane && s.is_active(index)) {
+					if segment.merged_at == Some(index) {
+						'/'
+					}
+					else {
+						'|'
+					}
+				}
+				else {
+					' '
+				};
+				graph.push(rail);
+				graph.push(' ');
+			}
+			graph
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use std::io::Write;
+
+	use tempfile::Builder;
+
+	use super::*;
+
+	fn assert_graph(lines: &[&str], expected: &[&str]) {
+		let todo_file_path = Builder::new()
+			.prefix("git-rebase-todo-scratch")
+			.suffix("")
+			.tempfile()
+			.unwrap();
+		write!(todo_file_path.as_file(), "{}", lines.join("\n")).unwrap();
+		let mut todo_file = TodoFile::new(todo_file_path.path().to_str().unwrap(), 1, "#");
+		todo_file.load_file().unwrap();
+		assert_eq!(get_graph_lines(&todo_file), expected);
+	}
+
+	#[test]
+	fn empty() {
+		assert_graph(&[], &[]);
+	}
+
+	#[test]
+	fn without_merges() {
+		assert_graph(&["pick aaa c1", "exec make", "pick bbb c2"], &["* ", "| ", "* "]);
+	}
+
+	#[test]
+	fn single_branch() {
+		assert_graph(
+			&[
+				"label onto",
+				"reset onto",
+				"pick aaa c1",
+				"label feature",
+				"reset onto",
+				"merge -C bbb feature # Merge",
+			],
+			&["|   ", "| \\ ", "| * ", "| | ", "| | ", "* / "],
+		);
+	}
+
+	#[test]
+	fn overlapping_branches() {
+		assert_graph(
+			&[
+				"label onto",
+				"reset onto",
+				"pick aaa c1",
+				"label feature",
+				"reset onto",
+				"pick bbb c2",
+				"label other",
+				"reset onto",
+				"merge -C ccc feature # Merge feature",
+				"merge -C ddd other # Merge other",
+			],
+			&[
+				"|     ", "|   \\ ", "|   * ", "|   | ", "| \\ | ", "| * | ", "| | | ", "| | | ", "* | / ", "* /   ",
+			],
+		);
+	}
+
+	#[test]
+	fn nested_branches() {
+		assert_graph(
+			&[
+				"label onto",
+				"reset onto",
+				"pick aaa c1",
+				"label inner",
+				"reset onto",
+				"merge -C bbb inner # Merge inner",
+				"label outer",
+				"reset onto",
+				"merge -C ccc outer # Merge outer",
+			],
+			&[
+				"|     ", "|   \\ ", "|   * ", "|   | ", "| \\ | ", "| * / ", "| |   ", "| |   ", "* /   ",
+			],
+		);
+	}
+
+	#[test]
+	fn sequential_branches_share_lane() {
+		assert_graph(
+			&[
+				"label onto",
+				"reset onto",
+				"pick aaa c1",
+				"label first",
+				"reset onto",
+				"merge -C bbb first",
+				"label main",
+				"reset main",
+				"pick ccc c2",
+				"label second",
+				"reset main",
+				"merge -C ddd second",
+			],
+			&[
+				"|   ", "| \\ ", "| * ", "| | ", "| | ", "* / ", "|   ", "| \\ ", "| * ", "| | ", "| | ", "* / ",
+			],
+		);
+	}
+}
@@ -17,7 +17,11 @@ pub(crate) fn get_event(event_handler: &EventHandler) -> Event {
 			e if key_bindings.action_pick.contains(&e) => Event::from(MetaEvent::ActionPick),
 			e if key_bindings.action_reword.contains(&e) => Event::from(MetaEvent::ActionReword),
 			e if key_bindings.action_squash.contains(&e) => Event::from(MetaEvent::ActionSquash),
+			e if key_bindings.autosquash.contains(&e) => Event::from(MetaEvent::Autosquash),
+			e if key_bindings.drop_applied_upstream.contains(&e) => Event::from(MetaEvent::DropAppliedUpstream),
 			e if key_bindings.edit.contains(&e) => Event::from(MetaEvent::Edit),
+			e if key_bindings.filter.contains(&e) => Event::from(MetaEvent::Filter),
+			e if key_bindings.cycle_fixup_option.contains(&e) => Event::from(MetaEvent::CycleFixupOption),
 			e if key_bindings.force_abort.contains(&e) => Event::from(MetaEvent::ForceAbort),
 			e if key_bindings.force_rebase.contains(&e) => Event::from(MetaEvent::ForceRebase),
 			e if key_bindings.insert_line.contains(&e) => Event::from(MetaEvent::InsertLine),
@@ -34,7 +38,11 @@ pub(crate) fn get_event(event_handler: &EventHandler) -> Event {
 			e if key_bindings.open_in_external_editor.contains(&e) => Event::from(MetaEvent::OpenInEditor),
 			e if key_bindings.rebase.contains(&e) => Event::from(MetaEvent::Rebase),
 			e if key_bindings.remove_line.contains(&e) => Event::from(MetaEvent::Delete),
+			e if key_bindings.search.contains(&e) => Event::from(MetaEvent::SearchStart),
+			e if key_bindings.search_next.contains(&e) => Event::from(MetaEvent::SearchNext),
+			e if key_bindings.search_previous.contains(&e) => Event::from(MetaEvent::SearchPrevious),
 			e if key_bindings.show_commit.contains(&e) => Event::from(MetaEvent::ShowCommit),
+			e if key_bindings.toggle_graph.contains(&e) => Event::from(MetaEvent::ToggleGraph),
 			e if key_bindings.toggle_visual_mode.contains(&e) => Event::from(MetaEvent::ToggleVisualMode),
 			Event::Mouse(mouse_event) => {
 				match mouse_event.kind {
@@ -1,25 +1,44 @@
+mod filter;
+mod graph;
 mod input;
+mod reorder;
+mod search;
 mod utils;
 
 #[cfg(all(unix, test))]
 mod tests;
 
-use std::cmp::min;
+use std::cmp::{max, min};
 
-use ::input::{Event, EventHandler, MetaEvent};
+use ::input::{Event, EventHandler, KeyCode, KeyEvent, MetaEvent};
 use captur::capture;
 use config::Config;
 use display::DisplayColor;
-use todo_file::{Action, EditContext, Line, TodoFile};
+use todo_file::{Action, Diagnostic, EditContext, Line, TodoFile};
 use view::{LineSegment, RenderContext, ViewData, ViewLine, ViewSender};
 
+pub(crate) use self::search::get_match_ranges;
 use self::{
+	filter::{get_visible_indices, Filter},
+	graph::get_graph_lines,
 	input::get_event,
-	utils::{get_list_normal_mode_help_lines, get_list_visual_mode_help_lines, get_todo_line_segments},
+	reorder::{get_commit_hashes, get_reordered_line_indices, get_reordered_pairs},
+	search::{find_match, find_next_match},
+	utils::{
+		get_done_line_segments,
+		get_list_normal_mode_help_lines,
+		get_list_visual_mode_help_lines,
+		get_progress_text,
+		get_sequencer_normal_mode_help_lines,
+		get_sequencer_visual_mode_help_lines,
+		get_todo_line_segments,
+		TodoLineOptions,
+	},
 };
 use crate::{
 	components::{edit::Edit, help::Help},
 	module::{ExitStatus, Module, ProcessResult, State},
+	modules::{is_path_changing_line, CommitPaths, SelectedRange},
 };
 
 #[derive(Debug, PartialEq)]
@@ -27,13 +46,27 @@ enum ListState {
 	Normal,
 	Visual,
 	Edit,
+	Search,
+	Filter,
 }
 
 pub(crate) struct List {
 	auto_select_next: bool,
+	commit_paths: CommitPaths,
+	diagnostics: Option<(usize, Vec<Diagnostic>)>,
 	edit: Edit,
+	filter: Option<Filter>,
 	height: usize,
+	// the todo file is checked for problems, unless turned off, as is done by tests with lines that repeat a commit
+	lint: bool,
 	normal_mode_help: Help,
+	reorder_overlaps: Vec<(String, String)>,
+	search_start_index: usize,
+	search_term: String,
+	selected_range: SelectedRange,
+	sequencer_normal_mode_help: Help,
+	sequencer_visual_mode_help: Help,
+	show_graph: bool,
 	state: ListState,
 	view_data: ViewData,
 	visual_index_start: Option<usize>,
@@ -46,6 +79,7 @@ impl Module for List {
 			ListState::Normal => self.get_normal_mode_view_data(todo_file, context),
 			ListState::Visual => self.get_visual_mode_view_data(todo_file, context),
 			ListState::Edit => self.edit.get_view_data(),
+			ListState::Search | ListState::Filter => self.update_list_view_data(context, todo_file),
 		}
 	}
 
@@ -55,11 +89,15 @@ impl Module for List {
 		view_sender: &ViewSender,
 		todo_file: &mut TodoFile,
 	) -> ProcessResult {
-		match self.state {
+		let result = match self.state {
 			ListState::Normal => self.handle_normal_mode_input(event_handler, view_sender, todo_file),
 			ListState::Visual => self.handle_visual_mode_input(event_handler, view_sender, todo_file),
 			ListState::Edit => self.handle_edit_mode_input(event_handler, todo_file),
-		}
+			ListState::Search => self.handle_search_mode_input(event_handler, todo_file),
+			ListState::Filter => self.handle_filter_mode_input(event_handler),
+		};
+		self.ensure_selected_line_visible(todo_file);
+		result
 	}
 }
 
@@ -72,9 +110,24 @@ impl List {
 
 		Self {
 			auto_select_next: config.auto_select_next,
+			commit_paths: CommitPaths::new(config),
+			diagnostics: None,
 			edit: Edit::new(),
+			filter: None,
 			height: 0,
+			lint: true,
 			normal_mode_help: Help::new_from_keybindings(&get_list_normal_mode_help_lines(&config.key_bindings)),
+			reorder_overlaps: vec![],
+			search_start_index: 0,
+			search_term: String::from(""),
+			selected_range: SelectedRange::new(),
+			sequencer_normal_mode_help: Help::new_from_keybindings(&get_sequencer_normal_mode_help_lines(
+				&config.key_bindings,
+			)),
+			sequencer_visual_mode_help: Help::new_from_keybindings(&get_sequencer_visual_mode_help_lines(
+				&config.key_bindings,
+			)),
+			show_graph: false,
 			state: ListState::Normal,
 			view_data,
 			visual_index_start: None,
@@ -82,57 +135,332 @@ impl List {
 		}
 	}
 
-	pub(crate) fn move_cursor_up(todo_file: &mut TodoFile, amount: usize) {
-		let current_selected_line_index = todo_file.get_selected_line_index();
-		todo_file.set_selected_line_index(
-			if amount > current_selected_line_index {
-				0
+	/// Share the cache of the paths changed by commits with other modules.
+	pub(crate) fn with_commit_paths(mut self, commit_paths: CommitPaths) -> Self {
+		self.commit_paths = commit_paths;
+		self
+	}
+
+	/// Share the range of lines selected in visual mode, for showing the changes of the selected commits.
+	pub(crate) fn with_selected_range(mut self, selected_range: SelectedRange) -> Self {
+		self.selected_range = selected_range;
+		self
+	}
+
+	// a pair of commits that change the same paths is recorded the first time a move reverses their order, and the
+	// pair is marked for as long as their order remains reversed
+	fn record_reorder_overlaps(&mut self, previous_hashes: &[String], rebase_todo: &TodoFile) {
+		for (earlier, later) in get_reordered_pairs(previous_hashes, rebase_todo) {
+			let recorded = self
+				.reorder_overlaps
+				.iter()
+				.any(|&(ref a, ref b)| (a == &earlier && b == &later) || (a == &later && b == &earlier));
+			if !recorded
+				&& !self
+					.commit_paths
+					.get_overlapping_paths(earlier.as_str(), later.as_str())
+					.is_empty()
+			{
+				self.reorder_overlaps.push((earlier, later));
 			}
-			else {
-				current_selected_line_index - amount
-			},
-		);
+		}
 	}
 
-	pub(crate) fn move_cursor_down(rebase_todo: &mut TodoFile, amount: usize) {
-		let current_selected_line_index = rebase_todo.get_selected_line_index();
-		rebase_todo.set_selected_line_index(current_selected_line_index + amount);
+	// the todo file of the sequencer supports fewer actions, so has its own help
+	fn get_normal_mode_help(&mut self, todo_file: &TodoFile) -> &mut Help {
+		if todo_file.is_sequencer() {
+			&mut self.sequencer_normal_mode_help
+		}
+		else {
+			&mut self.normal_mode_help
+		}
 	}
 
-	fn set_selected_line_action(&self, rebase_todo: &mut TodoFile, action: Action) {
-		let start_index = rebase_todo.get_selected_line_index();
-		let end_index = self.visual_index_start.unwrap_or(start_index);
+	fn get_visual_mode_help(&mut self, todo_file: &TodoFile) -> &mut Help {
+		if todo_file.is_sequencer() {
+			&mut self.sequencer_visual_mode_help
+		}
+		else {
+			&mut self.visual_mode_help
+		}
+	}
+
+	// the row of the selected line within the visible lines, or the row of the next visible line when the selected
+	// line is hidden
+	fn get_selected_row(visible_indices: &[usize], todo_file: &TodoFile) -> usize {
+		let selected_index = todo_file.get_selected_line_index();
+		visible_indices
+			.iter()
+			.position(|&index| index >= selected_index)
+			.unwrap_or(visible_indices.len())
+	}
+
+	pub(crate) fn move_cursor_up(&self, todo_file: &mut TodoFile, amount: usize) {
+		let visible_indices = get_visible_indices(todo_file, self.filter.as_ref());
+		let row = Self::get_selected_row(&visible_indices, todo_file);
+		if let Some(&index) = visible_indices.get(row.saturating_sub(amount)) {
+			todo_file.set_selected_line_index(index);
+		}
+	}
+
+	pub(crate) fn move_cursor_down(&self, rebase_todo: &mut TodoFile, amount: usize) {
+		let visible_indices = get_visible_indices(rebase_todo, self.filter.as_ref());
+		let row = Self::get_selected_row(&visible_indices, rebase_todo);
+		if row < visible_indices.len() {
+			rebase_todo.set_selected_line_index(visible_indices[min(row + amount, visible_indices.len() - 1)]);
+		}
+	}
+
+	fn ensure_selected_line_visible(&self, todo_file: &mut TodoFile) {
+		if let Some(filter) = self.filter.as_ref() {
+			if todo_file.get_selected_line().map_or(true, |line| filter.is_match(line)) {
+				return;
+			}
+			let visible_indices = get_visible_indices(todo_file, Some(filter));
+			let row = Self::get_selected_row(&visible_indices, todo_file);
+			if let Some(&index) = visible_indices.get(row).or_else(|| visible_indices.last()) {
+				todo_file.set_selected_line_index(index);
+			}
+		}
+	}
 
-		rebase_todo.update_range(start_index, end_index, &EditContext::new().action(action));
+	// the indices of the selected lines that are not hidden by the filter
+	fn get_selected_line_indices(&self, todo_file: &TodoFile) -> Vec<usize> {
+		let selected_index = todo_file.get_selected_line_index();
+		let visual_index = self.visual_index_start.unwrap_or(selected_index);
+		let (start_index, end_index) = if visual_index <= selected_index {
+			(visual_index, selected_index)
+		}
+		else {
+			(selected_index, visual_index)
+		};
+		get_visible_indices(todo_file, self.filter.as_ref())
+			.into_iter()
+			.filter(|&index| index >= start_index && index <= end_index)
+			.collect()
+	}
+
+	// while filtering, the selected lines are moved past the next visible line, along with any hidden lines between
+	fn swap_selected_lines_down(
+		&self,
+		todo_file: &mut TodoFile,
+		start_index: usize,
+		end_index: usize,
+	) -> Option<(usize, usize)> {
+		if self.filter.is_none() {
+			return todo_file.swap_range_down(start_index, end_index);
+		}
+		let range_end = max(start_index, end_index);
+		get_visible_indices(todo_file, self.filter.as_ref())
+			.into_iter()
+			.find(|&index| index > range_end)
+			.and_then(|index| todo_file.swap_range_down_past(start_index, end_index, index))
+	}
+
+	// while filtering, the selected lines are moved past the previous visible line, along with any hidden lines between
+	fn swap_selected_lines_up(
+		&self,
+		todo_file: &mut TodoFile,
+		start_index: usize,
+		end_index: usize,
+	) -> Option<(usize, usize)> {
+		if self.filter.is_none() {
+			return todo_file.swap_range_up(start_index, end_index);
+		}
+		let range_start = min(start_index, end_index);
+		get_visible_indices(todo_file, self.filter.as_ref())
+			.into_iter()
+			.rev()
+			.find(|&index| index < range_start)
+			.and_then(|index| todo_file.swap_range_up_past(start_index, end_index, index))
+	}
+
+	fn update_selected_lines(&self, rebase_todo: &mut TodoFile, edit_context: &EditContext) {
+		if self.filter.is_some() {
+			// hidden lines within the selection are left untouched, but the change is still a single undo step
+			rebase_todo.update_lines(&self.get_selected_line_indices(rebase_todo), edit_context);
+		}
+		else {
+			let start_index = rebase_todo.get_selected_line_index();
+			let end_index = self.visual_index_start.unwrap_or(start_index);
+			rebase_todo.update_range(start_index, end_index, edit_context);
+		}
+	}
+
+	fn set_selected_line_action(&self, rebase_todo: &mut TodoFile, action: Action) {
+		self.update_selected_lines(rebase_todo, &EditContext::new().action(action));
 		if self.state == ListState::Normal && self.auto_select_next {
-			Self::move_cursor_down(rebase_todo, 1);
+			self.move_cursor_down(rebase_todo, 1);
+		}
+	}
+
+	fn cycle_selected_line_fixup_option(&self, rebase_todo: &mut TodoFile) {
+		if let Some(selected_line) = rebase_todo.get_selected_line() {
+			if selected_line.get_action() != &Action::Fixup {
+				return;
+			}
+			// cycles through no option, "-C" and "-c", based on the line under the cursor, and the result is applied to
+			// every selected line so that a mixed selection ends up with a single option
+			let edit_context = match selected_line.get_option() {
+				None => EditContext::new().option("-C"),
+				Some("-C") => EditContext::new().option("-c"),
+				Some(_) => EditContext::new().without_option(),
+			};
+			self.update_selected_lines(rebase_todo, &edit_context);
 		}
 	}
 
+	// checking the todo file is slow for a large todo file, so the diagnostics are kept until the todo file changes
+	fn get_diagnostics(&mut self, todo_file: &TodoFile) -> &[Diagnostic] {
+		if !self.lint {
+			return &[];
+		}
+		let version = todo_file.get_version();
+		if self.diagnostics.as_ref().map_or(true, |&(v, _)| v != version) {
+			self.diagnostics = Some((version, todo_file.lint()));
+		}
+		self.diagnostics.as_ref().map_or(&[], |&(_, ref diagnostics)| diagnostics.as_slice())
+	}
+
 	fn update_list_view_data(&mut self, context: &RenderContext, todo_file: &TodoFile) -> &ViewData {
 		let is_visual_mode = self.state == ListState::Visual;
 		let selected_index = todo_file.get_selected_line_index();
 		let visual_index = self.visual_index_start.unwrap_or(selected_index);
+		let graph_lines = if self.show_graph {
+			get_graph_lines(todo_file)
+		}
+		else {
+			vec![]
+		};
+		let mut warnings: Vec<Vec<String>> = vec![vec![]; todo_file.iter().len()];
+		for diagnostic in self.get_diagnostics(todo_file) {
+			warnings[diagnostic.get_line_index()].push(diagnostic.to_string());
+		}
+		for (line_warnings, line) in warnings.iter_mut().zip(todo_file.iter()) {
+			if todo_file.is_applied_upstream(line) {
+				line_warnings.push(String::from("Already applied upstream"));
+			}
+		}
+		if let Some(conflicts) = todo_file.get_conflicts() {
+			for (line_warnings, paths) in warnings.iter_mut().zip(conflicts) {
+				if !paths.is_empty() {
+					line_warnings.push(format!("Predicted conflict in {}", paths.join(", ")));
+				}
+			}
+		}
+		let reordered_line_indices = get_reordered_line_indices(todo_file, &self.reorder_overlaps);
+		let search_term = (!self.search_term.is_empty()).then(|| self.search_term.as_str());
+		let filter = self.filter.as_ref();
+		let visible_indices = get_visible_indices(todo_file, filter);
+		let progress = todo_file.get_progress();
+		// the applied lines are hidden while filtering, since they can not match the filter
+		let done_lines = match (progress, filter) {
+			(Some(rebase_progress), None) => rebase_progress.get_done_lines(),
+			_ => &[],
+		};
+		let mut title_info = vec![];
+		if let Some(rebase_progress) = progress {
+			title_info.push(get_progress_text(rebase_progress, todo_file.iter().len()));
+		}
+		if filter.is_some() {
+			title_info.push(format!(
+				"filtered: {} of {}",
+				visible_indices.len(),
+				todo_file.iter().len()
+			));
+		}
+		let title_info = (!title_info.is_empty()).then(|| title_info.join(", "));
+		let input_line = match self.state {
+			ListState::Search => {
+				let mut segments = self.edit.get_line_segments();
+				if search_term.is_some() && find_match(todo_file, self.search_term.as_str(), filter, 0).is_none() {
+					segments.push(LineSegment::new_with_color(
+						"  No matches",
+						DisplayColor::IndicatorColor,
+					));
+				}
+				Some(segments)
+			},
+			ListState::Filter => {
+				let mut segments = self.edit.get_line_segments();
+				if let Some(error) = self.edit.get_error() {
+					segments.push(LineSegment::new_with_color(
+						format!("  {}", error).as_str(),
+						DisplayColor::IndicatorColor,
+					));
+				}
+				Some(segments)
+			},
+			ListState::Normal | ListState::Visual | ListState::Edit => None,
+		};
+		let selected_row = done_lines.len()
+			+ visible_indices
+				.iter()
+				.position(|&index| index >= selected_index)
+				.unwrap_or(0);
+		let visual_row = done_lines.len()
+			+ visible_indices
+				.iter()
+				.position(|&index| index >= visual_index)
+				.unwrap_or(0);
 
 		self.view_data.update_view_data(|updater| {
 			capture!(todo_file);
 			updater.clear();
+			updater.set_title_info(title_info.as_deref());
 			if todo_file.is_empty() {
 				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
 					"Rebase todo file is empty",
 					DisplayColor::IndicatorColor,
 				)));
 			}
+			else if visible_indices.is_empty() {
+				updater.push_leading_line(ViewLine::from(LineSegment::new_with_color(
+					"No lines match the filter",
+					DisplayColor::IndicatorColor,
+				)));
+			}
 			else {
-				for (index, line) in todo_file.iter().enumerate() {
+				for line in done_lines {
+					updater.push_line(ViewLine::from(get_done_line_segments(line, context.is_full_width())));
+				}
+				for (index, line) in todo_file
+					.iter()
+					.enumerate()
+					.filter(|&(_, line)| filter.map_or(true, |f| f.is_match(line)))
+				{
 					let selected_line = is_visual_mode
 						&& ((visual_index <= selected_index && index >= visual_index && index <= selected_index)
 							|| (visual_index > selected_index && index >= selected_index && index <= visual_index));
-					let mut view_line = ViewLine::new_with_pinned_segments(
-						get_todo_line_segments(line, selected_index == index, selected_line, context.is_full_width()),
-						if *line.get_action() == Action::Exec { 2 } else { 3 },
-					)
-					.set_selected(selected_index == index || selected_line);
+					let mut pinned_segments = if *line.get_action() == Action::Exec { 2 } else { 3 };
+					if line.get_option().is_some() {
+						pinned_segments += 1;
+					}
+					let graph = graph_lines.get(index).map(String::as_str);
+					if graph.is_some() {
+						pinned_segments += 1;
+					}
+					let warning = (!warnings[index].is_empty()).then(|| warnings[index].join("; "));
+					// the markers add a segment before the indicator, unless they replace the compact indicator
+					let reorder_overlap = reordered_line_indices.contains(&index);
+					if (warning.is_some() || reorder_overlap) && context.is_full_width() {
+						pinned_segments += 1;
+					}
+					let mut options = TodoLineOptions::new(context.is_full_width())
+						.set_selection(selected_index == index, selected_line)
+						.set_graph(graph)
+						.set_warning(warning.as_deref())
+						.set_reorder_overlap(reorder_overlap)
+						.set_search_term(search_term);
+					if todo_file.is_published(line) {
+						options = options.add_badge("published");
+					}
+					if todo_file.is_signed(line) {
+						options = options.add_badge("signed");
+					}
+					let mut view_line =
+						ViewLine::new_with_pinned_segments(get_todo_line_segments(line, &options), pinned_segments)
+							.set_selected(selected_index == index || selected_line);
 
 					if selected_index == index || selected_line {
 						view_line = view_line.set_selected(true).set_padding(' ');
@@ -141,17 +469,20 @@ impl List {
 					updater.push_line(view_line);
 				}
 			}
-			if visual_index != selected_index {
-				updater.ensure_line_visible(visual_index);
+			if let Some(segments) = input_line {
+				updater.push_trailing_line(ViewLine::new_pinned(segments));
 			}
-			updater.ensure_line_visible(selected_index);
+			if visual_row != selected_row {
+				updater.ensure_line_visible(visual_row);
+			}
+			updater.ensure_line_visible(selected_row);
 		});
 		&self.view_data
 	}
 
 	fn get_visual_mode_view_data(&mut self, todo_file: &TodoFile, context: &RenderContext) -> &ViewData {
-		if self.visual_mode_help.is_active() {
-			self.visual_mode_help.get_view_data()
+		if self.get_visual_mode_help(todo_file).is_active() {
+			self.get_visual_mode_help(todo_file).get_view_data()
 		}
 		else {
 			self.update_list_view_data(context, todo_file)
@@ -159,8 +490,8 @@ impl List {
 	}
 
 	fn get_normal_mode_view_data(&mut self, todo_file: &TodoFile, context: &RenderContext) -> &ViewData {
-		if self.normal_mode_help.is_active() {
-			self.normal_mode_help.get_view_data()
+		if self.get_normal_mode_help(todo_file).is_active() {
+			self.get_normal_mode_help(todo_file).get_view_data()
 		}
 		else {
 			self.update_list_view_data(context, todo_file)
@@ -179,14 +510,12 @@ impl List {
 				match meta_event {
 					MetaEvent::MoveCursorLeft => view_sender.scroll_left(),
 					MetaEvent::MoveCursorRight => view_sender.scroll_right(),
-					MetaEvent::MoveCursorDown => Self::move_cursor_down(rebase_todo, 1),
-					MetaEvent::MoveCursorUp => Self::move_cursor_up(rebase_todo, 1),
-					MetaEvent::MoveCursorPageDown => Self::move_cursor_down(rebase_todo, self.height / 2),
-					MetaEvent::MoveCursorPageUp => Self::move_cursor_up(rebase_todo, self.height / 2),
-					MetaEvent::MoveCursorHome => rebase_todo.set_selected_line_index(0),
-					MetaEvent::MoveCursorEnd => {
-						rebase_todo.set_selected_line_index(rebase_todo.get_max_selected_line_index());
-					},
+					MetaEvent::MoveCursorDown => self.move_cursor_down(rebase_todo, 1),
+					MetaEvent::MoveCursorUp => self.move_cursor_up(rebase_todo, 1),
+					MetaEvent::MoveCursorPageDown => self.move_cursor_down(rebase_todo, self.height / 2),
+					MetaEvent::MoveCursorPageUp => self.move_cursor_up(rebase_todo, self.height / 2),
+					MetaEvent::MoveCursorHome => self.move_cursor_up(rebase_todo, rebase_todo.iter().len()),
+					MetaEvent::MoveCursorEnd => self.move_cursor_down(rebase_todo, rebase_todo.iter().len()),
 					MetaEvent::Abort => result = result.state(State::ConfirmAbort),
 					MetaEvent::ForceAbort => {
 						rebase_todo.set_lines(vec![]);
@@ -197,31 +526,73 @@ impl List {
 					MetaEvent::SwapSelectedDown => {
 						let start_index = rebase_todo.get_selected_line_index();
 						let end_index = self.visual_index_start.unwrap_or(start_index);
+						let previous_hashes = get_commit_hashes(rebase_todo);
 
-						if rebase_todo.swap_range_down(start_index, end_index) {
-							if let Some(visual_index_start) = self.visual_index_start {
-								self.visual_index_start = Some(visual_index_start + 1);
+						if let Some((new_start_index, new_end_index)) =
+							self.swap_selected_lines_down(rebase_todo, start_index, end_index)
+						{
+							if self.visual_index_start.is_some() {
+								self.visual_index_start = Some(new_end_index);
 							}
-							Self::move_cursor_down(rebase_todo, 1);
+							rebase_todo.set_selected_line_index(new_start_index);
+							self.record_reorder_overlaps(&previous_hashes, rebase_todo);
 						}
 					},
 					MetaEvent::SwapSelectedUp => {
 						let start_index = rebase_todo.get_selected_line_index();
 						let end_index = self.visual_index_start.unwrap_or(start_index);
+						let previous_hashes = get_commit_hashes(rebase_todo);
 
-						if rebase_todo.swap_range_up(start_index, end_index) {
-							if let Some(visual_index_start) = self.visual_index_start {
-								self.visual_index_start = Some(visual_index_start - 1);
+						if let Some((new_start_index, new_end_index)) =
+							self.swap_selected_lines_up(rebase_todo, start_index, end_index)
+						{
+							if self.visual_index_start.is_some() {
+								self.visual_index_start = Some(new_end_index);
 							}
-							Self::move_cursor_up(rebase_todo, 1);
+							rebase_todo.set_selected_line_index(new_start_index);
+							self.record_reorder_overlaps(&previous_hashes, rebase_todo);
+						}
+					},
+					// the sequencer requires every line to have the same action, so actions cannot be changed
+					MetaEvent::ActionDrop if !rebase_todo.is_sequencer() => {
+						self.set_selected_line_action(rebase_todo, Action::Drop);
+					},
+					MetaEvent::ActionEdit if !rebase_todo.is_sequencer() => {
+						self.set_selected_line_action(rebase_todo, Action::Edit);
+					},
+					MetaEvent::ActionFixup if !rebase_todo.is_sequencer() => {
+						self.set_selected_line_action(rebase_todo, Action::Fixup);
+					},
+					MetaEvent::ActionPick if !rebase_todo.is_sequencer() => {
+						self.set_selected_line_action(rebase_todo, Action::Pick);
+					},
+					MetaEvent::ActionReword if !rebase_todo.is_sequencer() => {
+						self.set_selected_line_action(rebase_todo, Action::Reword);
+					},
+					MetaEvent::ActionSquash if !rebase_todo.is_sequencer() => {
+						self.set_selected_line_action(rebase_todo, Action::Squash);
+					},
+					MetaEvent::CycleFixupOption if !rebase_todo.is_sequencer() => {
+						self.cycle_selected_line_fixup_option(rebase_todo);
+					},
+					MetaEvent::DropAppliedUpstream if !rebase_todo.is_sequencer() => {
+						rebase_todo.drop_applied_upstream();
+					},
+					MetaEvent::Autosquash if !rebase_todo.is_sequencer() => {
+						let _ = rebase_todo.autosquash();
+					},
+					MetaEvent::ToggleGraph => self.show_graph = !self.show_graph,
+					MetaEvent::SearchNext | MetaEvent::SearchPrevious => {
+						if let Some(index) = find_next_match(
+							rebase_todo,
+							self.search_term.as_str(),
+							self.filter.as_ref(),
+							rebase_todo.get_selected_line_index(),
+							meta_event == MetaEvent::SearchNext,
+						) {
+							rebase_todo.set_selected_line_index(index);
 						}
 					},
-					MetaEvent::ActionDrop => self.set_selected_line_action(rebase_todo, Action::Drop),
-					MetaEvent::ActionEdit => self.set_selected_line_action(rebase_todo, Action::Edit),
-					MetaEvent::ActionFixup => self.set_selected_line_action(rebase_todo, Action::Fixup),
-					MetaEvent::ActionPick => self.set_selected_line_action(rebase_todo, Action::Pick),
-					MetaEvent::ActionReword => self.set_selected_line_action(rebase_todo, Action::Reword),
-					MetaEvent::ActionSquash => self.set_selected_line_action(rebase_todo, Action::Squash),
 					MetaEvent::Undo => {
 						if let Some((start_index, end_index)) = rebase_todo.undo() {
 							rebase_todo.set_selected_line_index(start_index);
@@ -252,10 +623,17 @@ impl List {
 						let start_index = rebase_todo.get_selected_line_index();
 						let end_index = self.visual_index_start.unwrap_or(start_index);
 
-						rebase_todo.remove_lines(start_index, end_index);
+						if self.filter.is_some() {
+							// hidden lines within the selection are kept, since the user cannot see them
+							rebase_todo.remove_lines_at(&self.get_selected_line_indices(rebase_todo));
+						}
+						else {
+							rebase_todo.remove_lines(start_index, end_index);
+						}
 						let new_index = min(start_index, end_index);
 
 						rebase_todo.set_selected_line_index(new_index);
+						self.ensure_selected_line_visible(rebase_todo);
 
 						if self.state == ListState::Visual {
 							self.visual_index_start = Some(rebase_todo.get_selected_line_index());
@@ -274,10 +652,10 @@ impl List {
 					},
 					MetaEvent::Help => {
 						if self.state == ListState::Visual {
-							self.visual_mode_help.set_active();
+							self.get_visual_mode_help(rebase_todo).set_active();
 						}
 						else {
-							self.normal_mode_help.set_active();
+							self.get_normal_mode_help(rebase_todo).set_active();
 						}
 					},
 					_ => return None,
@@ -298,8 +676,11 @@ impl List {
 		view_sender: &ViewSender,
 		rebase_todo: &mut TodoFile,
 	) -> ProcessResult {
-		if self.normal_mode_help.is_active() {
-			return ProcessResult::from(self.normal_mode_help.handle_event(event_handler, view_sender));
+		if self.get_normal_mode_help(rebase_todo).is_active() {
+			return ProcessResult::from(
+				self.get_normal_mode_help(rebase_todo)
+					.handle_event(event_handler, view_sender),
+			);
 		}
 
 		let event = get_event(event_handler);
@@ -313,11 +694,12 @@ impl List {
 					MetaEvent::ShowCommit => {
 						if let Some(selected_line) = rebase_todo.get_selected_line() {
 							if selected_line.has_reference() {
+								self.selected_range.clear();
 								result = result.state(State::ShowCommit);
 							}
 						}
 					},
-					MetaEvent::ActionBreak => {
+					MetaEvent::ActionBreak if !rebase_todo.is_sequencer() => {
 						let selected_line_index = rebase_todo.get_selected_line_index();
 						let next_action_is_break = rebase_todo
 							.get_line(selected_line_index + 1)
@@ -328,11 +710,11 @@ impl List {
 								.map_or(false, |line| line.get_action() == &Action::Break);
 							if selected_action_is_break {
 								rebase_todo.remove_lines(selected_line_index, selected_line_index);
-								Self::move_cursor_up(rebase_todo, 1);
+								self.move_cursor_up(rebase_todo, 1);
 							}
 							else {
 								rebase_todo.add_line(selected_line_index + 1, Line::new_break());
-								Self::move_cursor_down(rebase_todo, 1);
+								self.move_cursor_down(rebase_todo, 1);
 							}
 						}
 					},
@@ -340,7 +722,11 @@ impl List {
 						if let Some(selected_line) = rebase_todo.get_selected_line() {
 							if selected_line.is_editable() {
 								self.state = ListState::Edit;
-								self.edit.set_content(selected_line.get_content());
+								self.edit.clear();
+								if selected_line.get_action() == &Action::Merge {
+									self.edit.set_validator(Line::validate_merge);
+								}
+								self.edit.set_content(selected_line.get_edit_content().as_str());
 								self.edit
 									.set_label(format!("{} ", selected_line.get_action().as_string()).as_str());
 								self.edit
@@ -348,7 +734,22 @@ impl List {
 							}
 						}
 					},
-					MetaEvent::InsertLine => result = result.state(State::Insert),
+					MetaEvent::InsertLine if !rebase_todo.is_sequencer() => result = result.state(State::Insert),
+					MetaEvent::SearchStart => {
+						self.state = ListState::Search;
+						self.search_start_index = rebase_todo.get_selected_line_index();
+						self.edit.clear();
+						self.edit.set_label("/");
+						self.edit.set_content(self.search_term.as_str());
+					},
+					MetaEvent::Filter => {
+						self.state = ListState::Filter;
+						self.edit.clear();
+						self.edit.set_label("filter: ");
+						self.edit.set_validator(Filter::validate);
+						self.edit
+							.set_content(self.filter.as_ref().map_or("", |filter| filter.get_query()));
+					},
 					_ => {},
 				}
 			}
@@ -362,13 +763,37 @@ impl List {
 		view_sender: &ViewSender,
 		rebase_todo: &mut TodoFile,
 	) -> ProcessResult {
-		if self.visual_mode_help.is_active() {
-			return ProcessResult::from(self.visual_mode_help.handle_event(event_handler, view_sender));
+		if self.get_visual_mode_help(rebase_todo).is_active() {
+			return ProcessResult::from(
+				self.get_visual_mode_help(rebase_todo)
+					.handle_event(event_handler, view_sender),
+			);
 		}
 
 		let event = get_event(event_handler);
 		self.handle_common_list_input(event, view_sender, rebase_todo)
-			.unwrap_or_else(|| ProcessResult::from(event))
+			.unwrap_or_else(|| {
+				let mut result = ProcessResult::from(event);
+				if event == Event::from(MetaEvent::ShowCommit) {
+					let selected_index = rebase_todo.get_selected_line_index();
+					let visual_index = self.visual_index_start.unwrap_or(selected_index);
+					let (start_index, end_index) = (selected_index.min(visual_index), selected_index.max(visual_index));
+					let commit_count = (start_index..=end_index)
+						.filter_map(|index| rebase_todo.get_line(index))
+						.filter(|line| is_path_changing_line(line))
+						.count();
+					// a range with a single commit is shown the same as in normal mode
+					if commit_count > 1 {
+						self.selected_range.set(start_index, end_index);
+						result = result.state(State::ShowCommit);
+					}
+					else if rebase_todo.get_selected_line().map_or(false, Line::has_reference) {
+						self.selected_range.clear();
+						result = result.state(State::ShowCommit);
+					}
+				}
+				result
+			})
 	}
 
 	fn handle_edit_mode_input(&mut self, event_handler: &EventHandler, rebase_todo: &mut TodoFile) -> ProcessResult {
@@ -385,4 +810,51 @@ impl List {
 		}
 		result
 	}
+
+	fn handle_search_mode_input(&mut self, event_handler: &EventHandler, rebase_todo: &mut TodoFile) -> ProcessResult {
+		let event = self.edit.handle_event(event_handler);
+		let result = ProcessResult::from(event);
+		if let Event::Key(KeyEvent { code: KeyCode::Esc, .. }) = event {
+			self.search_term.clear();
+			rebase_todo.set_selected_line_index(self.search_start_index);
+			self.state = ListState::Normal;
+			return result;
+		}
+
+		self.search_term = self.edit.get_content();
+		if self.edit.is_finished() {
+			self.state = ListState::Normal;
+		}
+		else {
+			let index = find_match(
+				rebase_todo,
+				self.search_term.as_str(),
+				self.filter.as_ref(),
+				self.search_start_index,
+			)
+			.unwrap_or(self.search_start_index);
+			rebase_todo.set_selected_line_index(index);
+		}
+		result
+	}
+
+	fn handle_filter_mode_input(&mut self, event_handler: &EventHandler) -> ProcessResult {
+		let event = self.edit.handle_event(event_handler);
+		let result = ProcessResult::from(event);
+		if let Event::Key(KeyEvent { code: KeyCode::Esc, .. }) = event {
+			self.state = ListState::Normal;
+		}
+		else if self.edit.is_finished() {
+			let query = self.edit.get_content();
+			// the query has already been validated by the edit
+			self.filter = if query.is_empty() {
+				None
+			}
+			else {
+				Filter::new(query.as_str()).ok()
+			};
+			self.state = ListState::Normal;
+		}
+		result
+	}
 }
@@ -0,0 +1,60 @@
+use std::collections::{HashMap, HashSet};
+
+use todo_file::TodoFile;
+
+use crate::modules::is_path_changing_line;
+
+fn get_commit_positions(todo_file: &TodoFile) -> HashMap<&str, usize> {
+	let mut positions = HashMap::new();
+	for (index, line) in todo_file.iter().enumerate() {
+		if is_path_changing_line(line) {
+			let _ = positions.entry(line.get_hash()).or_insert(index);
+		}
+	}
+	positions
+}
+
+/// Get the hashes of the commits of the todo file, in order.
+pub(super) fn get_commit_hashes(todo_file: &TodoFile) -> Vec<String> {
+	todo_file
+		.iter()
+		.filter(|line| is_path_changing_line(line))
+		.map(|line| String::from(line.get_hash()))
+		.collect()
+}
+
+/// Get the pairs of commits that have changed order since `previous_hashes`, as the earlier and later commit of the
+/// previous order.
+pub(super) fn get_reordered_pairs(previous_hashes: &[String], todo_file: &TodoFile) -> Vec<(String, String)> {
+	let positions = get_commit_positions(todo_file);
+	let mut pairs = vec![];
+	for (index, earlier) in previous_hashes.iter().enumerate() {
+		for later in &previous_hashes[index + 1..] {
+			if let (Some(earlier_position), Some(later_position)) =
+				(positions.get(earlier.as_str()), positions.get(later.as_str()))
+			{
+				if earlier_position > later_position {
+					pairs.push((earlier.clone(), later.clone()));
+				}
+			}
+		}
+	}
+	pairs
+}
+
+/// Get the indices of the lines of the pairs that are still in the reverse of their recorded order.
+pub(super) fn get_reordered_line_indices(todo_file: &TodoFile, pairs: &[(String, String)]) -> HashSet<usize> {
+	let positions = get_commit_positions(todo_file);
+	let mut indices = HashSet::new();
+	for &(ref earlier, ref later) in pairs {
+		if let (Some(&earlier_position), Some(&later_position)) =
+			(positions.get(earlier.as_str()), positions.get(later.as_str()))
+		{
+			if earlier_position > later_position {
+				let _ = indices.insert(earlier_position);
+				let _ = indices.insert(later_position);
+			}
+		}
+	}
+	indices
+}
@@ -0,0 +1,111 @@
+use todo_file::{Line, TodoFile};
+
+use super::filter::Filter;
+
+/// Does the text contain the search term, ignoring ASCII case.
+fn contains_ignore_case(text: &str, term: &str) -> bool {
+	text.to_ascii_lowercase().contains(term.to_ascii_lowercase().as_str())
+}
+
+/// Does the commit hash start with the search term, ignoring ASCII case.
+pub(super) fn is_hash_match(hash: &str, term: &str) -> bool {
+	!term.is_empty()
+		&& hash
+			.to_ascii_lowercase()
+			.starts_with(term.to_ascii_lowercase().as_str())
+}
+
+/// Does the line match the search term, either as a prefix of the commit hash or anywhere in the content.
+pub(super) fn is_match(line: &Line, term: &str) -> bool {
+	!term.is_empty() && (is_hash_match(line.get_hash(), term) || contains_ignore_case(line.get_content(), term))
+}
+
+/// Get the ranges, as byte offsets, of the text that match the search term. Since only ASCII case is ignored, the
+/// offsets of the lowercase text match the offsets of the original text.
+pub(crate) fn get_match_ranges(text: &str, term: &str) -> Vec<(usize, usize)> {
+	if term.is_empty() {
+		return vec![];
+	}
+	let lowercase_term = term.to_ascii_lowercase();
+	text.to_ascii_lowercase()
+		.match_indices(lowercase_term.as_str())
+		.map(|(start, matched)| (start, start + matched.len()))
+		.collect()
+}
+
+fn find_match_at_offsets<I>(
+	todo_file: &TodoFile,
+	term: &str,
+	filter: Option<&Filter>,
+	index: usize,
+	offsets: I,
+) -> Option<usize>
+where
+	I: Iterator<Item = usize>,
+{
+	let len = todo_file.iter().len();
+	offsets.map(|offset| (index + offset) % len).find(|&i| {
+		todo_file.get_line(i).map_or(false, |line| {
+			is_match(line, term) && filter.map_or(true, |f| f.is_match(line))
+		})
+	})
+}
+
+/// Find the index of the first line that matches the search term, starting at the start index and wrapping around
+/// the end of the list. Lines hidden by the filter are never matched.
+pub(super) fn find_match(
+	todo_file: &TodoFile,
+	term: &str,
+	filter: Option<&Filter>,
+	start_index: usize,
+) -> Option<usize> {
+	let len = todo_file.iter().len();
+	find_match_at_offsets(todo_file, term, filter, start_index, 0..len)
+}
+
+/// Find the index of the next, or previous, line after the index that matches the search term, wrapping around the
+/// ends of the list. Lines hidden by the filter are never matched.
+pub(super) fn find_next_match(
+	todo_file: &TodoFile,
+	term: &str,
+	filter: Option<&Filter>,
+	index: usize,
+	forward: bool,
+) -> Option<usize> {
+	let len = todo_file.iter().len();
+	if forward {
+		find_match_at_offsets(todo_file, term, filter, index, 1..=len)
+	}
+	else {
+		find_match_at_offsets(todo_file, term, filter, index, (0..len).rev())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+
+	#[rstest]
+	#[case::empty_term("pick aaa c1", "", false)]
+	#[case::hash_prefix("pick abcdef c1", "abc", true)]
+	#[case::hash_prefix_ignore_case("pick abcdef c1", "ABC", true)]
+	#[case::hash_not_prefix("pick abcdef c1", "def", false)]
+	#[case::content("pick aaa Some summary", "summ", true)]
+	#[case::content_ignore_case("pick aaa Some summary", "SOME", true)]
+	#[case::no_match("pick aaa Some summary", "other", false)]
+	#[case::exec_command("exec make test", "test", true)]
+	fn is_match_line(#[case] line: &str, #[case] term: &str, #[case] expected: bool) {
+		assert_eq!(is_match(&Line::new(line).unwrap(), term), expected);
+	}
+
+	#[rstest]
+	#[case::empty_term("foo bar foo", "", &[])]
+	#[case::single("foo bar", "bar", &[(4, 7)])]
+	#[case::multiple("foo bar foo", "foo", &[(0, 3), (8, 11)])]
+	#[case::ignore_case("Foo bar FOO", "foo", &[(0, 3), (8, 11)])]
+	fn match_ranges(#[case] text: &str, #[case] term: &str, #[case] expected: &[(usize, usize)]) {
+		assert_eq!(get_match_ranges(text, term), expected);
+	}
+}
@@ -2,7 +2,7 @@ use ::input::{KeyCode, KeyModifiers, MouseEvent, MouseEventKind};
 use view::assert_rendered_output;
 
 use super::*;
-use crate::{assert_process_result, testutil::module_test};
+use crate::{assert_process_result, modules::CommitPaths, testutil::module_test};
 
 #[test]
 fn render_empty_list() {
@@ -34,6 +34,7 @@ fn render_full() {
 			"label ref",
 			"reset ref",
 			"merge command",
+			"update-ref reference",
 		],
 		&[],
 		|test_context| {
@@ -54,7 +55,9 @@ fn render_full() {
 				"{Normal}   {ActionEdit}edit   {Normal}11111111 {Normal}comment 7",
 				"{Normal}   {ActionLabel}label  {Normal}ref",
 				"{Normal}   {ActionReset}reset  {Normal}ref",
-				"{Normal}   {ActionMerge}merge  {Normal}command"
+				"{WarningColor}!{Normal}  {ActionMerge}merge  {Normal}command{WarningColor} ! Merge of unknown label: \
+				 command",
+				"{Normal}   {ActionUpdateRef}update-ref {Normal}reference"
 			);
 		},
 	);
@@ -76,6 +79,7 @@ fn render_compact() {
 			"label ref",
 			"reset ref",
 			"merge command",
+			"update-ref reference",
 		],
 		&[],
 		|mut test_context| {
@@ -97,7 +101,239 @@ fn render_compact() {
 				"{Normal} {ActionEdit}e {Normal}111 {Normal}comment 7",
 				"{Normal} {ActionLabel}l {Normal}ref",
 				"{Normal} {ActionReset}t {Normal}ref",
-				"{Normal} {ActionMerge}m {Normal}command"
+				"{WarningColor}!{ActionMerge}m {Normal}command{WarningColor} ! Merge of unknown label: command",
+				"{Normal} {ActionUpdateRef}u {Normal}reference"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_merge_with_commit() {
+	module_test(
+		&["merge -C aaaaaaaa ref # Merge 'ref'", "merge ref"],
+		&[],
+		|test_context| {
+			let mut module = List::new(&Config::new());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{WarningColor}!{Normal}> {ActionMerge}merge  {ActionMerge}-C {Normal}aaaaaaaa \
+				 {Normal}ref{Normal,Dimmed} # Merge 'ref'{WarningColor} ! Merge of unknown label: ref{Normal}{Pad( )}",
+				"{WarningColor}!{Normal}  {ActionMerge}merge  {Normal}ref{WarningColor} ! Merge of unknown label: ref"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_merge_with_commit_compact() {
+	module_test(
+		&["merge -C aaaaaaaa ref # Merge 'ref'", "merge ref"],
+		&[],
+		|mut test_context| {
+			test_context.render_context.update(30, 300);
+			let mut module = List::new(&Config::new());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal}>{ActionMerge}m {ActionMerge}-C {Normal}aaa {Normal}ref{Normal,Dimmed} # Merge \
+				 'ref'{WarningColor} ! Merge of unknown label: ref{Normal}{Pad( )}",
+				"{WarningColor}!{ActionMerge}m {Normal}ref{WarningColor} ! Merge of unknown label: ref"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_graph() {
+	module_test(
+		&[
+			"label onto",
+			"reset onto",
+			"pick aaa c1",
+			"label feature",
+			"reset onto",
+			"merge -C bbb feature # Merge",
+		],
+		&[Event::from(MetaEvent::ToggleGraph)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {IndicatorColor}|   {ActionLabel}label  {Normal}onto{Normal}{Pad( )}",
+				"{Normal}   {IndicatorColor}| \\ {ActionReset}reset  {Normal}onto",
+				"{Normal}   {IndicatorColor}| * {ActionPick}pick   {Normal}aaa      {Normal}c1",
+				"{Normal}   {IndicatorColor}| | {ActionLabel}label  {Normal}feature",
+				"{Normal}   {IndicatorColor}| | {ActionReset}reset  {Normal}onto",
+				"{Normal}   {IndicatorColor}* / {ActionMerge}merge  {ActionMerge}-C {Normal}bbb      \
+				 {Normal}feature{Normal,Dimmed} # Merge"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_graph_toggle_off() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::ToggleGraph), Event::from(MetaEvent::ToggleGraph)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_warning_duplicate_commit() {
+	module_test(&["pick aaa c1", "pick bbb c2", "pick aaa c1"], &[], |test_context| {
+		let mut module = List::new(&Config::new());
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2",
+			"{WarningColor}!{Normal}  {ActionPick}pick   {Normal}aaa      {Normal}c1{WarningColor} ! Commit aaa \
+			 appears more than once"
+		);
+	});
+}
+
+#[test]
+fn render_predicted_conflicts() {
+	module_test(&["pick aaa c1", "pick bbb c2"], &[], |mut test_context| {
+		let version = test_context.rebase_todo_file.get_version();
+		test_context.rebase_todo_file.set_conflicts(version, vec![vec![], vec![
+			String::from("a.txt"),
+			String::from("b.txt"),
+		]]);
+		let mut module = List::new(&Config::new());
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+			"{WarningColor}!{Normal}  {ActionPick}pick   {Normal}bbb      {Normal}c2{WarningColor} ! Predicted \
+			 conflict in a.txt, b.txt"
+		);
+	});
+}
+
+#[test]
+fn render_predicted_conflicts_outdated() {
+	module_test(&["pick aaa c1", "pick bbb c2"], &[], |mut test_context| {
+		let version = test_context.rebase_todo_file.get_version();
+		test_context
+			.rebase_todo_file
+			.set_conflicts(version, vec![vec![], vec![String::from("a.txt")]]);
+		let lines = test_context.rebase_todo_file.get_lines_owned();
+		test_context.rebase_todo_file.set_lines(lines);
+		let mut module = List::new(&Config::new());
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2"
+		);
+	});
+}
+
+#[test]
+fn render_applied_upstream() {
+	module_test(&["pick aaa c1", "pick bbb c2"], &[], |mut test_context| {
+		test_context
+			.rebase_todo_file
+			.set_applied_upstream(vec![String::from("bbb")]);
+		let mut module = List::new(&Config::new());
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+			"{WarningColor}!{Normal}  {ActionPick}pick   {Normal}bbb      {Normal}c2{WarningColor} ! Already applied \
+			 upstream"
+		);
+	});
+}
+
+#[test]
+fn render_published_and_signed() {
+	module_test(&["pick aaa c1", "pick bbb c2"], &[], |mut test_context| {
+		test_context.rebase_todo_file.set_published(vec![String::from("aaa")]);
+		test_context
+			.rebase_todo_file
+			.set_signed(vec![String::from("aaa"), String::from("bbb")]);
+		let mut module = List::new(&Config::new());
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{IndicatorColor} \
+			 [published]{IndicatorColor} [signed]{Normal}{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2{IndicatorColor} [signed]"
+		);
+	});
+}
+
+#[test]
+fn render_fixup_option() {
+	module_test(
+		&["fixup -C aaaaaaaa comment 1", "fixup -c bbbbbbbb comment 2"],
+		&[],
+		|test_context| {
+			let mut module = List::new(&Config::new());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{WarningColor}!{Normal}> {ActionFixup}fixup  {ActionFixup}-C {Normal}aaaaaaaa \
+				 {Normal}comment 1{WarningColor} ! Cannot fixup without a previous commit{Normal}{Pad( )}",
+				"{Normal}   {ActionFixup}fixup  {ActionFixup}-c {Normal}bbbbbbbb {Normal}comment 2"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_fixup_option_compact() {
+	module_test(
+		&["fixup -C aaaaaaaa comment 1", "fixup -c bbbbbbbb comment 2"],
+		&[],
+		|mut test_context| {
+			test_context.render_context.update(30, 300);
+			let mut module = List::new(&Config::new());
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal}>{ActionFixup}f {ActionFixup}-C {Normal}aaa {Normal}comment 1{WarningColor} ! \
+				 Cannot fixup without a previous commit{Normal}{Pad( )}",
+				"{Normal} {ActionFixup}f {ActionFixup}-c {Normal}bbb {Normal}comment 2"
 			);
 		},
 	);
@@ -110,6 +346,7 @@ fn move_cursor_down_1() {
 		&[Event::from(MetaEvent::MoveCursorDown)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -131,6 +368,7 @@ fn move_cursor_down_view_end() {
 		&[Event::from(MetaEvent::MoveCursorDown); 2],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -152,6 +390,7 @@ fn move_cursor_down_past_end() {
 		&[Event::from(MetaEvent::MoveCursorDown); 3],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -177,6 +416,7 @@ fn move_cursor_down_scroll_bottom_move_up_one() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -202,6 +442,7 @@ fn move_cursor_down_scroll_bottom_move_up_top() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -226,6 +467,7 @@ fn move_cursor_up_attempt_above_top() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -248,6 +490,7 @@ fn move_cursor_down_attempt_below_bottom() {
 		&[Event::from(MetaEvent::MoveCursorDown); 4],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -270,6 +513,7 @@ fn move_cursor_page_up_from_top() {
 		&[Event::from(MetaEvent::MoveCursorPageUp)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -297,6 +541,7 @@ fn move_cursor_page_up_from_one_page_down() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -324,6 +569,7 @@ fn move_cursor_page_up_from_one_page_down_minus_1() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -352,6 +598,7 @@ fn move_cursor_page_up_from_bottom() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -379,6 +626,7 @@ fn move_cursor_home() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -401,6 +649,7 @@ fn move_cursor_end() {
 		&[Event::from(MetaEvent::MoveCursorEnd)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -423,6 +672,7 @@ fn move_cursor_page_down_past_bottom() {
 		&[Event::from(MetaEvent::MoveCursorPageDown); 3],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -451,6 +701,7 @@ fn move_cursor_page_down_one_from_bottom() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -476,6 +727,7 @@ fn move_cursor_page_down_one_page_from_bottom() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -518,6 +770,7 @@ fn mouse_scroll() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -539,6 +792,7 @@ fn visual_mode_start() {
 		&[Event::from(MetaEvent::ToggleVisualMode)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -563,6 +817,7 @@ fn visual_mode_start_cursor_down_one() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -593,6 +848,7 @@ fn visual_mode_start_cursor_page_down() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			module.height = 4;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
@@ -630,6 +886,7 @@ fn visual_mode_start_cursor_from_bottom_move_up() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -669,6 +926,7 @@ fn visual_mode_start_cursor_from_bottom_to_top() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -736,7 +994,8 @@ fn change_selected_line_to_fixup() {
 				view_data,
 				"{TITLE}{HELP}",
 				"{BODY}",
-				"{Selected}{Normal} > {ActionFixup}fixup  {Normal}aaa      {Normal}c1{Normal}{Pad( )}"
+				"{Selected}{WarningColor}!{Normal}> {ActionFixup}fixup  {Normal}aaa      {Normal}c1{WarningColor} ! \
+				 Cannot fixup without a previous commit{Normal}{Pad( )}"
 			);
 		},
 	);
@@ -793,8 +1052,74 @@ fn change_selected_line_to_squash() {
 				view_data,
 				"{TITLE}{HELP}",
 				"{BODY}",
-				"{Selected}{Normal} > {ActionSquash}squash {Normal}aaa      {Normal}c1{Normal}{Pad( )}"
+				"{Selected}{WarningColor}!{Normal}> {ActionSquash}squash {Normal}aaa      {Normal}c1{WarningColor} ! \
+				 Cannot squash without a previous commit{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_cycle_fixup_option() {
+	module_test(
+		&["fixup aaa c1"],
+		&[Event::from(MetaEvent::CycleFixupOption)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"fixup -C aaa c1"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_cycle_fixup_option_with_editor() {
+	module_test(
+		&["fixup aaa c1"],
+		&[Event::from(MetaEvent::CycleFixupOption); 2],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"fixup -c aaa c1"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_cycle_fixup_option_cycle_to_none() {
+	module_test(
+		&["fixup aaa c1"],
+		&[Event::from(MetaEvent::CycleFixupOption); 3],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"fixup aaa c1"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_cycle_fixup_option_non_fixup() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::CycleFixupOption)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"pick aaa c1"
 			);
+			assert!(test_context.rebase_todo_file.undo().is_none());
 		},
 	);
 }
@@ -870,6 +1195,7 @@ fn change_selected_line_auto_select_next_with_next_line() {
 			let mut config = Config::new();
 			config.auto_select_next = true;
 			let mut module = List::new(&config);
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -890,6 +1216,7 @@ fn change_selected_line_swap_down() {
 		&[Event::from(MetaEvent::SwapSelectedDown)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -904,6 +1231,80 @@ fn change_selected_line_swap_down() {
 	);
 }
 
+fn create_commit_paths() -> CommitPaths {
+	let commit_paths = CommitPaths::new(&Config::new());
+	commit_paths.set_paths("aaa", vec![String::from("a.txt")]);
+	commit_paths.set_paths("bbb", vec![String::from("a.txt"), String::from("b.txt")]);
+	commit_paths.set_paths("ccc", vec![String::from("c.txt")]);
+	commit_paths
+}
+
+#[test]
+fn change_selected_line_swap_down_reorder_overlap() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&[Event::from(MetaEvent::SwapSelectedDown)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new()).with_commit_paths(create_commit_paths());
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{IndicatorColor}~{Normal}  {ActionPick}pick   {Normal}bbb      {Normal}c2",
+				"{Selected}{IndicatorColor}~{Normal}> {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      {Normal}c3"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_swap_down_no_reorder_overlap() {
+	module_test(
+		&["pick aaa c1", "pick ccc c3", "pick bbb c2"],
+		&[Event::from(MetaEvent::SwapSelectedDown)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new()).with_commit_paths(create_commit_paths());
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      {Normal}c3",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_swap_back_clears_reorder_overlap() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&[
+			Event::from(MetaEvent::SwapSelectedDown),
+			Event::from(MetaEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new()).with_commit_paths(create_commit_paths());
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      {Normal}c3"
+			);
+		},
+	);
+}
+
 #[test]
 fn change_selected_line_swap_up() {
 	module_test(
@@ -915,6 +1316,7 @@ fn change_selected_line_swap_up() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -930,10 +1332,74 @@ fn change_selected_line_swap_up() {
 }
 
 #[test]
-fn normal_mode_show_commit_when_hash_available() {
+fn change_selected_line_swap_down_with_update_ref() {
 	module_test(
-		&["pick aaa c1"],
-		&[Event::from(MetaEvent::ShowCommit)],
+		&["pick aaa c1", "update-ref ref", "pick aaa c2"],
+		&[Event::from(MetaEvent::SwapSelectedDown)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			module.lint = false;
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Normal}   {ActionPick}pick   {Normal}aaa      {Normal}c2",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionUpdateRef}update-ref {Normal}ref"
+			);
+		},
+	);
+}
+
+#[test]
+fn change_selected_line_swap_up_with_update_ref() {
+	module_test(
+		&["pick aaa c1", "update-ref ref", "pick aaa c2"],
+		&[
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			module.lint = false;
+			let _ = test_context.handle_all_events(&mut module);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c2{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}aaa      {Normal}c1",
+				"{Normal}   {ActionUpdateRef}update-ref {Normal}ref"
+			);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_show_commit_when_hash_available() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::ShowCommit)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCommit),
+				state = State::ShowCommit
+			);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_show_commit_when_merge_has_commit() {
+	module_test(
+		&["merge -C aaa ref # Merge"],
+		&[Event::from(MetaEvent::ShowCommit)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
 			assert_process_result!(
@@ -971,6 +1437,89 @@ fn normal_mode_do_not_show_commit_when_hash_not_available() {
 	);
 }
 
+#[test]
+fn visual_mode_show_commit_range() {
+	module_test(
+		&["pick aaa c1", "exec make", "pick bbb c2", "pick ccc c3"],
+		&[
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ShowCommit),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_n_events(&mut module, 3);
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCommit),
+				state = State::ShowCommit
+			);
+			assert_eq!(module.selected_range.get(), Some((0, 2)));
+		},
+	);
+}
+
+#[test]
+fn visual_mode_show_commit_range_with_single_commit() {
+	module_test(
+		&["pick aaa c1", "exec make", "pick bbb c2"],
+		&[
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ShowCommit),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			module.selected_range.set(0, 2);
+			let _ = test_context.handle_n_events(&mut module, 3);
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCommit),
+				state = State::ShowCommit
+			);
+			assert_eq!(module.selected_range.get(), None);
+		},
+	);
+}
+
+#[test]
+fn visual_mode_show_commit_range_without_commits() {
+	module_test(
+		&["pick aaa c1", "exec make", "break"],
+		&[
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ShowCommit),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_n_events(&mut module, 3);
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCommit)
+			);
+			assert_eq!(module.selected_range.get(), None);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_show_commit_clears_range() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::ShowCommit)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			module.selected_range.set(0, 1);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(module.selected_range.get(), None);
+		},
+	);
+}
+
 #[test]
 fn normal_mode_abort() {
 	module_test(
@@ -1105,6 +1654,109 @@ fn normal_mode_open_external_editor() {
 	);
 }
 
+#[test]
+fn normal_mode_autosquash() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2", "pick ccc fixup! c1"],
+		&[Event::from(MetaEvent::Autosquash)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::Autosquash)
+			);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionFixup}fixup  {Normal}ccc      {Normal}fixup! c1",
+				"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2"
+			);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_drop_applied_upstream() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&[Event::from(MetaEvent::DropAppliedUpstream)],
+		|mut test_context| {
+			test_context
+				.rebase_todo_file
+				.set_applied_upstream(vec![String::from("aaa"), String::from("ccc")]);
+			let mut module = List::new(&Config::new());
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::DropAppliedUpstream)
+			);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionDrop}drop   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2",
+				"{Normal}   {ActionDrop}drop   {Normal}ccc      {Normal}c3"
+			);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_drop_applied_upstream_undo() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2", "pick ccc c3"],
+		&[
+			Event::from(MetaEvent::DropAppliedUpstream),
+			Event::from(MetaEvent::Undo),
+		],
+		|mut test_context| {
+			test_context
+				.rebase_todo_file
+				.set_applied_upstream(vec![String::from("aaa"), String::from("ccc")]);
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{WarningColor}!{Normal}> {ActionPick}pick   {Normal}aaa      {Normal}c1{WarningColor} ! \
+				 Already applied upstream{Normal}{Pad( )}",
+				"{Selected}{Normal,Dimmed} > {ActionPick}pick   {Normal}bbb      {Normal}c2{Normal}{Pad( )}",
+				"{Selected}{WarningColor}!{Normal,Dimmed}> {ActionPick}pick   {Normal}ccc      \
+				 {Normal}c3{WarningColor} ! Already applied upstream{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_autosquash_undo() {
+	module_test(
+		&[
+			"pick aaa c1",
+			"pick bbb c2",
+			"pick ccc fixup! c1",
+			"pick ddd squash! c2",
+		],
+		&[Event::from(MetaEvent::Autosquash), Event::from(MetaEvent::Undo)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Normal}   {ActionPick}pick   {Normal}aaa      {Normal}c1",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}bbb      {Normal}c2{Normal}{Pad( )}",
+				"{Selected}{Normal,Dimmed} > {ActionPick}pick   {Normal}ccc      {Normal}fixup! c1{Normal}{Pad( )}",
+				"{Selected}{Normal,Dimmed} > {ActionPick}pick   {Normal}ddd      {Normal}squash! c2{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
 #[test]
 fn normal_mode_undo() {
 	module_test(
@@ -1308,6 +1960,7 @@ fn visual_mode_action_change_top_bottom() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1336,6 +1989,7 @@ fn visual_mode_action_change_bottom_top() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1369,6 +2023,7 @@ fn visual_mode_action_change_drop() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1404,6 +2059,7 @@ fn visual_mode_action_change_edit() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1439,6 +2095,7 @@ fn visual_mode_action_change_fixup() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1474,6 +2131,7 @@ fn visual_mode_action_change_pick() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1509,6 +2167,7 @@ fn visual_mode_action_change_reword() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1544,6 +2203,7 @@ fn visual_mode_action_change_squash() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1560,6 +2220,56 @@ fn visual_mode_action_change_squash() {
 	);
 }
 
+#[test]
+fn visual_mode_cycle_fixup_option() {
+	module_test(
+		&["fixup aaa c1", "pick bbb c2", "fixup ccc c3"],
+		&[
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::CycleFixupOption),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["fixup -C aaa c1", "pick bbb c2", "fixup -C ccc c3"]
+			);
+		},
+	);
+}
+
+#[test]
+fn visual_mode_cycle_fixup_option_mixed_options() {
+	module_test(
+		&["fixup -C aaa c1", "fixup bbb c2", "fixup -c ccc c3"],
+		&[
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::CycleFixupOption),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["fixup aaa c1", "fixup bbb c2", "fixup ccc c3"]
+			);
+		},
+	);
+}
+
 #[test]
 fn visual_mode_abort() {
 	module_test(
@@ -1655,6 +2365,7 @@ fn visual_mode_swap_down_from_top_to_bottom_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1692,6 +2403,7 @@ fn visual_mode_swap_down_from_bottom_to_top_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1727,6 +2439,7 @@ fn visual_mode_swap_up_from_top_to_bottom_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1764,6 +2477,7 @@ fn visual_mode_swap_up_from_bottom_to_top_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1802,6 +2516,7 @@ fn visual_mode_swap_down_to_limit_from_bottom_to_top_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1838,6 +2553,7 @@ fn visual_mode_swap_down_to_limit_from_top_to_bottom_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1874,6 +2590,7 @@ fn visual_mode_swap_up_to_limit_from_top_to_bottom_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -1912,6 +2629,7 @@ fn visual_mode_swap_up_to_limit_from_bottom_to_top_selection() {
 		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			module.lint = false;
 			let _ = test_context.handle_all_events(&mut module);
 			let view_data = test_context.build_view_data(&mut module);
 			assert_rendered_output!(
@@ -2272,40 +2990,124 @@ fn edit_mode_handle_event() {
 }
 
 #[test]
-fn scroll_right() {
+fn edit_mode_render_merge() {
 	module_test(
-		&["pick aaa c1"],
-		&[Event::from(MetaEvent::MoveCursorRight)],
+		&["merge -C aaa ref # Merge"],
+		&[Event::from(MetaEvent::Edit)],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
 			let _ = test_context.handle_all_events(&mut module);
-			test_context.view_sender_context.assert_render_action(&["ScrollRight"]);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Modifying line: merge -C aaa ref # Merge",
+				"",
+				"{BODY}",
+				"{Normal,Dimmed}merge {Normal}-C aaa ref # Merge{Normal,Underline}",
+				"{TRAILING}",
+				"{IndicatorColor}Enter to finish"
+			);
 		},
 	);
 }
 
 #[test]
-fn scroll_left() {
+fn edit_mode_merge_valid() {
 	module_test(
-		&["pick aaa c1"],
-		&[Event::from(MetaEvent::MoveCursorLeft)],
+		&["merge -C aaa ref # Merge"],
+		&[
+			Event::from(MetaEvent::Edit),
+			Event::from(KeyCode::Home),
+			Event::from(KeyCode::Right),
+			Event::from(KeyCode::Delete),
+			Event::from('c'),
+			Event::from(KeyCode::Enter),
+		],
 		|mut test_context| {
 			let mut module = List::new(&Config::new());
+			let _ = test_context.build_view_data(&mut module);
 			let _ = test_context.handle_all_events(&mut module);
-			test_context.view_sender_context.assert_render_action(&["ScrollLeft"]);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"merge -c aaa ref # Merge"
+			);
 		},
 	);
 }
 
 #[test]
-fn normal_mode_help() {
-	module_test(&["pick aaa c1"], &[Event::from(MetaEvent::Help)], |mut test_context| {
-		let mut module = List::new(&Config::new());
-		module.state = ListState::Normal;
-		let _ = test_context.handle_all_events(&mut module);
-		let view_data = test_context.build_view_data(&mut module);
-		assert_rendered_output!(
-			view_data,
+fn edit_mode_merge_invalid() {
+	module_test(
+		&["merge -C aaa ref"],
+		&[
+			Event::from(MetaEvent::Edit),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Edit);
+			let view_data = test_context.build_view_data(&mut module);
+			assert_rendered_output!(
+				view_data,
+				"{TITLE}",
+				"{LEADING}",
+				"{IndicatorColor}Modifying line: merge -C aaa ref",
+				"",
+				"{BODY}",
+				"{Normal,Dimmed}merge {Normal}-C aaa {Normal,Underline}",
+				"{TRAILING}",
+				"{IndicatorColor}Missing label for merge",
+				"{IndicatorColor}Enter to finish"
+			);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(0).unwrap().to_text(),
+				"merge -C aaa ref"
+			);
+		},
+	);
+}
+
+#[test]
+fn scroll_right() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::MoveCursorRight)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			test_context.view_sender_context.assert_render_action(&["ScrollRight"]);
+		},
+	);
+}
+
+#[test]
+fn scroll_left() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::MoveCursorLeft)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			test_context.view_sender_context.assert_render_action(&["ScrollLeft"]);
+		},
+	);
+}
+
+#[test]
+fn normal_mode_help() {
+	module_test(&["pick aaa c1"], &[Event::from(MetaEvent::Help)], |mut test_context| {
+		let mut module = List::new(&Config::new());
+		module.state = ListState::Normal;
+		let _ = test_context.handle_all_events(&mut module);
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
 			"{TITLE}",
 			"{LEADING}",
 			"{Normal,Underline} Key      Action{Normal,Underline}{Pad( )}",
@@ -2333,13 +3135,22 @@ fn normal_mode_help() {
 			"{IndicatorColor} e       {Normal,Dimmed}|{Normal}Set selected commit to be edited",
 			"{IndicatorColor} s       {Normal,Dimmed}|{Normal}Set selected commit to be squashed",
 			"{IndicatorColor} f       {Normal,Dimmed}|{Normal}Set selected commit to be fixed-up",
+			"{IndicatorColor} u       {Normal,Dimmed}|{Normal}Cycle selected fixup between no option, -C and -c",
+			"{IndicatorColor} A       {Normal,Dimmed}|{Normal}Move fixup!, squash! and amend! commits below their \
+			 targets",
 			"{IndicatorColor} d       {Normal,Dimmed}|{Normal}Set selected commit to be dropped",
+			"{IndicatorColor} D       {Normal,Dimmed}|{Normal}Drop the commits already applied upstream",
 			"{IndicatorColor} E       {Normal,Dimmed}|{Normal}Edit an exec action's command",
 			"{IndicatorColor} I       {Normal,Dimmed}|{Normal}Insert a new line",
 			"{IndicatorColor} Delete  {Normal,Dimmed}|{Normal}Completely remove the selected line",
 			"{IndicatorColor} Controlz{Normal,Dimmed}|{Normal}Undo the last change",
 			"{IndicatorColor} Controly{Normal,Dimmed}|{Normal}Redo the previous undone change",
 			"{IndicatorColor} !       {Normal,Dimmed}|{Normal}Open the todo file in the default editor",
+			"{IndicatorColor} G       {Normal,Dimmed}|{Normal}Toggle the rebase-merges graph view",
+			"{IndicatorColor} /       {Normal,Dimmed}|{Normal}Search the list",
+			"{IndicatorColor} n       {Normal,Dimmed}|{Normal}Move to the next search match",
+			"{IndicatorColor} N       {Normal,Dimmed}|{Normal}Move to the previous search match",
+			"{IndicatorColor} F       {Normal,Dimmed}|{Normal}Filter the list by pattern or action",
 			"{TRAILING}",
 			"{IndicatorColor}Press any key to close"
 		);
@@ -2382,6 +3193,7 @@ fn visual_mode_help() {
 			"{IndicatorColor} Left    {Normal,Dimmed}|{Normal}Scroll content to the left",
 			"{IndicatorColor} Right   {Normal,Dimmed}|{Normal}Scroll content to the right",
 			"{IndicatorColor} ?       {Normal,Dimmed}|{Normal}Show help",
+			"{IndicatorColor} c       {Normal,Dimmed}|{Normal}Show changes of selected commits",
 			"{IndicatorColor} j       {Normal,Dimmed}|{Normal}Move selected commits down",
 			"{IndicatorColor} k       {Normal,Dimmed}|{Normal}Move selected commits up",
 			"{IndicatorColor} p       {Normal,Dimmed}|{Normal}Set selected commits to be picked",
@@ -2389,10 +3201,14 @@ fn visual_mode_help() {
 			"{IndicatorColor} e       {Normal,Dimmed}|{Normal}Set selected commits to be edited",
 			"{IndicatorColor} s       {Normal,Dimmed}|{Normal}Set selected commits to be squashed",
 			"{IndicatorColor} f       {Normal,Dimmed}|{Normal}Set selected commits to be fixed-up",
+			"{IndicatorColor} u       {Normal,Dimmed}|{Normal}Cycle selected fixups between no option, -C and -c",
 			"{IndicatorColor} d       {Normal,Dimmed}|{Normal}Set selected commits to be dropped",
 			"{IndicatorColor} Delete  {Normal,Dimmed}|{Normal}Completely remove the selected lines",
 			"{IndicatorColor} Controlz{Normal,Dimmed}|{Normal}Undo the last change",
 			"{IndicatorColor} Controly{Normal,Dimmed}|{Normal}Redo the previous undone change",
+			"{IndicatorColor} G       {Normal,Dimmed}|{Normal}Toggle the rebase-merges graph view",
+			"{IndicatorColor} n       {Normal,Dimmed}|{Normal}Move selection to the next search match",
+			"{IndicatorColor} N       {Normal,Dimmed}|{Normal}Move selection to the previous search match",
 			"{IndicatorColor} v       {Normal,Dimmed}|{Normal}Exit visual mode",
 			"{TRAILING}",
 			"{IndicatorColor}Press any key to close"
@@ -2439,3 +3255,867 @@ fn resize() {
 		assert_eq!(module.height, 200);
 	});
 }
+
+#[test]
+fn search_mode_incremental() {
+	module_test(
+		&["pick aaa c1", "pick bbb foo", "pick ccc foo bar"],
+		&[Event::from(MetaEvent::SearchStart), Event::from('F'), Event::from('o')],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Search);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Normal}   {ActionPick}pick   {Normal}aaa      {Normal}c1",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}bbb      \
+				 {IndicatorColor,Underline}fo{Normal}o{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      {IndicatorColor,Underline}fo{Normal}o bar",
+				"{TRAILING}",
+				"{Normal,Dimmed}/{Normal}Fo{Normal,Underline}"
+			);
+		},
+	);
+}
+
+#[test]
+fn search_mode_no_matches() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::SearchStart), Event::from('x')],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{TRAILING}",
+				"{Normal,Dimmed}/{Normal}x{Normal,Underline} {IndicatorColor}  No matches"
+			);
+		},
+	);
+}
+
+#[test]
+fn search_mode_hash_match() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('b'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Normal}   {ActionPick}pick   {Normal}aaa      {Normal}c1",
+				"{Selected}{Normal} > {ActionPick}pick   {IndicatorColor}bbb      {Normal}c2{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn search_mode_cancel() {
+	module_test(
+		&["pick aaa c1", "pick bbb foo"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('f'),
+			Event::from(KeyCode::Esc),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}foo"
+			);
+		},
+	);
+}
+
+#[test]
+fn search_next_and_previous() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::SearchNext),
+			Event::from(MetaEvent::SearchNext),
+			Event::from(MetaEvent::SearchPrevious),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			for _ in 0..3 {
+				let _ = test_context.handle_event(&mut module);
+			}
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 0);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 0);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+		},
+	);
+}
+
+#[test]
+fn search_next_without_search() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2"],
+		&[Event::from(MetaEvent::SearchNext)],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn filter_mode_render() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo bar"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from('o'),
+			Event::from('o'),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Filter);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}foo{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}bbb      {Normal}c2",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      {Normal}foo bar",
+				"{TRAILING}",
+				"{Normal,Dimmed}filter: {Normal}foo{Normal,Underline}"
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_applied() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo bar"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from('o'),
+			Event::from('o'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(filtered: 2 of 3){HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}aaa      {Normal}foo{Normal}{Pad( )}",
+				"{Normal}   {ActionPick}pick   {Normal}ccc      {Normal}foo bar"
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_by_action() {
+	module_test(
+		&["pick aaa c1", "fixup bbb c2", "squash ccc c3", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('a'),
+			Event::from('c'),
+			Event::from('t'),
+			Event::from('i'),
+			Event::from('o'),
+			Event::from('n'),
+			Event::from(':'),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 1);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(filtered: 1 of 4){HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionFixup}fixup  {Normal}bbb      {Normal}c2{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_no_matches() {
+	module_test(
+		&["pick aaa c1"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('x'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(filtered: 0 of 1){HELP}",
+				"{LEADING}",
+				"{IndicatorColor}No lines match the filter"
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_invalid_pattern() {
+	module_test(
+		&["pick aaa c1"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('('),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Filter);
+			assert!(module.filter.is_none());
+			assert!(module.edit.get_error().unwrap().starts_with("Invalid pattern: "));
+		},
+	);
+}
+
+#[test]
+fn filter_cancel() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Esc),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.state, ListState::Normal);
+			assert!(module.filter.is_none());
+		},
+	);
+}
+
+#[test]
+fn filter_clear() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::Filter),
+			Event::from(KeyCode::Backspace),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			for _ in 0..3 {
+				let _ = test_context.handle_event(&mut module);
+			}
+			assert!(module.filter.is_some());
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(module.edit.get_content(), "f");
+			let _ = test_context.handle_all_events(&mut module);
+			assert!(module.filter.is_none());
+		},
+	);
+}
+
+#[test]
+fn filter_hides_selected_line() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo"],
+		&[
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+		},
+	);
+}
+
+#[test]
+fn filter_move_cursor() {
+	module_test(
+		&[
+			"pick aaa foo",
+			"pick bbb c2",
+			"pick ccc foo",
+			"pick ddd c4",
+			"pick eee foo",
+		],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::MoveCursorEnd),
+			Event::from(MetaEvent::MoveCursorUp),
+			Event::from(MetaEvent::MoveCursorHome),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			for _ in 0..3 {
+				let _ = test_context.handle_event(&mut module);
+			}
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 4);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+			let _ = test_context.handle_event(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn filter_visual_mode_action_change() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ActionDrop),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(filtered: 2 of 3){HELP}",
+				"{BODY}",
+				"{Selected}{Normal,Dimmed} > {ActionDrop}drop   {Normal}aaa      {Normal}foo{Normal}{Pad( )}",
+				"{Selected}{Normal} > {ActionDrop}drop   {Normal}ccc      {Normal}foo{Normal}{Pad( )}"
+			);
+			assert_eq!(
+				test_context.rebase_todo_file.get_line(1).unwrap().to_text(),
+				"pick bbb c2"
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_visual_mode_action_change_undo() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::ActionDrop),
+			Event::from(MetaEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			let lines = test_context
+				.rebase_todo_file
+				.iter()
+				.map(Line::to_text)
+				.collect::<Vec<String>>();
+			assert_eq!(lines, vec!["pick aaa foo", "pick bbb c2", "pick ccc foo"]);
+		},
+	);
+}
+
+#[test]
+fn filter_swap_down_past_hidden_lines() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["pick bbb c2", "pick ccc foo", "pick aaa foo", "pick ddd c4"]
+			);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+		},
+	);
+}
+
+#[test]
+fn filter_swap_up_past_hidden_lines() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::SwapSelectedUp),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["pick ccc foo", "pick aaa foo", "pick bbb c2", "pick ddd c4"]
+			);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 0);
+		},
+	);
+}
+
+#[test]
+fn filter_swap_down_last_visible_line() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::SwapSelectedDown),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"]
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_swap_undo() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::SwapSelectedDown),
+			Event::from(MetaEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"]
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_delete_with_hidden_lines() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::Delete),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(
+				test_context
+					.rebase_todo_file
+					.iter()
+					.map(Line::to_text)
+					.collect::<Vec<String>>(),
+				vec!["pick bbb c2", "pick ddd c4"]
+			);
+		},
+	);
+}
+
+#[test]
+fn filter_delete_with_hidden_lines_undo() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo", "pick ddd c4"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::ToggleVisualMode),
+			Event::from(MetaEvent::MoveCursorDown),
+			Event::from(MetaEvent::Delete),
+			Event::from(MetaEvent::Undo),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(test_context.rebase_todo_file.iter().len(), 4);
+		},
+	);
+}
+
+#[test]
+fn filter_delete_without_hidden_lines() {
+	module_test(
+		&["pick aaa foo", "pick bbb c2", "pick ccc foo"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::Delete),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(test_context.rebase_todo_file.iter().len(), 2);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 1);
+		},
+	);
+}
+
+#[test]
+fn filter_search_skips_hidden_lines() {
+	module_test(
+		&["pick aaa foo", "pick bbb foo c2", "pick ccc foo"],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('o'),
+			Event::from('$'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::SearchStart),
+			Event::from('f'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::SearchNext),
+		],
+		|mut test_context| {
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(test_context.rebase_todo_file.get_selected_line_index(), 2);
+		},
+	);
+}
+
+fn load_todo_file_with_progress(todo: &str, files: &[(&str, &str)]) -> (TodoFile, tempfile::TempDir) {
+	let state_directory = tempfile::tempdir().unwrap();
+	let todo_file_path = state_directory.path().join("git-rebase-todo");
+	std::fs::write(&todo_file_path, todo).unwrap();
+	for &(name, contents) in files {
+		std::fs::write(state_directory.path().join(name), contents).unwrap();
+	}
+	let mut todo_file = TodoFile::new(todo_file_path.to_str().unwrap(), 1, "#");
+	todo_file.load_file().unwrap();
+	(todo_file, state_directory)
+}
+
+#[test]
+fn render_done_lines() {
+	module_test(&[], &[], |mut test_context| {
+		let (todo_file, _state_directory) = load_todo_file_with_progress("fixup ccc c3\npick ddd c4\n", &[
+			("done", "pick aaa c1\nexec make\n"),
+			("msgnum", "2\n"),
+			("head-name", "refs/heads/feature\n"),
+			("onto", "1234567890abcdef\n"),
+		]);
+		test_context.rebase_todo_file = todo_file;
+		let mut module = List::new(&Config::new());
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}(feature: 2/4 applied onto 1234567){HELP}",
+			"{BODY}",
+			"{Normal,Dimmed}   pick   aaa      c1",
+			"{Normal,Dimmed}   exec   make",
+			"{Selected}{Normal} > {ActionFixup}fixup  {Normal}ccc      {Normal}c3{Normal}{Pad( )}",
+			"{Normal}   {ActionPick}pick   {Normal}ddd      {Normal}c4"
+		);
+	});
+}
+
+#[test]
+fn render_done_lines_onto_name() {
+	module_test(&[], &[], |mut test_context| {
+		let (mut todo_file, _state_directory) = load_todo_file_with_progress("pick ccc c3\n", &[
+			("done", "pick aaa c1\n"),
+			("head-name", "refs/heads/feature\n"),
+			("onto", "1234567890abcdef\n"),
+		]);
+		todo_file.set_onto_name(String::from("main"));
+		test_context.rebase_todo_file = todo_file;
+		let mut module = List::new(&Config::new());
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}(feature: 1/2 applied onto main){HELP}",
+			"{BODY}",
+			"{Normal,Dimmed}   pick   aaa      c1",
+			"{Selected}{Normal} > {ActionPick}pick   {Normal}ccc      {Normal}c3{Normal}{Pad( )}"
+		);
+	});
+}
+
+#[test]
+fn render_done_lines_compact() {
+	module_test(&[], &[], |mut test_context| {
+		let (todo_file, _state_directory) = load_todo_file_with_progress("pick ccc c3\n", &[("done", "pick aaa c1\n")]);
+		test_context.rebase_todo_file = todo_file;
+		test_context.render_context.update(30, 300);
+		let mut module = List::new(&Config::new());
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}(1/2 applied){HELP}",
+			"{BODY}",
+			"{Normal,Dimmed} p aaa c1",
+			"{Selected}{Normal}>{ActionPick}p {Normal}ccc {Normal}c3{Normal}{Pad( )}"
+		);
+	});
+}
+
+#[test]
+fn done_lines_hidden_while_filtered() {
+	module_test(
+		&[],
+		&[
+			Event::from(MetaEvent::Filter),
+			Event::from('c'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let (todo_file, _state_directory) =
+				load_todo_file_with_progress("pick ccc c3\n", &[("done", "pick aaa c1\n")]);
+			test_context.rebase_todo_file = todo_file;
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(1/2 applied, filtered: 1 of 1){HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}ccc      {Normal}c3{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_revert_line() {
+	module_test(&["revert aaa c1"], &[], |mut test_context| {
+		test_context.rebase_todo_file.set_sequencer(true);
+		let mut module = List::new(&Config::new());
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}{HELP}",
+			"{BODY}",
+			"{Selected}{Normal} > {ActionRevert}revert {Normal}aaa      {Normal}c1{Normal}{Pad( )}"
+		);
+	});
+}
+
+#[rstest::rstest]
+#[case::break_action(MetaEvent::ActionBreak)]
+#[case::drop(MetaEvent::ActionDrop)]
+#[case::edit(MetaEvent::ActionEdit)]
+#[case::fixup(MetaEvent::ActionFixup)]
+#[case::pick(MetaEvent::ActionPick)]
+#[case::reword(MetaEvent::ActionReword)]
+#[case::squash(MetaEvent::ActionSquash)]
+#[case::cycle_fixup_option(MetaEvent::CycleFixupOption)]
+#[case::autosquash(MetaEvent::Autosquash)]
+fn sequencer_action_unavailable(#[case] meta_event: MetaEvent) {
+	module_test(
+		&["revert aaa c1", "revert bbb fixup! c1"],
+		&[Event::from(meta_event)],
+		|mut test_context| {
+			test_context.rebase_todo_file.set_sequencer(true);
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionRevert}revert {Normal}aaa      {Normal}c1{Normal}{Pad( )}",
+				"{Normal}   {ActionRevert}revert {Normal}bbb      {Normal}fixup! c1"
+			);
+		},
+	);
+}
+
+#[test]
+fn sequencer_insert_line_unavailable() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::InsertLine)],
+		|mut test_context| {
+			test_context.rebase_todo_file.set_sequencer(true);
+			let mut module = List::new(&Config::new());
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::InsertLine)
+			);
+		},
+	);
+}
+
+#[test]
+fn sequencer_remove_line() {
+	module_test(
+		&["pick aaa c1", "pick bbb c2"],
+		&[Event::from(MetaEvent::Delete)],
+		|mut test_context| {
+			test_context.rebase_todo_file.set_sequencer(true);
+			let mut module = List::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{BODY}",
+				"{Selected}{Normal} > {ActionPick}pick   {Normal}bbb      {Normal}c2{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn sequencer_normal_mode_help() {
+	module_test(&["pick aaa c1"], &[Event::from(MetaEvent::Help)], |mut test_context| {
+		test_context.rebase_todo_file.set_sequencer(true);
+		let mut module = List::new(&Config::new());
+		let _ = test_context.handle_all_events(&mut module);
+		assert!(!module.normal_mode_help.is_active());
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}",
+			"{LEADING}",
+			"{Normal,Underline} Key      Action{Normal,Underline}{Pad( )}",
+			"{BODY}",
+			"{IndicatorColor} Up      {Normal,Dimmed}|{Normal}Move selection up",
+			"{IndicatorColor} Down    {Normal,Dimmed}|{Normal}Move selection down",
+			"{IndicatorColor} PageUp  {Normal,Dimmed}|{Normal}Move selection up 5 lines",
+			"{IndicatorColor} PageDown{Normal,Dimmed}|{Normal}Move selection down 5 lines",
+			"{IndicatorColor} Home    {Normal,Dimmed}|{Normal}Move selection to top of the list",
+			"{IndicatorColor} End     {Normal,Dimmed}|{Normal}Move selection to end of the list",
+			"{IndicatorColor} Left    {Normal,Dimmed}|{Normal}Scroll content to the left",
+			"{IndicatorColor} Right   {Normal,Dimmed}|{Normal}Scroll content to the right",
+			"{IndicatorColor} q       {Normal,Dimmed}|{Normal}Abort, removing every line",
+			"{IndicatorColor} Q       {Normal,Dimmed}|{Normal}Immediately abort, removing every line",
+			"{IndicatorColor} w       {Normal,Dimmed}|{Normal}Write sequencer todo file",
+			"{IndicatorColor} W       {Normal,Dimmed}|{Normal}Immediately write sequencer todo file",
+			"{IndicatorColor} v       {Normal,Dimmed}|{Normal}Enter visual mode",
+			"{IndicatorColor} ?       {Normal,Dimmed}|{Normal}Show help",
+			"{IndicatorColor} c       {Normal,Dimmed}|{Normal}Show commit information",
+			"{IndicatorColor} j       {Normal,Dimmed}|{Normal}Move selected commit down",
+			"{IndicatorColor} k       {Normal,Dimmed}|{Normal}Move selected commit up",
+			"{IndicatorColor} Delete  {Normal,Dimmed}|{Normal}Completely remove the selected line",
+			"{IndicatorColor} Controlz{Normal,Dimmed}|{Normal}Undo the last change",
+			"{IndicatorColor} Controly{Normal,Dimmed}|{Normal}Redo the previous undone change",
+			"{IndicatorColor} !       {Normal,Dimmed}|{Normal}Open the todo file in the default editor",
+			"{IndicatorColor} /       {Normal,Dimmed}|{Normal}Search the list",
+			"{IndicatorColor} n       {Normal,Dimmed}|{Normal}Move to the next search match",
+			"{IndicatorColor} N       {Normal,Dimmed}|{Normal}Move to the previous search match",
+			"{IndicatorColor} F       {Normal,Dimmed}|{Normal}Filter the list by pattern or action",
+			"{TRAILING}",
+			"{IndicatorColor}Press any key to close"
+		);
+	});
+}
+
+#[test]
+fn sequencer_visual_mode_help() {
+	module_test(&["pick aaa c1"], &[Event::from(MetaEvent::Help)], |mut test_context| {
+		test_context.rebase_todo_file.set_sequencer(true);
+		let mut module = List::new(&Config::new());
+		module.state = ListState::Visual;
+		let _ = test_context.handle_all_events(&mut module);
+		assert!(!module.visual_mode_help.is_active());
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}",
+			"{LEADING}",
+			"{Normal,Underline} Key      Action{Normal,Underline}{Pad( )}",
+			"{BODY}",
+			"{IndicatorColor} Up      {Normal,Dimmed}|{Normal}Move selection up",
+			"{IndicatorColor} Down    {Normal,Dimmed}|{Normal}Move selection down",
+			"{IndicatorColor} PageUp  {Normal,Dimmed}|{Normal}Move selection up 5 lines",
+			"{IndicatorColor} PageDown{Normal,Dimmed}|{Normal}Move selection down 5 lines",
+			"{IndicatorColor} Home    {Normal,Dimmed}|{Normal}Move selection to top of the list",
+			"{IndicatorColor} End     {Normal,Dimmed}|{Normal}Move selection to end of the list",
+			"{IndicatorColor} Left    {Normal,Dimmed}|{Normal}Scroll content to the left",
+			"{IndicatorColor} Right   {Normal,Dimmed}|{Normal}Scroll content to the right",
+			"{IndicatorColor} ?       {Normal,Dimmed}|{Normal}Show help",
+			"{IndicatorColor} c       {Normal,Dimmed}|{Normal}Show changes of selected commits",
+			"{IndicatorColor} j       {Normal,Dimmed}|{Normal}Move selected commits down",
+			"{IndicatorColor} k       {Normal,Dimmed}|{Normal}Move selected commits up",
+			"{IndicatorColor} Delete  {Normal,Dimmed}|{Normal}Completely remove the selected lines",
+			"{IndicatorColor} Controlz{Normal,Dimmed}|{Normal}Undo the last change",
+			"{IndicatorColor} Controly{Normal,Dimmed}|{Normal}Redo the previous undone change",
+			"{IndicatorColor} n       {Normal,Dimmed}|{Normal}Move selection to the next search match",
+			"{IndicatorColor} N       {Normal,Dimmed}|{Normal}Move selection to the previous search match",
+			"{IndicatorColor} v       {Normal,Dimmed}|{Normal}Exit visual mode",
+			"{TRAILING}",
+			"{IndicatorColor}Press any key to close"
+		);
+	});
+}
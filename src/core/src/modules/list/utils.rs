@@ -2,9 +2,11 @@ use std::cmp;
 
 use config::KeyBindings;
 use display::DisplayColor;
-use todo_file::{Action, Line};
+use todo_file::{Action, Line, RebaseProgress};
 use view::LineSegment;
 
+use super::search::{get_match_ranges, is_hash_match};
+
 pub(super) fn get_list_normal_mode_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec<String>, String)> {
 	vec![
 		(key_bindings.move_up.clone(), String::from("Move selection up")),
@@ -84,10 +86,22 @@ pub(super) fn get_list_normal_mode_help_lines(key_bindings: &KeyBindings) -> Vec
 			key_bindings.action_fixup.clone(),
 			String::from("Set selected commit to be fixed-up"),
 		),
+		(
+			key_bindings.cycle_fixup_option.clone(),
+			String::from("Cycle selected fixup between no option, -C and -c"),
+		),
+		(
+			key_bindings.autosquash.clone(),
+			String::from("Move fixup!, squash! and amend! commits below their targets"),
+		),
 		(
 			key_bindings.action_drop.clone(),
 			String::from("Set selected commit to be dropped"),
 		),
+		(
+			key_bindings.drop_applied_upstream.clone(),
+			String::from("Drop the commits already applied upstream"),
+		),
 		(key_bindings.edit.clone(), String::from("Edit an exec action's command")),
 		(key_bindings.insert_line.clone(), String::from("Insert a new line")),
 		(
@@ -103,6 +117,107 @@ pub(super) fn get_list_normal_mode_help_lines(key_bindings: &KeyBindings) -> Vec
 			key_bindings.open_in_external_editor.clone(),
 			String::from("Open the todo file in the default editor"),
 		),
+		(
+			key_bindings.toggle_graph.clone(),
+			String::from("Toggle the rebase-merges graph view"),
+		),
+		(key_bindings.search.clone(), String::from("Search the list")),
+		(
+			key_bindings.search_next.clone(),
+			String::from("Move to the next search match"),
+		),
+		(
+			key_bindings.search_previous.clone(),
+			String::from("Move to the previous search match"),
+		),
+		(
+			key_bindings.filter.clone(),
+			String::from("Filter the list by pattern or action"),
+		),
+	]
+}
+
+pub(super) fn get_sequencer_normal_mode_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec<String>, String)> {
+	vec![
+		(key_bindings.move_up.clone(), String::from("Move selection up")),
+		(key_bindings.move_down.clone(), String::from("Move selection down")),
+		(
+			key_bindings.move_up_step.clone(),
+			String::from("Move selection up 5 lines"),
+		),
+		(
+			key_bindings.move_down_step.clone(),
+			String::from("Move selection down 5 lines"),
+		),
+		(
+			key_bindings.move_home.clone(),
+			String::from("Move selection to top of the list"),
+		),
+		(
+			key_bindings.move_end.clone(),
+			String::from("Move selection to end of the list"),
+		),
+		(
+			key_bindings.move_left.clone(),
+			String::from("Scroll content to the left"),
+		),
+		(
+			key_bindings.move_right.clone(),
+			String::from("Scroll content to the right"),
+		),
+		(key_bindings.abort.clone(), String::from("Abort, removing every line")),
+		(
+			key_bindings.force_abort.clone(),
+			String::from("Immediately abort, removing every line"),
+		),
+		(key_bindings.rebase.clone(), String::from("Write sequencer todo file")),
+		(
+			key_bindings.force_rebase.clone(),
+			String::from("Immediately write sequencer todo file"),
+		),
+		(
+			key_bindings.toggle_visual_mode.clone(),
+			String::from("Enter visual mode"),
+		),
+		(key_bindings.help.clone(), String::from("Show help")),
+		(
+			key_bindings.show_commit.clone(),
+			String::from("Show commit information"),
+		),
+		(
+			key_bindings.move_selection_down.clone(),
+			String::from("Move selected commit down"),
+		),
+		(
+			key_bindings.move_selection_up.clone(),
+			String::from("Move selected commit up"),
+		),
+		(
+			key_bindings.remove_line.clone(),
+			String::from("Completely remove the selected line"),
+		),
+		(key_bindings.undo.clone(), String::from("Undo the last change")),
+		(
+			key_bindings.redo.clone(),
+			String::from("Redo the previous undone change"),
+		),
+		(
+			key_bindings.open_in_external_editor.clone(),
+			String::from("Open the todo file in the default editor"),
+		),
+		(key_bindings.search.clone(), String::from("Search the list")),
+		(
+			key_bindings.search_next.clone(),
+			String::from("Move to the next search match"),
+		),
+		(
+			key_bindings.search_previous.clone(),
+			String::from("Move to the previous search match"),
+		),
+		(
+			key_bindings.filter.clone(),
+			String::from("Filter the list by pattern or action"),
+		),
 	]
 }
 
@@ -135,6 +250,10 @@ pub(super) fn get_list_visual_mode_help_lines(key_bindings: &KeyBindings) -> Vec
 			String::from("Scroll content to the right"),
 		),
 		(key_bindings.help.clone(), String::from("Show help")),
+		(
+			key_bindings.show_commit.clone(),
+			String::from("Show changes of selected commits"),
+		),
 		(
 			key_bindings.move_selection_down.clone(),
 			String::from("Move selected commits down"),
@@ -163,6 +282,10 @@ pub(super) fn get_list_visual_mode_help_lines(key_bindings: &KeyBindings) -> Vec
 			key_bindings.action_fixup.clone(),
 			String::from("Set selected commits to be fixed-up"),
 		),
+		(
+			key_bindings.cycle_fixup_option.clone(),
+			String::from("Cycle selected fixups between no option, -C and -c"),
+		),
 		(
 			key_bindings.action_drop.clone(),
 			String::from("Set selected commits to be dropped"),
@@ -176,6 +299,83 @@ pub(super) fn get_list_visual_mode_help_lines(key_bindings: &KeyBindings) -> Vec
 			key_bindings.redo.clone(),
 			String::from("Redo the previous undone change"),
 		),
+		(
+			key_bindings.toggle_graph.clone(),
+			String::from("Toggle the rebase-merges graph view"),
+		),
+		(
+			key_bindings.search_next.clone(),
+			String::from("Move selection to the next search match"),
+		),
+		(
+			key_bindings.search_previous.clone(),
+			String::from("Move selection to the previous search match"),
+		),
+		(
+			key_bindings.toggle_visual_mode.clone(),
+			String::from("Exit visual mode"),
+		),
+	]
+}
+
+pub(super) fn get_sequencer_visual_mode_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec<String>, String)> {
+	vec![
+		(key_bindings.move_up.clone(), String::from("Move selection up")),
+		(key_bindings.move_down.clone(), String::from("Move selection down")),
+		(
+			key_bindings.move_up_step.clone(),
+			String::from("Move selection up 5 lines"),
+		),
+		(
+			key_bindings.move_down_step.clone(),
+			String::from("Move selection down 5 lines"),
+		),
+		(
+			key_bindings.move_home.clone(),
+			String::from("Move selection to top of the list"),
+		),
+		(
+			key_bindings.move_end.clone(),
+			String::from("Move selection to end of the list"),
+		),
+		(
+			key_bindings.move_left.clone(),
+			String::from("Scroll content to the left"),
+		),
+		(
+			key_bindings.move_right.clone(),
+			String::from("Scroll content to the right"),
+		),
+		(key_bindings.help.clone(), String::from("Show help")),
+		(
+			key_bindings.show_commit.clone(),
+			String::from("Show changes of selected commits"),
+		),
+		(
+			key_bindings.move_selection_down.clone(),
+			String::from("Move selected commits down"),
+		),
+		(
+			key_bindings.move_selection_up.clone(),
+			String::from("Move selected commits up"),
+		),
+		(
+			key_bindings.remove_line.clone(),
+			String::from("Completely remove the selected lines"),
+		),
+		(key_bindings.undo.clone(), String::from("Undo the last change")),
+		(
+			key_bindings.redo.clone(),
+			String::from("Redo the previous undone change"),
+		),
+		(
+			key_bindings.search_next.clone(),
+			String::from("Move selection to the next search match"),
+		),
+		(
+			key_bindings.search_previous.clone(),
+			String::from("Move selection to the previous search match"),
+		),
 		(
 			key_bindings.toggle_visual_mode.clone(),
 			String::from("Exit visual mode"),
@@ -196,17 +396,141 @@ const fn get_action_color(action: Action) -> DisplayColor {
 		Action::Label => DisplayColor::ActionLabel,
 		Action::Reset => DisplayColor::ActionReset,
 		Action::Merge => DisplayColor::ActionMerge,
+		Action::UpdateRef => DisplayColor::ActionUpdateRef,
+		Action::Revert => DisplayColor::ActionRevert,
 		// this is technically impossible, since noops should never be rendered
 		Action::Noop => DisplayColor::Normal,
 	}
 }
 
-pub(super) fn get_todo_line_segments(
-	line: &Line,
+fn get_action_name(action: Action, is_full_width: bool) -> String {
+	if is_full_width {
+		format!("{:6} ", action.as_string())
+	}
+	else {
+		format!("{:1} ", action.to_abbreviation())
+	}
+}
+
+fn get_hash_text(line: &Line, is_full_width: bool) -> Option<String> {
+	let has_hash = match *line.get_action() {
+		Action::Drop
+		| Action::Edit
+		| Action::Fixup
+		| Action::Pick
+		| Action::Revert
+		| Action::Reword
+		| Action::Squash => true,
+		Action::Merge => line.has_reference(),
+		Action::Exec | Action::Label | Action::Reset | Action::Break | Action::Noop | Action::UpdateRef => false,
+	};
+	has_hash.then(|| {
+		let action_width = if is_full_width { 8 } else { 3 };
+		let max_index = cmp::min(line.get_hash().len(), action_width);
+		format!(
+			"{:width$} ",
+			line.get_hash()[0..max_index].to_string(),
+			width = action_width
+		)
+	})
+}
+
+fn push_content_segments(segments: &mut Vec<LineSegment>, content: &str, search_term: &str) {
+	let mut index = 0;
+	for (start, end) in get_match_ranges(content, search_term) {
+		if start > index {
+			segments.push(LineSegment::new(&content[index..start]));
+		}
+		segments.push(LineSegment::new_with_color_and_style(
+			&content[start..end],
+			DisplayColor::IndicatorColor,
+			false,
+			true,
+			false,
+		));
+		index = end;
+	}
+	if index < content.len() {
+		segments.push(LineSegment::new(&content[index..]));
+	}
+}
+
+/// The options for the segments of a todo line, such as the markers, badges and search term shown on the line.
+#[derive(Debug)]
+pub(super) struct TodoLineOptions<'options> {
+	badges: Vec<&'options str>,
+	graph: Option<&'options str>,
 	is_cursor_line: bool,
-	selected: bool,
 	is_full_width: bool,
-) -> Vec<LineSegment> {
+	reorder_overlap: bool,
+	search_term: Option<&'options str>,
+	selected: bool,
+	warning: Option<&'options str>,
+}
+
+impl<'options> TodoLineOptions<'options> {
+	pub(super) const fn new(is_full_width: bool) -> Self {
+		Self {
+			badges: vec![],
+			graph: None,
+			is_cursor_line: false,
+			is_full_width,
+			reorder_overlap: false,
+			search_term: None,
+			selected: false,
+			warning: None,
+		}
+	}
+
+	/// Set if the line is the line of the cursor, and if the line is selected, as in visual mode.
+	pub(super) const fn set_selection(mut self, is_cursor_line: bool, selected: bool) -> Self {
+		self.is_cursor_line = is_cursor_line;
+		self.selected = selected;
+		self
+	}
+
+	/// Set the graph of the line, from the graph of a rebase with merges.
+	pub(super) const fn set_graph(mut self, graph: Option<&'options str>) -> Self {
+		self.graph = graph;
+		self
+	}
+
+	/// Set the warning of the line, which takes priority over the reorder marker.
+	pub(super) const fn set_warning(mut self, warning: Option<&'options str>) -> Self {
+		self.warning = warning;
+		self
+	}
+
+	/// Set if the line was reordered past a line that changes the same files.
+	pub(super) const fn set_reorder_overlap(mut self, reorder_overlap: bool) -> Self {
+		self.reorder_overlap = reorder_overlap;
+		self
+	}
+
+	/// Add a badge, which is shown after the content of the line.
+	pub(super) fn add_badge(mut self, badge: &'options str) -> Self {
+		self.badges.push(badge);
+		self
+	}
+
+	/// Set the search term, the matches of which are highlighted.
+	pub(super) const fn set_search_term(mut self, search_term: Option<&'options str>) -> Self {
+		self.search_term = search_term;
+		self
+	}
+}
+
+pub(super) fn get_todo_line_segments(line: &Line, options: &TodoLineOptions<'_>) -> Vec<LineSegment> {
+	let TodoLineOptions {
+		ref badges,
+		graph,
+		is_cursor_line,
+		is_full_width,
+		reorder_overlap,
+		search_term,
+		selected,
+		warning,
+	} = *options;
 	let mut segments: Vec<LineSegment> = vec![];
 
 	let action = line.get_action();
@@ -226,44 +550,123 @@ pub(super) fn get_todo_line_segments(
 		" "
 	};
 
-	segments.push(LineSegment::new_with_color_and_style(
-		indicator,
-		DisplayColor::Normal,
-		!is_cursor_line && selected,
-		false,
-		false,
-	));
-
-	let action_name = if is_full_width {
-		format!("{:6} ", action.as_string())
+	// the warning and reorder markers replace the first character of the indicator, so the lines stay aligned, with
+	// the warning taking priority, except for the cursor of the compact indicator, which has no other character
+	let marker = if !is_full_width && (is_cursor_line || selected) {
+		None
+	}
+	else if warning.is_some() {
+		Some(LineSegment::new_with_color("!", DisplayColor::WarningColor))
+	}
+	else if reorder_overlap {
+		Some(LineSegment::new_with_color("~", DisplayColor::IndicatorColor))
 	}
 	else {
-		format!("{:1} ", action.to_abbreviation())
+		None
+	};
+	let indicator = if let Some(marker) = marker {
+		segments.push(marker);
+		&indicator[1..]
+	}
+	else {
+		indicator
 	};
 
+	if !indicator.is_empty() {
+		segments.push(LineSegment::new_with_color_and_style(
+			indicator,
+			DisplayColor::Normal,
+			!is_cursor_line && selected,
+			false,
+			false,
+		));
+	}
+
+	if let Some(graph) = graph {
+		segments.push(LineSegment::new_with_color(graph, DisplayColor::IndicatorColor));
+	}
+
 	segments.push(LineSegment::new_with_color(
-		action_name.as_str(),
+		get_action_name(*action, is_full_width).as_str(),
 		get_action_color(*action),
 	));
 
-	match *action {
-		Action::Drop | Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash => {
-			let action_width = if is_full_width { 8 } else { 3 };
-			let max_index = cmp::min(line.get_hash().len(), action_width);
-			segments.push(LineSegment::new(
-				format!(
-					"{:width$} ",
-					line.get_hash()[0..max_index].to_string(),
-					width = action_width
-				)
-				.as_str(),
-			));
-		},
-		Action::Exec | Action::Label | Action::Reset | Action::Merge | Action::Break | Action::Noop => {},
+	if let Some(option) = line.get_option() {
+		segments.push(LineSegment::new_with_color(
+			format!("{} ", option).as_str(),
+			get_action_color(*action),
+		));
+	}
+
+	if let Some(hash) = get_hash_text(line, is_full_width) {
+		if search_term.map_or(false, |term| is_hash_match(line.get_hash(), term)) {
+			segments.push(LineSegment::new_with_color(hash.as_str(), DisplayColor::IndicatorColor));
+		}
+		else {
+			segments.push(LineSegment::new(hash.as_str()));
+		}
 	}
 	let content = line.get_content();
 	if !content.is_empty() {
-		segments.push(LineSegment::new(content));
+		push_content_segments(&mut segments, content, search_term.unwrap_or(""));
+	}
+	if let Some(message) = line.get_message() {
+		segments.push(LineSegment::new_with_color_and_style(
+			format!(" # {}", message).as_str(),
+			DisplayColor::Normal,
+			true,
+			false,
+			false,
+		));
+	}
+	for badge in badges {
+		segments.push(LineSegment::new_with_color(
+			format!(" [{}]", badge).as_str(),
+			DisplayColor::IndicatorColor,
+		));
+	}
+	if let Some(warning) = warning {
+		segments.push(LineSegment::new_with_color(
+			format!(" ! {}", warning).as_str(),
+			DisplayColor::WarningColor,
+		));
 	}
 	segments
 }
+
+/// Get the segments for a line that has already been applied, which is dimmed since it can no longer be changed.
+pub(super) fn get_done_line_segments(line: &Line, is_full_width: bool) -> Vec<LineSegment> {
+	let mut text = String::from(if is_full_width { "   " } else { " " });
+	text.push_str(get_action_name(*line.get_action(), is_full_width).as_str());
+	if let Some(option) = line.get_option() {
+		text.push_str(format!("{} ", option).as_str());
+	}
+	if let Some(hash) = get_hash_text(line, is_full_width) {
+		text.push_str(hash.as_str());
+	}
+	text.push_str(line.get_content());
+	vec![LineSegment::new_with_color_and_style(
+		text.as_str(),
+		DisplayColor::Normal,
+		true,
+		false,
+		false,
+	)]
+}
+
+/// Get a description of the progress of the rebase, such as "feature: 5/23 applied onto main". The commit that the
+/// rebase is applied onto is shown as a short hash, when no branch points to it.
+pub(super) fn get_progress_text(progress: &RebaseProgress, remaining_count: usize) -> String {
+	let applied_count = progress.get_applied_count();
+	let mut text = format!("{}/{} applied", applied_count, applied_count + remaining_count);
+	if let Some(onto_name) = progress.get_onto_name() {
+		text.push_str(format!(" onto {}", onto_name).as_str());
+	}
+	else if let Some(onto) = progress.get_onto() {
+		text.push_str(format!(" onto {}", &onto[0..cmp::min(onto.len(), 7)]).as_str());
+	}
+	if let Some(branch) = progress.get_branch_name() {
+		text = format!("{}: {}", branch, text);
+	}
+	text
+}
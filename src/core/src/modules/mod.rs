@@ -13,7 +13,7 @@ pub(crate) use self::{
 	error::Error,
 	external_editor::ExternalEditor,
 	insert::Insert,
-	list::List,
-	show_commit::ShowCommit,
+	list::{get_match_ranges, List},
+	show_commit::{is_path_changing_line, CommitPaths, SelectedRange, ShowCommit},
 	window_size_error::WindowSizeError,
 };
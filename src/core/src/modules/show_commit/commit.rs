@@ -2,18 +2,36 @@ use std::sync::Mutex;
 
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Local, TimeZone};
-use git2::{DiffFindOptions, DiffOptions, Error, Repository};
+use config::{Config, DiffIgnoreWhitespaceSetting};
+use git2::{
+	DiffDelta,
+	DiffFile,
+	DiffFindOptions,
+	DiffOptions,
+	Error,
+	FileFavor,
+	FileMode,
+	MergeOptions,
+	Repository,
+	Tree,
+};
+use todo_file::Line;
 
 use super::{
 	delta::Delta,
 	diff_line::DiffLine,
+	file_content::FileContent,
 	file_stat::FileStat,
 	file_stats_builder::FileStatsBuilder,
 	origin::Origin,
+	squash::build_squash_message,
 	status::Status,
 	user::User,
 };
 
+// objects are written to the highest priority backend, which must be above the loose and pack backends
+const MEMPACK_PRIORITY: i32 = 1000;
+
 #[derive(Copy, Clone, Debug)]
 #[allow(clippy::struct_excessive_bools)]
 pub(super) struct LoadCommitDiffOptions {
@@ -39,26 +57,24 @@ pub(crate) struct Commit {
 	pub(super) deletions: usize,
 }
 
-fn load_commit_state(hash: &str, config: &LoadCommitDiffOptions) -> Result<Commit, Error> {
-	let repo = Repository::open_from_env()?;
-	let commit = repo.find_commit(repo.revparse_single(hash)?.id())?;
-
-	let full_hash = commit.id().to_string();
-	let date = Local.timestamp(commit.time().seconds(), 0);
-	let body = commit.message().map(String::from);
-	let author = User::new(commit.author().name(), commit.author().email());
-	let mut committer = User::new(commit.committer().name(), commit.committer().email());
-	if committer == author {
-		committer = User::new(None, None);
+impl LoadCommitDiffOptions {
+	pub(super) fn new(config: &Config) -> Self {
+		Self {
+			context_lines: config.git.diff_context,
+			copies: config.git.diff_copies,
+			ignore_whitespace: config.diff_ignore_whitespace == DiffIgnoreWhitespaceSetting::All,
+			ignore_whitespace_change: config.diff_ignore_whitespace == DiffIgnoreWhitespaceSetting::Change,
+			interhunk_lines: config.git.diff_interhunk_lines,
+			rename_limit: config.git.diff_rename_limit,
+			renames: config.git.diff_renames,
+		}
 	}
-	let mut number_files_changed = 0;
-	let mut insertions = 0;
-	let mut deletions = 0;
-
-	let mut empty_diff_options = DiffOptions::new();
+}
 
+fn create_diff_options(config: &LoadCommitDiffOptions) -> DiffOptions {
+	let mut diff_options = DiffOptions::new();
 	// include_unmodified added to find copies from unmodified files
-	let diff_options = empty_diff_options
+	let _ = diff_options
 		.context_lines(config.context_lines)
 		.ignore_filemode(false)
 		.ignore_whitespace(config.ignore_whitespace)
@@ -69,113 +85,283 @@ fn load_commit_state(hash: &str, config: &LoadCommitDiffOptions) -> Result<Commi
 		.indent_heuristic(true)
 		.interhunk_lines(config.interhunk_lines)
 		.minimal(true);
+	diff_options
+}
 
-	let mut empty_diff_find_options = DiffFindOptions::new();
-	let diff_find_options = empty_diff_find_options
+fn create_diff_find_options(config: &LoadCommitDiffOptions) -> DiffFindOptions {
+	let mut diff_find_options = DiffFindOptions::new();
+	let _ = diff_find_options
 		.renames(config.renames)
 		.renames_from_rewrites(config.renames)
 		.rewrites(config.renames)
 		.rename_limit(config.rename_limit as usize)
 		.copies(config.copies)
 		.copies_from_unmodified(config.copies);
+	diff_find_options
+}
 
-	// some commits do not have parents, and can't have file stats
-	let file_stats = if commit.parent_ids().count() == 0 {
-		vec![]
+type FileStats = (Vec<FileStat>, usize, usize, usize);
+
+// the content of a submodule or symbolic link is the commit or target that it points to, and not lines of text
+fn load_file_content(repo: &Repository, diff_delta: &DiffDelta<'_>) -> FileContent {
+	let old_file = diff_delta.old_file();
+	let new_file = diff_delta.new_file();
+	let has_mode = |mode: FileMode| old_file.mode() == mode || new_file.mode() == mode;
+	if has_mode(FileMode::Commit) {
+		let get_commit = |file: &DiffFile<'_>| (file.mode() == FileMode::Commit).then(|| file.id().to_string());
+		FileContent::Submodule {
+			old_commit: get_commit(&old_file),
+			new_commit: get_commit(&new_file),
+		}
+	}
+	else if has_mode(FileMode::Link) {
+		let get_target = |file: &DiffFile<'_>| {
+			(file.mode() == FileMode::Link)
+				.then(|| repo.find_blob(file.id()).ok())
+				.flatten()
+				.map(|blob| String::from_utf8_lossy(blob.content()).into_owned())
+		};
+		FileContent::Symlink {
+			old_target: get_target(&old_file),
+			new_target: get_target(&new_file),
+		}
+	}
+	else if diff_delta.flags().is_binary() {
+		FileContent::Binary {
+			old_size: old_file.size(),
+			new_size: new_file.size(),
+		}
 	}
 	else {
-		let mut diff = repo.diff_tree_to_tree(
-			// parent exists from check above
-			Some(&commit.parent(0)?.tree()?),
-			Some(&commit.tree()?),
-			Some(diff_options),
-		)?;
-
-		diff.find_similar(Some(diff_find_options))?;
-
-		let mut unmodified_file_count: usize = 0;
-
-		let file_stats_builder = Mutex::new(FileStatsBuilder::new());
+		FileContent::Text
+	}
+}
 
-		// TODO trace file mode change and binary files
-		diff.foreach(
-			&mut |diff_delta, _| {
-				// unmodified files are included for copy detection, so ignore
-				if diff_delta.status() == git2::Delta::Unmodified {
-					unmodified_file_count += 1;
-					return true;
-				}
+// the file stats of the diff between two trees, along with the number of files changed, insertions and deletions
+fn load_file_stats(
+	repo: &Repository,
+	old_tree: Option<&Tree<'_>>,
+	new_tree: &Tree<'_>,
+	config: &LoadCommitDiffOptions,
+) -> Result<FileStats, Error> {
+	let mut number_files_changed = 0;
+	let mut insertions = 0;
+	let mut deletions = 0;
 
-				let mut fsb = file_stats_builder.lock().unwrap();
-
-				let from_file_path = diff_delta
-					.old_file()
-					.path()
-					.map_or_else(|| String::from("unknown"), |p| String::from(p.to_str().unwrap()));
-				let to_file_path = diff_delta
-					.new_file()
-					.path()
-					.map_or_else(|| String::from("unknown"), |p| String::from(p.to_str().unwrap()));
-
-				fsb.add_file_stat(FileStat::new(
-					from_file_path.as_str(),
-					to_file_path.as_str(),
-					Status::from(diff_delta.status()),
-				));
-
-				true
-			},
-			None,
-			Some(&mut |_, diff_hunk| {
-				let mut fsb = file_stats_builder.lock().unwrap();
-
-				let header = std::str::from_utf8(diff_hunk.header()).unwrap();
-
-				fsb.add_delta(Delta::new(
-					header,
-					diff_hunk.old_start(),
-					diff_hunk.new_start(),
-					diff_hunk.old_lines(),
-					diff_hunk.new_lines(),
-				));
-				true
-			}),
-			Some(&mut |_, _, diff_line| {
-				let mut fsb = file_stats_builder.lock().unwrap();
-				fsb.add_diff_line(DiffLine::new(
-					Origin::from(diff_line.origin()),
-					std::str::from_utf8(diff_line.content()).unwrap(),
-					diff_line.old_lineno(),
-					diff_line.new_lineno(),
-					diff_line.origin() == '=' || diff_line.origin() == '>' || diff_line.origin() == '<',
-				));
-				true
-			}),
-		)
-		.unwrap();
-
-		if let Ok(stats) = diff.stats() {
-			number_files_changed = stats.files_changed() - unmodified_file_count;
-			insertions = stats.insertions();
-			deletions = stats.deletions();
-		}
+	let mut diff_options = create_diff_options(config);
+	let mut diff_find_options = create_diff_find_options(config);
+
+	let mut diff = repo.diff_tree_to_tree(old_tree, Some(new_tree), Some(&mut diff_options))?;
+
+	diff.find_similar(Some(&mut diff_find_options))?;
+
+	let mut unmodified_file_count: usize = 0;
+
+	let file_stats_builder = Mutex::new(FileStatsBuilder::new());
+
+	diff.foreach(
+		&mut |diff_delta, _| {
+			// unmodified files are included for copy detection, so ignore
+			if diff_delta.status() == git2::Delta::Unmodified {
+				unmodified_file_count += 1;
+				return true;
+			}
+
+			let mut fsb = file_stats_builder.lock().unwrap();
+
+			let from_file_path = diff_delta
+				.old_file()
+				.path()
+				.map_or_else(|| String::from("unknown"), |p| String::from(p.to_str().unwrap()));
+			let to_file_path = diff_delta
+				.new_file()
+				.path()
+				.map_or_else(|| String::from("unknown"), |p| String::from(p.to_str().unwrap()));
+
+			let mut file_stat = FileStat::new(
+				from_file_path.as_str(),
+				to_file_path.as_str(),
+				Status::from(diff_delta.status()),
+			);
+			file_stat.set_modes(
+				u32::from(diff_delta.old_file().mode()),
+				u32::from(diff_delta.new_file().mode()),
+			);
+			file_stat.set_content(load_file_content(repo, &diff_delta));
+			fsb.add_file_stat(file_stat);
+
+			true
+		},
+		None,
+		Some(&mut |_, diff_hunk| {
+			let mut fsb = file_stats_builder.lock().unwrap();
+
+			let header = std::str::from_utf8(diff_hunk.header()).unwrap();
+
+			fsb.add_delta(Delta::new(
+				header,
+				diff_hunk.old_start(),
+				diff_hunk.new_start(),
+				diff_hunk.old_lines(),
+				diff_hunk.new_lines(),
+			));
+			true
+		}),
+		Some(&mut |_, _, diff_line| {
+			let mut fsb = file_stats_builder.lock().unwrap();
+			fsb.add_diff_line(DiffLine::new(
+				Origin::from(diff_line.origin()),
+				std::str::from_utf8(diff_line.content()).unwrap(),
+				diff_line.old_lineno(),
+				diff_line.new_lineno(),
+				diff_line.origin() == '=' || diff_line.origin() == '>' || diff_line.origin() == '<',
+			));
+			true
+		}),
+	)
+	.unwrap();
+
+	if let Ok(stats) = diff.stats() {
+		number_files_changed = stats.files_changed() - unmodified_file_count;
+		insertions = stats.insertions();
+		deletions = stats.deletions();
+	}
 
-		let fsb = file_stats_builder.into_inner().unwrap();
+	let fsb = file_stats_builder.into_inner().unwrap();
 
-		fsb.build()
-	};
+	Ok((fsb.build(), number_files_changed, insertions, deletions))
+}
 
-	Ok(Commit {
-		hash: full_hash,
+fn create_commit(commit: &git2::Commit<'_>, body: Option<String>, file_stats: FileStats) -> Commit {
+	let (file_stats, number_files_changed, insertions, deletions) = file_stats;
+	let author = User::new(commit.author().name(), commit.author().email());
+	let mut committer = User::new(commit.committer().name(), commit.committer().email());
+	if committer == author {
+		committer = User::new(None, None);
+	}
+	Commit {
+		hash: commit.id().to_string(),
 		author,
 		committer,
-		date,
+		date: Local.timestamp(commit.time().seconds(), 0),
 		file_stats,
 		body,
 		number_files_changed,
 		insertions,
 		deletions,
-	})
+	}
+}
+
+fn load_commit_state(hash: &str, config: &LoadCommitDiffOptions) -> Result<Commit, Error> {
+	let repo = Repository::open_from_env()?;
+	let commit = repo.find_commit(repo.revparse_single(hash)?.id())?;
+
+	// some commits do not have parents, and can't have file stats
+	let file_stats = if commit.parent_ids().count() == 0 {
+		(vec![], 0, 0, 0)
+	}
+	else {
+		// parent exists from check above
+		load_file_stats(&repo, Some(&commit.parent(0)?.tree()?), &commit.tree()?, config)?
+	};
+
+	Ok(create_commit(&commit, commit.message().map(String::from), file_stats))
+}
+
+// the tree that results from applying the changes of each of the commits in turn, onto the tree of the first commit
+fn combine_trees<'r>(repo: &'r Repository, commits: &[git2::Commit<'r>]) -> Result<Tree<'r>, Error> {
+	let mut tree = commits[0].tree()?;
+	for commit in &commits[1..] {
+		let ancestor = match commit.parent(0) {
+			Ok(parent) => parent.tree()?,
+			Err(_) => repo.find_tree(repo.treebuilder(None)?.write()?)?,
+		};
+		let theirs = commit.tree()?;
+		let mut index = repo.merge_trees(&ancestor, &tree, &theirs, None)?;
+		if index.has_conflicts() {
+			// like the conflict prediction, a conflict is assumed to be resolved in favour of the applied changes
+			let mut options = MergeOptions::new();
+			let _ = options.file_favor(FileFavor::Theirs);
+			index = repo.merge_trees(&ancestor, &tree, &theirs, Some(&options))?;
+		}
+		tree = repo.find_tree(index.write_tree_to(repo)?)?;
+	}
+	Ok(tree)
+}
+
+fn load_squash_state(group: &[Line], comment_char: &str, config: &LoadCommitDiffOptions) -> Result<Commit, Error> {
+	let repo = Repository::open_from_env()?;
+	// the combined trees are written to an in-memory object database, so the repository is not changed
+	let _mempack = repo.odb()?.add_new_mempack_backend(MEMPACK_PRIORITY)?;
+	let commits = group
+		.iter()
+		.map(|line| repo.find_commit(repo.revparse_single(line.get_hash())?.id()))
+		.collect::<Result<Vec<git2::Commit<'_>>, Error>>()?;
+
+	let first = &commits[0];
+	let tree = combine_trees(&repo, &commits)?;
+	// a root commit is compared to an empty tree
+	let parent_tree = first.parent(0).and_then(|parent| parent.tree()).ok();
+	let file_stats = load_file_stats(&repo, parent_tree.as_ref(), &tree, config)?;
+
+	let messages = group
+		.iter()
+		.zip(&commits)
+		.map(|(line, commit)| (line.clone(), String::from(commit.message().unwrap_or(""))))
+		.collect::<Vec<(Line, String)>>();
+	Ok(create_commit(
+		first,
+		Some(build_squash_message(&messages, comment_char)),
+		file_stats,
+	))
+}
+
+fn load_range_state(first_hash: &str, last_hash: &str, config: &LoadCommitDiffOptions) -> Result<Commit, Error> {
+	let repo = Repository::open_from_env()?;
+	let first = repo.find_commit(repo.revparse_single(first_hash)?.id())?;
+	let last = repo.find_commit(repo.revparse_single(last_hash)?.id())?;
+	// a root commit is compared to an empty tree
+	let parent_tree = first.parent(0).and_then(|parent| parent.tree()).ok();
+	let file_stats = load_file_stats(&repo, parent_tree.as_ref(), &last.tree()?, config)?;
+	Ok(create_commit(&last, None, file_stats))
+}
+
+/// Load the paths of the files changed by a commit, without loading the full diff. A renamed file includes both the
+/// source and destination paths, while a copied file only includes the destination, since the source is unchanged.
+pub(super) fn load_commit_paths(hash: &str, config: &LoadCommitDiffOptions) -> Result<Vec<String>> {
+	let load_paths = || -> Result<Vec<String>, Error> {
+		let repo = Repository::open_from_env()?;
+		let commit = repo.find_commit(repo.revparse_single(hash)?.id())?;
+		// like the file stats, a commit without a parent has no changed files
+		if commit.parent_ids().count() == 0 {
+			return Ok(vec![]);
+		}
+		let mut diff = repo.diff_tree_to_tree(
+			Some(&commit.parent(0)?.tree()?),
+			Some(&commit.tree()?),
+			Some(&mut create_diff_options(config)),
+		)?;
+		diff.find_similar(Some(&mut create_diff_find_options(config)))?;
+
+		let mut paths = vec![];
+		for diff_delta in diff.deltas() {
+			if diff_delta.status() == git2::Delta::Unmodified {
+				continue;
+			}
+			if diff_delta.status() != git2::Delta::Copied {
+				if let Some(path) = diff_delta.old_file().path() {
+					paths.push(path.to_string_lossy().into_owned());
+				}
+			}
+			if let Some(path) = diff_delta.new_file().path() {
+				paths.push(path.to_string_lossy().into_owned());
+			}
+		}
+		paths.sort();
+		paths.dedup();
+		Ok(paths)
+	};
+	load_paths().map_err(|err| anyhow!(err).context(anyhow!("Error loading commit: {}", hash)))
 }
 
 impl Commit {
@@ -184,6 +370,28 @@ impl Commit {
 		load_commit_state(hash, config).map_err(|err| anyhow!(err).context(anyhow!("Error loading commit: {}", hash)))
 	}
 
+	/// Load the overall changes from the parent of the first commit to the last commit, with the details of the last
+	/// commit and no message.
+	pub(super) fn new_from_range(first_hash: &str, last_hash: &str, config: &LoadCommitDiffOptions) -> Result<Self> {
+		load_range_state(first_hash, last_hash, config)
+			.map_err(|err| anyhow!(err).context(anyhow!("Error loading commit range: {}..{}", first_hash, last_hash)))
+	}
+
+	/// Load the commit that results from a squash or fixup group, with the combined changes of the commits of the
+	/// group, and the message assembled by git for the group.
+	pub(super) fn new_from_squash_group(
+		group: &[Line],
+		comment_char: &str,
+		config: &LoadCommitDiffOptions,
+	) -> Result<Self> {
+		load_squash_state(group, comment_char, config).map_err(|err| {
+			anyhow!(err).context(anyhow!(
+				"Error combining commits: {}",
+				group.iter().map(Line::get_hash).collect::<Vec<&str>>().join(", ")
+			))
+		})
+	}
+
 	pub(super) const fn get_author(&self) -> &User {
 		&self.author
 	}
@@ -227,7 +435,9 @@ mod tests {
 	// we test what is possible
 	use std::{env::set_var, path::Path};
 
+	use git2::Oid;
 	use serial_test::serial;
+	use tempfile::tempdir;
 
 	use super::*;
 
@@ -245,8 +455,8 @@ mod tests {
 		);
 	}
 
-	fn load_commit_from_hash(hash: &str) -> Result<Commit> {
-		Commit::new_from_hash(hash, &LoadCommitDiffOptions {
+	const fn create_load_options() -> LoadCommitDiffOptions {
+		LoadCommitDiffOptions {
 			context_lines: 3,
 			copies: true,
 			ignore_whitespace: false,
@@ -254,7 +464,35 @@ mod tests {
 			interhunk_lines: 3,
 			rename_limit: 200,
 			renames: true,
-		})
+		}
+	}
+
+	fn load_commit_from_hash(hash: &str) -> Result<Commit> {
+		Commit::new_from_hash(hash, &create_load_options())
+	}
+
+	// a submodule entry has the commit hash as content, since the commit is not in the repository
+	fn create_tree<'r>(repo: &'r Repository, entries: &[(&str, &str, i32)]) -> Tree<'r> {
+		let mut builder = repo.treebuilder(None).unwrap();
+		for &(name, content, mode) in entries {
+			let id = if mode == 0o160_000 {
+				Oid::from_str(content).unwrap()
+			}
+			else {
+				repo.blob(content.as_bytes()).unwrap()
+			};
+			let _ = builder.insert(name, id, mode).unwrap();
+		}
+		repo.find_tree(builder.write().unwrap()).unwrap()
+	}
+
+	fn load_file_stats_from_trees(old_entries: &[(&str, &str, i32)], new_entries: &[(&str, &str, i32)]) -> FileStat {
+		let directory = tempdir().unwrap();
+		let repo = Repository::init(directory.path()).unwrap();
+		let old_tree = create_tree(&repo, old_entries);
+		let new_tree = create_tree(&repo, new_entries);
+		let (file_stats, ..) = load_file_stats(&repo, Some(&old_tree), &new_tree, &create_load_options()).unwrap();
+		file_stats.first().unwrap().clone()
 	}
 
 	#[test]
@@ -385,4 +623,98 @@ mod tests {
 		assert_eq!(commit.get_number_insertions(), 0);
 		assert_eq!(commit.get_number_deletions(), 0);
 	}
+
+	fn load_paths_from_hash(hash: &str) -> Vec<String> {
+		load_commit_paths(hash, &LoadCommitDiffOptions {
+			context_lines: 3,
+			copies: true,
+			ignore_whitespace: false,
+			ignore_whitespace_change: false,
+			interhunk_lines: 3,
+			rename_limit: 200,
+			renames: true,
+		})
+		.unwrap()
+	}
+
+	#[test]
+	#[serial]
+	fn load_commit_paths_modified_file() {
+		set_git_dir("simple");
+		assert_eq!(load_paths_from_hash("1cc0456637cb220155e957c641f483e60724c581"), vec![
+			"a"
+		]);
+	}
+
+	#[test]
+	#[serial]
+	fn load_commit_paths_renamed_file() {
+		set_git_dir("simple");
+		assert_eq!(load_paths_from_hash("aed0fd1db3e73c0e568677ae8903a11c5fbc5659"), vec![
+			"c", "f"
+		]);
+	}
+
+	#[test]
+	#[serial]
+	fn load_commit_paths_copied_file() {
+		set_git_dir("simple");
+		assert_eq!(load_paths_from_hash("c028f42bdb2a5a9f80adea23d95eb240b994a6c2"), vec![
+			"g"
+		]);
+	}
+
+	#[test]
+	#[serial]
+	fn load_commit_paths_initial_commit() {
+		set_git_dir("simple");
+		assert!(load_paths_from_hash("e10b3f474644d8566947104c07acba4d6f4f4f9f").is_empty());
+	}
+
+	#[test]
+	fn file_stats_text_file() {
+		let file_stat = load_file_stats_from_trees(&[("a", "old\n", 0o100_644)], &[("a", "new\n", 0o100_644)]);
+		assert_eq!(file_stat.get_mode_change(), None);
+		assert_eq!(*file_stat.get_content(), FileContent::Text);
+		assert_eq!(file_stat.deltas().len(), 1);
+	}
+
+	#[test]
+	fn file_stats_mode_change() {
+		let file_stat = load_file_stats_from_trees(&[("a", "same\n", 0o100_644)], &[("a", "same\n", 0o100_755)]);
+		assert_eq!(*file_stat.get_status(), Status::Modified);
+		assert_eq!(file_stat.get_mode_change(), Some((0o100_644, 0o100_755)));
+		assert_eq!(*file_stat.get_content(), FileContent::Text);
+		assert!(file_stat.deltas().is_empty());
+	}
+
+	#[test]
+	fn file_stats_binary_file() {
+		let file_stat = load_file_stats_from_trees(&[("a", "old\0", 0o100_644)], &[("a", "new\0content", 0o100_644)]);
+		assert_eq!(*file_stat.get_content(), FileContent::Binary {
+			old_size: 4,
+			new_size: 11
+		});
+	}
+
+	#[test]
+	fn file_stats_symlink() {
+		let file_stat =
+			load_file_stats_from_trees(&[("a", "old/target", 0o120_000)], &[("a", "new/target", 0o120_000)]);
+		assert_eq!(*file_stat.get_content(), FileContent::Symlink {
+			old_target: Some(String::from("old/target")),
+			new_target: Some(String::from("new/target"))
+		});
+	}
+
+	#[test]
+	fn file_stats_submodule() {
+		let old_commit = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+		let new_commit = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+		let file_stat = load_file_stats_from_trees(&[("a", old_commit, 0o160_000)], &[("a", new_commit, 0o160_000)]);
+		assert_eq!(*file_stat.get_content(), FileContent::Submodule {
+			old_commit: Some(String::from(old_commit)),
+			new_commit: Some(String::from(new_commit))
+		});
+	}
 }
@@ -0,0 +1,122 @@
+use std::{cell::RefCell, collections::HashMap, rc::Rc};
+
+use config::Config;
+use todo_file::{Action, Line};
+
+use super::commit::{load_commit_paths, LoadCommitDiffOptions};
+
+/// Does the line apply the changes of a commit, and so change the paths of the commit.
+pub(crate) const fn is_path_changing_line(line: &Line) -> bool {
+	matches!(
+		*line.get_action(),
+		Action::Edit | Action::Fixup | Action::Pick | Action::Revert | Action::Reword | Action::Squash
+	)
+}
+
+/// A cache of the paths of the files changed by commits, keyed by the commit hash. Clones share the same cache, so
+/// the paths of a commit are only loaded once, no matter which module requests them.
+#[derive(Clone, Debug)]
+pub(crate) struct CommitPaths {
+	cache: Rc<RefCell<HashMap<String, Vec<String>>>>,
+	load_commit_diff_options: LoadCommitDiffOptions,
+}
+
+impl CommitPaths {
+	pub(crate) fn new(config: &Config) -> Self {
+		Self {
+			cache: Rc::new(RefCell::new(HashMap::new())),
+			load_commit_diff_options: LoadCommitDiffOptions::new(config),
+		}
+	}
+
+	/// Get the paths changed by a commit, loading them if they are not cached. A commit that cannot be loaded is
+	/// treated as changing no paths.
+	pub(crate) fn get_paths(&self, hash: &str) -> Vec<String> {
+		if let Some(paths) = self.cache.borrow().get(hash) {
+			return paths.clone();
+		}
+		let paths = load_commit_paths(hash, &self.load_commit_diff_options).unwrap_or_default();
+		self.set_paths(hash, paths.clone());
+		paths
+	}
+
+	/// Set the paths changed by a commit, such as from a commit that has already been loaded.
+	pub(crate) fn set_paths(&self, hash: &str, paths: Vec<String>) {
+		let _ = self.cache.borrow_mut().insert(String::from(hash), paths);
+	}
+
+	/// Create a function that loads the paths changed by a commit, without the cache, so the paths can be loaded on
+	/// another thread.
+	pub(crate) fn create_loader(&self) -> impl Fn(&str) -> Vec<String> + Send + 'static {
+		let load_commit_diff_options = self.load_commit_diff_options;
+		move |hash: &str| load_commit_paths(hash, &load_commit_diff_options).unwrap_or_default()
+	}
+
+	/// Get the paths that are changed by both commits, if the paths of both commits are cached, without loading
+	/// either commit.
+	pub(crate) fn get_cached_overlapping_paths(&self, first_hash: &str, second_hash: &str) -> Vec<String> {
+		let cache = self.cache.borrow();
+		match (cache.get(first_hash), cache.get(second_hash)) {
+			(Some(first_paths), Some(second_paths)) => {
+				first_paths
+					.iter()
+					.filter(|path| second_paths.contains(path))
+					.cloned()
+					.collect()
+			},
+			_ => vec![],
+		}
+	}
+
+	/// Get the paths that are changed by both commits.
+	pub(crate) fn get_overlapping_paths(&self, first_hash: &str, second_hash: &str) -> Vec<String> {
+		let second_paths = self.get_paths(second_hash);
+		self.get_paths(first_hash)
+			.into_iter()
+			.filter(|path| second_paths.contains(path))
+			.collect()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn get_paths_cached() {
+		let commit_paths = CommitPaths::new(&Config::new());
+		let shared_commit_paths = commit_paths.clone();
+		shared_commit_paths.set_paths("aaa", vec![String::from("a.txt")]);
+		assert_eq!(commit_paths.get_paths("aaa"), vec!["a.txt"]);
+	}
+
+	#[test]
+	fn get_paths_invalid_commit() {
+		let commit_paths = CommitPaths::new(&Config::new());
+		assert!(commit_paths.get_paths("not-a-commit").is_empty());
+	}
+
+	#[test]
+	fn get_overlapping_paths() {
+		let commit_paths = CommitPaths::new(&Config::new());
+		commit_paths.set_paths("aaa", vec![String::from("a.txt"), String::from("b.txt")]);
+		commit_paths.set_paths("bbb", vec![String::from("b.txt"), String::from("c.txt")]);
+		assert_eq!(commit_paths.get_overlapping_paths("aaa", "bbb"), vec!["b.txt"]);
+	}
+
+	#[test]
+	fn get_cached_overlapping_paths() {
+		let commit_paths = CommitPaths::new(&Config::new());
+		commit_paths.set_paths("aaa", vec![String::from("a.txt"), String::from("b.txt")]);
+		commit_paths.set_paths("bbb", vec![String::from("b.txt"), String::from("c.txt")]);
+		assert_eq!(commit_paths.get_cached_overlapping_paths("aaa", "bbb"), vec!["b.txt"]);
+	}
+
+	#[test]
+	fn get_cached_overlapping_paths_not_cached() {
+		let commit_paths = CommitPaths::new(&Config::new());
+		commit_paths.set_paths("aaa", vec![String::from("a.txt")]);
+		assert!(commit_paths.get_cached_overlapping_paths("aaa", "not-a-commit").is_empty());
+		assert!(commit_paths.get_cached_overlapping_paths("not-a-commit", "aaa").is_empty());
+	}
+}
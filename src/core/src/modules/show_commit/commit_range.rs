@@ -0,0 +1,47 @@
+use anyhow::Result;
+
+use super::commit::{Commit, LoadCommitDiffOptions};
+
+/// The commits of a range of todo lines, along with the overall diff from the parent of the first commit to the last
+/// commit.
+#[derive(Debug)]
+pub(crate) struct CommitRange {
+	commits: Vec<Commit>,
+	diff: Commit,
+	hashes: Vec<String>,
+}
+
+impl CommitRange {
+	/// Load the commits of `hashes`, in order, and the overall diff of the range. At least one hash is required.
+	pub(super) fn new_from_hashes(hashes: &[String], config: &LoadCommitDiffOptions) -> Result<Self> {
+		let commits = hashes
+			.iter()
+			.map(|hash| Commit::new_from_hash(hash, config))
+			.collect::<Result<Vec<Commit>>>()?;
+		let diff = Commit::new_from_range(
+			hashes.first().map_or("", String::as_str),
+			hashes.last().map_or("", String::as_str),
+			config,
+		)?;
+		Ok(Self {
+			commits,
+			diff,
+			hashes: hashes.to_vec(),
+		})
+	}
+
+	/// Get the hashes of the range, as they were provided.
+	pub(super) fn get_hashes(&self) -> &[String] {
+		&self.hashes
+	}
+
+	/// Get the commits of the range, in order.
+	pub(super) fn get_commits(&self) -> &[Commit] {
+		&self.commits
+	}
+
+	/// Get the overall diff of the range.
+	pub(super) const fn get_diff(&self) -> &Commit {
+		&self.diff
+	}
+}
@@ -0,0 +1,215 @@
+use crate::modules::get_match_ranges;
+
+/// The search of a diff, which is the search term along with the searchable lines of the diff, as the row of the line
+/// and the content of the line, and the match that is selected.
+#[derive(Debug, Default)]
+pub(super) struct DiffSearch {
+	lines: Vec<(usize, String)>,
+	selected: Option<usize>,
+	term: String,
+}
+
+impl DiffSearch {
+	pub(super) fn new() -> Self {
+		Self::default()
+	}
+
+	/// Clear the lines and the selected match, for when the diff is of other files. The search term is kept.
+	pub(super) fn reset(&mut self) {
+		self.lines.clear();
+		self.selected = None;
+	}
+
+	/// Clear the lines, before the diff is built again.
+	pub(super) fn clear_lines(&mut self) {
+		self.lines.clear();
+	}
+
+	/// Add a searchable line, with the row of the line. A row can have more than one line, as in a side by side diff.
+	pub(super) fn push_line(&mut self, row: usize, line: &str) {
+		self.lines.push((row, String::from(line)));
+	}
+
+	pub(super) fn get_term(&self) -> &str {
+		self.term.as_str()
+	}
+
+	/// Set the search term, which clears the selected match.
+	pub(super) fn set_term(&mut self, term: &str) {
+		self.term = String::from(term);
+		self.selected = None;
+	}
+
+	/// Get the ranges, as byte offsets, of the text that match the search term.
+	pub(super) fn get_match_ranges(&self, text: &str) -> Vec<(usize, usize)> {
+		get_match_ranges(text, self.term.as_str())
+	}
+
+	// the rows that have a line that matches the search term, in order
+	fn get_match_rows(&self) -> Vec<usize> {
+		let mut rows: Vec<usize> = vec![];
+		for &(row, ref line) in &self.lines {
+			if rows.last() != Some(&row) && !self.get_match_ranges(line).is_empty() {
+				rows.push(row);
+			}
+		}
+		rows
+	}
+
+	pub(super) fn get_match_count(&self) -> usize {
+		self.get_match_rows().len()
+	}
+
+	/// Get the row of the selected match.
+	pub(super) fn get_selected_row(&self) -> Option<usize> {
+		let rows = self.get_match_rows();
+		self.selected.and_then(|index| rows.get(index).copied())
+	}
+
+	/// Select the first match at or after the row, wrapping around the end of the diff. Returns the row of the match.
+	pub(super) fn select_match_from(&mut self, row: usize) -> Option<usize> {
+		let rows = self.get_match_rows();
+		self.selected = if rows.is_empty() {
+			None
+		}
+		else {
+			Some(rows.iter().position(|&match_row| match_row >= row).unwrap_or(0))
+		};
+		self.selected.map(|index| rows[index])
+	}
+
+	/// Select the next, or previous, match after the selected match, wrapping around the ends of the diff. Without a
+	/// selected match, the first, or last, match is selected. Returns the row of the match.
+	pub(super) fn select_next_match(&mut self, forward: bool) -> Option<usize> {
+		let rows = self.get_match_rows();
+		if rows.is_empty() {
+			self.selected = None;
+			return None;
+		}
+		let last_index = rows.len() - 1;
+		self.selected = Some(match (self.selected.map(|index| index.min(last_index)), forward) {
+			(Some(index), true) if index < last_index => index + 1,
+			(Some(index), false) if index > 0 => index - 1,
+			(_, true) => 0,
+			(_, false) => last_index,
+		});
+		self.selected.map(|index| rows[index])
+	}
+
+	/// Get the number of matches, as a text for the title, when there is a search term.
+	pub(super) fn get_match_info(&self) -> Option<String> {
+		if self.term.is_empty() {
+			return None;
+		}
+		let rows = self.get_match_rows();
+		Some(match self.selected {
+			Some(index) if !rows.is_empty() => format!("match {} of {}", index.min(rows.len() - 1) + 1, rows.len()),
+			_ => format!("{} matches", rows.len()),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+
+	// matches on rows 2, 5 and 9, where row 5 has two matching lines as in a side by side diff
+	fn create_diff_search() -> DiffSearch {
+		let mut search = DiffSearch::new();
+		search.push_line(1, "let a = 1;\n");
+		search.push_line(2, "let value = 2;\n");
+		search.push_line(5, "value\n");
+		search.push_line(5, "Value\n");
+		search.push_line(7, "other\n");
+		search.push_line(9, "return value;\n");
+		search.set_term("value");
+		search
+	}
+
+	#[test]
+	fn get_match_rows() {
+		assert_eq!(create_diff_search().get_match_rows(), vec![2, 5, 9]);
+	}
+
+	#[test]
+	fn get_match_count() {
+		assert_eq!(create_diff_search().get_match_count(), 3);
+	}
+
+	#[test]
+	fn get_match_rows_empty_term() {
+		let mut search = create_diff_search();
+		search.set_term("");
+		assert!(search.get_match_rows().is_empty());
+	}
+
+	#[rstest]
+	#[case::start(0, Some(2))]
+	#[case::on_match(5, Some(5))]
+	#[case::between_matches(6, Some(9))]
+	#[case::wrap(10, Some(2))]
+	fn select_match_from(#[case] row: usize, #[case] expected: Option<usize>) {
+		let mut search = create_diff_search();
+		assert_eq!(search.select_match_from(row), expected);
+		assert_eq!(search.get_selected_row(), expected);
+	}
+
+	#[test]
+	fn select_match_from_without_matches() {
+		let mut search = create_diff_search();
+		search.set_term("missing");
+		assert_eq!(search.select_match_from(0), None);
+		assert_eq!(search.get_selected_row(), None);
+	}
+
+	#[test]
+	fn select_next_match() {
+		let mut search = create_diff_search();
+		assert_eq!(search.select_next_match(true), Some(2));
+		assert_eq!(search.select_next_match(true), Some(5));
+		assert_eq!(search.select_next_match(true), Some(9));
+		assert_eq!(search.select_next_match(true), Some(2));
+	}
+
+	#[test]
+	fn select_previous_match() {
+		let mut search = create_diff_search();
+		assert_eq!(search.select_next_match(false), Some(9));
+		assert_eq!(search.select_next_match(false), Some(5));
+		assert_eq!(search.select_next_match(false), Some(2));
+		assert_eq!(search.select_next_match(false), Some(9));
+	}
+
+	#[test]
+	fn select_next_match_without_matches() {
+		let mut search = create_diff_search();
+		search.set_term("missing");
+		assert_eq!(search.select_next_match(true), None);
+	}
+
+	#[test]
+	fn reset() {
+		let mut search = create_diff_search();
+		let _ = search.select_next_match(true);
+		search.reset();
+		assert_eq!(search.get_selected_row(), None);
+		assert!(search.get_match_rows().is_empty());
+		assert_eq!(search.get_term(), "value");
+	}
+
+	#[rstest]
+	#[case::no_term("", false, None)]
+	#[case::not_selected("value", false, Some("3 matches"))]
+	#[case::selected("value", true, Some("match 1 of 3"))]
+	#[case::no_matches("missing", true, Some("0 matches"))]
+	fn get_match_info(#[case] term: &str, #[case] select: bool, #[case] expected: Option<&str>) {
+		let mut search = create_diff_search();
+		search.set_term(term);
+		if select {
+			let _ = search.select_next_match(true);
+		}
+		assert_eq!(search.get_match_info().as_deref(), expected);
+	}
+}
@@ -0,0 +1,275 @@
+use std::collections::HashSet;
+
+/// The sections of a diff, which are the rows of the header of each file and of each hunk of the file, along with the
+/// files that are collapsed and the file or hunk that is selected.
+#[derive(Debug, Default)]
+pub(super) struct DiffSections {
+	collapsed_files: HashSet<usize>,
+	files: Vec<(usize, Vec<usize>)>,
+	selected: Option<(usize, Option<usize>)>,
+}
+
+impl DiffSections {
+	pub(super) fn new() -> Self {
+		Self::default()
+	}
+
+	/// Expand all files and clear the selection, for when the diff is of other files.
+	pub(super) fn reset(&mut self) {
+		self.collapsed_files.clear();
+		self.files.clear();
+		self.selected = None;
+	}
+
+	/// Clear the rows of the sections, before the diff is built again.
+	pub(super) fn clear_rows(&mut self) {
+		self.files.clear();
+	}
+
+	/// Add a file, with the row of the header of the file.
+	pub(super) fn push_file(&mut self, row: usize) {
+		self.files.push((row, vec![]));
+	}
+
+	/// Add a hunk to the last added file, with the row of the header of the hunk.
+	pub(super) fn push_hunk(&mut self, row: usize) {
+		if let Some(&mut (_, ref mut hunks)) = self.files.last_mut() {
+			hunks.push(row);
+		}
+	}
+
+	pub(super) fn is_collapsed(&self, file_index: usize) -> bool {
+		self.collapsed_files.contains(&file_index)
+	}
+
+	/// Is the file, or a hunk of the file, the selected section.
+	pub(super) fn is_selected(&self, file_index: usize, hunk_index: Option<usize>) -> bool {
+		self.selected == Some((file_index, hunk_index))
+	}
+
+	pub(super) fn get_selected_file(&self) -> Option<usize> {
+		self.selected.map(|(file_index, _)| file_index)
+	}
+
+	/// Get the row of the header of the selected section.
+	pub(super) fn get_selected_row(&self) -> Option<usize> {
+		let (file_index, hunk_index) = self.selected?;
+		let &(file_row, ref hunks) = self.files.get(file_index)?;
+		hunk_index.map_or(Some(file_row), |index| hunks.get(index).copied())
+	}
+
+	/// Collapse the selected file if it is expanded, or expand it if it is collapsed. The first file is used when
+	/// nothing is selected. Since a collapsed file has no hunks, the file itself is then selected.
+	pub(super) fn toggle_collapsed(&mut self) {
+		let file_index = self.get_selected_file().unwrap_or(0);
+		if !self.collapsed_files.remove(&file_index) {
+			let _ = self.collapsed_files.insert(file_index);
+		}
+		self.selected = Some((file_index, None));
+	}
+
+	/// Select a file, expanding the file if it is collapsed.
+	pub(super) fn select_file(&mut self, file_index: usize) {
+		let _ = self.collapsed_files.remove(&file_index);
+		self.selected = Some((file_index, None));
+	}
+
+	/// Select the file after the selected file. Without a selection, the diff is at the first file.
+	pub(super) fn select_next_file(&mut self) {
+		let file_index = self.get_selected_file().map_or(1, |index| index + 1);
+		if file_index < self.files.len() {
+			self.selected = Some((file_index, None));
+		}
+	}
+
+	/// Select the file before the selected file, or the selected file when a hunk of the file is selected.
+	pub(super) fn select_previous_file(&mut self) {
+		self.selected = match self.selected {
+			Some((file_index, Some(_))) => Some((file_index, None)),
+			Some((file_index, None)) if file_index > 0 => Some((file_index - 1, None)),
+			_ if self.files.is_empty() => None,
+			_ => Some((0, None)),
+		};
+	}
+
+	// all hunks of the diff, as the index of the file and the index of the hunk within the file
+	fn get_hunks(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+		self.files
+			.iter()
+			.enumerate()
+			.flat_map(|(file_index, &(_, ref hunks))| (0..hunks.len()).map(move |hunk_index| (file_index, hunk_index)))
+	}
+
+	/// Select the hunk after the selected file or hunk, which can be in a following file.
+	pub(super) fn select_next_hunk(&mut self) {
+		let (selected_file, selected_hunk) = self.selected.unwrap_or((0, None));
+		let next = self.get_hunks().find(|&(file_index, hunk_index)| {
+			file_index > selected_file
+				|| (file_index == selected_file && selected_hunk.map_or(true, |selected| hunk_index > selected))
+		});
+		if let Some((file_index, hunk_index)) = next {
+			self.selected = Some((file_index, Some(hunk_index)));
+		}
+	}
+
+	/// Select the hunk before the selected file or hunk, which can be in a preceding file.
+	pub(super) fn select_previous_hunk(&mut self) {
+		let (selected_file, selected_hunk) = match self.selected {
+			Some(selected) => selected,
+			None => return,
+		};
+		let previous = self
+			.get_hunks()
+			.filter(|&(file_index, hunk_index)| {
+				file_index < selected_file
+					|| (file_index == selected_file && selected_hunk.map_or(false, |selected| hunk_index < selected))
+			})
+			.last();
+		if let Some((file_index, hunk_index)) = previous {
+			self.selected = Some((file_index, Some(hunk_index)));
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// three files, where the second file has no hunks
+	fn create_diff_sections() -> DiffSections {
+		let mut sections = DiffSections::new();
+		sections.push_file(1);
+		sections.push_hunk(3);
+		sections.push_hunk(8);
+		sections.push_file(12);
+		sections.push_file(14);
+		sections.push_hunk(16);
+		sections
+	}
+
+	#[test]
+	fn push_hunk_without_file() {
+		let mut sections = DiffSections::new();
+		sections.push_hunk(1);
+		assert_eq!(sections.get_hunks().count(), 0);
+	}
+
+	#[test]
+	fn get_selected_row() {
+		let mut sections = create_diff_sections();
+		assert_eq!(sections.get_selected_row(), None);
+		sections.select_file(1);
+		assert_eq!(sections.get_selected_row(), Some(12));
+		sections.selected = Some((0, Some(1)));
+		assert_eq!(sections.get_selected_row(), Some(8));
+		sections.selected = Some((5, None));
+		assert_eq!(sections.get_selected_row(), None);
+	}
+
+	#[test]
+	fn toggle_collapsed() {
+		let mut sections = create_diff_sections();
+		sections.selected = Some((2, Some(0)));
+		sections.toggle_collapsed();
+		assert!(sections.is_collapsed(2));
+		assert!(sections.is_selected(2, None));
+		sections.toggle_collapsed();
+		assert!(!sections.is_collapsed(2));
+	}
+
+	#[test]
+	fn toggle_collapsed_without_selection() {
+		let mut sections = create_diff_sections();
+		sections.toggle_collapsed();
+		assert!(sections.is_collapsed(0));
+		assert!(sections.is_selected(0, None));
+	}
+
+	#[test]
+	fn select_file_expands() {
+		let mut sections = create_diff_sections();
+		sections.toggle_collapsed();
+		sections.select_file(0);
+		assert!(!sections.is_collapsed(0));
+	}
+
+	#[test]
+	fn reset() {
+		let mut sections = create_diff_sections();
+		sections.toggle_collapsed();
+		sections.reset();
+		assert!(!sections.is_collapsed(0));
+		assert_eq!(sections.get_selected_file(), None);
+		assert_eq!(sections.get_hunks().count(), 0);
+	}
+
+	#[test]
+	fn select_next_file() {
+		let mut sections = create_diff_sections();
+		sections.select_next_file();
+		assert!(sections.is_selected(1, None));
+		sections.select_next_file();
+		assert!(sections.is_selected(2, None));
+		sections.select_next_file();
+		assert!(sections.is_selected(2, None));
+	}
+
+	#[test]
+	fn select_previous_file() {
+		let mut sections = create_diff_sections();
+		sections.select_previous_file();
+		assert!(sections.is_selected(0, None));
+		sections.selected = Some((2, Some(0)));
+		sections.select_previous_file();
+		assert!(sections.is_selected(2, None));
+		sections.select_previous_file();
+		assert!(sections.is_selected(1, None));
+	}
+
+	#[test]
+	fn select_previous_file_without_files() {
+		let mut sections = DiffSections::new();
+		sections.select_previous_file();
+		assert_eq!(sections.get_selected_file(), None);
+	}
+
+	#[test]
+	fn select_next_hunk() {
+		let mut sections = create_diff_sections();
+		sections.select_next_hunk();
+		assert!(sections.is_selected(0, Some(0)));
+		sections.select_next_hunk();
+		assert!(sections.is_selected(0, Some(1)));
+		sections.select_next_hunk();
+		assert!(sections.is_selected(2, Some(0)));
+		sections.select_next_hunk();
+		assert!(sections.is_selected(2, Some(0)));
+	}
+
+	#[test]
+	fn select_next_hunk_from_file() {
+		let mut sections = create_diff_sections();
+		sections.select_file(1);
+		sections.select_next_hunk();
+		assert!(sections.is_selected(2, Some(0)));
+	}
+
+	#[test]
+	fn select_previous_hunk() {
+		let mut sections = create_diff_sections();
+		sections.selected = Some((2, None));
+		sections.select_previous_hunk();
+		assert!(sections.is_selected(0, Some(1)));
+		sections.select_previous_hunk();
+		assert!(sections.is_selected(0, Some(0)));
+		sections.select_previous_hunk();
+		assert!(sections.is_selected(0, Some(0)));
+	}
+
+	#[test]
+	fn select_previous_hunk_without_selection() {
+		let mut sections = create_diff_sections();
+		sections.select_previous_hunk();
+		assert_eq!(sections.get_selected_file(), None);
+	}
+}
@@ -0,0 +1,18 @@
+/// The content of a changed file, for the changes that can not be shown as changed lines of text.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum FileContent {
+	/// Text content, where the changes are the deltas of the file
+	Text,
+	/// Binary content, with the size in bytes of the old and new file
+	Binary { old_size: u64, new_size: u64 },
+	/// A symbolic link, with the target of the old and new link, if the file is a link in that version
+	Symlink {
+		old_target: Option<String>,
+		new_target: Option<String>,
+	},
+	/// A submodule, with the commit of the old and new submodule, if the file is a submodule in that version
+	Submodule {
+		old_commit: Option<String>,
+		new_commit: Option<String>,
+	},
+}
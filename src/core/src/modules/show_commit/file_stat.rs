@@ -1,4 +1,4 @@
-use super::{delta::Delta, status::Status};
+use super::{delta::Delta, file_content::FileContent, status::Status};
 
 /// Represents a file change within a Git repository
 #[derive(Debug, Clone)]
@@ -10,6 +10,9 @@ pub(crate) struct FileStat {
 	pub(super) largest_old_line_number: u32,
 	pub(super) largest_new_line_number: u32,
 	pub(super) deltas: Vec<Delta>,
+	pub(super) old_mode: u32,
+	pub(super) new_mode: u32,
+	pub(super) content: FileContent,
 }
 
 impl FileStat {
@@ -22,9 +25,22 @@ impl FileStat {
 			largest_old_line_number: 0,
 			largest_new_line_number: 0,
 			deltas: vec![],
+			old_mode: 0,
+			new_mode: 0,
+			content: FileContent::Text,
 		}
 	}
 
+	/// Set the old and new file modes, where a mode of zero is a file that does not exist in that version.
+	pub(super) fn set_modes(&mut self, old_mode: u32, new_mode: u32) {
+		self.old_mode = old_mode;
+		self.new_mode = new_mode;
+	}
+
+	pub(super) fn set_content(&mut self, content: FileContent) {
+		self.content = content;
+	}
+
 	pub(super) fn add_delta(&mut self, delta: Delta) {
 		let last_old_line_number = delta.old_start() + delta.old_lines();
 		if self.largest_old_line_number < last_old_line_number {
@@ -63,10 +79,26 @@ impl FileStat {
 	pub(crate) const fn largest_new_line_number(&self) -> u32 {
 		self.largest_new_line_number
 	}
+
+	/// Get the old and new file modes, if the mode of the file was changed.
+	pub(crate) const fn get_mode_change(&self) -> Option<(u32, u32)> {
+		if self.old_mode == 0 || self.new_mode == 0 || self.old_mode == self.new_mode {
+			None
+		}
+		else {
+			Some((self.old_mode, self.new_mode))
+		}
+	}
+
+	pub(crate) const fn get_content(&self) -> &FileContent {
+		&self.content
+	}
 }
 
 #[cfg(test)]
 mod tests {
+	use rstest::rstest;
+
 	use super::*;
 
 	#[test]
@@ -78,6 +110,32 @@ mod tests {
 		assert_eq!(file_stat.largest_old_line_number(), 0);
 		assert_eq!(file_stat.largest_new_line_number(), 0);
 		assert!(file_stat.deltas().is_empty());
+		assert_eq!(file_stat.get_mode_change(), None);
+		assert_eq!(*file_stat.get_content(), FileContent::Text);
+	}
+
+	#[rstest]
+	#[case::changed(0o100_644, 0o100_755, Some((0o100_644, 0o100_755)))]
+	#[case::unchanged(0o100_644, 0o100_644, None)]
+	#[case::added(0, 0o100_644, None)]
+	#[case::deleted(0o100_755, 0, None)]
+	fn get_mode_change(#[case] old_mode: u32, #[case] new_mode: u32, #[case] expected: Option<(u32, u32)>) {
+		let mut file_stat = FileStat::new("/from/path", "/to/path", Status::Modified);
+		file_stat.set_modes(old_mode, new_mode);
+		assert_eq!(file_stat.get_mode_change(), expected);
+	}
+
+	#[test]
+	fn set_content() {
+		let mut file_stat = FileStat::new("/from/path", "/to/path", Status::Modified);
+		file_stat.set_content(FileContent::Binary {
+			old_size: 1,
+			new_size: 2,
+		});
+		assert_eq!(*file_stat.get_content(), FileContent::Binary {
+			old_size: 1,
+			new_size: 2
+		});
 	}
 
 	#[test]
@@ -1,34 +1,52 @@
 mod commit;
+mod commit_paths;
+mod commit_range;
 mod delta;
 mod diff_line;
+mod diff_search;
+mod diff_sections;
+mod file_content;
 mod file_stat;
 mod file_stats_builder;
 mod origin;
+mod selected_range;
 mod show_commit_state;
+mod squash;
 mod status;
+mod syntax;
 mod user;
 mod util;
 mod view_builder;
+mod word_diff;
 
 #[cfg(test)]
 mod tests;
 
 use anyhow::anyhow;
 use captur::capture;
-use config::{Config, DiffIgnoreWhitespaceSetting, DiffShowWhitespaceSetting};
-use input::{Event, EventHandler, InputOptions, MetaEvent};
+use config::{Config, DiffShowWhitespaceSetting};
+use display::DisplayColor;
+use input::{Event, EventHandler, InputOptions, KeyCode, KeyEvent, KeyModifiers, MetaEvent};
 use lazy_static::lazy_static;
 use todo_file::TodoFile;
-use view::{handle_view_data_scroll, RenderContext, ViewData, ViewSender};
+use view::{handle_view_data_scroll, LineSegment, RenderContext, ViewData, ViewLine, ViewSender};
 
+pub(crate) use self::{
+	commit_paths::{is_path_changing_line, CommitPaths},
+	selected_range::SelectedRange,
+};
 use self::{
 	commit::{Commit, LoadCommitDiffOptions},
+	commit_range::CommitRange,
+	diff_search::DiffSearch,
+	diff_sections::DiffSections,
 	show_commit_state::ShowCommitState,
+	squash::get_squash_group,
 	util::get_show_commit_help_lines,
 	view_builder::{ViewBuilder, ViewBuilderOptions},
 };
 use crate::{
-	components::help::Help,
+	components::{edit::Edit, help::Help},
 	module::{Module, ProcessResult, State},
 };
 
@@ -40,26 +58,56 @@ lazy_static! {
 		.resize(false);
 }
 
+// a side by side diff needs room for the content of both sides, so narrower terminals fall back to a unified diff
+const MINIMUM_SIDE_BY_SIDE_WIDTH: usize = 100;
+
+// the title and the two summary lines are shown above the scrolling lines of a diff
+const DIFF_PADDING_HEIGHT: usize = 3;
+
 pub(crate) struct ShowCommit {
+	comment_char: String,
 	commit: Option<Commit>,
+	commit_paths: CommitPaths,
+	conflicts: Vec<String>,
+	diff_search: DiffSearch,
+	diff_sections: DiffSections,
 	diff_view_data: ViewData,
+	file_list_index: Option<usize>,
+	file_list_view_data: ViewData,
 	help: Help,
 	load_commit_diff_options: LoadCommitDiffOptions,
 	overview_view_data: ViewData,
+	range: Option<CommitRange>,
+	selected_range: SelectedRange,
+	scroll_to_selected_section: bool,
+	search_edit: Edit,
+	search_start_row: usize,
+	searching: bool,
+	side_by_side: Option<bool>,
+	squash_commit: Option<Commit>,
+	squash_search: DiffSearch,
+	squash_sections: DiffSections,
+	squash_view_data: ViewData,
 	state: ShowCommitState,
+	touched_by: Vec<(String, String, Vec<String>)>,
 	view_builder: ViewBuilder,
 }
 
 impl Module for ShowCommit {
 	fn activate(&mut self, rebase_todo: &TodoFile, _: State) -> ProcessResult {
+		if let Some(hashes) = self.get_range_hashes(rebase_todo) {
+			return self.activate_range(&hashes);
+		}
+
 		if let Some(selected_line) = rebase_todo.get_selected_line() {
 			// skip loading commit data if the currently loaded commit has not changed, this retains
 			// position after returning to the list view or help
 			if let Some(ref commit) = self.commit {
-				if commit.get_hash() == selected_line.get_hash() {
+				if self.range.is_none() && commit.get_hash() == selected_line.get_hash() {
 					return ProcessResult::new();
 				}
 			}
+			self.range = None;
 			self.overview_view_data.update_view_data(|updater| {
 				updater.clear();
 				updater.reset_scroll_position();
@@ -69,6 +117,9 @@ impl Module for ShowCommit {
 				updater.clear();
 				updater.reset_scroll_position();
 			});
+			self.diff_sections.reset();
+			self.diff_search.reset();
+			self.file_list_index = None;
 
 			let new_commit = Commit::new_from_hash(selected_line.get_hash(), &self.load_commit_diff_options);
 
@@ -87,35 +138,148 @@ impl Module for ShowCommit {
 		}
 	}
 
-	fn build_view_data(&mut self, context: &RenderContext, _: &TodoFile) -> &ViewData {
+	fn build_view_data(&mut self, context: &RenderContext, rebase_todo: &TodoFile) -> &ViewData {
 		if self.help.is_active() {
 			return self.help.get_view_data();
 		}
 
-		let commit = self.commit.as_ref().unwrap(); // will only fail on programmer error
+		// the predicted conflicts can arrive after the overview is built, so the overview is rebuilt when they change
+		let selected_conflicts = rebase_todo
+			.get_conflicts()
+			.and_then(|conflicts| conflicts.get(rebase_todo.get_selected_line_index()))
+			.map_or_else(Vec::new, Clone::clone);
+		if selected_conflicts != self.conflicts {
+			self.conflicts = selected_conflicts;
+			self.overview_view_data.update_view_data(|updater| updater.clear());
+		}
+
+		// the paths of the other commits are loaded in the background, and the other lines can change while the
+		// commit is not shown, so the commits that touch the same files are found again on every render
+		let touched_by = self.get_touched_by(rebase_todo);
+		if touched_by != self.touched_by {
+			self.touched_by = touched_by;
+			self.overview_view_data.update_view_data(|updater| updater.clear());
+		}
+
+		let is_full_width = context.is_full_width();
+		if let Some(selected_index) = self.file_list_index {
+			let commit = if self.state == ShowCommitState::Squash {
+				self.squash_commit.as_ref()
+			}
+			else {
+				self.range.as_ref().map(CommitRange::get_diff).or(self.commit.as_ref())
+			}
+			.unwrap(); // will only fail on programmer error
+			// the selected index is moved without the number of files, so is limited to the last file here
+			let selected_index = selected_index.min(commit.get_file_stats().len().saturating_sub(1));
+			self.file_list_index = Some(selected_index);
+			self.file_list_view_data.update_view_data(|updater| {
+				capture!(commit);
+				updater.clear();
+				ViewBuilder::build_view_data_file_list(updater, commit, selected_index, is_full_width);
+			});
+			return &self.file_list_view_data;
+		}
+
+		let conflicts = &self.conflicts;
+		let touched_by = &self.touched_by;
 		let state = &self.state;
 		let view_builder = &self.view_builder;
-		let is_full_width = context.is_full_width();
+		// until the side by side diff is toggled, it is shown whenever the view is full width
+		let side_by_side_width = (self.side_by_side.unwrap_or(is_full_width)
+			&& is_full_width
+			&& context.width() >= MINIMUM_SIDE_BY_SIDE_WIDTH)
+			.then(|| context.width());
 
 		match *state {
 			ShowCommitState::Overview => {
 				if self.overview_view_data.is_empty() {
+					if let Some(ref range) = self.range {
+						self.overview_view_data.update_view_data(|updater| {
+							capture!(view_builder, range);
+							view_builder.build_view_data_for_range_overview(updater, range, is_full_width);
+						});
+						return &self.overview_view_data;
+					}
+					let commit = self.commit.as_ref().unwrap(); // will only fail on programmer error
 					self.overview_view_data.update_view_data(|updater| {
-						capture!(view_builder, commit);
-						view_builder.build_view_data_for_overview(updater, commit, is_full_width);
+						capture!(view_builder, commit, conflicts, touched_by);
+						view_builder.build_view_data_for_overview(
+							updater,
+							commit,
+							conflicts,
+							touched_by,
+							is_full_width,
+						);
 					});
 				}
 				&self.overview_view_data
 			},
 			ShowCommitState::Diff => {
+				let diff_sections = &mut self.diff_sections;
+				let diff_search = &mut self.diff_search;
 				if self.diff_view_data.is_empty() {
-					self.diff_view_data.update_view_data(|updater| {
-						capture!(view_builder, commit);
-						view_builder.build_view_data_diff(updater, commit, is_full_width);
-					});
+					if let Some(ref range) = self.range {
+						self.diff_view_data.update_view_data(|updater| {
+							capture!(view_builder, range);
+							view_builder.build_view_data_range_diff(
+								updater,
+								range,
+								is_full_width,
+								side_by_side_width,
+								diff_sections,
+								diff_search,
+							);
+						});
+					}
+					else {
+						let commit = self.commit.as_ref().unwrap(); // will only fail on programmer error
+						self.diff_view_data.update_view_data(|updater| {
+							capture!(view_builder, commit);
+							view_builder.build_view_data_diff(
+								updater,
+								commit,
+								is_full_width,
+								side_by_side_width,
+								diff_sections,
+								diff_search,
+							);
+						});
+					}
+					let search_edit = self.searching.then_some(&self.search_edit);
+					Self::update_search_view_data(&mut self.diff_view_data, &self.diff_search, search_edit);
+				}
+				if self.scroll_to_selected_section {
+					self.scroll_to_selected_section = false;
+					Self::scroll_to_section(&mut self.diff_view_data, &self.diff_sections, context);
 				}
 				&self.diff_view_data
 			},
+			ShowCommitState::Squash => {
+				let squash_sections = &mut self.squash_sections;
+				let squash_search = &mut self.squash_search;
+				if self.squash_view_data.is_empty() {
+					let squash_commit = self.squash_commit.as_ref().unwrap(); // will only fail on programmer error
+					self.squash_view_data.update_view_data(|updater| {
+						capture!(view_builder, squash_commit);
+						view_builder.build_view_data_squash(
+							updater,
+							squash_commit,
+							is_full_width,
+							side_by_side_width,
+							squash_sections,
+							squash_search,
+						);
+					});
+					let search_edit = self.searching.then_some(&self.search_edit);
+					Self::update_search_view_data(&mut self.squash_view_data, &self.squash_search, search_edit);
+				}
+				if self.scroll_to_selected_section {
+					self.scroll_to_selected_section = false;
+					Self::scroll_to_section(&mut self.squash_view_data, &self.squash_sections, context);
+				}
+				&self.squash_view_data
+			},
 		}
 	}
 
@@ -123,45 +287,163 @@ impl Module for ShowCommit {
 		&mut self,
 		event_handler: &EventHandler,
 		view_sender: &ViewSender,
-		_: &mut TodoFile,
+		rebase_todo: &mut TodoFile,
 	) -> ProcessResult {
 		if self.help.is_active() {
 			return ProcessResult::from(self.help.handle_event(event_handler, view_sender));
 		}
 
+		if self.searching {
+			return self.handle_search_input(event_handler, view_sender);
+		}
+
 		let event = event_handler.read_event(&INPUT_OPTIONS, |event, key_bindings| {
 			if key_bindings.show_diff.contains(&event) {
 				Event::from(MetaEvent::ShowDiff)
 			}
+			else if key_bindings.show_combined_diff.contains(&event) {
+				Event::from(MetaEvent::ShowCombinedDiff)
+			}
+			else if key_bindings.toggle_side_by_side.contains(&event) {
+				Event::from(MetaEvent::ToggleSideBySide)
+			}
+			else if key_bindings.next_file.contains(&event) {
+				Event::from(MetaEvent::NextFile)
+			}
+			else if key_bindings.previous_file.contains(&event) {
+				Event::from(MetaEvent::PreviousFile)
+			}
+			else if key_bindings.next_hunk.contains(&event) {
+				Event::from(MetaEvent::NextHunk)
+			}
+			else if key_bindings.previous_hunk.contains(&event) {
+				Event::from(MetaEvent::PreviousHunk)
+			}
+			else if key_bindings.toggle_fold.contains(&event) {
+				Event::from(MetaEvent::ToggleFold)
+			}
+			else if key_bindings.show_file_list.contains(&event) {
+				Event::from(MetaEvent::ShowFileList)
+			}
+			else if key_bindings.search.contains(&event) {
+				Event::from(MetaEvent::SearchStart)
+			}
+			else if key_bindings.search_next.contains(&event) {
+				Event::from(MetaEvent::SearchNext)
+			}
+			else if key_bindings.search_previous.contains(&event) {
+				Event::from(MetaEvent::SearchPrevious)
+			}
 			else {
 				event
 			}
 		});
 
+		if self.file_list_index.is_some() {
+			self.handle_file_list_event(event);
+			return ProcessResult::from(event);
+		}
+
 		let mut result = ProcessResult::from(event);
 
 		let active_view_data = match self.state {
 			ShowCommitState::Overview => &mut self.overview_view_data,
 			ShowCommitState::Diff => &mut self.diff_view_data,
+			ShowCommitState::Squash => &mut self.squash_view_data,
 		};
+		let (active_sections, active_search) = match self.state {
+			ShowCommitState::Squash => (&mut self.squash_sections, &mut self.squash_search),
+			ShowCommitState::Overview | ShowCommitState::Diff => (&mut self.diff_sections, &mut self.diff_search),
+		};
+		let is_diff_shown = self.state != ShowCommitState::Overview;
 
 		if handle_view_data_scroll(event, view_sender).is_none() {
 			match event {
 				Event::Meta(meta_event) if meta_event == MetaEvent::ShowDiff => {
 					active_view_data.update_view_data(|updater| updater.clear());
 					self.state = match self.state {
-						ShowCommitState::Overview => ShowCommitState::Diff,
+						ShowCommitState::Overview | ShowCommitState::Squash => ShowCommitState::Diff,
 						ShowCommitState::Diff => ShowCommitState::Overview,
 					}
 				},
+				Event::Meta(meta_event) if meta_event == MetaEvent::ShowCombinedDiff => {
+					if self.state == ShowCommitState::Squash {
+						active_view_data.update_view_data(|updater| updater.clear());
+						self.state = ShowCommitState::Overview;
+					}
+					// the group is loaded every time, since the todo file can change while the commit is not shown
+					else if let Some(group) = get_squash_group(rebase_todo) {
+						match Commit::new_from_squash_group(
+							&group,
+							self.comment_char.as_str(),
+							&self.load_commit_diff_options,
+						) {
+							Ok(squash_commit) => {
+								active_view_data.update_view_data(|updater| updater.clear());
+								self.squash_view_data.update_view_data(|updater| {
+									updater.clear();
+									updater.reset_scroll_position();
+								});
+								self.squash_sections.reset();
+								self.squash_search.reset();
+								self.squash_commit = Some(squash_commit);
+								self.state = ShowCommitState::Squash;
+							},
+							Err(err) => result = result.error(err),
+						}
+					}
+				},
+				Event::Meta(meta_event) if meta_event == MetaEvent::ToggleSideBySide => {
+					// the side by side diff is only shown at full width, where it is shown until it is toggled
+					self.side_by_side = Some(!self.side_by_side.unwrap_or(true));
+					self.diff_view_data.update_view_data(|updater| updater.clear());
+					self.squash_view_data.update_view_data(|updater| updater.clear());
+				},
+				Event::Meta(
+					meta_event @ (MetaEvent::NextFile
+					| MetaEvent::PreviousFile
+					| MetaEvent::NextHunk
+					| MetaEvent::PreviousHunk
+					| MetaEvent::ToggleFold),
+				) if is_diff_shown => {
+					match meta_event {
+						MetaEvent::NextFile => active_sections.select_next_file(),
+						MetaEvent::PreviousFile => active_sections.select_previous_file(),
+						MetaEvent::NextHunk => active_sections.select_next_hunk(),
+						MetaEvent::PreviousHunk => active_sections.select_previous_hunk(),
+						_ => active_sections.toggle_collapsed(),
+					}
+					// the diff is built again to show the selected section, which is then scrolled to
+					active_view_data.update_view_data(|updater| updater.clear());
+					self.scroll_to_selected_section = true;
+				},
+				Event::Meta(meta_event) if meta_event == MetaEvent::ShowFileList && is_diff_shown => {
+					self.file_list_index = Some(active_sections.get_selected_file().unwrap_or(0));
+				},
+				Event::Meta(meta_event) if meta_event == MetaEvent::SearchStart && is_diff_shown => {
+					// the search continues from the selected match, or starts at the top of the diff
+					self.search_start_row = active_search.get_selected_row().unwrap_or(0);
+					self.searching = true;
+					self.search_edit.clear();
+					self.search_edit.set_label("/");
+					self.search_edit.set_content(active_search.get_term());
+					active_view_data.update_view_data(|updater| updater.clear());
+				},
+				Event::Meta(meta_event @ (MetaEvent::SearchNext | MetaEvent::SearchPrevious)) if is_diff_shown => {
+					if let Some(row) = active_search.select_next_match(meta_event == MetaEvent::SearchNext) {
+						view_sender.scroll_to(row);
+					}
+					// the diff is built again to update the match counter of the title
+					active_view_data.update_view_data(|updater| updater.clear());
+				},
 				Event::Meta(meta_event) if meta_event == MetaEvent::Help => self.help.set_active(),
 				Event::Key(_) => {
 					active_view_data.update_view_data(|updater| updater.clear());
-					if self.state == ShowCommitState::Diff {
-						self.state = ShowCommitState::Overview;
+					if self.state == ShowCommitState::Overview {
+						result = result.state(State::List);
 					}
 					else {
-						result = result.state(State::List);
+						self.state = ShowCommitState::Overview;
 					}
 				},
 				Event::Resize(..) => active_view_data.update_view_data(|updater| updater.clear()),
@@ -182,6 +464,14 @@ impl ShowCommit {
 			updater.set_show_title(true);
 			updater.set_show_help(true);
 		});
+		let squash_view_data = ViewData::new(|updater| {
+			updater.set_show_title(true);
+			updater.set_show_help(true);
+		});
+		let file_list_view_data = ViewData::new(|updater| {
+			updater.set_show_title(true);
+			updater.set_show_help(true);
+		});
 		let view_builder_options = ViewBuilderOptions::new(
 			config.diff_tab_width as usize,
 			config.diff_tab_symbol.as_str(),
@@ -190,24 +480,198 @@ impl ShowCommit {
 				|| config.diff_show_whitespace == DiffShowWhitespaceSetting::Leading,
 			config.diff_show_whitespace == DiffShowWhitespaceSetting::Both
 				|| config.diff_show_whitespace == DiffShowWhitespaceSetting::Trailing,
+			config.diff_syntax_highlight,
+			config.diff_word_highlight,
 		);
-		let load_commit_diff_options = LoadCommitDiffOptions {
-			context_lines: config.git.diff_context,
-			copies: config.git.diff_copies,
-			ignore_whitespace: config.diff_ignore_whitespace == DiffIgnoreWhitespaceSetting::All,
-			ignore_whitespace_change: config.diff_ignore_whitespace == DiffIgnoreWhitespaceSetting::Change,
-			interhunk_lines: config.git.diff_interhunk_lines,
-			rename_limit: config.git.diff_rename_limit,
-			renames: config.git.diff_renames,
-		};
+		let load_commit_diff_options = LoadCommitDiffOptions::new(config);
 		Self {
+			comment_char: config.git.comment_char.clone(),
 			commit: None,
+			commit_paths: CommitPaths::new(config),
+			conflicts: vec![],
+			diff_search: DiffSearch::new(),
+			diff_sections: DiffSections::new(),
 			diff_view_data,
+			file_list_index: None,
+			file_list_view_data,
 			help: Help::new_from_keybindings(&get_show_commit_help_lines(&config.key_bindings)),
 			load_commit_diff_options,
 			overview_view_data,
+			range: None,
+			scroll_to_selected_section: false,
+			search_edit: Edit::new(),
+			search_start_row: 0,
+			searching: false,
+			selected_range: SelectedRange::new(),
+			side_by_side: None,
+			squash_commit: None,
+			squash_search: DiffSearch::new(),
+			squash_sections: DiffSections::new(),
+			squash_view_data,
 			state: ShowCommitState::Overview,
+			touched_by: vec![],
 			view_builder: ViewBuilder::new(view_builder_options),
 		}
 	}
+
+	/// Share the cache of the paths changed by commits with other modules.
+	pub(crate) fn with_commit_paths(mut self, commit_paths: CommitPaths) -> Self {
+		self.commit_paths = commit_paths;
+		self
+	}
+
+	/// Share the range of lines selected in the list, that is shown in place of the selected line.
+	pub(crate) fn with_selected_range(mut self, selected_range: SelectedRange) -> Self {
+		self.selected_range = selected_range;
+		self
+	}
+
+	// the scroll position is reset, so that ensuring the last line of the first page of the section is visible scrolls
+	// the header of the section to the top of the view
+	fn scroll_to_section(view_data: &mut ViewData, sections: &DiffSections, context: &RenderContext) {
+		if let Some(row) = sections.get_selected_row() {
+			let view_height = context.height().saturating_sub(DIFF_PADDING_HEIGHT).max(1);
+			view_data.update_view_data(|updater| {
+				updater.reset_scroll_position();
+				updater.ensure_line_visible(row + view_height - 1);
+			});
+		}
+	}
+
+	// the match counter of the search is shown in the title, and the search term is edited in a line below the diff
+	fn update_search_view_data(view_data: &mut ViewData, search: &DiffSearch, search_edit: Option<&Edit>) {
+		view_data.update_view_data(|updater| {
+			updater.set_title_info(search.get_match_info().as_deref());
+			if let Some(edit) = search_edit {
+				let mut segments = edit.get_line_segments();
+				if !search.get_term().is_empty() && search.get_match_count() == 0 {
+					segments.push(LineSegment::new_with_color("  No matches", DisplayColor::IndicatorColor));
+				}
+				updater.push_trailing_line(ViewLine::new_pinned(segments));
+			}
+		});
+	}
+
+	// the diff is scrolled to the first match of the search term as it is typed, escape cancels the search and enter
+	// finishes the search, keeping the search term for moving between the matches
+	fn handle_search_input(&mut self, event_handler: &EventHandler, view_sender: &ViewSender) -> ProcessResult {
+		let event = self.search_edit.handle_event(event_handler);
+		let (view_data, search) = match self.state {
+			ShowCommitState::Squash => (&mut self.squash_view_data, &mut self.squash_search),
+			ShowCommitState::Overview | ShowCommitState::Diff => (&mut self.diff_view_data, &mut self.diff_search),
+		};
+		if let Event::Key(KeyEvent { code: KeyCode::Esc, .. }) = event {
+			self.searching = false;
+			search.set_term("");
+		}
+		else if self.search_edit.is_finished() {
+			self.searching = false;
+		}
+		else {
+			let term = self.search_edit.get_content();
+			if term != search.get_term() {
+				search.set_term(term.as_str());
+				if let Some(row) = search.select_match_from(self.search_start_row) {
+					view_sender.scroll_to(row);
+				}
+			}
+		}
+		view_data.update_view_data(|updater| updater.clear());
+		ProcessResult::from(event)
+	}
+
+	// the file list is moved with the scroll keys, a file is selected with enter or the file list key, and any other
+	// key closes the list
+	fn handle_file_list_event(&mut self, event: Event) {
+		let selected_index = self.file_list_index.unwrap_or(0);
+		match event {
+			Event::Meta(meta_event) => {
+				match meta_event {
+					MetaEvent::ScrollUp => self.file_list_index = Some(selected_index.saturating_sub(1)),
+					MetaEvent::ScrollDown => self.file_list_index = Some(selected_index + 1),
+					MetaEvent::ScrollTop => self.file_list_index = Some(0),
+					MetaEvent::ScrollBottom => self.file_list_index = Some(usize::MAX),
+					MetaEvent::ShowFileList => self.select_file(selected_index),
+					_ => {},
+				}
+			},
+			Event::Key(KeyEvent {
+				code: KeyCode::Enter,
+				modifiers: KeyModifiers::NONE,
+			}) => self.select_file(selected_index),
+			Event::Key(_) => self.file_list_index = None,
+			_ => {},
+		}
+	}
+
+	fn select_file(&mut self, file_index: usize) {
+		self.file_list_index = None;
+		let (view_data, sections) = match self.state {
+			ShowCommitState::Squash => (&mut self.squash_view_data, &mut self.squash_sections),
+			ShowCommitState::Overview | ShowCommitState::Diff => (&mut self.diff_view_data, &mut self.diff_sections),
+		};
+		sections.select_file(file_index);
+		view_data.update_view_data(|updater| updater.clear());
+		self.scroll_to_selected_section = true;
+	}
+
+	// the unique hashes of the commits in the selected range, if the range has more than one commit
+	fn get_range_hashes(&self, rebase_todo: &TodoFile) -> Option<Vec<String>> {
+		let (start_index, end_index) = self.selected_range.get()?;
+		let mut hashes: Vec<String> = vec![];
+		for line in (start_index..=end_index).filter_map(|index| rebase_todo.get_line(index)) {
+			if is_path_changing_line(line) && !hashes.iter().any(|hash| hash == line.get_hash()) {
+				hashes.push(String::from(line.get_hash()));
+			}
+		}
+		(hashes.len() > 1).then_some(hashes)
+	}
+
+	fn activate_range(&mut self, hashes: &[String]) -> ProcessResult {
+		// like a single commit, the range is only loaded when it changes, to retain the scroll position
+		if self.range.as_ref().map_or(false, |range| range.get_hashes() == hashes) {
+			return ProcessResult::new();
+		}
+		self.overview_view_data.update_view_data(|updater| {
+			updater.clear();
+			updater.reset_scroll_position();
+		});
+		self.diff_view_data.update_view_data(|updater| {
+			updater.clear();
+			updater.reset_scroll_position();
+		});
+		self.diff_sections.reset();
+		self.diff_search.reset();
+		self.file_list_index = None;
+		match CommitRange::new_from_hashes(hashes, &self.load_commit_diff_options) {
+			Ok(range) => {
+				self.range = Some(range);
+				ProcessResult::new()
+			},
+			Err(err) => ProcessResult::new().error(err).state(State::List),
+		}
+	}
+
+	// the other commits in the todo file that change any of the same paths as the selected commit, along with the
+	// shared paths, limited to the commits with loaded paths
+	fn get_touched_by(&self, rebase_todo: &TodoFile) -> Vec<(String, String, Vec<String>)> {
+		let selected_index = rebase_todo.get_selected_line_index();
+		let selected_line = match rebase_todo.get_selected_line() {
+			Some(line) if is_path_changing_line(line) => line,
+			_ => return vec![],
+		};
+		rebase_todo
+			.iter()
+			.enumerate()
+			.filter(|&(index, line)| {
+				index != selected_index && is_path_changing_line(line) && line.get_hash() != selected_line.get_hash()
+			})
+			.filter_map(|(_, line)| {
+				let paths = self
+					.commit_paths
+					.get_cached_overlapping_paths(selected_line.get_hash(), line.get_hash());
+				(!paths.is_empty()).then(|| (String::from(line.get_hash()), String::from(line.get_content()), paths))
+			})
+			.collect()
+	}
 }
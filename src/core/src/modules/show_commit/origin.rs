@@ -1,5 +1,4 @@
-#[derive(Debug, Clone)]
-#[cfg_attr(test, derive(PartialEq))]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum Origin {
 	Context,
 	Addition,
@@ -0,0 +1,50 @@
+use std::{cell::Cell, rc::Rc};
+
+/// The range of todo lines selected in visual mode when showing commits. Clones share the same range, so the list can
+/// set the range that is then shown.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SelectedRange {
+	range: Rc<Cell<Option<(usize, usize)>>>,
+}
+
+impl SelectedRange {
+	pub(crate) fn new() -> Self {
+		Self::default()
+	}
+
+	/// Set the range of lines, from the first to the last index, inclusive.
+	pub(crate) fn set(&self, start_index: usize, end_index: usize) {
+		self.range.set(Some((start_index.min(end_index), start_index.max(end_index))));
+	}
+
+	/// Clear the range, so only the selected line is shown.
+	pub(crate) fn clear(&self) {
+		self.range.set(None);
+	}
+
+	/// Get the first and last index of the range, if a range is set.
+	pub(crate) fn get(&self) -> Option<(usize, usize)> {
+		self.range.get()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn set_shared() {
+		let selected_range = SelectedRange::new();
+		let shared_selected_range = selected_range.clone();
+		shared_selected_range.set(5, 2);
+		assert_eq!(selected_range.get(), Some((2, 5)));
+	}
+
+	#[test]
+	fn clear() {
+		let selected_range = SelectedRange::new();
+		selected_range.set(1, 2);
+		selected_range.clear();
+		assert_eq!(selected_range.get(), None);
+	}
+}
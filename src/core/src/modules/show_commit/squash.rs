@@ -0,0 +1,218 @@
+use todo_file::{Action, Line, TodoFile};
+
+const fn is_squash_line(line: &Line) -> bool {
+	matches!(*line.get_action(), Action::Fixup | Action::Squash)
+}
+
+// a `fixup -C` or `fixup -c` line uses the message of its commit in place of the previous messages
+fn is_fixup_with_message(line: &Line) -> bool {
+	*line.get_action() == Action::Fixup && line.get_option().is_some()
+}
+
+fn get_ordinal(number: usize) -> String {
+	if number == 1 {
+		String::from("1st")
+	}
+	else {
+		format!("#{}", number)
+	}
+}
+
+fn push_commented_lines(message: &mut String, content: &str, comment_char: &str) {
+	for line in content.lines() {
+		message.push_str(comment_char);
+		if !line.is_empty() {
+			message.push(' ');
+			message.push_str(line);
+		}
+		message.push('\n');
+	}
+}
+
+/// Get the lines of the squash or fixup group of the selected line, which is a line that applies a commit followed
+/// by the `squash` and `fixup` lines that are combined into it. Returns `None` if the selected line is not part of a
+/// group.
+pub(super) fn get_squash_group(rebase_todo: &TodoFile) -> Option<Vec<Line>> {
+	let lines = rebase_todo.get_lines_owned();
+	let mut start = rebase_todo.get_selected_line_index();
+	while start > 0 && lines.get(start).map_or(false, is_squash_line) {
+		start -= 1;
+	}
+	if !matches!(
+		*lines.get(start)?.get_action(),
+		Action::Edit | Action::Pick | Action::Reword
+	) {
+		return None;
+	}
+	let end = start + 1 + lines[start + 1..].iter().take_while(|line| is_squash_line(line)).count();
+	(end > start + 1).then(|| lines[start..end].to_vec())
+}
+
+/// Assemble the message of a squash or fixup group, from the lines of the group and the messages of their commits,
+/// the same way that git does. A group of only plain `fixup` lines keeps the message of the first commit, while any
+/// other group results in the commented message that git opens in the editor.
+pub(super) fn build_squash_message(group: &[(Line, String)], comment_char: &str) -> String {
+	let is_fixup_only = group
+		.iter()
+		.skip(1)
+		.all(|&(ref line, _)| *line.get_action() == Action::Fixup && line.get_option().is_none());
+	if is_fixup_only {
+		return group.first().map_or_else(String::new, |&(_, ref message)| message.clone());
+	}
+
+	// each message is either kept or skipped, and a fixup with a message skips all previous messages, unless a squash
+	// has kept a message
+	let mut skipped = vec![false; group.len()];
+	let mut seen_squash = false;
+	for (index, &(ref line, _)) in group.iter().enumerate().skip(1) {
+		if is_fixup_with_message(line) && !seen_squash {
+			skipped[..index].iter_mut().for_each(|skip| *skip = true);
+		}
+		else if *line.get_action() == Action::Fixup && line.get_option().is_none() {
+			skipped[index] = true;
+		}
+		seen_squash |= *line.get_action() == Action::Squash;
+	}
+
+	let mut message = format!("{} This is a combination of {} commits.\n", comment_char, group.len());
+	for (index, &(_, ref content)) in group.iter().enumerate() {
+		if index > 0 {
+			message.push('\n');
+		}
+		let ordinal = get_ordinal(index + 1);
+		if skipped[index] {
+			let heading = if index == 0 {
+				format!("The {} commit message will be skipped:", ordinal)
+			}
+			else {
+				format!("The commit message {} will be skipped:", ordinal)
+			};
+			message.push_str(format!("{} {}\n\n", comment_char, heading).as_str());
+			push_commented_lines(&mut message, content, comment_char);
+		}
+		else {
+			let heading = if index == 0 {
+				format!("This is the {} commit message:", ordinal)
+			}
+			else {
+				format!("This is the commit message {}:", ordinal)
+			};
+			message.push_str(format!("{} {}\n\n", comment_char, heading).as_str());
+			message.push_str(content);
+			if !content.ends_with('\n') {
+				message.push('\n');
+			}
+		}
+	}
+	message
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn create_todo_file(lines: &[&str], selected_index: usize) -> TodoFile {
+		let mut todo_file = TodoFile::new("git-rebase-todo", 1, "#");
+		todo_file.set_lines(lines.iter().map(|line| Line::new(line).unwrap()).collect());
+		todo_file.set_selected_line_index(selected_index);
+		todo_file
+	}
+
+	fn create_group(lines: &[(&str, &str)]) -> Vec<(Line, String)> {
+		lines
+			.iter()
+			.map(|&(line, message)| (Line::new(line).unwrap(), String::from(message)))
+			.collect()
+	}
+
+	fn get_group_hashes(todo_file: &TodoFile) -> Option<Vec<String>> {
+		get_squash_group(todo_file).map(|lines| lines.iter().map(|line| String::from(line.get_hash())).collect())
+	}
+
+	#[test]
+	fn get_squash_group_from_pick() {
+		let todo_file = create_todo_file(
+			&[
+				"pick aaa c1",
+				"pick bbb c2",
+				"fixup ccc c3",
+				"squash ddd c4",
+				"pick eee c5",
+			],
+			1,
+		);
+		assert_eq!(
+			get_group_hashes(&todo_file),
+			Some(vec![String::from("bbb"), String::from("ccc"), String::from("ddd")])
+		);
+	}
+
+	#[test]
+	fn get_squash_group_from_fixup() {
+		let todo_file = create_todo_file(&["reword aaa c1", "squash bbb c2", "fixup -C ccc c3", "exec make"], 2);
+		assert_eq!(
+			get_group_hashes(&todo_file),
+			Some(vec![String::from("aaa"), String::from("bbb"), String::from("ccc")])
+		);
+	}
+
+	#[test]
+	fn get_squash_group_without_fixup() {
+		let todo_file = create_todo_file(&["pick aaa c1", "pick bbb c2"], 0);
+		assert_eq!(get_group_hashes(&todo_file), None);
+	}
+
+	#[test]
+	fn get_squash_group_without_pick() {
+		let todo_file = create_todo_file(&["exec make", "fixup aaa c1"], 1);
+		assert_eq!(get_group_hashes(&todo_file), None);
+	}
+
+	#[test]
+	fn build_squash_message_fixup_only() {
+		let group = create_group(&[("pick aaa c1", "c1\n\nbody\n"), ("fixup bbb c2", "c2\n")]);
+		assert_eq!(build_squash_message(&group, "#"), "c1\n\nbody\n");
+	}
+
+	#[test]
+	fn build_squash_message_squash() {
+		let group = create_group(&[
+			("pick aaa c1", "c1\n\nbody\n"),
+			("squash bbb c2", "c2\n"),
+			("fixup ccc c3", "c3\n\nbody"),
+		]);
+		assert_eq!(
+			build_squash_message(&group, "#"),
+			"# This is a combination of 3 commits.\n# This is the 1st commit message:\n\nc1\n\nbody\n\n# This is the \
+			 commit message #2:\n\nc2\n\n# The commit message #3 will be skipped:\n\n# c3\n#\n# body\n"
+		);
+	}
+
+	#[test]
+	fn build_squash_message_fixup_with_message() {
+		let group = create_group(&[
+			("pick aaa c1", "c1\n"),
+			("fixup bbb c2", "c2\n"),
+			("fixup -C ccc c3", "c3\n"),
+		]);
+		assert_eq!(
+			build_squash_message(&group, ";"),
+			"; This is a combination of 3 commits.\n; The 1st commit message will be skipped:\n\n; c1\n\n; The commit \
+			 message #2 will be skipped:\n\n; c2\n\n; This is the commit message #3:\n\nc3\n"
+		);
+	}
+
+	#[test]
+	fn build_squash_message_fixup_with_message_after_squash() {
+		let group = create_group(&[
+			("pick aaa c1", "c1\n"),
+			("squash bbb c2", "c2\n"),
+			("fixup -c ccc c3", "c3\n"),
+		]);
+		assert_eq!(
+			build_squash_message(&group, "#"),
+			"# This is a combination of 3 commits.\n# This is the 1st commit message:\n\nc1\n\n# This is the commit \
+			 message #2:\n\nc2\n\n# This is the commit message #3:\n\nc3\n"
+		);
+	}
+}
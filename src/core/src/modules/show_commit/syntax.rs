@@ -0,0 +1,185 @@
+use std::path::Path;
+
+use display::DisplayColor;
+
+/// The grammar of a language, for a simple highlighting of the tokens of a single line. Since a diff only has parts of
+/// a file, the lines are highlighted without the context of the surrounding lines.
+pub(super) struct Grammar {
+	extensions: &'static [&'static str],
+	keywords: &'static [&'static str],
+	line_comments: &'static [&'static str],
+	block_comment: Option<(&'static str, &'static str)>,
+	string_delimiters: &'static [char],
+}
+
+static GRAMMARS: &[Grammar] = &[
+	Grammar {
+		extensions: &["rs"],
+		keywords: &[
+			"as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false",
+			"fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref", "return",
+			"self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where", "while",
+		],
+		line_comments: &["//"],
+		block_comment: Some(("/*", "*/")),
+		string_delimiters: &['"'],
+	},
+	Grammar {
+		extensions: &["c", "h", "cc", "cpp", "cxx", "hh", "hpp", "hxx"],
+		keywords: &[
+			"auto", "break", "case", "char", "class", "const", "continue", "default", "delete", "do", "double", "else",
+			"enum", "extern", "false", "float", "for", "goto", "if", "inline", "int", "long", "namespace", "new",
+			"nullptr", "private", "protected", "public", "return", "short", "signed", "sizeof", "static", "struct",
+			"switch", "template", "this", "true", "typedef", "typename", "union", "unsigned", "using", "virtual",
+			"void", "volatile", "while",
+		],
+		line_comments: &["//"],
+		block_comment: Some(("/*", "*/")),
+		string_delimiters: &['"', '\''],
+	},
+	Grammar {
+		extensions: &["go"],
+		keywords: &[
+			"break", "case", "chan", "const", "continue", "default", "defer", "else", "fallthrough", "false", "for",
+			"func", "go", "goto", "if", "import", "interface", "map", "nil", "package", "range", "return", "select",
+			"struct", "switch", "true", "type", "var",
+		],
+		line_comments: &["//"],
+		block_comment: Some(("/*", "*/")),
+		string_delimiters: &['"', '\'', '`'],
+	},
+	Grammar {
+		extensions: &["java", "kt", "cs"],
+		keywords: &[
+			"abstract", "boolean", "break", "byte", "case", "catch", "char", "class", "continue", "default", "do",
+			"double", "else", "enum", "extends", "false", "final", "finally", "float", "for", "fun", "if",
+			"implements", "import", "instanceof", "int", "interface", "long", "namespace", "new", "null", "override",
+			"package", "private", "protected", "public", "return", "short", "static", "super", "switch", "this",
+			"throw", "throws", "true", "try", "val", "var", "void", "while",
+		],
+		line_comments: &["//"],
+		block_comment: Some(("/*", "*/")),
+		string_delimiters: &['"', '\''],
+	},
+	Grammar {
+		extensions: &["js", "jsx", "mjs", "cjs", "ts", "tsx"],
+		keywords: &[
+			"async", "await", "break", "case", "catch", "class", "const", "continue", "default", "delete", "do",
+			"else", "enum", "export", "extends", "false", "finally", "for", "function", "if", "import", "in",
+			"instanceof", "interface", "let", "new", "null", "return", "super", "switch", "this", "throw", "true",
+			"try", "type", "typeof", "undefined", "var", "void", "while", "yield",
+		],
+		line_comments: &["//"],
+		block_comment: Some(("/*", "*/")),
+		string_delimiters: &['"', '\'', '`'],
+	},
+	Grammar {
+		extensions: &["py"],
+		keywords: &[
+			"and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del", "elif", "else",
+			"except", "False", "finally", "for", "from", "global", "if", "import", "in", "is", "lambda", "None",
+			"nonlocal", "not", "or", "pass", "raise", "return", "True", "try", "while", "with", "yield",
+		],
+		line_comments: &["#"],
+		block_comment: None,
+		string_delimiters: &['"', '\''],
+	},
+	Grammar {
+		extensions: &["rb"],
+		keywords: &[
+			"begin", "break", "case", "class", "def", "do", "else", "elsif", "end", "ensure", "false", "for", "if",
+			"in", "module", "next", "nil", "not", "or", "rescue", "return", "self", "super", "then", "true", "unless",
+			"until", "when", "while", "yield",
+		],
+		line_comments: &["#"],
+		block_comment: None,
+		string_delimiters: &['"', '\''],
+	},
+	Grammar {
+		extensions: &["sh", "bash", "zsh"],
+		keywords: &[
+			"case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if", "in", "local",
+			"return", "then", "until", "while",
+		],
+		line_comments: &["#"],
+		block_comment: None,
+		string_delimiters: &['"', '\''],
+	},
+	Grammar {
+		extensions: &["toml", "yaml", "yml"],
+		keywords: &["false", "null", "true"],
+		line_comments: &["#"],
+		block_comment: None,
+		string_delimiters: &['"', '\''],
+	},
+];
+
+fn is_identifier_character(c: char) -> bool {
+	c.is_alphanumeric() || c == '_'
+}
+
+// the index after the closing delimiter of the string that starts at `start`, skipping escaped delimiters
+fn find_string_end(line: &str, start: usize, delimiter: char) -> usize {
+	let mut is_escaped = false;
+	for (index, c) in line[start + delimiter.len_utf8()..].char_indices() {
+		if is_escaped {
+			is_escaped = false;
+		}
+		else if c == '\\' {
+			is_escaped = true;
+		}
+		else if c == delimiter {
+			return start + delimiter.len_utf8() + index + c.len_utf8();
+		}
+	}
+	line.len()
+}
+
+/// Find the grammar for the language of a file, from the extension of the file.
+pub(super) fn get_grammar(path: &str) -> Option<&'static Grammar> {
+	let extension = Path::new(path).extension()?.to_str()?;
+	GRAMMARS.iter().find(|grammar| grammar.extensions.contains(&extension))
+}
+
+/// Find the comments, strings, numbers and keywords of a line, as byte ranges with the color of the token.
+pub(super) fn get_syntax_spans(line: &str, grammar: &Grammar) -> Vec<(usize, usize, DisplayColor)> {
+	let line = line.trim_end_matches('\n');
+	let mut spans = vec![];
+	let mut index = 0;
+	while let Some(c) = line[index..].chars().next() {
+		let rest = &line[index..];
+		if grammar.line_comments.iter().any(|comment| rest.starts_with(comment)) {
+			spans.push((index, line.len(), DisplayColor::DiffSyntaxCommentColor));
+			break;
+		}
+		if let Some((start, end)) = grammar.block_comment.filter(|&(start, _)| rest.starts_with(start)) {
+			let comment_end = rest[start.len()..]
+				.find(end)
+				.map_or(line.len(), |end_index| index + start.len() + end_index + end.len());
+			spans.push((index, comment_end, DisplayColor::DiffSyntaxCommentColor));
+			index = comment_end;
+		}
+		else if grammar.string_delimiters.contains(&c) {
+			let string_end = find_string_end(line, index, c);
+			spans.push((index, string_end, DisplayColor::DiffSyntaxStringColor));
+			index = string_end;
+		}
+		else if is_identifier_character(c) {
+			let length = rest
+				.find(|character| !is_identifier_character(character))
+				.unwrap_or(rest.len());
+			let word = &rest[..length];
+			if c.is_ascii_digit() {
+				spans.push((index, index + length, DisplayColor::DiffSyntaxNumberColor));
+			}
+			else if grammar.keywords.contains(&word) {
+				spans.push((index, index + length, DisplayColor::DiffSyntaxKeywordColor));
+			}
+			index += length;
+		}
+		else {
+			index += c.len_utf8();
+		}
+	}
+	spans
+}
@@ -1,9 +1,21 @@
 use anyhow::anyhow;
 use chrono::Local;
+use config::DiffWordHighlightSetting;
 use rstest::rstest;
 use view::{assert_rendered_output, ViewLine};
 
-use super::{delta::Delta, diff_line::DiffLine, file_stat::FileStat, origin::Origin, status::Status, user::User, *};
+use super::{
+	delta::Delta,
+	diff_line::DiffLine,
+	file_content::FileContent,
+	file_stat::FileStat,
+	origin::Origin,
+	status::Status,
+	syntax::{get_grammar, get_syntax_spans},
+	user::User,
+	word_diff::{get_changed_pairs, get_changed_spans},
+	*,
+};
 use crate::{assert_process_result, testutil::module_test};
 
 fn create_minimal_commit() -> Commit {
@@ -73,6 +85,126 @@ fn activate_error() {
 	});
 }
 
+const RANGE_LINES: &[&str] = &[
+	"pick 1cc0456637cb220155e957c641f483e60724c581 File status - modified",
+	"exec make",
+	"pick d85479638307e4db37e1f1f2c3c807f7ff36a0ff File status - deleted",
+	"pick c1ac7f2c32f9e00012f409572d223c9457ae497b File status - added",
+];
+
+#[test]
+fn render_range_overview() {
+	module_test(RANGE_LINES, &[], |test_context| {
+		let selected_range = SelectedRange::new();
+		selected_range.set(0, 3);
+		let mut module = ShowCommit::new(&Config::new()).with_selected_range(selected_range);
+		assert_process_result!(test_context.activate(&mut module, State::List));
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}{HELP}",
+			"{LEADING}",
+			"{IndicatorColor}Range: {Normal}1cc0456637cb220155e957c641f483e60724c581..\
+			 c1ac7f2c32f9e00012f409572d223c9457ae497b{IndicatorColor} (3 commits)",
+			"{BODY}",
+			"{IndicatorColor}3{Normal} files{Normal} with {DiffAddColor}2{Normal} insertions{Normal} and \
+			 {DiffRemoveColor}1{Normal} deletion",
+			"{DiffChangeColor}modified: {DiffChangeColor}a",
+			"{DiffRemoveColor} deleted: {DiffRemoveColor}b",
+			"{DiffAddColor}   added: {DiffAddColor}e",
+			"{Normal}",
+			"{IndicatorColor}Commits:",
+			"{Normal}  1cc04566 File status - modified",
+			"{Normal}    {DiffChangeColor}modified: {DiffChangeColor}a",
+			"{Normal}  d8547963 File status - deleted",
+			"{Normal}    {DiffRemoveColor} deleted: {DiffRemoveColor}b",
+			"{Normal}  c1ac7f2c File status - added",
+			"{Normal}    {DiffAddColor}   added: {DiffAddColor}e"
+		);
+	});
+}
+
+#[test]
+fn render_range_diff_compact() {
+	module_test(RANGE_LINES, &[], |mut test_context| {
+		test_context.render_context.update(30, 300);
+		let selected_range = SelectedRange::new();
+		selected_range.set(2, 3);
+		let mut config = Config::new();
+		config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+		let mut module = ShowCommit::new(&config).with_selected_range(selected_range);
+		assert_process_result!(test_context.activate(&mut module, State::List));
+		module.state = ShowCommitState::Diff;
+		assert_rendered_output!(
+			test_context.build_view_data(&mut module),
+			"{TITLE}{HELP}",
+			"{LEADING}",
+			"{Normal}d8547963..c1ac7f2c{IndicatorColor} (2 commits)",
+			"{IndicatorColor}2{Normal} / {DiffAddColor}1{Normal} / {DiffRemoveColor}1",
+			"{BODY}",
+			"{Normal}{Pad(―)}",
+			"{DiffRemoveColor} deleted: {DiffRemoveColor}b",
+			"",
+			"{Normal,Dimmed}@@{DiffContextColor} -1,1 +0,0 {Normal,Dimmed}@@{DiffContextColor}",
+			"{Normal,Dimmed}{Pad(┈)}",
+			"{Normal}1{Normal} {Normal} {Normal}| {DiffRemoveColor}B",
+			"{Normal}{Pad(―)}",
+			"{DiffAddColor}   added: {DiffAddColor}e",
+			"",
+			"{Normal,Dimmed}@@{DiffContextColor} -0,0 +1,1 {Normal,Dimmed}@@{DiffContextColor}",
+			"{Normal,Dimmed}{Pad(┈)}",
+			"{Normal} {Normal} {Normal}1{Normal}| {DiffAddColor}e"
+		);
+	});
+}
+
+#[test]
+fn activate_range_with_single_commit() {
+	module_test(RANGE_LINES, &[], |test_context| {
+		let selected_range = SelectedRange::new();
+		selected_range.set(0, 1);
+		let mut module = ShowCommit::new(&Config::new()).with_selected_range(selected_range);
+		assert_process_result!(test_context.activate(&mut module, State::List));
+		assert!(module.range.is_none());
+		assert!(module.commit.is_some());
+	});
+}
+
+#[test]
+fn activate_range_cached() {
+	module_test(RANGE_LINES, &[], |test_context| {
+		let selected_range = SelectedRange::new();
+		selected_range.set(0, 3);
+		let mut module = ShowCommit::new(&Config::new()).with_selected_range(selected_range.clone());
+		assert_process_result!(test_context.activate(&mut module, State::List));
+		module
+			.overview_view_data
+			.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+		assert_process_result!(test_context.activate(&mut module, State::List));
+		assert!(!module.overview_view_data.is_empty());
+		selected_range.clear();
+		assert_process_result!(test_context.activate(&mut module, State::List));
+		assert!(module.range.is_none());
+		assert!(module.overview_view_data.is_empty());
+	});
+}
+
+#[test]
+fn activate_range_error() {
+	module_test(&["pick aaaaaaaaaa comment1", "pick bbbbbbbbbb comment2"], &[], |test_context| {
+		let selected_range = SelectedRange::new();
+		selected_range.set(0, 1);
+		let mut module = ShowCommit::new(&Config::new()).with_selected_range(selected_range);
+		assert_process_result!(
+			test_context.activate(&mut module, State::List),
+			state = State::List,
+			error = anyhow!(
+				"Error loading commit: aaaaaaaaaa: revspec 'aaaaaaaaaa' not found; class=Reference (4); code=NotFound \
+				 (-3)"
+			)
+		);
+	});
+}
+
 #[test]
 fn render_overview_minimal_commit() {
 	module_test(
@@ -301,6 +433,147 @@ fn render_overview_with_file_stats() {
 	);
 }
 
+#[test]
+fn render_overview_with_predicted_conflicts() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|mut test_context| {
+			let version = test_context.rebase_todo_file.get_version();
+			test_context
+				.rebase_todo_file
+				.set_conflicts(version, vec![vec![String::from("file.1a"), String::from("file.2a")]]);
+			let mut module = ShowCommit::new(&Config::new());
+			let commit = create_minimal_commit();
+			let commit_date = commit.get_date().format("%c %z").to_string();
+			module.commit = Some(commit);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{BODY}",
+				format!("{{IndicatorColor}}Date: {{Normal}}{}", commit_date).as_str(),
+				"{Normal}",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{Normal}",
+				"{WarningColor}Predicted conflicts:",
+				"{Normal}  {WarningColor}file.1a",
+				"{Normal}  {WarningColor}file.2a"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_overview_rebuilt_on_predicted_conflicts() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			let commit = create_minimal_commit();
+			let commit_date = commit.get_date().format("%c %z").to_string();
+			module.commit = Some(commit);
+			let _ = test_context.build_view_data(&mut module);
+			let version = test_context.rebase_todo_file.get_version();
+			test_context
+				.rebase_todo_file
+				.set_conflicts(version, vec![vec![String::from("file.1a")]]);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{BODY}",
+				format!("{{IndicatorColor}}Date: {{Normal}}{}", commit_date).as_str(),
+				"{Normal}",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{Normal}",
+				"{WarningColor}Predicted conflicts:",
+				"{Normal}  {WarningColor}file.1a"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_overview_touched_by() {
+	module_test(
+		&[
+			"pick 0123456789abcdef0123456789abcdef comment1",
+			"pick aaaaaaaaaaaa comment2",
+			"pick bbbbbbbbbbbb comment3",
+		],
+		&[],
+		|test_context| {
+			let commit_paths = CommitPaths::new(&Config::new());
+			commit_paths.set_paths("0123456789abcdef0123456789abcdef", vec![
+				String::from("file.1a"),
+				String::from("file.2a"),
+			]);
+			commit_paths.set_paths("aaaaaaaaaaaa", vec![String::from("file.2a")]);
+			commit_paths.set_paths("bbbbbbbbbbbb", vec![String::from("file.3a")]);
+			let mut module = ShowCommit::new(&Config::new()).with_commit_paths(commit_paths);
+			let commit = create_minimal_commit();
+			let commit_date = commit.get_date().format("%c %z").to_string();
+			module.commit = Some(commit);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{BODY}",
+				format!("{{IndicatorColor}}Date: {{Normal}}{}", commit_date).as_str(),
+				"{Normal}",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{Normal}",
+				"{IndicatorColor}Also touched by:",
+				"{Normal}  aaaaaaaa comment2{DiffChangeColor} (file.2a)"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_overview_touched_by_loaded_later() {
+	module_test(
+		&[
+			"pick 0123456789abcdef0123456789abcdef comment1",
+			"pick aaaaaaaaaaaa comment2",
+		],
+		&[],
+		|test_context| {
+			let commit_paths = CommitPaths::new(&Config::new());
+			commit_paths.set_paths("0123456789abcdef0123456789abcdef", vec![String::from("file.1a")]);
+			let mut module = ShowCommit::new(&Config::new()).with_commit_paths(commit_paths.clone());
+			let commit = create_minimal_commit();
+			let commit_date = commit.get_date().format("%c %z").to_string();
+			module.commit = Some(commit);
+			let _ = test_context.build_view_data(&mut module);
+			assert!(module.touched_by.is_empty());
+			commit_paths.set_paths("aaaaaaaaaaaa", vec![String::from("file.1a")]);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{BODY}",
+				format!("{{IndicatorColor}}Date: {{Normal}}{}", commit_date).as_str(),
+				"{Normal}",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{Normal}",
+				"{IndicatorColor}Also touched by:",
+				"{Normal}  aaaaaaaa comment2{DiffChangeColor} (file.1a)"
+			);
+		},
+	);
+}
+
 #[test]
 fn render_overview_with_file_stats_compact() {
 	module_test(
@@ -603,6 +876,7 @@ fn render_diff_end_new_line_missing() {
 		&[],
 		|test_context| {
 			let mut module = ShowCommit::new(&Config::new());
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -641,6 +915,7 @@ fn render_diff_add_line() {
 			let mut config = Config::new();
 			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -677,6 +952,7 @@ fn render_diff_delete_line() {
 			let mut config = Config::new();
 			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -713,6 +989,7 @@ fn render_diff_context_add_remove_lines() {
 			let mut config = Config::new();
 			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -755,6 +1032,7 @@ fn render_diff_add_line_with_show_whitespace() {
 			let mut config = Config::new();
 			config.diff_show_whitespace = DiffShowWhitespaceSetting::Both;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -791,6 +1069,7 @@ fn render_diff_delete_line_with_show_whitespace() {
 			let mut config = Config::new();
 			config.diff_show_whitespace = DiffShowWhitespaceSetting::Both;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -827,6 +1106,7 @@ fn render_diff_context_add_remove_lines_with_show_whitespace() {
 			let mut config = Config::new();
 			config.diff_show_whitespace = DiffShowWhitespaceSetting::Both;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -14,2 +13,3 @@ context", 14, 14, 0, 1);
@@ -895,6 +1175,7 @@ fn render_diff_show_both_whitespace() {
 			config.diff_space_symbol = String::from("%");
 			config.diff_tab_width = 2;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			file_stat.add_delta(generate_white_space_delta());
@@ -941,6 +1222,7 @@ fn render_diff_show_leading_whitespace() {
 			config.diff_space_symbol = String::from("%");
 			config.diff_tab_width = 2;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			file_stat.add_delta(generate_white_space_delta());
@@ -987,6 +1269,7 @@ fn render_diff_show_no_whitespace() {
 			config.diff_space_symbol = String::from("%");
 			config.diff_tab_width = 2;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			file_stat.add_delta(generate_white_space_delta());
@@ -1030,6 +1313,7 @@ fn render_diff_show_whitespace_all_spaces() {
 			config.diff_space_symbol = String::from("%");
 			config.diff_tab_width = 2;
 			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
 			let mut commit = create_minimal_commit();
 			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
 			let mut delta = Delta::new("@@ -1,7 +1,7 @@ context", 1, 1, 7, 7);
@@ -1057,96 +1341,1166 @@ fn render_diff_show_whitespace_all_spaces() {
 	);
 }
 
+fn create_side_by_side_commit() -> Commit {
+	let mut commit = create_minimal_commit();
+	let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
+	let mut delta = Delta::new("@@ -13,4 +13,3 @@ context", 13, 13, 4, 3);
+	delta.add_line(DiffLine::new(Origin::Context, "context 1", Some(13), Some(13), false));
+	delta.add_line(DiffLine::new(Origin::Deletion, "old line", Some(14), None, false));
+	delta.add_line(DiffLine::new(Origin::Deletion, "old line 2", Some(15), None, false));
+	delta.add_line(DiffLine::new(Origin::Addition, "new line", None, Some(14), false));
+	delta.add_line(DiffLine::new(
+		Origin::Context,
+		"a long line of context, that is longer than one side",
+		Some(16),
+		Some(15),
+		false,
+	));
+	file_stat.add_delta(delta);
+	commit.file_stats = vec![file_stat];
+	commit
+}
+
 #[test]
-fn handle_event_toggle_diff_to_overview() {
+fn render_diff_side_by_side() {
 	module_test(
-		&["pick 0123456789abcdef0123456789abcdef c1"],
-		&[Event::from(MetaEvent::ShowDiff)],
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
 		|mut test_context| {
-			let mut module = ShowCommit::new(&Config::new());
-			module
-				.diff_view_data
-				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+			test_context.render_context.update(100, 300);
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.commit = Some(create_side_by_side_commit());
 			module.state = ShowCommitState::Diff;
-			assert_process_result!(
-				test_context.handle_event(&mut module),
-				event = Event::from(MetaEvent::ShowDiff)
+			module.side_by_side = Some(true);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}file.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -13,4 +13,3 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				format!(
+					"{{Normal}}13{{Normal}}| {{DiffContextColor}}context 1{{Normal}}{}{{Normal}} \
+					 {{Normal}}13{{Normal}}| {{DiffContextColor}}context 1",
+					" ".repeat(36)
+				),
+				format!(
+					"{{Normal}}14{{Normal}}| {{DiffRemoveColor}}old line{{Normal}}{}{{Normal}} {{Normal}}14{{Normal}}| \
+					 {{DiffAddColor}}new line",
+					" ".repeat(37)
+				),
+				format!(
+					"{{Normal}}15{{Normal}}| {{DiffRemoveColor}}old line 2{{Normal}}{}{{Normal}} {{Normal}}  {{Normal}}|",
+					" ".repeat(35)
+				),
+				"{Normal}16{Normal}| {DiffContextColor}a long line of context, that is longer than o{Normal} {Normal}15{Normal}| \
+				 {DiffContextColor}a long line of context, that is longer than one side"
 			);
-			assert!(module.diff_view_data.is_empty());
-			assert_eq!(module.state, ShowCommitState::Overview);
 		},
 	);
 }
 
 #[test]
-fn handle_event_toggle_overview_to_diff() {
+fn render_diff_side_by_side_default_full_width() {
 	module_test(
-		&["pick 0123456789abcdef0123456789abcdef c1"],
-		&[Event::from(MetaEvent::ShowDiff)],
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
 		|mut test_context| {
-			let mut module = ShowCommit::new(&Config::new());
-			module
-				.overview_view_data
-				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
-			module.state = ShowCommitState::Overview;
-			assert_process_result!(
-				test_context.handle_event(&mut module),
-				event = Event::from(MetaEvent::ShowDiff)
+			test_context.render_context.update(100, 300);
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.commit = Some(create_side_by_side_commit());
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}file.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -13,4 +13,3 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				format!(
+					"{{Normal}}13{{Normal}}| {{DiffContextColor}}context 1{{Normal}}{}{{Normal}} \
+					 {{Normal}}13{{Normal}}| {{DiffContextColor}}context 1",
+					" ".repeat(36)
+				),
+				format!(
+					"{{Normal}}14{{Normal}}| {{DiffRemoveColor}}old line{{Normal}}{}{{Normal}} {{Normal}}14{{Normal}}| \
+					 {{DiffAddColor}}new line",
+					" ".repeat(37)
+				),
+				format!(
+					"{{Normal}}15{{Normal}}| {{DiffRemoveColor}}old line 2{{Normal}}{}{{Normal}} {{Normal}}  {{Normal}}|",
+					" ".repeat(35)
+				),
+				"{Normal}16{Normal}| {DiffContextColor}a long line of context, that is longer than o{Normal} {Normal}15{Normal}| \
+				 {DiffContextColor}a long line of context, that is longer than one side"
 			);
-			assert!(module.diff_view_data.is_empty());
-			assert_eq!(module.state, ShowCommitState::Diff);
 		},
 	);
 }
 
 #[test]
-fn handle_event_resize() {
+fn render_diff_side_by_side_narrow() {
 	module_test(
-		&["pick 0123456789abcdef0123456789abcdef c1"],
-		&[Event::Resize(100, 100)],
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
 		|mut test_context| {
-			let mut module = ShowCommit::new(&Config::new());
-			assert_process_result!(test_context.handle_event(&mut module), event = Event::Resize(100, 100));
+			test_context.render_context.update(80, 300);
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.commit = Some(create_side_by_side_commit());
+			module.state = ShowCommitState::Diff;
+			module.side_by_side = Some(true);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}file.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -13,4 +13,3 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}13{Normal} {Normal}13{Normal}| {DiffContextColor}context 1",
+				"{Normal}14{Normal} {Normal}  {Normal}| {DiffRemoveColor}old line",
+				"{Normal}15{Normal} {Normal}  {Normal}| {DiffRemoveColor}old line 2",
+				"{Normal}  {Normal} {Normal}14{Normal}| {DiffAddColor}new line",
+				"{Normal}16{Normal} {Normal}15{Normal}| {DiffContextColor}a long line of context, that is longer than one side"
+			);
 		},
 	);
 }
 
 #[test]
-fn render_help() {
-	module_test(&["pick aaa c1"], &[Event::from(MetaEvent::Help)], |mut test_context| {
-		let mut module = ShowCommit::new(&Config::new());
-		let _ = test_context.handle_all_events(&mut module);
-		let view_data = test_context.build_view_data(&mut module);
-		assert_rendered_output!(
-			view_data,
-			"{TITLE}",
-			"{LEADING}",
-			"{Normal,Underline} Key      Action{Normal,Underline}{Pad( )}",
-			"{BODY}",
-			"{IndicatorColor} Up      {Normal,Dimmed}|{Normal}Scroll up",
-			"{IndicatorColor} Down    {Normal,Dimmed}|{Normal}Scroll down",
-			"{IndicatorColor} PageUp  {Normal,Dimmed}|{Normal}Scroll up half a page",
-			"{IndicatorColor} PageDown{Normal,Dimmed}|{Normal}Scroll down half a page",
-			"{IndicatorColor} Right   {Normal,Dimmed}|{Normal}Scroll right",
-			"{IndicatorColor} Left    {Normal,Dimmed}|{Normal}Scroll left",
-			"{IndicatorColor} d       {Normal,Dimmed}|{Normal}Show full diff",
-			"{IndicatorColor} ?       {Normal,Dimmed}|{Normal}Show help",
-			"{TRAILING}",
-			"{IndicatorColor}Press any key to close"
-		);
-	});
-}
-
-#[test]
-fn handle_help_event() {
+fn render_diff_word_highlight() {
 	module_test(
-		&["pick aaa c1"],
-		&[Event::from(MetaEvent::Help), Event::from(MetaEvent::ShowDiff)],
-		|mut test_context| {
-			let mut module = ShowCommit::new(&Config::new());
-			let _ = test_context.handle_all_events(&mut module);
-			assert!(!module.help.is_active());
-		},
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|test_context| {
+			let mut config = Config::new();
+			config.diff_word_highlight = DiffWordHighlightSetting::Word;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			let mut commit = create_minimal_commit();
+			let mut file_stat = FileStat::new("file.txt", "file.txt", Status::Modified);
+			let mut delta = Delta::new("@@ -14,2 +14,2 @@ context", 14, 14, 2, 2);
+			delta.add_line(DiffLine::new(Origin::Deletion, "  let a = 1; ", Some(14), None, false));
+			delta.add_line(DiffLine::new(Origin::Deletion, "old line", Some(15), None, false));
+			delta.add_line(DiffLine::new(Origin::Addition, "  let b = 1; ", None, Some(14), false));
+			delta.add_line(DiffLine::new(Origin::Addition, "new text", None, Some(15), false));
+			file_stat.add_delta(delta);
+			commit.file_stats = vec![file_stat];
+			module.commit = Some(commit);
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}file.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -14,2 +14,2 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}14{Normal} {Normal}  {Normal}| {DiffWhitespaceColor}··{DiffRemoveColor}let \
+				 {DiffRemoveHighlightColor,Reversed}a{DiffRemoveColor} = 1;{DiffWhitespaceColor}·",
+				"{Normal}15{Normal} {Normal}  {Normal}| {DiffRemoveColor}old line",
+				"{Normal}  {Normal} {Normal}14{Normal}| {DiffWhitespaceColor}··{DiffAddColor}let \
+				 {DiffAddHighlightColor,Reversed}b{DiffAddColor} = 1;{DiffWhitespaceColor}·",
+				"{Normal}  {Normal} {Normal}15{Normal}| {DiffAddColor}new text"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_diff_side_by_side_word_highlight() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|mut test_context| {
+			test_context.render_context.update(100, 300);
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			config.diff_word_highlight = DiffWordHighlightSetting::Character;
+			let mut module = ShowCommit::new(&config);
+			module.commit = Some(create_side_by_side_commit());
+			module.state = ShowCommitState::Diff;
+			module.side_by_side = Some(true);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}file.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -13,4 +13,3 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				format!(
+					"{{Normal}}13{{Normal}}| {{DiffContextColor}}context 1{{Normal}}{}{{Normal}} \
+					 {{Normal}}13{{Normal}}| {{DiffContextColor}}context 1",
+					" ".repeat(36)
+				),
+				format!(
+					"{{Normal}}14{{Normal}}| {{DiffRemoveHighlightColor,Reversed}}old{{DiffRemoveColor}} \
+					 line{{Normal}}{}{{Normal}} {{Normal}}14{{Normal}}| {{DiffAddHighlightColor,Reversed}}new{{DiffAddColor}} \
+					 line",
+					" ".repeat(37)
+				),
+				format!(
+					"{{Normal}}15{{Normal}}| {{DiffRemoveColor}}old line 2{{Normal}}{}{{Normal}} {{Normal}}  {{Normal}}|",
+					" ".repeat(35)
+				),
+				"{Normal}16{Normal}| {DiffContextColor}a long line of context, that is longer than o{Normal} \
+				 {Normal}15{Normal}| {DiffContextColor}a long line of context, that is longer than one side"
+			);
+		},
+	);
+}
+
+fn create_syntax_commit() -> Commit {
+	let mut commit = create_minimal_commit();
+	let mut file_stat = FileStat::new("main.rs", "main.rs", Status::Modified);
+	let mut delta = Delta::new("@@ -1,2 +1,2 @@ context", 1, 1, 2, 2);
+	delta.add_line(DiffLine::new(Origin::Context, "let a = 1; // one", Some(1), Some(1), false));
+	delta.add_line(DiffLine::new(Origin::Addition, "let b = \"b\";", None, Some(2), false));
+	file_stat.add_delta(delta);
+	commit.file_stats = vec![file_stat];
+	commit
+}
+
+#[test]
+fn render_diff_syntax_highlight() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|test_context| {
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}main.rs",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,2 +1,2 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal}1{Normal}| {DiffSyntaxKeywordColor}let{DiffContextColor} a = \
+				 {DiffSyntaxNumberColor}1{DiffContextColor}; {DiffSyntaxCommentColor}// one",
+				"{Normal} {Normal} {Normal}2{Normal}| {DiffSyntaxKeywordColor}let{DiffAddColor} b = \
+				 {DiffSyntaxStringColor}\"b\"{DiffAddColor};"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_diff_syntax_highlight_changed_lines() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|test_context| {
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			config.diff_word_highlight = DiffWordHighlightSetting::Word;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			let mut commit = create_minimal_commit();
+			let mut file_stat = FileStat::new("main.rs", "main.rs", Status::Modified);
+			let mut delta = Delta::new("@@ -1,1 +1,1 @@ context", 1, 1, 1, 1);
+			delta.add_line(DiffLine::new(Origin::Deletion, "let a = 1;", Some(1), None, false));
+			delta.add_line(DiffLine::new(Origin::Addition, "let b = 1;", None, Some(1), false));
+			file_stat.add_delta(delta);
+			commit.file_stats = vec![file_stat];
+			module.commit = Some(commit);
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}main.rs",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,1 +1,1 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal} {Normal}| {DiffSyntaxKeywordColor}let{DiffRemoveColor} \
+				 {DiffRemoveHighlightColor,Reversed}a{DiffRemoveColor} = {DiffSyntaxNumberColor}1{DiffRemoveColor};",
+				"{Normal} {Normal} {Normal}1{Normal}| {DiffSyntaxKeywordColor}let{DiffAddColor} \
+				 {DiffAddHighlightColor,Reversed}b{DiffAddColor} = {DiffSyntaxNumberColor}1{DiffAddColor};"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_diff_search() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('o'),
+			Event::from('n'),
+		],
+		|mut test_context| {
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			// the searchable lines are known once the diff is built
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(match 1 of 1){HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}main.rs",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,2 +1,2 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal}1{Normal}| {DiffSyntaxKeywordColor}let{DiffContextColor} a = \
+				 {DiffSyntaxNumberColor}1{DiffContextColor}; {DiffSyntaxCommentColor}// \
+				 {IndicatorColor,Reversed}on{DiffSyntaxCommentColor}e",
+				"{Normal} {Normal} {Normal}2{Normal}| {DiffSyntaxKeywordColor}let{DiffAddColor} b = \
+				 {DiffSyntaxStringColor}\"b\"{DiffAddColor};",
+				"{TRAILING}",
+				"{Normal,Dimmed}/{Normal}on{Normal,Underline}"
+			);
+			test_context
+				.view_sender_context
+				.assert_render_action(&["ScrollTo(5)", "ScrollTo(5)"]);
+		},
+	);
+}
+
+#[test]
+fn render_diff_search_no_matches() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[Event::from(MetaEvent::SearchStart), Event::from('x')],
+		|mut test_context| {
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}(0 matches){HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}main.rs",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,2 +1,2 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal}1{Normal}| {DiffSyntaxKeywordColor}let{DiffContextColor} a = \
+				 {DiffSyntaxNumberColor}1{DiffContextColor}; {DiffSyntaxCommentColor}// one",
+				"{Normal} {Normal} {Normal}2{Normal}| {DiffSyntaxKeywordColor}let{DiffAddColor} b = \
+				 {DiffSyntaxStringColor}\"b\"{DiffAddColor};",
+				"{TRAILING}",
+				"{Normal,Dimmed}/{Normal}x{Normal,Underline} {IndicatorColor}  No matches"
+			);
+			test_context.view_sender_context.assert_render_action(&[]);
+		},
+	);
+}
+
+#[test]
+fn search_finish() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('b'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_all_events(&mut module);
+			assert!(!module.searching);
+			assert_eq!(module.diff_search.get_term(), "b");
+			assert_eq!(module.diff_search.get_selected_row(), Some(6));
+			assert_eq!(module.state, ShowCommitState::Diff);
+		},
+	);
+}
+
+#[test]
+fn search_cancel() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('b'),
+			Event::from(KeyCode::Esc),
+		],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_all_events(&mut module);
+			assert!(!module.searching);
+			assert_eq!(module.diff_search.get_term(), "");
+			assert_eq!(module.diff_search.get_selected_row(), None);
+			assert_eq!(module.state, ShowCommitState::Diff);
+		},
+	);
+}
+
+#[test]
+fn search_next_and_previous() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('l'),
+			Event::from('e'),
+			Event::from('t'),
+			Event::from(KeyCode::Enter),
+			Event::from(MetaEvent::SearchNext),
+			Event::from(MetaEvent::SearchNext),
+			Event::from(MetaEvent::SearchPrevious),
+		],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_all_events(&mut module);
+			test_context.view_sender_context.assert_render_action(&[
+				"ScrollTo(5)",
+				"ScrollTo(5)",
+				"ScrollTo(5)",
+				"ScrollTo(6)",
+				"ScrollTo(5)",
+				"ScrollTo(6)",
+			]);
+			assert_eq!(module.diff_search.get_selected_row(), Some(6));
+			assert_eq!(module.diff_search.get_match_info().as_deref(), Some("match 2 of 2"));
+		},
+	);
+}
+
+#[test]
+fn search_side_by_side() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[
+			Event::from(MetaEvent::SearchStart),
+			Event::from('n'),
+			Event::from('e'),
+			Event::from('w'),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_side_by_side_commit());
+			module.state = ShowCommitState::Diff;
+			module.side_by_side = Some(true);
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.diff_search.get_selected_row(), Some(6));
+		},
+	);
+}
+
+#[test]
+fn search_in_overview() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(MetaEvent::SearchStart), Event::from(MetaEvent::SearchNext)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert!(!module.searching);
+			test_context.view_sender_context.assert_render_action(&[]);
+		},
+	);
+}
+
+#[test]
+fn render_diff_syntax_highlight_disabled() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|test_context| {
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			config.diff_syntax_highlight = false;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			module.commit = Some(create_syntax_commit());
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}main.rs",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,2 +1,2 {Normal,Dimmed}@@{DiffContextColor} context",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal}1{Normal}| {DiffContextColor}let a = 1; // one",
+				"{Normal} {Normal} {Normal}2{Normal}| {DiffAddColor}let b = \"b\";"
+			);
+		},
+	);
+}
+
+fn create_file_change_commit() -> Commit {
+	let mut commit = create_minimal_commit();
+	let mut mode_stat = FileStat::new("script.sh", "script.sh", Status::Modified);
+	mode_stat.set_modes(0o100_644, 0o100_755);
+	let mut binary_stat = FileStat::new("image.png", "image.png", Status::Modified);
+	binary_stat.set_modes(0o100_644, 0o100_644);
+	binary_stat.set_content(FileContent::Binary {
+		old_size: 12_288,
+		new_size: 14_336,
+	});
+	let mut symlink_stat = FileStat::new("link", "link", Status::Modified);
+	symlink_stat.set_modes(0o120_000, 0o120_000);
+	symlink_stat.set_content(FileContent::Symlink {
+		old_target: Some(String::from("old/target")),
+		new_target: Some(String::from("new/target")),
+	});
+	let mut delta = Delta::new("@@ -1 +1 @@", 1, 1, 1, 1);
+	delta.add_line(DiffLine::new(Origin::Deletion, "old/target", Some(1), None, false));
+	delta.add_line(DiffLine::new(Origin::Addition, "new/target", None, Some(1), false));
+	symlink_stat.add_delta(delta);
+	let mut submodule_stat = FileStat::new("vendor", "vendor", Status::Added);
+	submodule_stat.set_modes(0, 0o160_000);
+	submodule_stat.set_content(FileContent::Submodule {
+		old_commit: None,
+		new_commit: Some(String::from("abcdef")),
+	});
+	commit.file_stats = vec![mode_stat, binary_stat, symlink_stat, submodule_stat];
+	commit
+}
+
+#[test]
+fn render_diff_file_changes() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_file_change_commit());
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}script.sh",
+				"",
+				"{DiffChangeColor}mode {DiffRemoveColor}100644{Normal} → {DiffAddColor}100755",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}image.png",
+				"",
+				"{DiffChangeColor}Binary file changed {DiffRemoveColor}12KB{Normal} → {DiffAddColor}14KB",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}link",
+				"",
+				"{DiffChangeColor}Symbolic link to {DiffRemoveColor}old/target{Normal} → {DiffAddColor}new/target",
+				"{Normal}{Pad(―)}",
+				"{DiffAddColor}   added: {DiffAddColor}vendor",
+				"",
+				"{DiffChangeColor}Submodule commit {DiffAddColor}abcdef"
+			);
+		},
+	);
+}
+
+#[rstest]
+#[case::added(Status::Added, 0, 512, "{DiffAddColor}   added: {DiffAddColor}data.bin", "added {DiffAddColor}512B")]
+#[case::deleted(
+	Status::Deleted,
+	1536,
+	0,
+	"{DiffRemoveColor} deleted: {DiffRemoveColor}data.bin",
+	"deleted {DiffRemoveColor}1.5KB"
+)]
+#[case::changed(
+	Status::Modified,
+	1023,
+	3_250_585,
+	"{DiffChangeColor}modified: {DiffChangeColor}data.bin",
+	"changed {DiffRemoveColor}1023B{Normal} → {DiffAddColor}3.1MB"
+)]
+#[case::largest_unit(
+	Status::Modified,
+	10_240,
+	5_000_000_000_000_000,
+	"{DiffChangeColor}modified: {DiffChangeColor}data.bin",
+	"changed {DiffRemoveColor}10KB{Normal} → {DiffAddColor}4547TB"
+)]
+fn render_diff_binary_file(
+	#[case] status: Status,
+	#[case] old_size: u64,
+	#[case] new_size: u64,
+	#[case] expected_status: &str,
+	#[case] expected_change: &str,
+) {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[],
+		|test_context| {
+			let mut commit = create_minimal_commit();
+			let mut file_stat = FileStat::new("data.bin", "data.bin", status);
+			file_stat.set_content(FileContent::Binary { old_size, new_size });
+			commit.file_stats = vec![file_stat];
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(commit);
+			module.state = ShowCommitState::Diff;
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				expected_status,
+				"",
+				format!("{{DiffChangeColor}}Binary file {}", expected_change)
+			);
+		},
+	);
+}
+
+fn create_sections_commit() -> Commit {
+	let mut commit = create_minimal_commit();
+	let mut file_stat_1 = FileStat::new("a.txt", "a.txt", Status::Modified);
+	let mut delta = Delta::new("@@ -1,1 +1,1 @@ first", 1, 1, 1, 1);
+	delta.add_line(DiffLine::new(Origin::Context, "a1", Some(1), Some(1), false));
+	file_stat_1.add_delta(delta);
+	let mut delta = Delta::new("@@ -5,1 +5,1 @@ second", 5, 5, 1, 1);
+	delta.add_line(DiffLine::new(Origin::Context, "a5", Some(5), Some(5), false));
+	file_stat_1.add_delta(delta);
+	let mut file_stat_2 = FileStat::new("b.txt", "b.txt", Status::Modified);
+	let mut delta = Delta::new("@@ -2,1 +2,1 @@ third", 2, 2, 1, 1);
+	delta.add_line(DiffLine::new(Origin::Context, "b2", Some(2), Some(2), false));
+	file_stat_2.add_delta(delta);
+	commit.file_stats = vec![file_stat_1, file_stat_2];
+	commit
+}
+
+#[test]
+fn render_diff_collapsed_file() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[Event::from(MetaEvent::ToggleFold)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.side_by_side = Some(false);
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{Selected}{DiffChangeColor}modified: {DiffChangeColor}a.txt{Normal,Dimmed} (collapsed)",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}b.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -2,1 +2,1 {Normal,Dimmed}@@{DiffContextColor} third",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}2{Normal} {Normal}2{Normal}| {DiffContextColor}b2"
+			);
+		},
+	);
+}
+
+#[test]
+fn render_diff_selected_hunk() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[Event::from(MetaEvent::NextHunk), Event::from(MetaEvent::NextHunk)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.side_by_side = Some(false);
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			// the rows of the hunks are known once the diff is built
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_event(&mut module);
+			let _ = test_context.build_view_data(&mut module);
+			let _ = test_context.handle_event(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}0123456789abcdef0123456789abcdef",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}a.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,1 +1,1 {Normal,Dimmed}@@{DiffContextColor} first",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal}1{Normal}| {DiffContextColor}a1",
+				"",
+				"{Selected}{Normal,Dimmed}@@{DiffContextColor} -5,1 +5,1 {Normal,Dimmed}@@{DiffContextColor} second",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}5{Normal} {Normal}5{Normal}| {DiffContextColor}a5",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}b.txt",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -2,1 +2,1 {Normal,Dimmed}@@{DiffContextColor} third",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}2{Normal} {Normal}2{Normal}| {DiffContextColor}b2"
+			);
+			assert!(!module.scroll_to_selected_section);
+		},
+	);
+}
+
+#[rstest]
+#[case::next_file(MetaEvent::NextFile, Some(1))]
+#[case::previous_file(MetaEvent::PreviousFile, Some(0))]
+#[case::next_hunk(MetaEvent::NextHunk, Some(0))]
+#[case::previous_hunk(MetaEvent::PreviousHunk, None)]
+#[case::toggle_fold(MetaEvent::ToggleFold, Some(0))]
+fn handle_event_diff_sections(#[case] meta_event: MetaEvent, #[case] expected_file: Option<usize>) {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(meta_event)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.build_view_data(&mut module);
+			assert_process_result!(test_context.handle_event(&mut module), event = Event::from(meta_event));
+			assert!(module.diff_view_data.is_empty());
+			assert!(module.scroll_to_selected_section);
+			assert_eq!(module.diff_sections.get_selected_file(), expected_file);
+		},
+	);
+}
+
+#[test]
+fn handle_event_diff_sections_in_overview() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(MetaEvent::NextFile)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module
+				.overview_view_data
+				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::NextFile)
+			);
+			assert!(!module.overview_view_data.is_empty());
+			assert!(!module.scroll_to_selected_section);
+			assert_eq!(module.diff_sections.get_selected_file(), None);
+		},
+	);
+}
+
+#[test]
+fn render_file_list() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[Event::from(MetaEvent::ShowFileList), Event::from(MetaEvent::ScrollDown)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.handle_all_events(&mut module);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}0{Normal} files{Normal} with {DiffAddColor}0{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}0{Normal} deletions",
+				"{Normal}{Pad(―)}",
+				"{BODY}",
+				"{DiffChangeColor}modified: {DiffChangeColor}a.txt",
+				"{Selected}{DiffChangeColor}modified: {DiffChangeColor}b.txt{Normal}{Pad( )}"
+			);
+		},
+	);
+}
+
+#[test]
+fn file_list_limited_to_last_file() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[Event::from(MetaEvent::ShowFileList), Event::from(MetaEvent::ScrollBottom)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.handle_all_events(&mut module);
+			let _ = test_context.build_view_data(&mut module);
+			assert_eq!(module.file_list_index, Some(1));
+		},
+	);
+}
+
+#[test]
+fn file_list_select_file() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[
+			Event::from(MetaEvent::ToggleFold),
+			Event::from(MetaEvent::ShowFileList),
+			Event::from(KeyCode::Enter),
+		],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.file_list_index, None);
+			assert!(module.scroll_to_selected_section);
+			assert!(!module.diff_sections.is_collapsed(0));
+			assert!(module.diff_sections.is_selected(0, None));
+			assert_eq!(module.state, ShowCommitState::Diff);
+		},
+	);
+}
+
+#[test]
+fn file_list_close() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef comment1"],
+		&[Event::from(MetaEvent::ShowFileList), Event::from('x')],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module.commit = Some(create_sections_commit());
+			module.state = ShowCommitState::Diff;
+			let _ = test_context.handle_all_events(&mut module);
+			assert_eq!(module.file_list_index, None);
+			assert!(!module.scroll_to_selected_section);
+			assert_eq!(module.state, ShowCommitState::Diff);
+		},
+	);
+}
+
+#[test]
+fn handle_event_toggle_side_by_side() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(MetaEvent::ToggleSideBySide)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module
+				.diff_view_data
+				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+			module.state = ShowCommitState::Diff;
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ToggleSideBySide)
+			);
+			assert!(module.diff_view_data.is_empty());
+			assert_eq!(module.side_by_side, Some(false));
+			assert_eq!(module.state, ShowCommitState::Diff);
+		},
+	);
+}
+
+#[test]
+fn handle_event_toggle_diff_to_overview() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(MetaEvent::ShowDiff)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module
+				.diff_view_data
+				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+			module.state = ShowCommitState::Diff;
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowDiff)
+			);
+			assert!(module.diff_view_data.is_empty());
+			assert_eq!(module.state, ShowCommitState::Overview);
+		},
+	);
+}
+
+#[test]
+fn handle_event_toggle_overview_to_diff() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(MetaEvent::ShowDiff)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module
+				.overview_view_data
+				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+			module.state = ShowCommitState::Overview;
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowDiff)
+			);
+			assert!(module.diff_view_data.is_empty());
+			assert_eq!(module.state, ShowCommitState::Diff);
+		},
+	);
+}
+
+#[test]
+fn handle_event_show_combined_diff() {
+	module_test(
+		&[
+			"pick 1cc0456637cb220155e957c641f483e60724c581 File status - modified",
+			"fixup d85479638307e4db37e1f1f2c3c807f7ff36a0ff File status - deleted",
+			"squash c1ac7f2c32f9e00012f409572d223c9457ae497b File status - added",
+		],
+		&[Event::from(MetaEvent::ShowCombinedDiff)],
+		|mut test_context| {
+			test_context.rebase_todo_file.set_selected_line_index(1);
+			let mut config = Config::new();
+			config.diff_show_whitespace = DiffShowWhitespaceSetting::None;
+			let mut module = ShowCommit::new(&config);
+			module.side_by_side = Some(false);
+			assert_process_result!(test_context.activate(&mut module, State::List));
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCombinedDiff)
+			);
+			assert_eq!(module.state, ShowCommitState::Squash);
+			assert_rendered_output!(
+				test_context.build_view_data(&mut module),
+				"{TITLE}{HELP}",
+				"{LEADING}",
+				"{IndicatorColor}Commit: {Normal}1cc0456637cb220155e957c641f483e60724c581{IndicatorColor} (combined)",
+				"{IndicatorColor}3{Normal} files{Normal} with {DiffAddColor}2{Normal} insertions{Normal} and \
+				 {DiffRemoveColor}1{Normal} deletion",
+				"{BODY}",
+				"{Normal}{Pad(―)}",
+				"{Normal}# This is a combination of 3 commits.",
+				"{Normal}# This is the 1st commit message:",
+				"{Normal}",
+				"{Normal}File status - modified",
+				"{Normal}",
+				"{Normal}# The commit message #2 will be skipped:",
+				"{Normal}",
+				"{Normal}# File status - deleted",
+				"{Normal}",
+				"{Normal}# This is the commit message #3:",
+				"{Normal}",
+				"{Normal}File status - added",
+				"{Normal}{Pad(―)}",
+				"{DiffChangeColor}modified: {DiffChangeColor}a",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,1 +1,2 {Normal,Dimmed}@@{DiffContextColor}",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal}1{Normal}| {DiffContextColor}A",
+				"{Normal} {Normal} {Normal}2{Normal}| {DiffAddColor}aa",
+				"{Normal}{Pad(―)}",
+				"{DiffRemoveColor} deleted: {DiffRemoveColor}b",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -1,1 +0,0 {Normal,Dimmed}@@{DiffContextColor}",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal}1{Normal} {Normal} {Normal}| {DiffRemoveColor}B",
+				"{Normal}{Pad(―)}",
+				"{DiffAddColor}   added: {DiffAddColor}e",
+				"",
+				"{Normal,Dimmed}@@{DiffContextColor} -0,0 +1,1 {Normal,Dimmed}@@{DiffContextColor}",
+				"{Normal,Dimmed}{Pad(┈)}",
+				"{Normal} {Normal} {Normal}1{Normal}| {DiffAddColor}e"
+			);
+		},
+	);
+}
+
+#[test]
+fn handle_event_show_combined_diff_without_group() {
+	module_test(
+		&["pick 1cc0456637cb220155e957c641f483e60724c581 File status - modified"],
+		&[Event::from(MetaEvent::ShowCombinedDiff)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCombinedDiff)
+			);
+			assert_eq!(module.state, ShowCommitState::Overview);
+		},
+	);
+}
+
+#[test]
+fn handle_event_show_combined_diff_error() {
+	module_test(
+		&[
+			"pick 1cc0456637cb220155e957c641f483e60724c581 File status - modified",
+			"fixup aaaaaaaaaa comment",
+		],
+		&[Event::from(MetaEvent::ShowCombinedDiff)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCombinedDiff),
+				error = anyhow!(
+					"Error combining commits: 1cc0456637cb220155e957c641f483e60724c581, aaaaaaaaaa: revspec \
+					 'aaaaaaaaaa' not found; class=Reference (4); code=NotFound (-3)"
+				)
+			);
+			assert_eq!(module.state, ShowCommitState::Overview);
+		},
+	);
+}
+
+#[test]
+fn handle_event_toggle_combined_diff_to_overview() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::from(MetaEvent::ShowCombinedDiff)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			module
+				.squash_view_data
+				.update_view_data(|updater| updater.push_line(ViewLine::from("foo")));
+			module.state = ShowCommitState::Squash;
+			assert_process_result!(
+				test_context.handle_event(&mut module),
+				event = Event::from(MetaEvent::ShowCombinedDiff)
+			);
+			assert!(module.squash_view_data.is_empty());
+			assert_eq!(module.state, ShowCommitState::Overview);
+		},
+	);
+}
+
+#[test]
+fn handle_event_resize() {
+	module_test(
+		&["pick 0123456789abcdef0123456789abcdef c1"],
+		&[Event::Resize(100, 100)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			assert_process_result!(test_context.handle_event(&mut module), event = Event::Resize(100, 100));
+		},
+	);
+}
+
+#[test]
+fn render_help() {
+	module_test(&["pick aaa c1"], &[Event::from(MetaEvent::Help)], |mut test_context| {
+		let mut module = ShowCommit::new(&Config::new());
+		let _ = test_context.handle_all_events(&mut module);
+		let view_data = test_context.build_view_data(&mut module);
+		assert_rendered_output!(
+			view_data,
+			"{TITLE}",
+			"{LEADING}",
+			"{Normal,Underline} Key      Action{Normal,Underline}{Pad( )}",
+			"{BODY}",
+			"{IndicatorColor} Up      {Normal,Dimmed}|{Normal}Scroll up",
+			"{IndicatorColor} Down    {Normal,Dimmed}|{Normal}Scroll down",
+			"{IndicatorColor} PageUp  {Normal,Dimmed}|{Normal}Scroll up half a page",
+			"{IndicatorColor} PageDown{Normal,Dimmed}|{Normal}Scroll down half a page",
+			"{IndicatorColor} Right   {Normal,Dimmed}|{Normal}Scroll right",
+			"{IndicatorColor} Left    {Normal,Dimmed}|{Normal}Scroll left",
+			"{IndicatorColor} d       {Normal,Dimmed}|{Normal}Show full diff",
+			"{IndicatorColor} s       {Normal,Dimmed}|{Normal}Show combined diff of squash or fixup group",
+			"{IndicatorColor} S       {Normal,Dimmed}|{Normal}Toggle side by side diff",
+			"{IndicatorColor} ]       {Normal,Dimmed}|{Normal}Move to next file of diff",
+			"{IndicatorColor} [       {Normal,Dimmed}|{Normal}Move to previous file of diff",
+			"{IndicatorColor} }       {Normal,Dimmed}|{Normal}Move to next hunk of diff",
+			"{IndicatorColor} {       {Normal,Dimmed}|{Normal}Move to previous hunk of diff",
+			"{IndicatorColor} z       {Normal,Dimmed}|{Normal}Collapse or expand file of diff",
+			"{IndicatorColor} f       {Normal,Dimmed}|{Normal}Show list of files of diff",
+			"{IndicatorColor} /       {Normal,Dimmed}|{Normal}Search the diff",
+			"{IndicatorColor} n       {Normal,Dimmed}|{Normal}Move to the next search match",
+			"{IndicatorColor} N       {Normal,Dimmed}|{Normal}Move to the previous search match",
+			"{IndicatorColor} ?       {Normal,Dimmed}|{Normal}Show help",
+			"{TRAILING}",
+			"{IndicatorColor}Press any key to close"
+		);
+	});
+}
+
+#[test]
+fn handle_help_event() {
+	module_test(
+		&["pick aaa c1"],
+		&[Event::from(MetaEvent::Help), Event::from(MetaEvent::ShowDiff)],
+		|mut test_context| {
+			let mut module = ShowCommit::new(&Config::new());
+			let _ = test_context.handle_all_events(&mut module);
+			assert!(!module.help.is_active());
+		},
 	);
 }
 
@@ -1194,3 +2548,130 @@ fn scroll_events(#[case] event: MetaEvent) {
 		assert_process_result!(test_context.handle_event(&mut module), event = Event::from(event));
 	});
 }
+
+#[rstest]
+#[case::rust("src/main.rs", true)]
+#[case::nested_extension("archive.tar.sh", true)]
+#[case::unknown_extension("file.txt", false)]
+#[case::no_extension("Makefile", false)]
+fn get_grammar_from_path(#[case] path: &str, #[case] expected: bool) {
+	assert_eq!(get_grammar(path).is_some(), expected);
+}
+
+#[test]
+fn get_syntax_spans_rust() {
+	let grammar = get_grammar("lib.rs").unwrap();
+	assert_eq!(
+		get_syntax_spans("let value = \"a \\\" b\" + 42; // done\n", grammar),
+		vec![
+			(0, 3, DisplayColor::DiffSyntaxKeywordColor),
+			(12, 20, DisplayColor::DiffSyntaxStringColor),
+			(23, 25, DisplayColor::DiffSyntaxNumberColor),
+			(27, 34, DisplayColor::DiffSyntaxCommentColor),
+		]
+	);
+}
+
+#[test]
+fn get_syntax_spans_block_comment() {
+	let grammar = get_grammar("main.c").unwrap();
+	assert_eq!(get_syntax_spans("int /* size */ a = 'b'; /* open", grammar), vec![
+		(0, 3, DisplayColor::DiffSyntaxKeywordColor),
+		(4, 14, DisplayColor::DiffSyntaxCommentColor),
+		(19, 22, DisplayColor::DiffSyntaxStringColor),
+		(24, 31, DisplayColor::DiffSyntaxCommentColor),
+	]);
+}
+
+#[test]
+fn get_syntax_spans_unclosed_string() {
+	let grammar = get_grammar("script.py").unwrap();
+	assert_eq!(get_syntax_spans("x = 'abc", grammar), vec![(
+		4,
+		8,
+		DisplayColor::DiffSyntaxStringColor
+	)]);
+}
+
+#[test]
+fn get_syntax_spans_keyword_within_identifier() {
+	let grammar = get_grammar("main.go").unwrap();
+	assert!(get_syntax_spans("format_for iffy", grammar).is_empty());
+}
+
+fn create_changed_line(origin: Origin, end_of_file: bool) -> DiffLine {
+	DiffLine::new(origin, "line", None, None, end_of_file)
+}
+
+#[test]
+fn get_changed_pairs_runs() {
+	let lines = vec![
+		create_changed_line(Origin::Context, false),
+		create_changed_line(Origin::Deletion, false),
+		create_changed_line(Origin::Deletion, false),
+		create_changed_line(Origin::Addition, false),
+		create_changed_line(Origin::Context, false),
+		create_changed_line(Origin::Addition, false),
+		create_changed_line(Origin::Deletion, false),
+		create_changed_line(Origin::Addition, false),
+		create_changed_line(Origin::Addition, false),
+	];
+	assert_eq!(get_changed_pairs(&lines), vec![
+		None,
+		Some(3),
+		None,
+		Some(1),
+		None,
+		None,
+		Some(7),
+		Some(6),
+		None
+	]);
+}
+
+#[test]
+fn get_changed_pairs_end_of_file() {
+	let lines = vec![
+		create_changed_line(Origin::Deletion, false),
+		create_changed_line(Origin::Deletion, true),
+		create_changed_line(Origin::Addition, false),
+	];
+	assert_eq!(get_changed_pairs(&lines), vec![None, None, None]);
+}
+
+#[rstest]
+#[case::none(DiffWordHighlightSetting::None, "let a = 1;", "let b = 1;", vec![], vec![])]
+#[case::word(
+	DiffWordHighlightSetting::Word,
+	"let value = 1;",
+	"let other_value = 12;",
+	vec![(4, 9), (12, 13)],
+	vec![(4, 15), (18, 20)]
+)]
+#[case::character(
+	DiffWordHighlightSetting::Character,
+	"let value = 1;",
+	"let values = 12;",
+	vec![],
+	vec![(9, 10), (14, 15)]
+)]
+#[case::only_whitespace_in_common(
+	DiffWordHighlightSetting::Word,
+	"old line",
+	"new text",
+	vec![],
+	vec![]
+)]
+#[case::unchanged(DiffWordHighlightSetting::Word, "same", "same", vec![], vec![])]
+fn get_changed_spans_setting(
+	#[case] setting: DiffWordHighlightSetting,
+	#[case] old_line: &str,
+	#[case] new_line: &str,
+	#[case] expected_old: Vec<(usize, usize)>,
+	#[case] expected_new: Vec<(usize, usize)>,
+) {
+	assert_eq!(
+		get_changed_spans(old_line, new_line, setting),
+		(expected_old, expected_new)
+	);
+}
@@ -18,6 +18,35 @@ pub(super) fn get_show_commit_help_lines(key_bindings: &KeyBindings) -> Vec<(Vec
 		(key_bindings.move_right.clone(), String::from("Scroll right")),
 		(key_bindings.move_left.clone(), String::from("Scroll left")),
 		(key_bindings.show_diff.clone(), String::from("Show full diff")),
+		(
+			key_bindings.show_combined_diff.clone(),
+			String::from("Show combined diff of squash or fixup group"),
+		),
+		(
+			key_bindings.toggle_side_by_side.clone(),
+			String::from("Toggle side by side diff"),
+		),
+		(key_bindings.next_file.clone(), String::from("Move to next file of diff")),
+		(
+			key_bindings.previous_file.clone(),
+			String::from("Move to previous file of diff"),
+		),
+		(key_bindings.next_hunk.clone(), String::from("Move to next hunk of diff")),
+		(
+			key_bindings.previous_hunk.clone(),
+			String::from("Move to previous hunk of diff"),
+		),
+		(key_bindings.toggle_fold.clone(), String::from("Collapse or expand file of diff")),
+		(key_bindings.show_file_list.clone(), String::from("Show list of files of diff")),
+		(key_bindings.search.clone(), String::from("Search the diff")),
+		(
+			key_bindings.search_next.clone(),
+			String::from("Move to the next search match"),
+		),
+		(
+			key_bindings.search_previous.clone(),
+			String::from("Move to the previous search match"),
+		),
 		(key_bindings.help.clone(), String::from("Show help")),
 	]
 }
@@ -1,19 +1,82 @@
+use config::DiffWordHighlightSetting;
 use display::DisplayColor;
+use unicode_segmentation::UnicodeSegmentation;
 use view::{LineSegment, ViewDataUpdater, ViewLine};
 
 use super::{
 	commit::Commit,
+	commit_range::CommitRange,
 	diff_line::DiffLine,
+	diff_search::DiffSearch,
+	diff_sections::DiffSections,
+	file_content::FileContent,
+	file_stat::FileStat,
 	origin::Origin,
+	status::Status,
+	syntax::{get_grammar, get_syntax_spans, Grammar},
 	util::{get_files_changed_summary, get_partition_index_on_whitespace_for_line, get_stat_item_segments},
+	word_diff::{get_changed_pairs, get_changed_spans},
 };
 
+const FILE_SIZE_UNITS: [&str; 4] = ["KB", "MB", "GB", "TB"];
+
+// a size in bytes in the largest unit that fits, with a decimal place for small values, such as "1.5KB" or "14KB"
+fn format_file_size(size: u64) -> String {
+	if size < 1024 {
+		return format!("{}B", size);
+	}
+	let mut unit = 0;
+	let mut divisor = 1024;
+	while size >= divisor * 1024 && unit + 1 < FILE_SIZE_UNITS.len() {
+		divisor *= 1024;
+		unit += 1;
+	}
+	let tenths = (size * 10 + divisor / 2) / divisor;
+	if tenths < 100 {
+		format!("{}.{}{}", tenths / 10, tenths % 10, FILE_SIZE_UNITS[unit])
+	}
+	else {
+		format!("{}{}", (size + divisor / 2) / divisor, FILE_SIZE_UNITS[unit])
+	}
+}
+
+// a part of a line, as a byte range, that is shown with a color and whether the color is reversed
+type StyledSpan = (usize, usize, DisplayColor, bool);
+
+// the spans of `top` are shown over the spans of `bottom`, so the parts of the bottom spans that overlap a top span
+// are removed
+fn layer_spans(bottom: Vec<StyledSpan>, top: &[StyledSpan]) -> Vec<StyledSpan> {
+	if top.is_empty() {
+		return bottom;
+	}
+	let mut layered_spans = top.to_vec();
+	for (start, end, color, reversed) in bottom {
+		let mut index = start;
+		for &(top_start, top_end, ..) in top
+			.iter()
+			.filter(|&&(top_start, top_end, ..)| top_start < end && top_end > start)
+		{
+			if top_start > index {
+				layered_spans.push((index, top_start, color, reversed));
+			}
+			index = index.max(top_end);
+		}
+		if index < end {
+			layered_spans.push((index, end, color, reversed));
+		}
+	}
+	layered_spans.sort_by_key(|&(start, ..)| start);
+	layered_spans
+}
+
 pub(super) struct ViewBuilderOptions {
 	space_character: String,
 	tab_character: String,
 	tab_width: usize,
 	show_leading_whitespace: bool,
 	show_trailing_whitespace: bool,
+	syntax_highlight: bool,
+	word_highlight: DiffWordHighlightSetting,
 }
 
 impl ViewBuilderOptions {
@@ -23,6 +86,8 @@ impl ViewBuilderOptions {
 		space_character: &str,
 		show_leading_whitespace: bool,
 		show_trailing_whitespace: bool,
+		syntax_highlight: bool,
+		word_highlight: DiffWordHighlightSetting,
 	) -> Self {
 		Self {
 			space_character: String::from(space_character),
@@ -30,6 +95,8 @@ impl ViewBuilderOptions {
 			tab_width,
 			show_leading_whitespace,
 			show_trailing_whitespace,
+			syntax_highlight,
+			word_highlight,
 		}
 	}
 }
@@ -40,6 +107,8 @@ pub(super) struct ViewBuilder {
 	visible_space_string: String,
 	show_leading_whitespace: bool,
 	show_trailing_whitespace: bool,
+	syntax_highlight: bool,
+	word_highlight: DiffWordHighlightSetting,
 }
 
 impl ViewBuilder {
@@ -50,6 +119,8 @@ impl ViewBuilder {
 			visible_space_string: options.space_character,
 			show_leading_whitespace: options.show_leading_whitespace,
 			show_trailing_whitespace: options.show_trailing_whitespace,
+			syntax_highlight: options.syntax_highlight,
+			word_highlight: options.word_highlight,
 		}
 	}
 
@@ -66,6 +137,10 @@ impl ViewBuilder {
 	}
 
 	fn build_leading_summary(commit: &Commit, is_full_width: bool) -> ViewLine {
+		ViewLine::from(Self::build_leading_summary_segments(commit, is_full_width))
+	}
+
+	fn build_leading_summary_segments(commit: &Commit, is_full_width: bool) -> Vec<LineSegment> {
 		let mut segments = vec![];
 		if is_full_width {
 			segments.push(LineSegment::new_with_color("Commit: ", DisplayColor::IndicatorColor));
@@ -81,14 +156,88 @@ impl ViewBuilder {
 			}
 			.as_str(),
 		));
+		segments
+	}
+
+	fn build_range_leading_summary(range: &CommitRange, is_full_width: bool) -> ViewLine {
+		let commits = range.get_commits();
+		let get_hash = |commit: Option<&Commit>| {
+			let hash = commit.map_or("", Commit::get_hash);
+			if is_full_width {
+				String::from(hash)
+			}
+			else {
+				String::from(&hash[0..hash.len().min(8)])
+			}
+		};
+		let mut segments = vec![];
+		if is_full_width {
+			segments.push(LineSegment::new_with_color("Range: ", DisplayColor::IndicatorColor));
+		}
+		segments.push(LineSegment::new(
+			format!("{}..{}", get_hash(commits.first()), get_hash(commits.last())).as_str(),
+		));
+		segments.push(LineSegment::new_with_color(
+			format!(" ({} commits)", commits.len()).as_str(),
+			DisplayColor::IndicatorColor,
+		));
 		ViewLine::from(segments)
 	}
 
+	/// Build the overview of a range of commits, with the files changed by the range, followed by the files changed by
+	/// each of the commits.
+	#[allow(clippy::unused_self)]
+	pub(super) fn build_view_data_for_range_overview(
+		&self,
+		updater: &mut ViewDataUpdater<'_>,
+		range: &CommitRange,
+		is_full_width: bool,
+	) {
+		updater.push_leading_line(Self::build_range_leading_summary(range, is_full_width));
+		let diff = range.get_diff();
+		updater.push_line(get_files_changed_summary(diff, is_full_width));
+		for stat in diff.get_file_stats() {
+			updater.push_line(ViewLine::from(get_stat_item_segments(
+				stat.get_status(),
+				stat.get_to_name(),
+				stat.get_from_name(),
+				is_full_width,
+			)));
+		}
+
+		updater.push_line(ViewLine::from(""));
+		updater.push_line(ViewLine::from(LineSegment::new_with_color(
+			"Commits:",
+			DisplayColor::IndicatorColor,
+		)));
+		for commit in range.get_commits() {
+			let hash = commit.get_hash();
+			let summary = commit
+				.get_body()
+				.as_deref()
+				.and_then(|body| body.lines().next())
+				.unwrap_or("");
+			updater.push_line(ViewLine::from(format!("  {} {}", &hash[0..hash.len().min(8)], summary).as_str()));
+			for stat in commit.get_file_stats() {
+				let mut segments = vec![LineSegment::new("    ")];
+				segments.extend(get_stat_item_segments(
+					stat.get_status(),
+					stat.get_to_name(),
+					stat.get_from_name(),
+					is_full_width,
+				));
+				updater.push_line(ViewLine::from(segments));
+			}
+		}
+	}
+
 	#[allow(clippy::unused_self)]
 	pub(super) fn build_view_data_for_overview(
 		&self,
 		updater: &mut ViewDataUpdater<'_>,
 		commit: &Commit,
+		conflicts: &[String],
+		touched_by: &[(String, String, Vec<String>)],
 		is_full_width: bool,
 	) {
 		updater.push_leading_line(Self::build_leading_summary(commit, is_full_width));
@@ -137,108 +286,540 @@ impl ViewBuilder {
 				is_full_width,
 			)));
 		}
+
+		if !touched_by.is_empty() {
+			updater.push_line(ViewLine::from(""));
+			updater.push_line(ViewLine::from(LineSegment::new_with_color(
+				if is_full_width {
+					"Also touched by:"
+				}
+				else {
+					"Touched by:"
+				},
+				DisplayColor::IndicatorColor,
+			)));
+			for &(ref hash, ref content, ref paths) in touched_by {
+				let max_index = hash.len().min(8);
+				updater.push_line(ViewLine::from(vec![
+					LineSegment::new(format!("  {} {}", &hash[0..max_index], content).as_str()),
+					LineSegment::new_with_color(
+						format!(" ({})", paths.join(", ")).as_str(),
+						DisplayColor::DiffChangeColor,
+					),
+				]));
+			}
+		}
+
+		if !conflicts.is_empty() {
+			updater.push_line(ViewLine::from(""));
+			updater.push_line(ViewLine::from(LineSegment::new_with_color(
+				if is_full_width {
+					"Predicted conflicts:"
+				}
+				else {
+					"Conflicts:"
+				},
+				DisplayColor::WarningColor,
+			)));
+			for path in conflicts {
+				updater.push_line(ViewLine::from(vec![
+					LineSegment::new("  "),
+					LineSegment::new_with_color(path.as_str(), DisplayColor::WarningColor),
+				]));
+			}
+		}
+	}
+
+	fn get_line_number_segment(line_number: Option<u32>, largest_line_number_length: usize) -> LineSegment {
+		match line_number {
+			Some(line_number) => {
+				LineSegment::new(format!("{:<width$}", line_number, width = largest_line_number_length).as_str())
+			},
+			None => LineSegment::new(" ".repeat(largest_line_number_length).as_str()),
+		}
+	}
+
+	// the parts of a line that are styled, which are the syntax of the line, with the changes within a line that is
+	// paired with the line that it replaces, or that replaces it, layered over the syntax, and the matches of the
+	// search over all other styles
+	fn get_styled_spans(
+		&self,
+		diff_line: &DiffLine,
+		paired_line: Option<&DiffLine>,
+		grammar: Option<&Grammar>,
+		search: &DiffSearch,
+	) -> Vec<StyledSpan> {
+		let syntax_spans = grammar.map_or_else(Vec::new, |line_grammar| {
+			get_syntax_spans(diff_line.line(), line_grammar)
+				.into_iter()
+				.map(|(start, end, color)| (start, end, color, false))
+				.collect()
+		});
+		let changed_spans: Vec<StyledSpan> = match (diff_line.origin(), paired_line) {
+			(&Origin::Deletion, Some(new_line)) => {
+				get_changed_spans(diff_line.line(), new_line.line(), self.word_highlight)
+					.0
+					.into_iter()
+					.map(|(start, end)| (start, end, DisplayColor::DiffRemoveHighlightColor, true))
+					.collect()
+			},
+			(&Origin::Addition, Some(old_line)) => {
+				get_changed_spans(old_line.line(), diff_line.line(), self.word_highlight)
+					.1
+					.into_iter()
+					.map(|(start, end)| (start, end, DisplayColor::DiffAddHighlightColor, true))
+					.collect()
+			},
+			_ => vec![],
+		};
+		let match_spans: Vec<StyledSpan> = search
+			.get_match_ranges(diff_line.line())
+			.into_iter()
+			.map(|(start, end)| (start, end, DisplayColor::IndicatorColor, true))
+			.collect();
+		layer_spans(layer_spans(syntax_spans, &changed_spans), &match_spans)
+	}
+
+	// the content of a diff line, split into the parts that are shown with a different color or style
+	fn get_diff_content_parts(
+		&self,
+		diff_line: &DiffLine,
+		spans: &[StyledSpan],
+	) -> Vec<(String, DisplayColor, bool)> {
+		let origin_color = match *diff_line.origin() {
+			Origin::Addition => DisplayColor::DiffAddColor,
+			Origin::Deletion => DisplayColor::DiffRemoveColor,
+			Origin::Context => DisplayColor::DiffContextColor,
+		};
+		let line = diff_line.line();
+		let show_whitespace = self.show_leading_whitespace || self.show_trailing_whitespace;
+		if !show_whitespace && spans.is_empty() {
+			return vec![(self.replace_whitespace(line, false), origin_color, false)];
+		}
+
+		let (start, end) = if !show_whitespace {
+			(0, line.len())
+		}
+		else if line.trim().is_empty() {
+			(line.len(), line.len())
+		}
+		else {
+			get_partition_index_on_whitespace_for_line(line)
+		};
+
+		let mut parts = vec![(
+			self.replace_whitespace(&line[0..start], self.show_leading_whitespace || start == end),
+			DisplayColor::DiffWhitespaceColor,
+			false,
+		)];
+		let mut index = start;
+		for &(span_start, span_end, color, reversed) in spans {
+			let span_start = span_start.clamp(index, end);
+			let span_end = span_end.clamp(span_start, end);
+			if span_start == span_end {
+				continue;
+			}
+			parts.push((self.replace_whitespace(&line[index..span_start], false), origin_color, false));
+			parts.push((self.replace_whitespace(&line[span_start..span_end], false), color, reversed));
+			index = span_end;
+		}
+		parts.push((self.replace_whitespace(&line[index..end], false), origin_color, false));
+		parts.push((
+			self.replace_whitespace(&line[end..], self.show_trailing_whitespace),
+			DisplayColor::DiffWhitespaceColor,
+			false,
+		));
+		parts.into_iter().filter(|&(ref part, ..)| !part.is_empty()).collect()
 	}
 
 	fn get_diff_line_segments(
 		&self,
 		diff_line: &DiffLine,
+		spans: &[StyledSpan],
 		old_largest_line_number_length: usize,
 		new_largest_line_number_length: usize,
 	) -> Vec<LineSegment> {
 		let mut line_segments = vec![
-			match diff_line.old_line_number() {
-				Some(line_number) => {
-					LineSegment::new(
-						format!("{:<width$}", line_number, width = old_largest_line_number_length).as_str(),
-					)
-				},
-				None => LineSegment::new(" ".repeat(old_largest_line_number_length).as_str()),
-			},
+			Self::get_line_number_segment(diff_line.old_line_number(), old_largest_line_number_length),
 			LineSegment::new(" "),
-			match diff_line.new_line_number() {
-				Some(line_number) => {
-					LineSegment::new(
-						format!("{:<width$}", line_number, width = new_largest_line_number_length).as_str(),
-					)
-				},
-				None => LineSegment::new(" ".repeat(new_largest_line_number_length).as_str()),
-			},
+			Self::get_line_number_segment(diff_line.new_line_number(), new_largest_line_number_length),
 			LineSegment::new("| "),
 		];
 
-		if self.show_leading_whitespace || self.show_trailing_whitespace {
-			let line = diff_line.line();
-			let (leading, content, trailing) = if line.trim().is_empty() {
-				(
-					self.replace_whitespace(line, self.show_leading_whitespace || self.show_trailing_whitespace),
-					String::from(""),
-					String::from(""),
-				)
+		line_segments.extend(
+			self.get_diff_content_parts(diff_line, spans)
+				.into_iter()
+				.map(|(content, color, highlighted)| {
+					LineSegment::new_with_color_and_style(content.as_str(), color, false, false, highlighted)
+				}),
+		);
+		line_segments
+	}
+
+	// one side of a side by side diff line, with the content cut or padded to the width of the side when `width` is
+	// provided
+	fn push_side_by_side_segments(
+		&self,
+		line_segments: &mut Vec<LineSegment>,
+		diff_line: Option<&DiffLine>,
+		spans: &[StyledSpan],
+		line_number: Option<u32>,
+		largest_line_number_length: usize,
+		width: Option<usize>,
+	) {
+		line_segments.push(Self::get_line_number_segment(line_number, largest_line_number_length));
+		line_segments.push(LineSegment::new("| "));
+		let mut remaining = width.unwrap_or(usize::MAX);
+		for (part, color, highlighted) in
+			diff_line.map_or_else(Vec::new, |line| self.get_diff_content_parts(line, spans))
+		{
+			if remaining == 0 {
+				break;
 			}
-			else {
-				let (start, end) = get_partition_index_on_whitespace_for_line(line);
-				(
-					self.replace_whitespace(&line[0..start], self.show_leading_whitespace),
-					self.replace_whitespace(&line[start..end], false),
-					self.replace_whitespace(&line[end..], self.show_trailing_whitespace),
-				)
-			};
+			let part = UnicodeSegmentation::graphemes(part.as_str(), true)
+				.take(remaining)
+				.collect::<String>();
+			remaining -= UnicodeSegmentation::graphemes(part.as_str(), true).count();
+			line_segments.push(LineSegment::new_with_color_and_style(
+				part.as_str(),
+				color,
+				false,
+				false,
+				highlighted,
+			));
+		}
+		if width.is_some() && remaining > 0 {
+			line_segments.push(LineSegment::new(" ".repeat(remaining).as_str()));
+		}
+	}
 
-			if !leading.is_empty() {
-				line_segments.push(LineSegment::new_with_color(
-					leading.as_str(),
-					DisplayColor::DiffWhitespaceColor,
-				));
+	#[allow(clippy::too_many_arguments)]
+	fn get_side_by_side_line_segments(
+		&self,
+		old_line: Option<&DiffLine>,
+		new_line: Option<&DiffLine>,
+		grammar: Option<&Grammar>,
+		search: &DiffSearch,
+		old_largest_line_number_length: usize,
+		new_largest_line_number_length: usize,
+		side_width: usize,
+	) -> Vec<LineSegment> {
+		let old_spans = old_line.map_or_else(Vec::new, |line| self.get_styled_spans(line, new_line, grammar, search));
+		let new_spans = new_line.map_or_else(Vec::new, |line| self.get_styled_spans(line, old_line, grammar, search));
+		let mut line_segments = vec![];
+		self.push_side_by_side_segments(
+			&mut line_segments,
+			old_line,
+			old_spans.as_slice(),
+			old_line.and_then(DiffLine::old_line_number),
+			old_largest_line_number_length,
+			Some(side_width),
+		);
+		line_segments.push(LineSegment::new(" "));
+		self.push_side_by_side_segments(
+			&mut line_segments,
+			new_line,
+			new_spans.as_slice(),
+			new_line.and_then(DiffLine::new_line_number),
+			new_largest_line_number_length,
+			None,
+		);
+		line_segments
+	}
+
+	// the removed lines are shown next to the added lines that replace them, while context lines are shown on both sides
+	#[allow(clippy::too_many_arguments)]
+	fn push_side_by_side_lines(
+		&self,
+		updater: &mut ViewDataUpdater<'_>,
+		lines: &[DiffLine],
+		grammar: Option<&Grammar>,
+		search: &mut DiffSearch,
+		old_largest_line_number_length: usize,
+		new_largest_line_number_length: usize,
+		width: usize,
+	) {
+		// a line number and separator on each side, with a space between the sides
+		let side_width = width.saturating_sub(old_largest_line_number_length + new_largest_line_number_length + 5) / 2;
+		let mut deletions: Vec<&DiffLine> = vec![];
+		let mut additions: Vec<&DiffLine> = vec![];
+		let push_changed_lines = |line_updater: &mut ViewDataUpdater<'_>,
+		                          line_search: &mut DiffSearch,
+		                          old_lines: &mut Vec<&DiffLine>,
+		                          new_lines: &mut Vec<&DiffLine>| {
+			for index in 0..old_lines.len().max(new_lines.len()) {
+				let row = line_updater.get_lines_length();
+				for line in old_lines.get(index).iter().chain(new_lines.get(index).iter()) {
+					line_search.push_line(row, line.line());
+				}
+				line_updater.push_line(ViewLine::from(self.get_side_by_side_line_segments(
+					old_lines.get(index).copied(),
+					new_lines.get(index).copied(),
+					grammar,
+					line_search,
+					old_largest_line_number_length,
+					new_largest_line_number_length,
+					side_width,
+				)));
 			}
-			if !content.is_empty() {
-				line_segments.push(LineSegment::new_with_color(
-					content.as_str(),
-					match *diff_line.origin() {
-						Origin::Addition => DisplayColor::DiffAddColor,
-						Origin::Deletion => DisplayColor::DiffRemoveColor,
-						Origin::Context => DisplayColor::DiffContextColor,
-					},
+			old_lines.clear();
+			new_lines.clear();
+		};
+
+		for line in lines {
+			if line.end_of_file() && line.line() != "\n" {
+				push_changed_lines(updater, search, &mut deletions, &mut additions);
+				updater.push_line(Self::build_end_of_file_line(
+					old_largest_line_number_length,
+					new_largest_line_number_length,
 				));
+				continue;
 			}
-			if !trailing.is_empty() {
-				line_segments.push(LineSegment::new_with_color(
-					trailing.as_str(),
-					DisplayColor::DiffWhitespaceColor,
-				));
+			match *line.origin() {
+				Origin::Deletion => {
+					if !additions.is_empty() {
+						push_changed_lines(updater, search, &mut deletions, &mut additions);
+					}
+					deletions.push(line);
+				},
+				Origin::Addition => additions.push(line),
+				Origin::Context => {
+					push_changed_lines(updater, search, &mut deletions, &mut additions);
+					search.push_line(updater.get_lines_length(), line.line());
+					updater.push_line(ViewLine::from(self.get_side_by_side_line_segments(
+						Some(line),
+						Some(line),
+						grammar,
+						search,
+						old_largest_line_number_length,
+						new_largest_line_number_length,
+						side_width,
+					)));
+				},
 			}
 		}
-		else {
-			line_segments.push(LineSegment::new_with_color(
-				self.replace_whitespace(diff_line.line(), false).as_str(),
-				match *diff_line.origin() {
-					Origin::Addition => DisplayColor::DiffAddColor,
-					Origin::Deletion => DisplayColor::DiffRemoveColor,
-					Origin::Context => DisplayColor::DiffContextColor,
-				},
-			));
+		push_changed_lines(updater, search, &mut deletions, &mut additions);
+	}
+
+	fn build_end_of_file_line(old_largest_line_number_length: usize, new_largest_line_number_length: usize) -> ViewLine {
+		ViewLine::from(vec![
+			LineSegment::new(" ".repeat(old_largest_line_number_length + new_largest_line_number_length + 3).as_str()),
+			LineSegment::new_with_color("\\ No newline at end of file", DisplayColor::DiffContextColor),
+		])
+	}
+
+	// a description of a change with the old and new values, in the style of a renamed file
+	fn build_file_change_line(description: &str, old_value: Option<String>, new_value: Option<String>) -> ViewLine {
+		let mut segments = vec![LineSegment::new_with_color(
+			format!("{} ", description).as_str(),
+			DisplayColor::DiffChangeColor,
+		)];
+		match (old_value, new_value) {
+			(Some(old), Some(new)) if old == new => {
+				segments.push(LineSegment::new_with_color(new.as_str(), DisplayColor::Normal));
+			},
+			(Some(old), Some(new)) => {
+				segments.push(LineSegment::new_with_color(old.as_str(), DisplayColor::DiffRemoveColor));
+				segments.push(LineSegment::new_with_color(" → ", DisplayColor::Normal));
+				segments.push(LineSegment::new_with_color(new.as_str(), DisplayColor::DiffAddColor));
+			},
+			(Some(old), None) => segments.push(LineSegment::new_with_color(old.as_str(), DisplayColor::DiffRemoveColor)),
+			(None, Some(new)) => segments.push(LineSegment::new_with_color(new.as_str(), DisplayColor::DiffAddColor)),
+			(None, None) => {},
 		}
+		ViewLine::from(segments)
+	}
 
-		line_segments
+	// the changes of a file that are not changed lines of text, such as a changed file mode or binary content
+	fn build_file_change_lines(stat: &FileStat) -> Vec<ViewLine> {
+		let mut lines = vec![];
+		if let Some((old_mode, new_mode)) = stat.get_mode_change() {
+			lines.push(Self::build_file_change_line(
+				"mode",
+				Some(format!("{:o}", old_mode)),
+				Some(format!("{:o}", new_mode)),
+			));
+		}
+		match *stat.get_content() {
+			FileContent::Text => {},
+			FileContent::Binary { old_size, new_size } => {
+				let (description, old_size, new_size) = match *stat.get_status() {
+					Status::Added => ("Binary file added", None, Some(new_size)),
+					Status::Deleted => ("Binary file deleted", Some(old_size), None),
+					_ => ("Binary file changed", Some(old_size), Some(new_size)),
+				};
+				lines.push(Self::build_file_change_line(
+					description,
+					old_size.map(format_file_size),
+					new_size.map(format_file_size),
+				));
+			},
+			FileContent::Symlink {
+				ref old_target,
+				ref new_target,
+			} => {
+				lines.push(Self::build_file_change_line(
+					"Symbolic link to",
+					old_target.clone(),
+					new_target.clone(),
+				));
+			},
+			FileContent::Submodule {
+				ref old_commit,
+				ref new_commit,
+			} => {
+				lines.push(Self::build_file_change_line(
+					"Submodule commit",
+					old_commit.clone(),
+					new_commit.clone(),
+				));
+			},
+		}
+		lines
 	}
 
-	pub(super) fn build_view_data_diff(&self, updater: &mut ViewDataUpdater<'_>, commit: &Commit, is_full_width: bool) {
+	/// Build the diff of a commit, which is shown side by side when `side_by_side_width` is provided.
+	pub(super) fn build_view_data_diff(
+		&self,
+		updater: &mut ViewDataUpdater<'_>,
+		commit: &Commit,
+		is_full_width: bool,
+		side_by_side_width: Option<usize>,
+		sections: &mut DiffSections,
+		search: &mut DiffSearch,
+	) {
 		updater.push_leading_line(Self::build_leading_summary(commit, is_full_width));
 		updater.push_leading_line(get_files_changed_summary(commit, is_full_width));
 		updater.push_line(ViewLine::new_empty_line().set_padding('―'));
+		self.push_file_diffs(updater, commit, side_by_side_width, sections, search);
+	}
 
-		let file_stats = commit.get_file_stats();
-		for (s_i, stat) in file_stats.iter().enumerate() {
-			updater.push_line(ViewLine::from(get_stat_item_segments(
+	/// Build the overall diff of a range of commits.
+	pub(super) fn build_view_data_range_diff(
+		&self,
+		updater: &mut ViewDataUpdater<'_>,
+		range: &CommitRange,
+		is_full_width: bool,
+		side_by_side_width: Option<usize>,
+		sections: &mut DiffSections,
+		search: &mut DiffSearch,
+	) {
+		updater.push_leading_line(Self::build_range_leading_summary(range, is_full_width));
+		updater.push_leading_line(get_files_changed_summary(range.get_diff(), is_full_width));
+		updater.push_line(ViewLine::new_empty_line().set_padding('―'));
+		self.push_file_diffs(updater, range.get_diff(), side_by_side_width, sections, search);
+	}
+
+	/// Build the view of the commit that results from a squash or fixup group, with the assembled message followed
+	/// by the combined diff.
+	pub(super) fn build_view_data_squash(
+		&self,
+		updater: &mut ViewDataUpdater<'_>,
+		commit: &Commit,
+		is_full_width: bool,
+		side_by_side_width: Option<usize>,
+		sections: &mut DiffSections,
+		search: &mut DiffSearch,
+	) {
+		let mut leading_segments = Self::build_leading_summary_segments(commit, is_full_width);
+		leading_segments.push(LineSegment::new_with_color(
+			if is_full_width { " (combined)" } else { " (C)" },
+			DisplayColor::IndicatorColor,
+		));
+		updater.push_leading_line(ViewLine::from(leading_segments));
+		updater.push_leading_line(get_files_changed_summary(commit, is_full_width));
+		updater.push_line(ViewLine::new_empty_line().set_padding('―'));
+		if let Some(ref body) = *commit.get_body() {
+			for line in body.lines() {
+				updater.push_line(ViewLine::from(line));
+			}
+		}
+		updater.push_line(ViewLine::new_empty_line().set_padding('―'));
+		self.push_file_diffs(updater, commit, side_by_side_width, sections, search);
+	}
+
+	/// Build the list of the changed files of a commit, with the file at `selected_index` selected.
+	pub(super) fn build_view_data_file_list(
+		updater: &mut ViewDataUpdater<'_>,
+		commit: &Commit,
+		selected_index: usize,
+		is_full_width: bool,
+	) {
+		updater.push_leading_line(get_files_changed_summary(commit, is_full_width));
+		updater.push_leading_line(ViewLine::new_empty_line().set_padding('―'));
+		for (index, stat) in commit.get_file_stats().iter().enumerate() {
+			let mut view_line = ViewLine::from(get_stat_item_segments(
 				stat.get_status(),
 				stat.get_to_name(),
 				stat.get_from_name(),
-				true,
-			)));
+				is_full_width,
+			));
+			if index == selected_index {
+				view_line = view_line.set_selected(true).set_padding(' ');
+			}
+			updater.push_line(view_line);
+		}
+		updater.ensure_line_visible(selected_index);
+	}
 
+	fn push_file_diffs(
+		&self,
+		updater: &mut ViewDataUpdater<'_>,
+		commit: &Commit,
+		side_by_side_width: Option<usize>,
+		sections: &mut DiffSections,
+		search: &mut DiffSearch,
+	) {
+		sections.clear_rows();
+		search.clear_lines();
+		let file_stats = commit.get_file_stats();
+		for (s_i, stat) in file_stats.iter().enumerate() {
+			let is_collapsed = sections.is_collapsed(s_i);
+			sections.push_file(updater.get_lines_length());
+			let mut header_segments =
+				get_stat_item_segments(stat.get_status(), stat.get_to_name(), stat.get_from_name(), true);
+			if is_collapsed {
+				header_segments.push(LineSegment::new_with_color_and_style(
+					" (collapsed)",
+					DisplayColor::Normal,
+					true,
+					false,
+					false,
+				));
+			}
+			updater.push_line(ViewLine::from(header_segments).set_selected(sections.is_selected(s_i, None)));
+
+			let file_change_lines = if is_collapsed {
+				vec![]
+			}
+			else {
+				Self::build_file_change_lines(stat)
+			};
+			if !file_change_lines.is_empty() {
+				updater.push_line(ViewLine::new_empty_line());
+				for line in file_change_lines {
+					updater.push_line(line);
+				}
+			}
+
+			// the deltas of a binary file, symbolic link or submodule are not meaningful lines of text
+			let deltas = if !is_collapsed && *stat.get_content() == FileContent::Text {
+				stat.deltas().as_slice()
+			}
+			else {
+				&[]
+			};
+			let grammar = if self.syntax_highlight {
+				get_grammar(stat.get_to_name())
+			}
+			else {
+				None
+			};
 			let old_largest_line_number_length = stat.largest_old_line_number().to_string().len();
 			let new_largest_line_number_length = stat.largest_new_line_number().to_string().len();
-			for delta in stat.deltas() {
+			for (d_i, delta) in deltas.iter().enumerate() {
 				updater.push_line(ViewLine::new_empty_line());
+				sections.push_hunk(updater.get_lines_length());
 				updater.push_line(ViewLine::from(vec![
 					LineSegment::new_with_color_and_style("@@", DisplayColor::Normal, true, false, false),
 					LineSegment::new_with_color(
@@ -257,7 +838,8 @@ impl ViewBuilder {
 						format!(" {}", delta.context()).as_str(),
 						DisplayColor::DiffContextColor,
 					),
-				]));
+				])
+				.set_selected(sections.is_selected(s_i, Some(d_i))));
 				updater.push_line(ViewLine::new_pinned(vec![]).set_padding_with_color_and_style(
 					'┈',
 					DisplayColor::Normal,
@@ -266,20 +848,34 @@ impl ViewBuilder {
 					false,
 				));
 
-				for line in delta.lines() {
+				if let Some(width) = side_by_side_width {
+					self.push_side_by_side_lines(
+						updater,
+						delta.lines(),
+						grammar,
+						search,
+						old_largest_line_number_length,
+						new_largest_line_number_length,
+						width,
+					);
+					continue;
+				}
+
+				let pairs = get_changed_pairs(delta.lines());
+				for (index, line) in delta.lines().iter().enumerate() {
 					if line.end_of_file() && line.line() != "\n" {
-						updater.push_line(ViewLine::from(vec![
-							LineSegment::new(
-								" ".repeat(old_largest_line_number_length + new_largest_line_number_length + 3)
-									.as_str(),
-							),
-							LineSegment::new_with_color("\\ No newline at end of file", DisplayColor::DiffContextColor),
-						]));
+						updater.push_line(Self::build_end_of_file_line(
+							old_largest_line_number_length,
+							new_largest_line_number_length,
+						));
 						continue;
 					}
 
+					let paired_line = pairs[index].map(|paired_index| &delta.lines()[paired_index]);
+					search.push_line(updater.get_lines_length(), line.line());
 					updater.push_line(ViewLine::from(self.get_diff_line_segments(
 						line,
+						self.get_styled_spans(line, paired_line, grammar, search).as_slice(),
 						old_largest_line_number_length,
 						new_largest_line_number_length,
 					)));
@@ -0,0 +1,116 @@
+use config::DiffWordHighlightSetting;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::{diff_line::DiffLine, origin::Origin};
+
+// comparing the tokens of long lines is slow, and the result of highlighting such lines is rarely useful
+const MAXIMUM_TOKEN_COMPARISONS: usize = 250_000;
+
+// the changed byte ranges of a removed line, and of the added line that replaces it
+type ChangedSpans = (Vec<(usize, usize)>, Vec<(usize, usize)>);
+
+fn is_changed_line(line: &DiffLine, origin: &Origin) -> bool {
+	line.origin() == origin && !(line.end_of_file() && line.line() != "\n")
+}
+
+fn tokenize(line: &str, setting: DiffWordHighlightSetting) -> Vec<(usize, &str)> {
+	if setting == DiffWordHighlightSetting::Character {
+		line.grapheme_indices(true).collect()
+	}
+	else {
+		line.split_word_bound_indices().collect()
+	}
+}
+
+// merge the adjacent changed tokens into byte ranges of the line
+fn get_spans(tokens: &[(usize, &str)], changed: &[bool]) -> Vec<(usize, usize)> {
+	let mut spans: Vec<(usize, usize)> = vec![];
+	for (&(start, token), _) in tokens.iter().zip(changed).filter(|&(_, &is_changed)| is_changed) {
+		let end = start + token.len();
+		match spans.last_mut() {
+			Some(span) if span.1 == start => span.1 = end,
+			_ => spans.push((start, end)),
+		}
+	}
+	spans
+}
+
+/// Pair the removed lines of a delta with the added lines that directly follow them, in order. Returns the index of
+/// the paired line for each of the lines of the delta.
+pub(super) fn get_changed_pairs(lines: &[DiffLine]) -> Vec<Option<usize>> {
+	let mut pairs = vec![None; lines.len()];
+	let mut index = 0;
+	while index < lines.len() {
+		if !is_changed_line(&lines[index], &Origin::Deletion) {
+			index += 1;
+			continue;
+		}
+		let deletions_start = index;
+		while index < lines.len() && is_changed_line(&lines[index], &Origin::Deletion) {
+			index += 1;
+		}
+		let additions_start = index;
+		while index < lines.len() && is_changed_line(&lines[index], &Origin::Addition) {
+			index += 1;
+		}
+		for offset in 0..(additions_start - deletions_start).min(index - additions_start) {
+			pairs[deletions_start + offset] = Some(additions_start + offset);
+			pairs[additions_start + offset] = Some(deletions_start + offset);
+		}
+	}
+	pairs
+}
+
+/// Find the parts of a removed line and the added line that replaces it that differ, as byte ranges of each line.
+/// Nothing is highlighted when the lines have nothing but whitespace in common, since the whole line has changed.
+pub(super) fn get_changed_spans(old_line: &str, new_line: &str, setting: DiffWordHighlightSetting) -> ChangedSpans {
+	if setting == DiffWordHighlightSetting::None {
+		return (vec![], vec![]);
+	}
+	let old_tokens = tokenize(old_line, setting);
+	let new_tokens = tokenize(new_line, setting);
+	if old_tokens.len() * new_tokens.len() > MAXIMUM_TOKEN_COMPARISONS {
+		return (vec![], vec![]);
+	}
+
+	// the longest common subsequence of the tokens, where lengths[i][j] is the length for the tokens after i and j
+	let mut lengths = vec![vec![0_usize; new_tokens.len() + 1]; old_tokens.len() + 1];
+	for old_index in (0..old_tokens.len()).rev() {
+		for new_index in (0..new_tokens.len()).rev() {
+			lengths[old_index][new_index] = if old_tokens[old_index].1 == new_tokens[new_index].1 {
+				lengths[old_index + 1][new_index + 1] + 1
+			}
+			else {
+				lengths[old_index + 1][new_index].max(lengths[old_index][new_index + 1])
+			};
+		}
+	}
+
+	let mut old_changed = vec![true; old_tokens.len()];
+	let mut new_changed = vec![true; new_tokens.len()];
+	let mut has_common_content = false;
+	let (mut old_index, mut new_index) = (0, 0);
+	while old_index < old_tokens.len() && new_index < new_tokens.len() {
+		if old_tokens[old_index].1 == new_tokens[new_index].1 {
+			has_common_content |= !old_tokens[old_index].1.trim().is_empty();
+			old_changed[old_index] = false;
+			new_changed[new_index] = false;
+			old_index += 1;
+			new_index += 1;
+		}
+		else if lengths[old_index + 1][new_index] >= lengths[old_index][new_index + 1] {
+			old_index += 1;
+		}
+		else {
+			new_index += 1;
+		}
+	}
+
+	if !has_common_content {
+		return (vec![], vec![]);
+	}
+	(
+		get_spans(&old_tokens, &old_changed),
+		get_spans(&new_tokens, &new_changed),
+	)
+}
@@ -0,0 +1,66 @@
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		mpsc,
+		Arc,
+	},
+	thread,
+};
+
+use crate::modules::CommitPaths;
+
+/// Loads the paths changed by the commits of the todo file on a separate thread, so the commits that touch the same
+/// paths can be shown without loading every commit when a commit is shown.
+pub(crate) struct CommitPathsLoader {
+	cancelled: Arc<AtomicBool>,
+	commit_paths: CommitPaths,
+	result_receiver: mpsc::Receiver<(String, Vec<String>)>,
+}
+
+impl CommitPathsLoader {
+	/// Start the loading thread, that loads the paths of the commits of `hashes` in order.
+	pub(crate) fn spawn(commit_paths: CommitPaths, hashes: Vec<String>) -> (Self, thread::JoinHandle<()>) {
+		let cancelled = Arc::new(AtomicBool::new(false));
+		let (result_sender, result_receiver) = mpsc::channel();
+		let thread_cancelled = Arc::clone(&cancelled);
+		let load_paths = commit_paths.create_loader();
+
+		let handle = thread::spawn(move || {
+			for hash in hashes {
+				if thread_cancelled.load(Ordering::Acquire) {
+					break;
+				}
+				let paths = load_paths(hash.as_str());
+				// the receiver is dropped once the process ends, which is expected when the loading is cancelled
+				if result_sender.send((hash, paths)).is_err() {
+					break;
+				}
+			}
+		});
+
+		(
+			Self {
+				cancelled,
+				commit_paths,
+				result_receiver,
+			},
+			handle,
+		)
+	}
+
+	/// Add the paths of the commits that have been loaded since the last call to the cache. Returns true if the paths
+	/// of any commit were added.
+	pub(crate) fn try_receive(&self) -> bool {
+		let mut received = false;
+		while let Ok((hash, paths)) = self.result_receiver.try_recv() {
+			self.commit_paths.set_paths(hash.as_str(), paths);
+			received = true;
+		}
+		received
+	}
+
+	/// Cancel the loading, if it is still in progress.
+	pub(crate) fn end(self) {
+		self.cancelled.store(true, Ordering::Release);
+	}
+}
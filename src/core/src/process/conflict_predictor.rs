@@ -0,0 +1,138 @@
+use std::{
+	sync::{
+		atomic::{AtomicUsize, Ordering},
+		mpsc,
+		Arc,
+	},
+	thread,
+};
+
+use git::{RebaseStep, Repository};
+use todo_file::{Action, Line, TodoFile};
+
+// the version of the todo file that the conflicts were predicted for, and the conflicting paths for each line
+type Prediction = (usize, Vec<Vec<String>>);
+
+struct Request {
+	generation: usize,
+	lines: Vec<Line>,
+	version: usize,
+}
+
+fn get_rebase_step(line: &Line) -> Option<RebaseStep> {
+	match *line.get_action() {
+		Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash => {
+			Some(RebaseStep::Pick(String::from(line.get_hash())))
+		},
+		Action::Revert => Some(RebaseStep::Revert(String::from(line.get_hash()))),
+		Action::Label => Some(RebaseStep::Label(String::from(line.get_content()))),
+		Action::Reset => Some(RebaseStep::Reset(String::from(line.get_content()))),
+		Action::Merge => {
+			Some(RebaseStep::Merge {
+				commit: line.has_reference().then(|| String::from(line.get_hash())),
+				references: line.get_labels().into_iter().map(String::from).collect(),
+			})
+		},
+		Action::Break | Action::Drop | Action::Exec | Action::Noop | Action::UpdateRef => None,
+	}
+}
+
+pub(super) fn predict_conflicts<F>(
+	repository: &Repository,
+	base: &str,
+	lines: &[Line],
+	is_cancelled: F,
+) -> Option<Vec<Vec<String>>>
+where
+	F: Fn() -> bool,
+{
+	let (indices, steps): (Vec<usize>, Vec<RebaseStep>) = lines
+		.iter()
+		.enumerate()
+		.filter_map(|(index, line)| get_rebase_step(line).map(|step| (index, step)))
+		.unzip();
+	// a failed prediction, such as from a commit that does not exist, is not shown
+	let step_conflicts = repository.predict_conflicts(base, &steps, is_cancelled).ok()??;
+	let mut conflicts = vec![vec![]; lines.len()];
+	for (index, paths) in indices.into_iter().zip(step_conflicts) {
+		conflicts[index] = paths;
+	}
+	Some(conflicts)
+}
+
+/// Predicts the conflicts of the todo file on a separate thread, so the prediction does not block input.
+pub(crate) struct ConflictPredictor {
+	generation: Arc<AtomicUsize>,
+	request_sender: mpsc::Sender<Request>,
+	requested_version: Option<usize>,
+	result_receiver: mpsc::Receiver<Prediction>,
+}
+
+impl ConflictPredictor {
+	/// Start the prediction thread, that replays the lines onto the `base` commit.
+	pub(crate) fn spawn(base: &str) -> (Self, thread::JoinHandle<()>) {
+		let generation = Arc::new(AtomicUsize::new(0));
+		let (request_sender, request_receiver) = mpsc::channel::<Request>();
+		let (result_sender, result_receiver) = mpsc::channel();
+		let thread_generation = Arc::clone(&generation);
+		let base = String::from(base);
+
+		let handle = thread::spawn(move || {
+			let repository = match Repository::open_from_env() {
+				Ok(repository) => repository,
+				Err(_) => return,
+			};
+			while let Ok(mut request) = request_receiver.recv() {
+				// only the latest request is predicted, since any earlier requests are already out of date
+				while let Ok(newer_request) = request_receiver.try_recv() {
+					request = newer_request;
+				}
+				let is_cancelled = || thread_generation.load(Ordering::Acquire) != request.generation;
+				if let Some(conflicts) = predict_conflicts(&repository, base.as_str(), &request.lines, is_cancelled) {
+					if result_sender.send((request.version, conflicts)).is_err() {
+						break;
+					}
+				}
+			}
+		});
+
+		(
+			Self {
+				generation,
+				request_sender,
+				requested_version: None,
+				result_receiver,
+			},
+			handle,
+		)
+	}
+
+	/// Request a prediction for the lines of the todo file, if they have changed since the last request. A
+	/// prediction that is in progress is cancelled. Returns false if the prediction thread has stopped, such as when
+	/// the repository could not be opened, and so no prediction will be made.
+	pub(crate) fn request(&mut self, todo_file: &TodoFile) -> bool {
+		let version = todo_file.get_version();
+		if self.requested_version == Some(version) {
+			return true;
+		}
+		let generation = self.generation.fetch_add(1, Ordering::AcqRel) + 1;
+		self.requested_version = Some(version);
+		self.request_sender
+			.send(Request {
+				generation,
+				lines: todo_file.get_lines_owned(),
+				version,
+			})
+			.is_ok()
+	}
+
+	/// Get the lines and conflicts of a completed prediction, if there is one.
+	pub(crate) fn try_receive(&self) -> Option<Prediction> {
+		self.result_receiver.try_recv().ok()
+	}
+
+	/// Cancel any prediction in progress and stop the prediction thread.
+	pub(crate) fn end(self) {
+		let _ = self.generation.fetch_add(1, Ordering::AcqRel);
+	}
+}
@@ -1,5 +1,8 @@
+mod commit_paths_loader;
+mod conflict_predictor;
 #[cfg(test)]
 mod tests;
+mod upstream_detector;
 
 use std::{process::Command, thread};
 
@@ -9,15 +12,26 @@ use input::{Event, EventHandler, MetaEvent};
 use todo_file::TodoFile;
 use view::{spawn_view_thread, RenderContext, View, ViewSender};
 
-use crate::module::{ExitStatus, Modules, ProcessResult, State};
+use self::{
+	commit_paths_loader::CommitPathsLoader,
+	conflict_predictor::ConflictPredictor,
+	upstream_detector::UpstreamDetector,
+};
+use crate::{
+	module::{ExitStatus, Modules, ProcessResult, State},
+	modules::{is_path_changing_line, CommitPaths},
+};
 
 pub(crate) struct Process {
+	commit_paths_loader: Option<CommitPathsLoader>,
+	conflict_predictor: Option<ConflictPredictor>,
 	event_handler: EventHandler,
 	exit_status: Option<ExitStatus>,
 	rebase_todo: TodoFile,
 	render_context: RenderContext,
 	state: State,
 	threads: Vec<thread::JoinHandle<()>>,
+	upstream_detector: Option<UpstreamDetector>,
 	view_sender: ViewSender,
 }
 
@@ -35,16 +49,53 @@ impl Process {
 		threads.push(view_thread);
 
 		Self {
+			commit_paths_loader: None,
+			conflict_predictor: None,
 			event_handler,
 			exit_status: None,
 			rebase_todo,
 			render_context: RenderContext::new(view_size.width() as u16, view_size.height() as u16),
 			state: State::List,
 			threads,
+			upstream_detector: None,
 			view_sender,
 		}
 	}
 
+	/// Predict the conflicts of the todo file, by replaying the lines onto the `base` commit, after each change to the
+	/// todo file.
+	pub(crate) fn start_conflict_prediction(&mut self, base: &str) {
+		let (conflict_predictor, handle) = ConflictPredictor::spawn(base);
+		self.conflict_predictor = Some(conflict_predictor);
+		self.threads.push(handle);
+	}
+
+	/// Load the paths changed by the commits of the todo file into `commit_paths`.
+	pub(crate) fn start_commit_paths_loading(&mut self, commit_paths: CommitPaths) {
+		let mut hashes: Vec<String> = vec![];
+		for line in self.rebase_todo.iter().filter(|line| is_path_changing_line(line)) {
+			if !hashes.iter().any(|hash| hash == line.get_hash()) {
+				hashes.push(String::from(line.get_hash()));
+			}
+		}
+		let (commit_paths_loader, handle) = CommitPathsLoader::spawn(commit_paths, hashes);
+		self.commit_paths_loader = Some(commit_paths_loader);
+		self.threads.push(handle);
+	}
+
+	/// Find the commits of the todo file that are already applied to `upstream`.
+	pub(crate) fn start_upstream_detection(&mut self, upstream: &str) {
+		let hashes = self
+			.rebase_todo
+			.iter()
+			.filter(|line| is_path_changing_line(line))
+			.map(|line| String::from(line.get_hash()))
+			.collect();
+		let (upstream_detector, handle) = UpstreamDetector::spawn(upstream, hashes);
+		self.upstream_detector = Some(upstream_detector);
+		self.threads.push(handle);
+	}
+
 	pub(crate) fn run(&mut self, mut modules: Modules) -> Result<ExitStatus> {
 		if self.view_sender.start().is_err() {
 			self.exit_status = Some(ExitStatus::StateError);
@@ -60,6 +111,12 @@ impl Process {
 		);
 		self.activate(&mut modules, State::List);
 		while self.exit_status.is_none() {
+			let conflict_predictor_stopped = self.conflict_predictor.as_mut().map_or(false, |conflict_predictor| {
+				!conflict_predictor.request(&self.rebase_todo)
+			});
+			if conflict_predictor_stopped {
+				self.conflict_predictor = None;
+			}
 			let view_data = modules.build_view_data(self.state, &self.render_context, &self.rebase_todo);
 			if self.view_sender.render(view_data).is_err() {
 				self.exit_status = Some(ExitStatus::StateError);
@@ -83,8 +140,42 @@ impl Process {
 						break;
 					}
 				}
+
+				if let Some((version, conflicts)) = self
+					.conflict_predictor
+					.as_ref()
+					.and_then(ConflictPredictor::try_receive)
+				{
+					self.rebase_todo.set_conflicts(version, conflicts);
+					if self.rebase_todo.get_conflicts().is_some() {
+						break;
+					}
+				}
+
+				if let Some(applied_upstream) = self.upstream_detector.as_ref().and_then(UpstreamDetector::try_receive)
+				{
+					self.rebase_todo.set_applied_upstream(applied_upstream);
+					break;
+				}
+
+				if self
+					.commit_paths_loader
+					.as_ref()
+					.is_some_and(CommitPathsLoader::try_receive)
+				{
+					break;
+				}
 			}
 		}
+		if let Some(conflict_predictor) = self.conflict_predictor.take() {
+			conflict_predictor.end();
+		}
+		if let Some(upstream_detector) = self.upstream_detector.take() {
+			upstream_detector.end();
+		}
+		if let Some(commit_paths_loader) = self.commit_paths_loader.take() {
+			commit_paths_loader.end();
+		}
 		if self.view_sender.stop().is_err() {
 			return Ok(ExitStatus::StateError);
 		}
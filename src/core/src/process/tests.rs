@@ -4,6 +4,7 @@ use anyhow::anyhow;
 use config::Theme;
 use display::{testutil::CrossTerm, Display, Size};
 use input::InputOptions;
+use todo_file::Line;
 use view::{assert_rendered_output, ViewData};
 
 use super::*;
@@ -487,3 +488,49 @@ fn handle_process_result_external_command_status_error() {
 		);
 	});
 }
+
+fn create_file_commit(repository: &git2::Repository, content: &str, parent: Option<git2::Oid>) -> String {
+	let signature = git2::Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+	let mut builder = repository.treebuilder(None).unwrap();
+	let blob = repository.blob(content.as_bytes()).unwrap();
+	let _ = builder.insert("file.txt", blob, 0o100_644).unwrap();
+	let tree = repository.find_tree(builder.write().unwrap()).unwrap();
+	let parent = parent.map(|id| repository.find_commit(id).unwrap());
+	let parents = parent.iter().collect::<Vec<&git2::Commit<'_>>>();
+	repository
+		.commit(None, &signature, &signature, "commit", &tree, &parents)
+		.unwrap()
+		.to_string()
+}
+
+#[test]
+fn predict_conflicts_reordered_lines() {
+	let directory = tempfile::tempdir().unwrap();
+	let repository = git2::Repository::init(directory.path()).unwrap();
+	let base = create_file_commit(&repository, "1\n", None);
+	let first = create_file_commit(&repository, "2\n", git2::Oid::from_str(&base).ok());
+	let second = create_file_commit(&repository, "3\n", git2::Oid::from_str(&first).ok());
+	let lines = vec![
+		Line::new(format!("pick {} second", second).as_str()).unwrap(),
+		Line::new("exec make").unwrap(),
+		Line::new(format!("fixup {} first", first).as_str()).unwrap(),
+	];
+	assert_eq!(
+		conflict_predictor::predict_conflicts(&git::Repository::from(repository), &base, &lines, || false),
+		Some(vec![vec![String::from("file.txt")], vec![], vec![String::from(
+			"file.txt"
+		)]])
+	);
+}
+
+#[test]
+fn predict_conflicts_invalid_commit() {
+	let directory = tempfile::tempdir().unwrap();
+	let repository = git2::Repository::init(directory.path()).unwrap();
+	let base = create_file_commit(&repository, "1\n", None);
+	let lines = vec![Line::new("pick aaaaaaaa missing").unwrap()];
+	assert_eq!(
+		conflict_predictor::predict_conflicts(&git::Repository::from(repository), &base, &lines, || false),
+		None
+	);
+}
@@ -0,0 +1,56 @@
+use std::{
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		mpsc,
+		Arc,
+	},
+	thread,
+};
+
+use git::Repository;
+
+/// Finds the commits of the todo file that are already applied upstream on a separate thread, since comparing the
+/// patch-ids of a long upstream can take some time.
+pub(crate) struct UpstreamDetector {
+	cancelled: Arc<AtomicBool>,
+	result_receiver: mpsc::Receiver<Vec<String>>,
+}
+
+impl UpstreamDetector {
+	/// Start the detection thread, that compares the commits of `hashes` with the commits on `upstream`.
+	pub(crate) fn spawn(upstream: &str, hashes: Vec<String>) -> (Self, thread::JoinHandle<()>) {
+		let cancelled = Arc::new(AtomicBool::new(false));
+		let (result_sender, result_receiver) = mpsc::channel();
+		let thread_cancelled = Arc::clone(&cancelled);
+		let upstream = String::from(upstream);
+
+		let handle = thread::spawn(move || {
+			let is_cancelled = || thread_cancelled.load(Ordering::Acquire);
+			// a failed detection, such as from an upstream that does not exist, is not shown
+			if let Ok(Some(applied)) = Repository::open_from_env()
+				.and_then(|repository| repository.find_applied_upstream(upstream.as_str(), &hashes, is_cancelled))
+			{
+				// the receiver is dropped once the process ends, which is expected when the detection is cancelled
+				let _ = result_sender.send(applied);
+			}
+		});
+
+		(
+			Self {
+				cancelled,
+				result_receiver,
+			},
+			handle,
+		)
+	}
+
+	/// Get the hashes of the commits that are already applied upstream, once the detection completes.
+	pub(crate) fn try_receive(&self) -> Option<Vec<String>> {
+		self.result_receiver.try_recv().ok()
+	}
+
+	/// Cancel the detection, if it is still in progress.
+	pub(crate) fn end(self) {
+		self.cancelled.store(true, Ordering::Release);
+	}
+}
@@ -1,3 +1,9 @@
+use std::{
+	fs::{read_to_string, remove_file, write},
+	path::Path,
+	process::Command,
+};
+
 use config::Config;
 #[cfg(test)]
 use display::testutil::CrossTerm;
@@ -6,24 +12,62 @@ use display::CrossTerm;
 use display::{Display, Tui};
 use git::Repository;
 use input::{EventHandler, KeyBindings};
-use todo_file::TodoFile;
+use todo_file::{Action, TodoFile};
 use view::View;
 
 use crate::{
-	arguments::Args,
+	arguments::{Args, RebaseOptions},
 	exit::Exit,
 	help::build_help,
 	module::{ExitStatus, Modules, State},
-	modules::{ConfirmAbort, ConfirmRebase, Error, ExternalEditor, Insert, List, ShowCommit, WindowSizeError},
+	modules::{
+		CommitPaths,
+		ConfirmAbort,
+		ConfirmRebase,
+		Error,
+		ExternalEditor,
+		Insert,
+		List,
+		SelectedRange,
+		ShowCommit,
+		WindowSizeError,
+	},
 	process::Process,
 };
 
+const REBASE_TODO_FILE_NAME: &str = "interactive-rebase-tool-todo";
+
+fn open_repository() -> Result<Repository, Exit> {
+	Repository::open_from_env().map_err(|err| {
+		Exit::new(
+			ExitStatus::StateError,
+			format!("Unable to load Git repository: {}", err).as_str(),
+		)
+	})
+}
+
+// git runs the sequence editor using a shell, so the path must be quoted
+fn quote_shell_argument(argument: &str) -> String {
+	format!("'{}'", argument.replace('\'', "'\\''"))
+}
+
 pub(super) fn load_config(repo: &Repository) -> Result<Config, Exit> {
 	Config::try_from(repo).map_err(|err| Exit::new(ExitStatus::ConfigError, format!("{:#}", err).as_str()))
 }
 
+// the sequencer, used by `git cherry-pick` and `git revert`, keeps its todo file at `.git/sequencer/todo`
+fn is_sequencer_todo_file(filepath: &str) -> bool {
+	let path = Path::new(filepath);
+	path.file_name().map_or(false, |name| name == "todo")
+		&& path
+			.parent()
+			.and_then(Path::file_name)
+			.map_or(false, |name| name == "sequencer")
+}
+
 pub(super) fn load_todo_file(filepath: &str, config: &Config) -> Result<TodoFile, Exit> {
 	let mut todo_file = TodoFile::new(filepath, config.undo_limit, config.git.comment_char.as_str());
+	todo_file.set_sequencer(is_sequencer_todo_file(filepath));
 	if let Err(err) = todo_file.load_file() {
 		return Err(Exit::new(ExitStatus::FileReadError, err.to_string().as_str()));
 	}
@@ -45,11 +89,192 @@ pub(super) fn load_todo_file(filepath: &str, config: &Config) -> Result<TodoFile
 	Ok(todo_file)
 }
 
-pub(super) fn run_process(todo_file: TodoFile, event_handler: EventHandler, config: &Config) -> Exit {
+// git records the commit that the rebase is onto in the rebase state directory, before the todo file is edited
+fn read_onto(todo_file: &TodoFile) -> Option<String> {
+	Path::new(todo_file.get_filepath())
+		.parent()
+		.and_then(|state_directory| read_to_string(state_directory.join("onto")).ok())
+		.map(|onto| String::from(onto.trim()))
+		.filter(|onto| !onto.is_empty())
+}
+
+// conflicts are predicted by replaying the lines onto the commit that the rebase applies the remaining lines to
+pub(super) fn get_conflict_base(todo_file: &TodoFile, rebase_options: Option<&RebaseOptions>) -> Option<String> {
+	if todo_file.get_progress().is_some() || todo_file.is_sequencer() {
+		return Some(String::from("HEAD"));
+	}
+	if let Some(options) = rebase_options {
+		return Some(String::from(options.onto().unwrap_or_else(|| options.upstream())));
+	}
+	read_onto(todo_file)
+}
+
+// the upstream that commits may already be applied to, which is not known for the sequencer, since it has no upstream
+pub(super) fn get_upstream(todo_file: &TodoFile, rebase_options: Option<&RebaseOptions>) -> Option<String> {
+	if todo_file.is_sequencer() {
+		return None;
+	}
+	rebase_options.map_or_else(|| read_onto(todo_file), |options| Some(String::from(options.upstream())))
+}
+
+// the commits that the rebase rewrites, which for the sequencer are copied rather than rewritten
+pub(super) fn load_rewritten_commit_status(repo: &Repository, todo_file: &mut TodoFile, base: Option<&str>) {
+	if todo_file.is_sequencer() {
+		return;
+	}
+	let hashes = todo_file
+		.iter()
+		.filter(|line| line.has_reference())
+		.map(|line| String::from(line.get_hash()))
+		.collect::<Vec<String>>();
+	// the status only adds a warning, so a failure to load it does not prevent editing
+	if let Ok(published) = repo.find_published_commits(&hashes) {
+		todo_file.set_published(published);
+	}
+	todo_file.set_signed(repo.find_signed_commits(&hashes));
+	// git keeps, rather than rewrites, the leading picks of commits that are already on top of the base
+	if let Some(base) = base {
+		let leading_pick_hashes = todo_file
+			.iter()
+			.take_while(|line| line.get_action() == &Action::Pick)
+			.map(|line| String::from(line.get_hash()))
+			.collect::<Vec<String>>();
+		todo_file.set_unchanged(repo.find_unchanged_commits(base, &leading_pick_hashes));
+	}
+}
+
+// the progress names the commit that the rebase is applied onto by a branch, when a branch points to the commit
+pub(super) fn load_onto_name(repo: &Repository, todo_file: &mut TodoFile) {
+	let onto_name = todo_file
+		.get_progress()
+		.and_then(|progress| progress.get_onto())
+		.and_then(|onto| repo.find_branch_name(onto));
+	if let Some(name) = onto_name {
+		todo_file.set_onto_name(name);
+	}
+}
+
+pub(super) fn create_rebase_todo_file(
+	repo: &Repository,
+	rebase_options: &RebaseOptions,
+	config: &Config,
+) -> Result<TodoFile, Exit> {
+	let lines = repo
+		.create_rebase_todo(rebase_options.upstream(), rebase_options.rebase_merges())
+		.map_err(|err| Exit::new(ExitStatus::StateError, format!("{:#}", err).as_str()))?;
+	if lines.iter().all(|line| line == "noop") {
+		return Err(Exit::new(
+			ExitStatus::Good,
+			format!("There are no commits to rebase onto {}", rebase_options.upstream()).as_str(),
+		));
+	}
+
+	let todo_file_path = repo.get_git_directory().join(REBASE_TODO_FILE_NAME);
+	if let Err(err) = write(&todo_file_path, format!("{}\n", lines.join("\n"))) {
+		return Err(Exit::new(ExitStatus::FileWriteError, err.to_string().as_str()));
+	}
+	let mut todo_file = load_todo_file(todo_file_path.to_string_lossy().as_ref(), config)?;
+	if rebase_options.autosquash() {
+		let _ = todo_file.autosquash();
+	}
+	Ok(todo_file)
+}
+
+pub(super) fn build_rebase_command(rebase_options: &RebaseOptions, todo_file_path: &str) -> Command {
+	let mut command = Command::new("git");
+	let _ = command.args(["rebase", "--interactive"]);
+	if let Some(onto) = rebase_options.onto() {
+		let _ = command.args(["--onto", onto]);
+	}
+	if rebase_options.rebase_merges() {
+		let _ = command.arg("--rebase-merges");
+	}
+	let _ = command.arg(rebase_options.upstream());
+	// the todo file that git generates is replaced with the already edited todo file
+	let _ = command.env(
+		"GIT_SEQUENCE_EDITOR",
+		format!("cp {}", quote_shell_argument(todo_file_path)),
+	);
+	command
+}
+
+fn start_rebase(rebase_options: &RebaseOptions, todo_file_path: &str, config: &Config) -> Exit {
+	let mut todo_file = TodoFile::new(todo_file_path, 1, config.git.comment_char.as_str());
+	if let Err(err) = todo_file.load_file() {
+		return Exit::new(ExitStatus::FileReadError, err.to_string().as_str());
+	}
+	if todo_file.is_empty() {
+		return Exit::new(ExitStatus::Good, "The rebase was aborted");
+	}
+
+	match build_rebase_command(rebase_options, todo_file_path).status() {
+		Ok(status) if status.success() => Exit::from(ExitStatus::Good),
+		Ok(_) => Exit::new(ExitStatus::StateError, "The rebase did not complete"),
+		Err(err) => {
+			Exit::new(
+				ExitStatus::StateError,
+				format!("Unable to run git rebase: {}", err).as_str(),
+			)
+		},
+	}
+}
+
+fn run_rebase(rebase_options: &RebaseOptions) -> Exit {
+	let repo = match open_repository() {
+		Ok(repo) => repo,
+		Err(exit) => return exit,
+	};
+	let config = match load_config(&repo) {
+		Ok(config) => config,
+		Err(exit) => return exit,
+	};
+	let todo_file = match create_rebase_todo_file(&repo, rebase_options, &config) {
+		Ok(todo_file) => todo_file,
+		Err(exit) => return exit,
+	};
+	let todo_file_path = String::from(todo_file.get_filepath());
+	let event_handler = EventHandler::new(CrossTerm::read_event, KeyBindings::new(&config.key_bindings));
+	let exit = run_process(todo_file, event_handler, &config, Some(rebase_options));
+	let exit = if exit.get_status() == &ExitStatus::Good {
+		start_rebase(rebase_options, todo_file_path.as_str(), &config)
+	}
+	else {
+		exit
+	};
+	if Path::new(todo_file_path.as_str()).exists() {
+		let _ = remove_file(todo_file_path.as_str());
+	}
+	exit
+}
+
+pub(super) fn run_process(
+	mut todo_file: TodoFile,
+	event_handler: EventHandler,
+	config: &Config,
+	rebase_options: Option<&RebaseOptions>,
+) -> Exit {
+	let conflict_base = get_conflict_base(&todo_file, rebase_options);
+	let upstream = get_upstream(&todo_file, rebase_options);
+	if let Ok(repo) = Repository::open_from_env() {
+		load_rewritten_commit_status(&repo, &mut todo_file, conflict_base.as_deref());
+		load_onto_name(&repo, &mut todo_file);
+	}
+	let commit_paths = CommitPaths::new(config);
+	let selected_range = SelectedRange::new();
 	let mut modules = Modules::new();
 	modules.register_module(State::Error, Error::new());
-	modules.register_module(State::List, List::new(config));
-	modules.register_module(State::ShowCommit, ShowCommit::new(config));
+	modules.register_module(
+		State::List,
+		List::new(config)
+			.with_commit_paths(commit_paths.clone())
+			.with_selected_range(selected_range.clone()),
+	);
+	modules.register_module(
+		State::ShowCommit,
+		ShowCommit::new(config)
+			.with_commit_paths(commit_paths.clone())
+			.with_selected_range(selected_range),
+	);
 	modules.register_module(State::WindowSizeError, WindowSizeError::new());
 	modules.register_module(
 		State::ConfirmAbort,
@@ -77,6 +302,13 @@ pub(super) fn run_process(todo_file: TodoFile, event_handler: EventHandler, conf
 				.as_str(),
 		),
 	);
+	process.start_commit_paths_loading(commit_paths);
+	if let Some(base) = conflict_base {
+		process.start_conflict_prediction(base.as_str());
+	}
+	if let Some(upstream) = upstream {
+		process.start_upstream_detection(upstream.as_str());
+	}
 	match process.run(modules) {
 		Ok(status) => Exit::from(status),
 		Err(err) => Exit::new(ExitStatus::FileWriteError, err.to_string().as_str()),
@@ -84,15 +316,13 @@ pub(super) fn run_process(todo_file: TodoFile, event_handler: EventHandler, conf
 }
 
 pub(crate) fn run(args: &Args) -> Exit {
-	if let Some(filepath) = args.todo_file_path().as_ref() {
-		let repo = match Repository::open_from_env() {
+	if let Some(rebase_options) = args.rebase_options().as_ref() {
+		run_rebase(rebase_options)
+	}
+	else if let Some(filepath) = args.todo_file_path().as_ref() {
+		let repo = match open_repository() {
 			Ok(repo) => repo,
-			Err(err) => {
-				return Exit::new(
-					ExitStatus::StateError,
-					format!("Unable to load Git repository: {}", err).as_str(),
-				);
-			},
+			Err(exit) => return exit,
 		};
 		let config = match load_config(&repo) {
 			Ok(config) => config,
@@ -103,7 +333,7 @@ pub(crate) fn run(args: &Args) -> Exit {
 			Err(exit) => return exit,
 		};
 		let event_handler = EventHandler::new(CrossTerm::read_event, KeyBindings::new(&config.key_bindings));
-		run_process(todo_file, event_handler, &config)
+		run_process(todo_file, event_handler, &config, None)
 	}
 	else {
 		Exit::new(
@@ -1,13 +1,26 @@
-use std::{env::set_var, fs::File, path::Path};
+use std::{env::set_var, ffi::OsStr, fs::File, path::Path};
 
+use config::Config;
 use display::{testutil::CrossTerm, Tui};
 use git::Repository;
 use input::{Event, EventHandler, KeyBindings, MetaEvent};
+use todo_file::TodoFile;
 
 use super::*;
 use crate::{
+	arguments::Args,
 	module::ExitStatus,
-	run::{load_config, load_todo_file, run_process},
+	run::{
+		build_rebase_command,
+		create_rebase_todo_file,
+		get_conflict_base,
+		get_upstream,
+		load_config,
+		load_onto_name,
+		load_rewritten_commit_status,
+		load_todo_file,
+		run_process,
+	},
 };
 
 fn set_git_directory(repo: &str) -> String {
@@ -138,7 +151,7 @@ fn run_process_error() {
 	let event_handler = EventHandler::new(CrossTerm::read_event, KeyBindings::new(&config.key_bindings));
 	event_handler.push_event(Event::from(MetaEvent::Exit));
 	assert_eq!(
-		run_process(rebase_todo_file, event_handler, &config),
+		run_process(rebase_todo_file, event_handler, &config, None),
 		Exit::new(
 			ExitStatus::FileWriteError,
 			format!("Error opening file: {}", todo_file_path.to_str().unwrap()).as_str()
@@ -157,7 +170,274 @@ fn run_process_success() {
 	let event_handler = EventHandler::new(CrossTerm::read_event, KeyBindings::new(&config.key_bindings));
 	event_handler.push_event(Event::from(MetaEvent::Exit));
 	assert_eq!(
-		run_process(rebase_todo_file, event_handler, &config),
+		run_process(rebase_todo_file, event_handler, &config, None),
 		Exit::from(ExitStatus::Abort)
 	);
 }
+
+fn create_commit(repository: &git2::Repository, summary: &str, parent: Option<git2::Oid>) -> git2::Oid {
+	let signature = git2::Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+	let tree_id = repository.treebuilder(None).unwrap().write().unwrap();
+	let tree = repository.find_tree(tree_id).unwrap();
+	let parent = parent.map(|id| repository.find_commit(id).unwrap());
+	let parents = parent.iter().collect::<Vec<&git2::Commit<'_>>>();
+	repository
+		.commit(Some("HEAD"), &signature, &signature, summary, &tree, &parents)
+		.unwrap()
+}
+
+fn with_rebase_repository<F>(callback: F)
+where F: FnOnce(&Repository, Vec<git2::Oid>) {
+	let directory = tempfile::tempdir().unwrap();
+	let repository = git2::Repository::init(directory.path()).unwrap();
+	let base = create_commit(&repository, "base", None);
+	let first = create_commit(&repository, "first", Some(base));
+	let fixup = create_commit(&repository, "fixup! base", Some(first));
+	let repo = Repository::open_from_path(directory.path()).unwrap();
+	callback(&repo, vec![base, first, fixup]);
+}
+
+fn get_todo_lines(todo_file: &TodoFile) -> Vec<String> {
+	todo_file
+		.iter()
+		.map(|line| format!("{} {}", line.get_action().as_string(), line.get_content()))
+		.collect()
+}
+
+#[test]
+#[serial_test::serial]
+fn create_rebase_todo_file_success() {
+	with_rebase_repository(|repo, commits| {
+		let config = load_config(repo).unwrap();
+		let args = Args::try_from(args(&["--rebase", commits[0].to_string().as_str()])).unwrap();
+		let todo_file = create_rebase_todo_file(repo, args.rebase_options().as_ref().unwrap(), &config).unwrap();
+		assert_eq!(get_todo_lines(&todo_file), vec!["pick first", "pick fixup! base"]);
+		assert!(todo_file.get_filepath().ends_with("interactive-rebase-tool-todo"));
+	});
+}
+
+#[test]
+#[serial_test::serial]
+fn create_rebase_todo_file_autosquash() {
+	with_rebase_repository(|repo, commits| {
+		let config = load_config(repo).unwrap();
+		let args = Args::try_from(args(&["--autosquash", "--rebase", commits[1].to_string().as_str()])).unwrap();
+		let todo_file = create_rebase_todo_file(repo, args.rebase_options().as_ref().unwrap(), &config).unwrap();
+		assert_eq!(get_todo_lines(&todo_file), vec!["pick fixup! base"]);
+	});
+}
+
+#[test]
+#[serial_test::serial]
+fn create_rebase_todo_file_no_commits() {
+	with_rebase_repository(|repo, _| {
+		let config = load_config(repo).unwrap();
+		let args = Args::try_from(args(&["--rebase", "HEAD"])).unwrap();
+		assert_eq!(
+			create_rebase_todo_file(repo, args.rebase_options().as_ref().unwrap(), &config).unwrap_err(),
+			Exit::new(ExitStatus::Good, "There are no commits to rebase onto HEAD")
+		);
+	});
+}
+
+#[test]
+#[serial_test::serial]
+fn create_rebase_todo_file_invalid_upstream() {
+	with_rebase_repository(|repo, _| {
+		let config = load_config(repo).unwrap();
+		let args = Args::try_from(args(&["--rebase", "does-not-exist"])).unwrap();
+		let exit = create_rebase_todo_file(repo, args.rebase_options().as_ref().unwrap(), &config).unwrap_err();
+		assert_eq!(exit.get_status(), &ExitStatus::StateError);
+		assert!(exit
+			.get_message()
+			.as_ref()
+			.unwrap()
+			.starts_with("Unable to resolve upstream: does-not-exist"));
+	});
+}
+
+#[test]
+fn build_rebase_command_arguments() {
+	let args = Args::try_from(args(&["--rebase", "main", "--onto", "next", "--rebase-merges"])).unwrap();
+	let command = build_rebase_command(args.rebase_options().as_ref().unwrap(), "/tmp/it's-todo");
+	assert_eq!(command.get_program(), "git");
+	assert_eq!(command.get_args().collect::<Vec<&OsStr>>(), vec![
+		"rebase",
+		"--interactive",
+		"--onto",
+		"next",
+		"--rebase-merges",
+		"main"
+	]);
+	assert_eq!(command.get_envs().collect::<Vec<(&OsStr, Option<&OsStr>)>>(), vec![(
+		OsStr::new("GIT_SEQUENCE_EDITOR"),
+		Some(OsStr::new("cp '/tmp/it'\\''s-todo'"))
+	)]);
+}
+
+#[test]
+fn load_todo_file_sequencer() {
+	let directory = tempfile::tempdir().unwrap();
+	let sequencer_directory = directory.path().join("sequencer");
+	std::fs::create_dir(&sequencer_directory).unwrap();
+	let todo_file_path = sequencer_directory.join("todo");
+	std::fs::write(&todo_file_path, "revert aaa c1\n").unwrap();
+	let todo_file = load_todo_file(todo_file_path.to_str().unwrap(), &Config::new()).unwrap();
+	assert!(todo_file.is_sequencer());
+}
+
+#[test]
+#[serial_test::serial]
+fn load_todo_file_not_sequencer() {
+	let path = set_git_directory("fixtures/simple");
+	let todo_file_path = Path::new(path.as_str()).join("rebase-todo");
+	let todo_file = load_todo_file(todo_file_path.to_str().unwrap(), &Config::new()).unwrap();
+	assert!(!todo_file.is_sequencer());
+}
+
+#[test]
+fn get_conflict_base_rebase_options() {
+	let args = Args::try_from(args(&["--rebase", "main"])).unwrap();
+	let todo_file = TodoFile::new("todo", 1, "#");
+	assert_eq!(
+		get_conflict_base(&todo_file, args.rebase_options().as_ref()),
+		Some(String::from("main"))
+	);
+}
+
+#[test]
+fn get_conflict_base_rebase_options_onto() {
+	let args = Args::try_from(args(&["--rebase", "main", "--onto", "next"])).unwrap();
+	let todo_file = TodoFile::new("todo", 1, "#");
+	assert_eq!(
+		get_conflict_base(&todo_file, args.rebase_options().as_ref()),
+		Some(String::from("next"))
+	);
+}
+
+#[test]
+fn get_conflict_base_onto_file() {
+	let directory = tempfile::tempdir().unwrap();
+	std::fs::write(directory.path().join("onto"), "0123456789abcdef\n").unwrap();
+	let todo_file = TodoFile::new(directory.path().join("git-rebase-todo").to_str().unwrap(), 1, "#");
+	assert_eq!(
+		get_conflict_base(&todo_file, None),
+		Some(String::from("0123456789abcdef"))
+	);
+}
+
+#[test]
+fn get_conflict_base_sequencer() {
+	let mut todo_file = TodoFile::new("todo", 1, "#");
+	todo_file.set_sequencer(true);
+	assert_eq!(get_conflict_base(&todo_file, None), Some(String::from("HEAD")));
+}
+
+#[test]
+fn get_conflict_base_unknown() {
+	let directory = tempfile::tempdir().unwrap();
+	let todo_file = TodoFile::new(directory.path().join("git-rebase-todo").to_str().unwrap(), 1, "#");
+	assert_eq!(get_conflict_base(&todo_file, None), None);
+}
+
+#[test]
+fn get_upstream_rebase_options() {
+	let args = Args::try_from(args(&["--rebase", "main", "--onto", "next"])).unwrap();
+	let todo_file = TodoFile::new("todo", 1, "#");
+	assert_eq!(
+		get_upstream(&todo_file, args.rebase_options().as_ref()),
+		Some(String::from("main"))
+	);
+}
+
+#[test]
+fn get_upstream_onto_file() {
+	let directory = tempfile::tempdir().unwrap();
+	std::fs::write(directory.path().join("onto"), "0123456789abcdef\n").unwrap();
+	let todo_file = TodoFile::new(directory.path().join("git-rebase-todo").to_str().unwrap(), 1, "#");
+	assert_eq!(get_upstream(&todo_file, None), Some(String::from("0123456789abcdef")));
+}
+
+#[test]
+fn get_upstream_sequencer() {
+	let mut todo_file = TodoFile::new("todo", 1, "#");
+	todo_file.set_sequencer(true);
+	assert_eq!(get_upstream(&todo_file, None), None);
+}
+
+fn create_published_commit_todo_file(sequencer: bool, with_base: bool) -> TodoFile {
+	let directory = tempfile::tempdir().unwrap();
+	let repository = git2::Repository::init(directory.path()).unwrap();
+	let (base, published) = {
+		let signature = git2::Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+		let tree = repository
+			.find_tree(repository.treebuilder(None).unwrap().write().unwrap())
+			.unwrap();
+		let base = repository
+			.commit(None, &signature, &signature, "base", &tree, &[])
+			.unwrap();
+		let base_commit = repository.find_commit(base).unwrap();
+		let published = repository
+			.commit(None, &signature, &signature, "published", &tree, &[&base_commit])
+			.unwrap();
+		(base, published)
+	};
+	let _ = repository
+		.reference("refs/remotes/origin/main", published, false, "push")
+		.unwrap();
+	let mut todo_file = TodoFile::new("todo", 1, "#");
+	todo_file.set_sequencer(sequencer);
+	todo_file.set_lines(vec![todo_file::Line::new(
+		format!("pick {} published", published).as_str(),
+	)
+	.unwrap()]);
+	let base = with_base.then(|| base.to_string());
+	load_rewritten_commit_status(&Repository::from(repository), &mut todo_file, base.as_deref());
+	todo_file
+}
+
+#[test]
+fn load_rewritten_commit_status_published() {
+	let todo_file = create_published_commit_todo_file(false, false);
+	let line = todo_file.get_line(0).unwrap();
+	assert!(todo_file.is_published(line));
+	assert!(!todo_file.is_signed(line));
+	assert!(todo_file.is_rewritten(0));
+}
+
+#[test]
+fn load_rewritten_commit_status_unchanged() {
+	let todo_file = create_published_commit_todo_file(false, true);
+	assert!(todo_file.is_published(todo_file.get_line(0).unwrap()));
+	assert!(!todo_file.is_rewritten(0));
+}
+
+#[test]
+fn load_rewritten_commit_status_sequencer() {
+	let todo_file = create_published_commit_todo_file(true, false);
+	assert!(!todo_file.is_published(todo_file.get_line(0).unwrap()));
+}
+
+#[test]
+fn load_onto_name_branch() {
+	let directory = tempfile::tempdir().unwrap();
+	let repository = git2::Repository::init(directory.path().join("repository")).unwrap();
+	let onto = {
+		let signature = git2::Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+		let tree = repository
+			.find_tree(repository.treebuilder(None).unwrap().write().unwrap())
+			.unwrap();
+		repository
+			.commit(None, &signature, &signature, "onto", &tree, &[])
+			.unwrap()
+	};
+	let _ = repository.reference("refs/heads/main", onto, false, "branch").unwrap();
+	let todo_file_path = directory.path().join("git-rebase-todo");
+	std::fs::write(&todo_file_path, "pick bbb c2\n").unwrap();
+	std::fs::write(directory.path().join("done"), "pick aaa c1\n").unwrap();
+	std::fs::write(directory.path().join("onto"), format!("{}\n", onto)).unwrap();
+	let mut todo_file = TodoFile::new(todo_file_path.to_str().unwrap(), 1, "#");
+	todo_file.load_file().unwrap();
+	load_onto_name(&Repository::from(repository), &mut todo_file);
+	assert_eq!(todo_file.get_progress().unwrap().get_onto_name(), Some("main"));
+}
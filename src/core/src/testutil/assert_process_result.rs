@@ -126,6 +126,9 @@ macro_rules! assert_process_result {
 	($actual:expr, event = $event:expr, state = $state:expr) => {
 		crate::testutil::_assert_process_result(&$actual, Some($event), Some($state), None, &None, &None)
 	};
+	($actual:expr, event = $event:expr, error = $error:expr) => {
+		crate::testutil::_assert_process_result(&$actual, Some($event), None, None, &Some($error), &None)
+	};
 	($actual:expr, event = $event:expr, exit_status = $exit_status:expr) => {
 		crate::testutil::_assert_process_result(&$actual, Some($event), None, Some($exit_status), &None, &None)
 	};
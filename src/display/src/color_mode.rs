@@ -24,6 +24,13 @@ impl ColorMode {
 		self == FourBit || self == EightBit || self == TrueColor
 	}
 
+	/// Supports 8 bit or more of color.
+	#[inline]
+	#[must_use]
+	pub fn has_minimum_eight_bit_color(self) -> bool {
+		self == EightBit || self == TrueColor
+	}
+
 	/// Has true color support.
 	#[inline]
 	#[must_use]
@@ -62,6 +69,31 @@ mod tests {
 		assert!(TrueColor.has_minimum_four_bit_color());
 	}
 
+	#[test]
+	fn color_mode_has_minimum_eight_bit_color_two_tone() {
+		assert!(!TwoTone.has_minimum_eight_bit_color());
+	}
+
+	#[test]
+	fn color_mode_has_minimum_eight_bit_color_three_bit() {
+		assert!(!ThreeBit.has_minimum_eight_bit_color());
+	}
+
+	#[test]
+	fn color_mode_has_minimum_eight_bit_color_four_bit() {
+		assert!(!FourBit.has_minimum_eight_bit_color());
+	}
+
+	#[test]
+	fn color_mode_has_minimum_eight_bit_color_eight_bit() {
+		assert!(EightBit.has_minimum_eight_bit_color());
+	}
+
+	#[test]
+	fn color_mode_has_minimum_eight_bit_color_true_color() {
+		assert!(TrueColor.has_minimum_eight_bit_color());
+	}
+
 	#[test]
 	fn color_mode_has_true_color_two_tone() {
 		assert!(!TwoTone.has_true_color());
@@ -24,18 +24,36 @@ pub enum DisplayColor {
 	ActionReset,
 	/// The color for the merge action.
 	ActionMerge,
+	/// The color for the update-ref action.
+	ActionUpdateRef,
+	/// The color for the revert action.
+	ActionRevert,
 	/// The color for added lines in a diff.
 	DiffAddColor,
+	/// The color for the highlighted changes within added lines in a diff.
+	DiffAddHighlightColor,
 	/// The color for changed lines in a diff.
 	DiffChangeColor,
 	/// The color for removed lines in a diff.
 	DiffRemoveColor,
+	/// The color for the highlighted changes within removed lines in a diff.
+	DiffRemoveHighlightColor,
 	/// The color for context lines in a diff.
 	DiffContextColor,
 	/// The color for whitespace characters in a diff.
 	DiffWhitespaceColor,
+	/// The color for comments in the syntax highlighting of a diff.
+	DiffSyntaxCommentColor,
+	/// The color for keywords in the syntax highlighting of a diff.
+	DiffSyntaxKeywordColor,
+	/// The color for numbers in the syntax highlighting of a diff.
+	DiffSyntaxNumberColor,
+	/// The color for strings in the syntax highlighting of a diff.
+	DiffSyntaxStringColor,
 	/// The color for indicator text.
 	IndicatorColor,
 	/// The color for the standard text.
 	Normal,
+	/// The color for warnings.
+	WarningColor,
 }
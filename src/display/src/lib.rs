@@ -141,14 +141,23 @@ pub struct Display<T: Tui> {
 	action_reset: (Colors, Colors),
 	action_reword: (Colors, Colors),
 	action_squash: (Colors, Colors),
+	action_update_ref: (Colors, Colors),
+	action_revert: (Colors, Colors),
 	tui: T,
 	diff_add: (Colors, Colors),
+	diff_add_highlight: (Colors, Colors),
 	diff_change: (Colors, Colors),
 	diff_context: (Colors, Colors),
 	diff_remove: (Colors, Colors),
+	diff_remove_highlight: (Colors, Colors),
+	diff_syntax_comment: (Colors, Colors),
+	diff_syntax_keyword: (Colors, Colors),
+	diff_syntax_number: (Colors, Colors),
+	diff_syntax_string: (Colors, Colors),
 	diff_whitespace: (Colors, Colors),
 	indicator: (Colors, Colors),
 	normal: (Colors, Colors),
+	warning: (Colors, Colors),
 }
 
 impl<T: Tui> Display<T> {
@@ -168,6 +177,12 @@ impl<T: Tui> Display<T> {
 			theme.color_background,
 			theme.color_selected_background,
 		);
+		let warning = register_selectable_color_pairs(
+			color_mode,
+			theme.color_warning,
+			theme.color_background,
+			theme.color_selected_background,
+		);
 		let action_break = register_selectable_color_pairs(
 			color_mode,
 			theme.color_action_break,
@@ -234,12 +249,30 @@ impl<T: Tui> Display<T> {
 			theme.color_background,
 			theme.color_selected_background,
 		);
+		let action_update_ref = register_selectable_color_pairs(
+			color_mode,
+			theme.color_action_update_ref,
+			theme.color_background,
+			theme.color_selected_background,
+		);
+		let action_revert = register_selectable_color_pairs(
+			color_mode,
+			theme.color_action_revert,
+			theme.color_background,
+			theme.color_selected_background,
+		);
 		let diff_add = register_selectable_color_pairs(
 			color_mode,
 			theme.color_diff_add,
 			theme.color_background,
 			theme.color_selected_background,
 		);
+		let diff_add_highlight = register_selectable_color_pairs(
+			color_mode,
+			theme.color_diff_add_highlight,
+			theme.color_background,
+			theme.color_selected_background,
+		);
 		let diff_change = register_selectable_color_pairs(
 			color_mode,
 			theme.color_diff_change,
@@ -252,6 +285,12 @@ impl<T: Tui> Display<T> {
 			theme.color_background,
 			theme.color_selected_background,
 		);
+		let diff_remove_highlight = register_selectable_color_pairs(
+			color_mode,
+			theme.color_diff_remove_highlight,
+			theme.color_background,
+			theme.color_selected_background,
+		);
 		let diff_context = register_selectable_color_pairs(
 			color_mode,
 			theme.color_diff_context,
@@ -264,6 +303,25 @@ impl<T: Tui> Display<T> {
 			theme.color_background,
 			theme.color_selected_background,
 		);
+		// with fewer colors, the syntax colors cannot be told apart from the diff colors, so context lines keep the
+		// context color
+		let register_syntax_color_pairs = |color| {
+			if color_mode.has_minimum_eight_bit_color() {
+				register_selectable_color_pairs(
+					color_mode,
+					color,
+					theme.color_background,
+					theme.color_selected_background,
+				)
+			}
+			else {
+				diff_context
+			}
+		};
+		let diff_syntax_comment = register_syntax_color_pairs(theme.color_diff_syntax_comment);
+		let diff_syntax_keyword = register_syntax_color_pairs(theme.color_diff_syntax_keyword);
+		let diff_syntax_number = register_syntax_color_pairs(theme.color_diff_syntax_number);
+		let diff_syntax_string = register_syntax_color_pairs(theme.color_diff_syntax_string);
 
 		Self {
 			action_break,
@@ -277,14 +335,23 @@ impl<T: Tui> Display<T> {
 			action_reset,
 			action_reword,
 			action_squash,
+			action_update_ref,
+			action_revert,
 			tui,
 			diff_add,
+			diff_add_highlight,
 			diff_change,
 			diff_context,
 			diff_remove,
+			diff_remove_highlight,
+			diff_syntax_comment,
+			diff_syntax_keyword,
+			diff_syntax_number,
+			diff_syntax_string,
 			diff_whitespace,
 			indicator,
 			normal,
+			warning,
 		}
 	}
 
@@ -328,13 +395,22 @@ impl<T: Tui> Display<T> {
 					DisplayColor::ActionLabel => self.action_label.1,
 					DisplayColor::ActionReset => self.action_reset.1,
 					DisplayColor::ActionMerge => self.action_merge.1,
+					DisplayColor::ActionUpdateRef => self.action_update_ref.1,
+					DisplayColor::ActionRevert => self.action_revert.1,
 					DisplayColor::Normal => self.normal.1,
 					DisplayColor::IndicatorColor => self.indicator.1,
 					DisplayColor::DiffAddColor => self.diff_add.1,
+					DisplayColor::DiffAddHighlightColor => self.diff_add_highlight.1,
 					DisplayColor::DiffRemoveColor => self.diff_remove.1,
+					DisplayColor::DiffRemoveHighlightColor => self.diff_remove_highlight.1,
 					DisplayColor::DiffChangeColor => self.diff_change.1,
 					DisplayColor::DiffContextColor => self.diff_context.1,
 					DisplayColor::DiffWhitespaceColor => self.diff_whitespace.1,
+					DisplayColor::DiffSyntaxCommentColor => self.diff_syntax_comment.1,
+					DisplayColor::DiffSyntaxKeywordColor => self.diff_syntax_keyword.1,
+					DisplayColor::DiffSyntaxNumberColor => self.diff_syntax_number.1,
+					DisplayColor::DiffSyntaxStringColor => self.diff_syntax_string.1,
+					DisplayColor::WarningColor => self.warning.1,
 				}
 			}
 			else {
@@ -350,13 +426,22 @@ impl<T: Tui> Display<T> {
 					DisplayColor::ActionLabel => self.action_label.0,
 					DisplayColor::ActionReset => self.action_reset.0,
 					DisplayColor::ActionMerge => self.action_merge.0,
+					DisplayColor::ActionUpdateRef => self.action_update_ref.0,
+					DisplayColor::ActionRevert => self.action_revert.0,
 					DisplayColor::Normal => self.normal.0,
 					DisplayColor::IndicatorColor => self.indicator.0,
 					DisplayColor::DiffAddColor => self.diff_add.0,
+					DisplayColor::DiffAddHighlightColor => self.diff_add_highlight.0,
 					DisplayColor::DiffRemoveColor => self.diff_remove.0,
+					DisplayColor::DiffRemoveHighlightColor => self.diff_remove_highlight.0,
 					DisplayColor::DiffChangeColor => self.diff_change.0,
 					DisplayColor::DiffContextColor => self.diff_context.0,
 					DisplayColor::DiffWhitespaceColor => self.diff_whitespace.0,
+					DisplayColor::DiffSyntaxCommentColor => self.diff_syntax_comment.0,
+					DisplayColor::DiffSyntaxKeywordColor => self.diff_syntax_keyword.0,
+					DisplayColor::DiffSyntaxNumberColor => self.diff_syntax_number.0,
+					DisplayColor::DiffSyntaxStringColor => self.diff_syntax_string.0,
+					DisplayColor::WarningColor => self.warning.0,
 				}
 			},
 		)
@@ -531,6 +616,25 @@ mod tests {
 		CrosstermColor::DarkYellow,
 		CrosstermColor::AnsiValue(237)
 	)]
+	#[case::action_update_ref(
+		DisplayColor::ActionUpdateRef,
+		false,
+		CrosstermColor::DarkMagenta,
+		CrosstermColor::Reset
+	)]
+	#[case::action_update_ref_selected(
+		DisplayColor::ActionUpdateRef,
+		true,
+		CrosstermColor::DarkMagenta,
+		CrosstermColor::AnsiValue(237)
+	)]
+	#[case::action_revert(DisplayColor::ActionRevert, false, CrosstermColor::DarkRed, CrosstermColor::Reset)]
+	#[case::action_revert_selected(
+		DisplayColor::ActionRevert,
+		true,
+		CrosstermColor::DarkRed,
+		CrosstermColor::AnsiValue(237)
+	)]
 	#[case::normal(DisplayColor::Normal, false, CrosstermColor::Reset, CrosstermColor::Reset)]
 	#[case::normal_selected(DisplayColor::Normal, true, CrosstermColor::Reset, CrosstermColor::AnsiValue(237))]
 	#[case::indicator(DisplayColor::IndicatorColor, false, CrosstermColor::Cyan, CrosstermColor::Reset)]
@@ -547,6 +651,18 @@ mod tests {
 		CrosstermColor::Green,
 		CrosstermColor::AnsiValue(237)
 	)]
+	#[case::diff_add_highlight(
+		DisplayColor::DiffAddHighlightColor,
+		false,
+		CrosstermColor::Green,
+		CrosstermColor::Reset
+	)]
+	#[case::diff_add_highlight_selected(
+		DisplayColor::DiffAddHighlightColor,
+		true,
+		CrosstermColor::Green,
+		CrosstermColor::AnsiValue(237)
+	)]
 	#[case::diff_remove(DisplayColor::DiffRemoveColor, false, CrosstermColor::Red, CrosstermColor::Reset)]
 	#[case::diff_remove_selected(
 		DisplayColor::DiffRemoveColor,
@@ -554,6 +670,18 @@ mod tests {
 		CrosstermColor::Red,
 		CrosstermColor::AnsiValue(237)
 	)]
+	#[case::diff_remove_highlight(
+		DisplayColor::DiffRemoveHighlightColor,
+		false,
+		CrosstermColor::Red,
+		CrosstermColor::Reset
+	)]
+	#[case::diff_remove_highlight_selected(
+		DisplayColor::DiffRemoveHighlightColor,
+		true,
+		CrosstermColor::Red,
+		CrosstermColor::AnsiValue(237)
+	)]
 	#[case::diff_change(DisplayColor::DiffChangeColor, false, CrosstermColor::Yellow, CrosstermColor::Reset)]
 	#[case::diff_change_selected(
 		DisplayColor::DiffChangeColor,
@@ -580,6 +708,17 @@ mod tests {
 		CrosstermColor::DarkGrey,
 		CrosstermColor::AnsiValue(237)
 	)]
+	#[case::diff_syntax_comment(DisplayColor::DiffSyntaxCommentColor, false, CrosstermColor::White, CrosstermColor::Reset)]
+	#[case::diff_syntax_keyword(DisplayColor::DiffSyntaxKeywordColor, false, CrosstermColor::White, CrosstermColor::Reset)]
+	#[case::diff_syntax_number(DisplayColor::DiffSyntaxNumberColor, false, CrosstermColor::White, CrosstermColor::Reset)]
+	#[case::diff_syntax_string(DisplayColor::DiffSyntaxStringColor, false, CrosstermColor::White, CrosstermColor::Reset)]
+	#[case::warning(DisplayColor::WarningColor, false, CrosstermColor::Red, CrosstermColor::Reset)]
+	#[case::warning_selected(
+		DisplayColor::WarningColor,
+		true,
+		CrosstermColor::Red,
+		CrosstermColor::AnsiValue(237)
+	)]
 	fn color(
 		#[case] display_color: DisplayColor,
 		#[case] selected: bool,
@@ -593,6 +732,17 @@ mod tests {
 			.is_colors_enabled(Colors::new(expected_foreground, expected_background)));
 	}
 
+	#[test]
+	fn color_syntax_with_eight_bit_color() {
+		let mut crossterm = CrossTerm::new();
+		crossterm.set_color_mode(ColorMode::EightBit);
+		let mut display = Display::new(crossterm, &Theme::new());
+		display.color(DisplayColor::DiffSyntaxKeywordColor, false).unwrap();
+		assert!(display
+			.tui
+			.is_colors_enabled(Colors::new(CrosstermColor::Magenta, CrosstermColor::Reset)));
+	}
+
 	#[rstest]
 	#[case::all_off(false, false, false)]
 	#[case::reverse(false, false, true)]
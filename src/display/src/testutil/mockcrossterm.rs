@@ -139,6 +139,12 @@ impl CrossTerm {
 		}
 	}
 
+	/// Set the color mode of the terminal.
+	#[inline]
+	pub fn set_color_mode(&mut self, color_mode: ColorMode) {
+		self.color_mode = color_mode;
+	}
+
 	/// Get a representation of the rendered output.
 	#[inline]
 	#[must_use]
@@ -0,0 +1,88 @@
+use git2::{BranchType, Repository};
+
+/// Find the name of a branch whose tip is the commit of `hash`, preferring local branches over remote-tracking
+/// branches. Returns `None` if `hash` cannot be resolved to a commit, or no branch points to the commit.
+pub(crate) fn find_branch_name(repository: &Repository, hash: &str) -> Option<String> {
+	let id = repository
+		.revparse_single(hash)
+		.and_then(|object| object.peel_to_commit())
+		.ok()?
+		.id();
+	[BranchType::Local, BranchType::Remote]
+		.into_iter()
+		.filter_map(|branch_type| repository.branches(Some(branch_type)).ok())
+		.flatten()
+		.filter_map(Result::ok)
+		// symbolic references, such as `refs/remotes/origin/HEAD`, are named after the branch they point to instead
+		.filter(|(branch, _)| branch.get().symbolic_target().is_none())
+		.find(|(branch, _)| branch.get().peel_to_commit().is_ok_and(|commit| commit.id() == id))
+		.and_then(|(branch, _)| branch.name().ok().flatten().map(String::from))
+}
+
+#[cfg(test)]
+mod tests {
+	use git2::{Oid, Signature};
+	use tempfile::tempdir;
+
+	use super::*;
+
+	fn commit(repository: &Repository, summary: &str) -> Oid {
+		let signature = Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+		let tree = repository
+			.find_tree(repository.treebuilder(None).unwrap().write().unwrap())
+			.unwrap();
+		repository
+			.commit(None, &signature, &signature, summary, &tree, &[])
+			.unwrap()
+	}
+
+	fn with_repository<F>(callback: F)
+	where F: FnOnce(&Repository) {
+		let directory = tempdir().unwrap();
+		let repository = Repository::init(directory.path()).unwrap();
+		callback(&repository);
+	}
+
+	#[test]
+	fn local_branch() {
+		with_repository(|repository| {
+			let onto = commit(repository, "onto");
+			let _ = repository
+				.reference("refs/remotes/origin/main", onto, false, "push")
+				.unwrap();
+			let _ = repository.reference("refs/heads/main", onto, false, "branch").unwrap();
+			assert_eq!(
+				find_branch_name(repository, onto.to_string().as_str()),
+				Some(String::from("main"))
+			);
+		});
+	}
+
+	#[test]
+	fn remote_branch() {
+		with_repository(|repository| {
+			let onto = commit(repository, "onto");
+			let _ = repository
+				.reference("refs/remotes/origin/main", onto, false, "push")
+				.unwrap();
+			let _ = repository
+				.reference_symbolic("refs/remotes/origin/HEAD", "refs/remotes/origin/main", false, "head")
+				.unwrap();
+			assert_eq!(
+				find_branch_name(repository, onto.to_string().as_str()),
+				Some(String::from("origin/main"))
+			);
+		});
+	}
+
+	#[test]
+	fn no_branch() {
+		with_repository(|repository| {
+			let onto = commit(repository, "onto");
+			let other = commit(repository, "other");
+			let _ = repository.reference("refs/heads/main", other, false, "branch").unwrap();
+			assert_eq!(find_branch_name(repository, onto.to_string().as_str()), None);
+			assert_eq!(find_branch_name(repository, "does-not-exist"), None);
+		});
+	}
+}
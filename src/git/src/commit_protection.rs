@@ -0,0 +1,258 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use git2::{Commit, Oid, Repository};
+
+use crate::rebase_todo::map_git_error;
+
+fn find_commits<'repo>(repository: &'repo Repository, hashes: &'repo [String]) -> Vec<(&'repo String, Commit<'repo>)> {
+	hashes
+		.iter()
+		.filter_map(|hash| {
+			repository
+				.revparse_single(hash)
+				.and_then(|object| object.peel_to_commit())
+				.ok()
+				.map(|commit| (hash, commit))
+		})
+		.collect()
+}
+
+fn get_remote_tips(repository: &Repository) -> Result<Vec<Oid>> {
+	let mut tips = vec![];
+	for remote_reference in repository
+		.references_glob("refs/remotes/*")
+		.map_err(|err| map_git_error(&err))?
+	{
+		// symbolic references, such as `refs/remotes/origin/HEAD`, point to a tip that is already listed
+		let tip_id = remote_reference
+			.and_then(|reference| reference.resolve())
+			.and_then(|resolved| resolved.peel_to_commit())
+			.map(|commit| commit.id());
+		if let Ok(tip) = tip_id {
+			if !tips.contains(&tip) {
+				tips.push(tip);
+			}
+		}
+	}
+	Ok(tips)
+}
+
+/// Find the commits of `hashes` that are reachable from a remote-tracking branch, and so have been published. Hashes
+/// that cannot be resolved to a commit are ignored.
+pub(crate) fn find_published_commits(repository: &Repository, hashes: &[String]) -> Result<Vec<String>> {
+	let tips = get_remote_tips(repository)?;
+	let commits = find_commits(repository, hashes);
+	if tips.is_empty() || commits.is_empty() {
+		return Ok(vec![]);
+	}
+	let ids = commits.iter().map(|(_, commit)| commit.id()).collect::<HashSet<Oid>>();
+
+	// a single walk from the remote-tracking branches finds every published commit, and the walk is stopped at the
+	// commits that the commits are based on, since no commit beyond them can be one of the commits
+	let mut revwalk = repository.revwalk().map_err(|err| map_git_error(&err))?;
+	for tip in tips {
+		revwalk.push(tip).map_err(|err| map_git_error(&err))?;
+	}
+	for (_, commit) in &commits {
+		for parent in commit.parent_ids().filter(|parent| !ids.contains(parent)) {
+			revwalk.hide(parent).map_err(|err| map_git_error(&err))?;
+		}
+	}
+	let mut reachable = HashSet::new();
+	for revwalk_id in revwalk {
+		let id = revwalk_id.map_err(|err| map_git_error(&err))?;
+		if ids.contains(&id) {
+			let _ = reachable.insert(id);
+			if reachable.len() == ids.len() {
+				break;
+			}
+		}
+	}
+	Ok(commits
+		.into_iter()
+		.filter(|(_, commit)| reachable.contains(&commit.id()))
+		.map(|(hash, _)| hash.clone())
+		.collect())
+}
+
+/// Find the leading commits of `hashes` that git keeps, rather than rewrites, when the commits are picked onto `base`
+/// in order, because the parent of each commit is the commit before it, with the parent of the first being `base`.
+pub(crate) fn find_unchanged_commits(repository: &Repository, base: &str, hashes: &[String]) -> Vec<String> {
+	let mut head = match repository
+		.revparse_single(base)
+		.and_then(|object| object.peel_to_commit())
+	{
+		Ok(commit) => commit.id(),
+		Err(_) => return vec![],
+	};
+	let mut unchanged = vec![];
+	for hash in hashes {
+		let commit = match repository
+			.revparse_single(hash)
+			.and_then(|object| object.peel_to_commit())
+		{
+			Ok(commit) => commit,
+			Err(_) => break,
+		};
+		if commit.parent_count() != 1 || commit.parent_id(0).ok() != Some(head) {
+			break;
+		}
+		head = commit.id();
+		unchanged.push(hash.clone());
+	}
+	unchanged
+}
+
+/// Find the commits of `hashes` that carry a GPG or SSH signature, which is lost when the commit is rewritten. Hashes
+/// that cannot be resolved to a commit are ignored.
+pub(crate) fn find_signed_commits(repository: &Repository, hashes: &[String]) -> Vec<String> {
+	find_commits(repository, hashes)
+		.into_iter()
+		.filter(|(_, commit)| commit.header_field_bytes("gpgsig").is_ok())
+		.map(|(hash, _)| hash.clone())
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use git2::Signature;
+	use tempfile::tempdir;
+
+	use super::*;
+
+	fn create_signature() -> Signature<'static> {
+		Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap()
+	}
+
+	fn commit(repository: &Repository, summary: &str, parent: Option<Oid>) -> Oid {
+		let signature = create_signature();
+		let tree = repository
+			.find_tree(repository.treebuilder(None).unwrap().write().unwrap())
+			.unwrap();
+		let parent_commit = parent.map(|id| repository.find_commit(id).unwrap());
+		let parents = parent_commit.iter().collect::<Vec<&Commit<'_>>>();
+		repository
+			.commit(None, &signature, &signature, summary, &tree, &parents)
+			.unwrap()
+	}
+
+	fn with_repository<F>(callback: F)
+	where F: FnOnce(&Repository) {
+		let directory = tempdir().unwrap();
+		let repository = Repository::init(directory.path()).unwrap();
+		callback(&repository);
+	}
+
+	#[test]
+	fn published_from_remote_tip() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", None);
+			let pushed = commit(repository, "pushed", Some(base));
+			let local = commit(repository, "local", Some(pushed));
+			let _ = repository
+				.reference("refs/remotes/origin/main", pushed, false, "push")
+				.unwrap();
+			let _ = repository
+				.reference_symbolic("refs/remotes/origin/HEAD", "refs/remotes/origin/main", false, "head")
+				.unwrap();
+			let hashes = vec![base.to_string(), pushed.to_string(), local.to_string()];
+			assert_eq!(find_published_commits(repository, &hashes).unwrap(), vec![
+				base.to_string(),
+				pushed.to_string()
+			]);
+		});
+	}
+
+	#[test]
+	fn published_from_remote_tip_with_base() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", None);
+			let pushed = commit(repository, "pushed", Some(base));
+			let local = commit(repository, "local", Some(pushed));
+			let _ = repository
+				.reference("refs/remotes/origin/feature", local, false, "push")
+				.unwrap();
+			let _ = repository
+				.reference("refs/remotes/origin/main", base, false, "push")
+				.unwrap();
+			let hashes = vec![pushed.to_string(), local.to_string()];
+			assert_eq!(find_published_commits(repository, &hashes).unwrap(), vec![
+				pushed.to_string(),
+				local.to_string()
+			]);
+		});
+	}
+
+	#[test]
+	fn published_remote_tip_not_reachable() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", None);
+			let local = commit(repository, "local", Some(base));
+			let other = commit(repository, "other", Some(base));
+			let _ = repository
+				.reference("refs/remotes/origin/main", other, false, "push")
+				.unwrap();
+			assert!(find_published_commits(repository, &[local.to_string()])
+				.unwrap()
+				.is_empty());
+		});
+	}
+
+	#[test]
+	fn published_without_remotes() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", None);
+			let hashes = vec![base.to_string(), String::from("does-not-exist")];
+			assert!(find_published_commits(repository, &hashes).unwrap().is_empty());
+		});
+	}
+
+	#[test]
+	fn unchanged_leading_commits() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", None);
+			let first = commit(repository, "first", Some(base));
+			let second = commit(repository, "second", Some(first));
+			let third = commit(repository, "third", Some(second));
+			let hashes = vec![first.to_string(), third.to_string(), second.to_string()];
+			assert_eq!(
+				find_unchanged_commits(repository, base.to_string().as_str(), &hashes),
+				vec![first.to_string()]
+			);
+		});
+	}
+
+	#[test]
+	fn unchanged_none_on_new_base() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", None);
+			let first = commit(repository, "first", Some(base));
+			let upstream = commit(repository, "upstream", Some(base));
+			let hashes = vec![first.to_string()];
+			assert!(find_unchanged_commits(repository, upstream.to_string().as_str(), &hashes).is_empty());
+			assert!(find_unchanged_commits(repository, "does-not-exist", &hashes).is_empty());
+		});
+	}
+
+	#[test]
+	fn signed() {
+		with_repository(|repository| {
+			let signature = create_signature();
+			let base = commit(repository, "base", None);
+			let tree = repository.find_commit(base).unwrap().tree().unwrap();
+			let content = repository
+				.commit_create_buffer(&signature, &signature, "signed", &tree, &[])
+				.unwrap();
+			let signed = repository
+				.commit_signed(
+					content.as_str().unwrap(),
+					"-----BEGIN PGP SIGNATURE-----\n\nsignature\n-----END PGP SIGNATURE-----",
+					None,
+				)
+				.unwrap();
+			let hashes = vec![base.to_string(), signed.to_string(), String::from("does-not-exist")];
+			assert_eq!(find_signed_commits(repository, &hashes), vec![signed.to_string()]);
+		});
+	}
+}
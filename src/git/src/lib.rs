@@ -87,9 +87,14 @@
 //! these utilities are not tested, and often are optimized for developer experience than
 //! performance, they should only be used in test code.
 
+mod branch_name;
+mod commit_protection;
+mod patch_id;
+mod rebase_simulation;
+mod rebase_todo;
 mod repository;
 pub mod testutil;
 
 pub use git2::{Config, ErrorCode};
 
-pub use crate::repository::Repository;
+pub use crate::{rebase_simulation::RebaseStep, repository::Repository};
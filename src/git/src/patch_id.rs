@@ -0,0 +1,179 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use git2::{Commit, Oid, Repository};
+
+use crate::rebase_todo::map_git_error;
+
+// like git, commits that are merges or that have no changes have no patch-id
+fn get_patch_id(repository: &Repository, commit: &Commit<'_>) -> Result<Option<Oid>> {
+	if commit.parent_count() != 1 {
+		return Ok(None);
+	}
+	let parent_tree = commit
+		.parent(0)
+		.and_then(|parent| parent.tree())
+		.map_err(|err| map_git_error(&err))?;
+	let tree = commit.tree().map_err(|err| map_git_error(&err))?;
+	let diff = repository
+		.diff_tree_to_tree(Some(&parent_tree), Some(&tree), None)
+		.map_err(|err| map_git_error(&err))?;
+	if diff.deltas().len() == 0 {
+		return Ok(None);
+	}
+	diff.patchid(None).map(Some).map_err(|err| map_git_error(&err))
+}
+
+/// Find the commits of `hashes` that are already applied to `upstream`, either because the commit is reachable from
+/// `upstream`, or because a commit on `upstream` has the same patch-id, like `git cherry` does. Hashes that cannot be
+/// resolved to a commit are ignored. Returns `None` if `is_cancelled` returns true before the search completes.
+pub(crate) fn find_applied_upstream<F>(
+	repository: &Repository,
+	upstream: &str,
+	hashes: &[String],
+	is_cancelled: F,
+) -> Result<Option<Vec<String>>>
+where
+	F: Fn() -> bool,
+{
+	let upstream_commit = repository
+		.revparse_single(upstream)
+		.and_then(|object| object.peel_to_commit())
+		.map_err(|err| map_git_error(&err).context(format!("Unable to resolve upstream: {}", upstream)))?;
+
+	let commits = hashes
+		.iter()
+		.filter_map(|hash| {
+			repository
+				.revparse_single(hash)
+				.and_then(|object| object.peel_to_commit())
+				.ok()
+				.map(|commit| (hash, commit))
+		})
+		.collect::<Vec<(&String, Commit<'_>)>>();
+
+	// only the commits on upstream that are not part of the rebased commits need to be compared
+	let mut revwalk = repository.revwalk().map_err(|err| map_git_error(&err))?;
+	revwalk.push(upstream_commit.id()).map_err(|err| map_git_error(&err))?;
+	for (_, commit) in &commits {
+		revwalk.hide(commit.id()).map_err(|err| map_git_error(&err))?;
+	}
+	let mut upstream_patch_ids = HashSet::new();
+	for id in revwalk {
+		if is_cancelled() {
+			return Ok(None);
+		}
+		let commit = repository
+			.find_commit(id.map_err(|err| map_git_error(&err))?)
+			.map_err(|err| map_git_error(&err))?;
+		if let Some(patch_id) = get_patch_id(repository, &commit)? {
+			let _ = upstream_patch_ids.insert(patch_id);
+		}
+	}
+
+	let mut applied = vec![];
+	for (hash, commit) in commits {
+		if is_cancelled() {
+			return Ok(None);
+		}
+		let reachable = commit.id() == upstream_commit.id()
+			|| repository
+				.graph_descendant_of(upstream_commit.id(), commit.id())
+				.map_err(|err| map_git_error(&err))?;
+		if reachable
+			|| get_patch_id(repository, &commit)?.is_some_and(|patch_id| upstream_patch_ids.contains(&patch_id))
+		{
+			applied.push(hash.clone());
+		}
+	}
+	Ok(Some(applied))
+}
+
+#[cfg(test)]
+mod tests {
+	use git2::Signature;
+	use tempfile::tempdir;
+
+	use super::*;
+
+	fn commit(repository: &Repository, summary: &str, contents: &[(&str, &str)], parent: Option<Oid>) -> Oid {
+		let signature = Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+		let mut builder = repository.treebuilder(None).unwrap();
+		for &(path, content) in contents {
+			let blob = repository.blob(content.as_bytes()).unwrap();
+			let _ = builder.insert(path, blob, 0o100_644).unwrap();
+		}
+		let tree = repository.find_tree(builder.write().unwrap()).unwrap();
+		let parent_commit = parent.map(|id| repository.find_commit(id).unwrap());
+		let parents = parent_commit.iter().collect::<Vec<&Commit<'_>>>();
+		repository
+			.commit(None, &signature, &signature, summary, &tree, &parents)
+			.unwrap()
+	}
+
+	fn with_repository<F>(callback: F)
+	where F: FnOnce(&Repository) {
+		let directory = tempdir().unwrap();
+		let repository = Repository::init(directory.path()).unwrap();
+		callback(&repository);
+	}
+
+	#[test]
+	fn cherry_picked_upstream() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n"), ("b", "1\n")], None);
+			let upstream_picked = commit(repository, "picked", &[("a", "2\n"), ("b", "1\n")], Some(base));
+			let upstream = commit(
+				repository,
+				"other",
+				&[("a", "2\n"), ("b", "1\n"), ("c", "1\n")],
+				Some(upstream_picked),
+			);
+			let picked = commit(repository, "picked", &[("a", "2\n"), ("b", "1\n")], Some(base));
+			let topic = commit(repository, "topic", &[("a", "2\n"), ("b", "2\n")], Some(picked));
+			let hashes = vec![picked.to_string(), topic.to_string()];
+			assert_eq!(
+				find_applied_upstream(repository, &upstream.to_string(), &hashes, || false).unwrap(),
+				Some(vec![picked.to_string()])
+			);
+		});
+	}
+
+	#[test]
+	fn reachable_from_upstream() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], None);
+			let first = commit(repository, "first", &[("a", "2\n")], Some(base));
+			let upstream = commit(repository, "second", &[("a", "3\n")], Some(first));
+			let hashes = vec![first.to_string()];
+			assert_eq!(
+				find_applied_upstream(repository, &upstream.to_string(), &hashes, || false).unwrap(),
+				Some(vec![first.to_string()])
+			);
+		});
+	}
+
+	#[test]
+	fn unknown_hashes_ignored() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], None);
+			let hashes = vec![String::from("does-not-exist")];
+			assert_eq!(
+				find_applied_upstream(repository, &base.to_string(), &hashes, || false).unwrap(),
+				Some(vec![])
+			);
+		});
+	}
+
+	#[test]
+	fn cancelled() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], None);
+			let upstream = commit(repository, "upstream", &[("a", "2\n")], Some(base));
+			assert_eq!(
+				find_applied_upstream(repository, &upstream.to_string(), &[], || true).unwrap(),
+				None
+			);
+		});
+	}
+}
@@ -0,0 +1,373 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use git2::{FileFavor, MergeOptions, Oid, Repository, Tree};
+
+use crate::rebase_todo::map_git_error;
+
+// objects are written to the highest priority backend, which must be above the loose and pack backends
+const MEMPACK_PRIORITY: i32 = 1000;
+const REWRITTEN_REFERENCE_PREFIX: &str = "refs/rewritten/";
+
+/// A line of a rebase todo file that changes the tree of the rebase, used to predict conflicts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RebaseStep {
+	/// Apply the changes of a commit, as a `pick`, `reword`, `edit`, `squash` or `fixup` line does.
+	Pick(String),
+	/// Apply the reverse of the changes of a commit.
+	Revert(String),
+	/// Label the current tree.
+	Label(String),
+	/// Reset the current tree to a label or a commit.
+	Reset(String),
+	/// Merge labels or commits into the current tree, optionally recreating an existing merge commit.
+	Merge {
+		/// The merge commit that is recreated.
+		commit: Option<String>,
+		/// The labels or commits that are merged.
+		references: Vec<String>,
+	},
+}
+
+struct Simulation<'repo> {
+	labels: HashMap<String, Oid>,
+	repository: &'repo Repository,
+	stopped: bool,
+	tree: Oid,
+}
+
+impl<'repo> Simulation<'repo> {
+	fn find_commit(&self, reference: &str) -> Result<git2::Commit<'repo>> {
+		self.repository
+			.revparse_single(reference)
+			.and_then(|object| object.peel_to_commit())
+			.map_err(|err| map_git_error(&err).context(format!("Unable to resolve commit: {}", reference)))
+	}
+
+	fn find_tree(&self, id: Oid) -> Result<Tree<'repo>> {
+		self.repository.find_tree(id).map_err(|err| map_git_error(&err))
+	}
+
+	fn resolve_tree(&self, reference: &str) -> Result<Oid> {
+		if let Some(&tree) = self.labels.get(reference) {
+			return Ok(tree);
+		}
+		// labels created before the todo file was edited are stored by git as references
+		let rewritten = format!("{}{}", REWRITTEN_REFERENCE_PREFIX, reference);
+		if let Ok(commit) = self.find_commit(rewritten.as_str()) {
+			return Ok(commit.tree_id());
+		}
+		Ok(self.find_commit(reference)?.tree_id())
+	}
+
+	fn parent_tree(&self, commit: &git2::Commit<'repo>) -> Result<Oid> {
+		match commit.parent(0) {
+			Ok(parent) => Ok(parent.tree_id()),
+			// a root commit is applied as if its parent was empty
+			Err(_) => {
+				self.repository
+					.treebuilder(None)
+					.and_then(|builder| builder.write())
+					.map_err(|err| map_git_error(&err))
+			},
+		}
+	}
+
+	// merges the changes from the ancestor to theirs into the current tree, returning the conflicting paths
+	fn apply(&mut self, ancestor_id: Oid, theirs_id: Oid, fallback: Oid) -> Result<Vec<String>> {
+		let ancestor = self.find_tree(ancestor_id)?;
+		let ours = self.find_tree(self.tree)?;
+		let theirs = self.find_tree(theirs_id)?;
+		let mut index = self
+			.repository
+			.merge_trees(&ancestor, &ours, &theirs, None)
+			.map_err(|err| map_git_error(&err))?;
+		if !index.has_conflicts() {
+			self.tree = index
+				.write_tree_to(self.repository)
+				.map_err(|err| map_git_error(&err))?;
+			return Ok(vec![]);
+		}
+
+		let mut paths = vec![];
+		for conflict_result in index.conflicts().map_err(|err| map_git_error(&err))? {
+			let conflict = conflict_result.map_err(|err| map_git_error(&err))?;
+			if let Some(entry) = conflict.our.or(conflict.their).or(conflict.ancestor) {
+				paths.push(String::from_utf8_lossy(entry.path.as_slice()).into_owned());
+			}
+		}
+		paths.sort();
+		paths.dedup();
+
+		// the conflict is assumed to be resolved in favour of the applied changes, so that the changes that did not
+		// conflict are still part of the tree for the following lines
+		let mut options = MergeOptions::new();
+		let _ = options.file_favor(FileFavor::Theirs);
+		let mut resolved = self
+			.repository
+			.merge_trees(&ancestor, &ours, &theirs, Some(&options))
+			.map_err(|err| map_git_error(&err))?;
+		self.tree = if resolved.has_conflicts() {
+			fallback
+		}
+		else {
+			resolved
+				.write_tree_to(self.repository)
+				.map_err(|err| map_git_error(&err))?
+		};
+		Ok(paths)
+	}
+
+	fn merge(&mut self, merge_hash: Option<&str>, references: &[String]) -> Result<Vec<String>> {
+		// without the original merge commit, or with more than one merged branch, the merge base is not known
+		let (commit, reference) = if let (Some(hash), [reference]) = (merge_hash, references) {
+			(self.find_commit(hash)?, reference)
+		}
+		else {
+			self.stopped = true;
+			return Ok(vec![]);
+		};
+		let ancestor = if let (Ok(first), Ok(second)) = (commit.parent_id(0), commit.parent_id(1)) {
+			let merge_base = self
+				.repository
+				.merge_base(first, second)
+				.map_err(|err| map_git_error(&err))?;
+			self.find_commit(merge_base.to_string().as_str())?.tree_id()
+		}
+		else {
+			self.stopped = true;
+			return Ok(vec![]);
+		};
+		let theirs = self.resolve_tree(reference)?;
+		self.apply(ancestor, theirs, commit.tree_id())
+	}
+
+	fn step(&mut self, step: &RebaseStep) -> Result<Vec<String>> {
+		match step {
+			RebaseStep::Pick(hash) => {
+				let commit = self.find_commit(hash)?;
+				let ancestor = self.parent_tree(&commit)?;
+				self.apply(ancestor, commit.tree_id(), commit.tree_id())
+			},
+			RebaseStep::Revert(hash) => {
+				let commit = self.find_commit(hash)?;
+				let parent = self.parent_tree(&commit)?;
+				self.apply(commit.tree_id(), parent, parent)
+			},
+			RebaseStep::Label(label) => {
+				let _ = self.labels.insert(label.clone(), self.tree);
+				Ok(vec![])
+			},
+			RebaseStep::Reset(reference) => {
+				self.tree = self.resolve_tree(reference)?;
+				Ok(vec![])
+			},
+			RebaseStep::Merge { commit, references } => self.merge(commit.as_deref(), references),
+		}
+	}
+}
+
+/// Replay `steps` onto `base`, returning the paths that conflict for each step, or `None` if `is_cancelled` returns
+/// true before the replay completes. Once a step cannot be replayed, such as a merge without the original merge
+/// commit, the following steps are reported as having no conflicts.
+///
+/// The trees created by the replay are written to an in-memory object database, so the repository is not changed.
+pub(crate) fn simulate_rebase<F>(
+	repository: &Repository,
+	base: &str,
+	steps: &[RebaseStep],
+	is_cancelled: F,
+) -> Result<Option<Vec<Vec<String>>>>
+where
+	F: Fn() -> bool,
+{
+	let odb = repository.odb().map_err(|err| map_git_error(&err))?;
+	let _mempack = odb
+		.add_new_mempack_backend(MEMPACK_PRIORITY)
+		.map_err(|err| map_git_error(&err))?;
+
+	let mut simulation = Simulation {
+		labels: HashMap::new(),
+		repository,
+		stopped: false,
+		tree: Oid::zero(),
+	};
+	simulation.tree = simulation.find_commit(base)?.tree_id();
+	let _ = simulation.labels.insert(String::from("onto"), simulation.tree);
+
+	let mut conflicts = vec![];
+	for step in steps {
+		if is_cancelled() {
+			return Ok(None);
+		}
+		conflicts.push(
+			if simulation.stopped {
+				vec![]
+			}
+			else {
+				simulation.step(step)?
+			},
+		);
+	}
+	Ok(Some(conflicts))
+}
+
+#[cfg(test)]
+mod tests {
+	use git2::Signature;
+	use tempfile::tempdir;
+
+	use super::*;
+
+	fn commit(repository: &Repository, summary: &str, contents: &[(&str, &str)], parents: &[Oid]) -> String {
+		let signature = Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+		let mut builder = repository.treebuilder(None).unwrap();
+		for &(path, content) in contents {
+			let blob = repository.blob(content.as_bytes()).unwrap();
+			let _ = builder.insert(path, blob, 0o100_644).unwrap();
+		}
+		let tree = repository.find_tree(builder.write().unwrap()).unwrap();
+		let parent_commits = parents
+			.iter()
+			.map(|&id| repository.find_commit(id).unwrap())
+			.collect::<Vec<git2::Commit<'_>>>();
+		let parent_refs = parent_commits.iter().collect::<Vec<&git2::Commit<'_>>>();
+		repository
+			.commit(None, &signature, &signature, summary, &tree, &parent_refs)
+			.unwrap()
+			.to_string()
+	}
+
+	fn oid(hash: &str) -> Oid {
+		Oid::from_str(hash).unwrap()
+	}
+
+	fn with_repository<F>(callback: F)
+	where F: FnOnce(&Repository) {
+		let directory = tempdir().unwrap();
+		let repository = Repository::init(directory.path()).unwrap();
+		callback(&repository);
+	}
+
+	fn pick(hash: &str) -> RebaseStep {
+		RebaseStep::Pick(String::from(hash))
+	}
+
+	#[test]
+	fn no_conflicts() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "2\n")], &[oid(&base)]);
+			let second = commit(repository, "second", &[("a", "2\n"), ("b", "1\n")], &[oid(&first)]);
+			assert_eq!(
+				simulate_rebase(repository, &base, &[pick(&first), pick(&second)], || false).unwrap(),
+				Some(vec![vec![], vec![]])
+			);
+		});
+	}
+
+	#[test]
+	fn reordered_conflicts() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n"), ("b", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "2\n"), ("b", "1\n")], &[oid(&base)]);
+			let second = commit(repository, "second", &[("a", "3\n"), ("b", "2\n")], &[oid(&first)]);
+			assert_eq!(
+				simulate_rebase(repository, &base, &[pick(&second), pick(&first)], || false).unwrap(),
+				Some(vec![vec![String::from("a")], vec![String::from("a")]])
+			);
+		});
+	}
+
+	#[test]
+	fn revert_conflicts() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "2\n")], &[oid(&base)]);
+			let second = commit(repository, "second", &[("a", "3\n")], &[oid(&first)]);
+			assert_eq!(
+				simulate_rebase(repository, &second, &[RebaseStep::Revert(first)], || false).unwrap(),
+				Some(vec![vec![String::from("a")]])
+			);
+		});
+	}
+
+	#[test]
+	fn reset_to_label() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "2\n")], &[oid(&base)]);
+			let second = commit(repository, "second", &[("a", "3\n")], &[oid(&base)]);
+			assert_eq!(
+				simulate_rebase(
+					repository,
+					&base,
+					&[pick(&first), RebaseStep::Reset(String::from("onto")), pick(&second)],
+					|| false
+				)
+				.unwrap(),
+				Some(vec![vec![], vec![], vec![]])
+			);
+		});
+	}
+
+	#[test]
+	fn merge_without_commit_stops() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "2\n")], &[oid(&base)]);
+			let second = commit(repository, "second", &[("a", "3\n")], &[oid(&base)]);
+			assert_eq!(
+				simulate_rebase(
+					repository,
+					&base,
+					&[
+						RebaseStep::Merge {
+							commit: None,
+							references: vec![first]
+						},
+						pick(&second)
+					],
+					|| false
+				)
+				.unwrap(),
+				Some(vec![vec![], vec![]])
+			);
+		});
+	}
+
+	#[test]
+	fn cancelled() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "2\n")], &[oid(&base)]);
+			assert_eq!(
+				simulate_rebase(repository, &base, &[pick(&first)], || true).unwrap(),
+				None
+			);
+		});
+	}
+
+	#[test]
+	fn repository_not_modified() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[("a", "1\n")], &[]);
+			let first = commit(repository, "first", &[("a", "1\n2\n")], &[oid(&base)]);
+			let second = commit(repository, "second", &[("a", "0\n1\n")], &[oid(&base)]);
+			let _ = simulate_rebase(repository, &first, &[pick(&second)], || false).unwrap();
+			let mut count: usize = 0;
+			Repository::open(repository.path())
+				.unwrap()
+				.odb()
+				.unwrap()
+				.foreach(|_| {
+					count += 1;
+					true
+				})
+				.unwrap();
+			// three commits, three trees and three blobs
+			assert_eq!(count, 9);
+		});
+	}
+}
@@ -0,0 +1,433 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{anyhow, Result};
+use git2::{Oid, Repository, Sort};
+
+use crate::patch_id::find_applied_upstream;
+
+const ONTO_LABEL: &str = "onto";
+const BRANCH_POINT_LABEL: &str = "branch-point";
+const MERGE_BRANCH_PREFIX: &str = "Merge branch '";
+
+pub(crate) fn map_git_error(err: &git2::Error) -> anyhow::Error {
+	anyhow!(String::from(err.message()))
+}
+
+struct RangeCommit {
+	parents: Vec<Oid>,
+	short_id: String,
+	summary: String,
+}
+
+// like git, the label for a merged branch is taken from the merge summary when possible
+fn get_branch_label(summary: &str) -> Option<String> {
+	let name = summary.strip_prefix(MERGE_BRANCH_PREFIX)?.split('\'').next()?;
+	let label = name
+		.chars()
+		.map(|c| {
+			if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' || c == '/' {
+				c
+			}
+			else {
+				'-'
+			}
+		})
+		.collect::<String>();
+	(!label.is_empty()).then_some(label)
+}
+
+struct MergeScript<'commits> {
+	commits: &'commits HashMap<Oid, RangeCommit>,
+	emitted: HashSet<Oid>,
+	labels: HashMap<Oid, String>,
+	lines: Vec<String>,
+	merge_base: Option<Oid>,
+}
+
+impl<'commits> MergeScript<'commits> {
+	fn new(
+		commits: &'commits HashMap<Oid, RangeCommit>,
+		merge_base: Option<Oid>,
+		labels: HashMap<Oid, String>,
+	) -> Self {
+		Self {
+			commits,
+			emitted: HashSet::new(),
+			labels,
+			lines: vec![format!("label {}", ONTO_LABEL)],
+			merge_base,
+		}
+	}
+
+	fn is_pending(&self, id: Oid) -> bool {
+		self.commits.contains_key(&id) && !self.emitted.contains(&id)
+	}
+
+	fn reference(&mut self, commit_id: Option<Oid>, label_hint: Option<String>) -> String {
+		let id = match commit_id {
+			Some(id) => id,
+			None => return String::from(ONTO_LABEL),
+		};
+		if !self.commits.contains_key(&id) {
+			return if Some(id) == self.merge_base {
+				String::from(ONTO_LABEL)
+			}
+			else {
+				id.to_string()
+			};
+		}
+		if let Some(label) = self.labels.get(&id) {
+			return label.clone();
+		}
+		let name = label_hint.unwrap_or_else(|| String::from(BRANCH_POINT_LABEL));
+		let is_taken = |label: &String| label == ONTO_LABEL || self.labels.values().any(|l| l == label);
+		// like git, a taken label is made unique by adding the first free numbered suffix, starting from two, and there
+		// are more suffixes than taken labels, so a free label is always found
+		let label = if is_taken(&name) {
+			(2..=self.labels.len().saturating_add(2))
+				.map(|suffix| format!("{}-{}", name, suffix))
+				.find(|label| !is_taken(label))
+				.unwrap_or(name)
+		}
+		else {
+			name
+		};
+		let _ = self.labels.insert(id, label.clone());
+		label
+	}
+
+	fn emit_chain(&mut self, tip: Oid) {
+		let mut chain = vec![];
+		let mut current = Some(tip);
+		while let Some(id) = current {
+			if !self.is_pending(id) || chain.contains(&id) {
+				break;
+			}
+			chain.push(id);
+			current = self.commits.get(&id).and_then(|commit| commit.parents.first().copied());
+		}
+		chain.reverse();
+
+		let base = chain
+			.first()
+			.and_then(|id| self.commits.get(id))
+			.and_then(|commit| commit.parents.first().copied());
+		let base_reference = self.reference(base, None);
+		self.lines.push(format!("reset {}", base_reference));
+
+		let commits = self.commits;
+		let mut previous = base;
+		for (id, commit) in chain
+			.into_iter()
+			.filter_map(|id| commits.get(&id).map(|commit| (id, commit)))
+		{
+			if commit.parents.len() > 1 {
+				let label_hint = get_branch_label(commit.summary.as_str());
+				let merged = commit.parents.iter().skip(1).copied().collect::<Vec<Oid>>();
+				if merged.iter().any(|&parent| self.is_pending(parent)) {
+					let position = self.reference(previous, None);
+					for &parent in &merged {
+						if self.is_pending(parent) {
+							self.emit_chain(parent);
+						}
+					}
+					self.lines.push(format!("reset {}", position));
+				}
+				let merged_references = merged
+					.iter()
+					.map(|&parent| self.reference(Some(parent), label_hint.clone()))
+					.collect::<Vec<String>>();
+				self.lines.push(format!(
+					"merge -C {} {} # {}",
+					commit.short_id,
+					merged_references.join(" "),
+					commit.summary
+				));
+			}
+			else {
+				self.lines.push(format!("pick {} {}", commit.short_id, commit.summary));
+			}
+			let _ = self.emitted.insert(id);
+			if let Some(label) = self.labels.get(&id) {
+				self.lines.push(format!("label {}", label));
+			}
+			previous = Some(id);
+		}
+	}
+}
+
+// the commits on `HEAD` that are not on the upstream, in the order that they are applied
+struct CommitRange {
+	commits: HashMap<Oid, RangeCommit>,
+	merge_base: Option<Oid>,
+	order: Vec<Oid>,
+}
+
+fn load_range(repository: &Repository, upstream: &str) -> Result<CommitRange> {
+	let head = repository
+		.head()
+		.and_then(|head| head.peel_to_commit())
+		.map_err(|err| map_git_error(&err).context("Unable to resolve HEAD"))?;
+	let upstream_commit = repository
+		.revparse_single(upstream)
+		.and_then(|object| object.peel_to_commit())
+		.map_err(|err| map_git_error(&err).context(format!("Unable to resolve upstream: {}", upstream)))?;
+
+	let mut revwalk = repository.revwalk().map_err(|err| map_git_error(&err))?;
+	revwalk
+		.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)
+		.map_err(|err| map_git_error(&err))?;
+	revwalk.push(head.id()).map_err(|err| map_git_error(&err))?;
+	revwalk.hide(upstream_commit.id()).map_err(|err| map_git_error(&err))?;
+
+	let mut order = vec![];
+	let mut commits = HashMap::new();
+	for revwalk_id in revwalk {
+		let id = revwalk_id.map_err(|err| map_git_error(&err))?;
+		let commit = repository.find_commit(id).map_err(|err| map_git_error(&err))?;
+		let short_id = commit
+			.as_object()
+			.short_id()
+			.map_err(|err| map_git_error(&err))?
+			.as_str()
+			.map_or_else(|| id.to_string(), String::from);
+		order.push(id);
+		let _ = commits.insert(id, RangeCommit {
+			parents: commit.parent_ids().collect(),
+			short_id,
+			summary: String::from(commit.summary().unwrap_or("")),
+		});
+	}
+	let merge_base = repository.merge_base(head.id(), upstream_commit.id()).ok();
+	Ok(CommitRange {
+		commits,
+		merge_base,
+		order,
+	})
+}
+
+/// Create the lines of a rebase todo file for the commits on `HEAD` that are not on `upstream`. Without
+/// `rebase_merges`, commits that are already applied upstream are left out, like git does, but they are kept when
+/// rebasing merges, to keep the labels of the merged branches intact.
+pub(crate) fn create_rebase_todo(repository: &Repository, upstream: &str, rebase_merges: bool) -> Result<Vec<String>> {
+	let CommitRange {
+		commits,
+		merge_base,
+		order,
+	} = load_range(repository, upstream)?;
+
+	if order.is_empty() {
+		return Ok(vec![String::from("noop")]);
+	}
+
+	if !rebase_merges {
+		// like git, commits that are already applied upstream are dropped, while commits that start empty are kept,
+		// as with the default of `git rebase --keep-empty`
+		let hashes = order.iter().map(Oid::to_string).collect::<Vec<String>>();
+		let applied = find_applied_upstream(repository, upstream, &hashes, || false)?.unwrap_or_default();
+		let lines = order
+			.iter()
+			.filter(|id| !applied.contains(&id.to_string()))
+			.filter_map(|id| commits.get(id))
+			.filter(|commit| commit.parents.len() <= 1)
+			.map(|commit| format!("pick {} {}", commit.short_id, commit.summary))
+			.collect::<Vec<String>>();
+		if lines.is_empty() {
+			return Ok(vec![String::from("noop")]);
+		}
+		return Ok(lines);
+	}
+
+	let head = *order.last().expect("range is not empty");
+	// labels are only known once a commit is referenced, which can happen after the commit has been emitted, so a
+	// first pass is used to find the labels, and a second to emit the lines with the labels in place
+	let mut first_pass = MergeScript::new(&commits, merge_base, HashMap::new());
+	first_pass.emit_chain(head);
+	let mut script = MergeScript::new(&commits, merge_base, first_pass.labels);
+	script.emit_chain(head);
+	Ok(script.lines)
+}
+
+#[cfg(test)]
+mod tests {
+	use git2::Signature;
+	use tempfile::tempdir;
+
+	use super::*;
+
+	fn commit(repository: &Repository, summary: &str, parents: &[Oid]) -> Oid {
+		commit_with_file(repository, summary, None, parents)
+	}
+
+	fn commit_with_file(repository: &Repository, summary: &str, file: Option<&str>, parents: &[Oid]) -> Oid {
+		let signature = Signature::new("Name", "name@example.com", &git2::Time::new(0, 0)).unwrap();
+		let mut builder = repository.treebuilder(None).unwrap();
+		if let Some(content) = file {
+			let blob = repository.blob(content.as_bytes()).unwrap();
+			let _ = builder.insert("file", blob, 0o100_644).unwrap();
+		}
+		let tree = repository.find_tree(builder.write().unwrap()).unwrap();
+		let parent_commits = parents
+			.iter()
+			.map(|&id| repository.find_commit(id).unwrap())
+			.collect::<Vec<git2::Commit<'_>>>();
+		let parent_refs = parent_commits.iter().collect::<Vec<&git2::Commit<'_>>>();
+		repository
+			.commit(None, &signature, &signature, summary, &tree, &parent_refs)
+			.unwrap()
+	}
+
+	fn short_id(repository: &Repository, id: Oid) -> String {
+		String::from(
+			repository
+				.find_object(id, None)
+				.unwrap()
+				.short_id()
+				.unwrap()
+				.as_str()
+				.unwrap(),
+		)
+	}
+
+	fn with_repository<F>(callback: F)
+	where F: FnOnce(&Repository) {
+		let directory = tempdir().unwrap();
+		let repository = Repository::init(directory.path()).unwrap();
+		callback(&repository);
+	}
+
+	fn set_head(repository: &Repository, id: Oid) {
+		let _ = repository.reference("refs/heads/main", id, true, "test").unwrap();
+		repository.set_head("refs/heads/main").unwrap();
+	}
+
+	#[test]
+	fn linear() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[]);
+			let first = commit(repository, "first", &[base]);
+			let second = commit(repository, "second", &[first]);
+			set_head(repository, second);
+			assert_eq!(create_rebase_todo(repository, &base.to_string(), false).unwrap(), vec![
+				format!("pick {} first", short_id(repository, first)),
+				format!("pick {} second", short_id(repository, second)),
+			]);
+		});
+	}
+
+	#[test]
+	fn empty_range() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[]);
+			set_head(repository, base);
+			assert_eq!(create_rebase_todo(repository, "HEAD", false).unwrap(), vec!["noop"]);
+		});
+	}
+
+	#[test]
+	fn linear_drops_applied_upstream() {
+		with_repository(|repository| {
+			let base = commit_with_file(repository, "base", Some("1\n"), &[]);
+			let picked = commit_with_file(repository, "picked", Some("2\n"), &[base]);
+			let upstream = commit_with_file(repository, "picked upstream", Some("2\n"), &[base]);
+			let second = commit_with_file(repository, "second", Some("3\n"), &[picked]);
+			set_head(repository, second);
+			assert_eq!(
+				create_rebase_todo(repository, &upstream.to_string(), false).unwrap(),
+				vec![format!("pick {} second", short_id(repository, second))]
+			);
+		});
+	}
+
+	#[test]
+	fn linear_only_applied_upstream() {
+		with_repository(|repository| {
+			let base = commit_with_file(repository, "base", Some("1\n"), &[]);
+			let picked = commit_with_file(repository, "picked", Some("2\n"), &[base]);
+			let upstream = commit_with_file(repository, "picked upstream", Some("2\n"), &[base]);
+			set_head(repository, picked);
+			assert_eq!(
+				create_rebase_todo(repository, &upstream.to_string(), false).unwrap(),
+				vec!["noop"]
+			);
+		});
+	}
+
+	#[test]
+	fn linear_keeps_empty() {
+		with_repository(|repository| {
+			let base = commit_with_file(repository, "base", Some("1\n"), &[]);
+			let empty = commit_with_file(repository, "empty", Some("1\n"), &[base]);
+			let upstream = commit_with_file(repository, "upstream", Some("2\n"), &[base]);
+			set_head(repository, empty);
+			assert_eq!(
+				create_rebase_todo(repository, &upstream.to_string(), false).unwrap(),
+				vec![format!("pick {} empty", short_id(repository, empty))]
+			);
+		});
+	}
+
+	#[test]
+	fn linear_skips_merges() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[]);
+			let topic = commit(repository, "topic", &[base]);
+			let first = commit(repository, "first", &[base]);
+			let merge = commit(repository, "Merge branch 'topic'", &[first, topic]);
+			set_head(repository, merge);
+			let lines = create_rebase_todo(repository, &base.to_string(), false).unwrap();
+			assert_eq!(lines.len(), 2);
+			assert!(lines.contains(&format!("pick {} topic", short_id(repository, topic))));
+			assert!(lines.contains(&format!("pick {} first", short_id(repository, first))));
+		});
+	}
+
+	#[test]
+	fn rebase_merges() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[]);
+			let first = commit(repository, "first", &[base]);
+			let topic = commit(repository, "topic", &[first]);
+			let second = commit(repository, "second", &[first]);
+			let merge = commit(repository, "Merge branch 'topic'", &[second, topic]);
+			set_head(repository, merge);
+			assert_eq!(create_rebase_todo(repository, &base.to_string(), true).unwrap(), vec![
+				String::from("label onto"),
+				String::from("reset onto"),
+				format!("pick {} first", short_id(repository, first)),
+				String::from("label branch-point-2"),
+				format!("pick {} second", short_id(repository, second)),
+				String::from("label branch-point"),
+				String::from("reset branch-point-2"),
+				format!("pick {} topic", short_id(repository, topic)),
+				String::from("label topic"),
+				String::from("reset branch-point"),
+				format!("merge -C {} topic # Merge branch 'topic'", short_id(repository, merge)),
+			]);
+		});
+	}
+
+	#[test]
+	fn invalid_upstream() {
+		with_repository(|repository| {
+			let base = commit(repository, "base", &[]);
+			set_head(repository, base);
+			assert!(format!(
+				"{:#}",
+				create_rebase_todo(repository, "does-not-exist", false).unwrap_err()
+			)
+			.starts_with("Unable to resolve upstream: does-not-exist"));
+		});
+	}
+
+	#[test]
+	fn branch_label() {
+		assert_eq!(get_branch_label("Merge branch 'topic'").as_deref(), Some("topic"));
+		assert_eq!(
+			get_branch_label("Merge branch 'topic' into main").as_deref(),
+			Some("topic")
+		);
+		assert_eq!(get_branch_label("Merge branch 'a b'").as_deref(), Some("a-b"));
+		assert_eq!(get_branch_label("Merge pull request #1"), None);
+	}
+}
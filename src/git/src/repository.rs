@@ -2,7 +2,14 @@ use std::path::Path;
 
 use anyhow::{anyhow, Result};
 
-use crate::Config;
+use crate::{
+	branch_name::find_branch_name,
+	commit_protection::{find_published_commits, find_signed_commits, find_unchanged_commits},
+	patch_id::find_applied_upstream,
+	rebase_simulation::{simulate_rebase, RebaseStep},
+	rebase_todo::create_rebase_todo,
+	Config,
+};
 
 /// A light simple wrapper around the `git2::Repository` struct
 pub struct Repository {
@@ -42,6 +49,89 @@ impl Repository {
 	pub fn load_config(&self) -> Result<Config> {
 		self.repository.config().map_err(|e| anyhow!(String::from(e.message())))
 	}
+
+	/// Get the path to the `.git` directory of the repository.
+	#[inline]
+	#[must_use]
+	pub fn get_git_directory(&self) -> &Path {
+		self.repository.path()
+	}
+
+	/// Create the lines of a rebase todo file for the commits on `HEAD` that are not on `upstream`, in the same
+	/// order `git rebase --interactive` would use. Merge commits are skipped, unless `rebase_merges` is set, in which
+	/// case the todo recreates the merges using `label`, `reset` and `merge` lines.
+	///
+	/// # Errors
+	/// Will result in an error if `HEAD` or `upstream` cannot be resolved to a commit, or the commits cannot be read.
+	#[inline]
+	pub fn create_rebase_todo(&self, upstream: &str, rebase_merges: bool) -> Result<Vec<String>> {
+		create_rebase_todo(&self.repository, upstream, rebase_merges)
+	}
+
+	/// Replay `steps` onto the `base` commit without changing the repository or the working tree, and return the
+	/// paths that would conflict for each step. Returns `None` if `is_cancelled` returns true before the replay
+	/// completes.
+	///
+	/// # Errors
+	/// Will result in an error if `base` or a commit of a step cannot be resolved, or the trees cannot be merged.
+	#[inline]
+	pub fn predict_conflicts<F>(
+		&self,
+		base: &str,
+		steps: &[RebaseStep],
+		is_cancelled: F,
+	) -> Result<Option<Vec<Vec<String>>>>
+	where
+		F: Fn() -> bool,
+	{
+		// a separate handle is used, so the in-memory objects of the replay are discarded along with it
+		let repository = git2::Repository::open(self.repository.path())
+			.map_err(|e| anyhow!(String::from(e.message())).context("Could not open repository from path"))?;
+		simulate_rebase(&repository, base, steps, is_cancelled)
+	}
+
+	/// Find the commits of `hashes` that are already applied to `upstream`, either because the commit is reachable
+	/// from `upstream`, or because a commit on `upstream` makes the same change, as found by comparing patch-ids.
+	/// Returns `None` if `is_cancelled` returns true before the search completes.
+	///
+	/// # Errors
+	/// Will result in an error if `upstream` cannot be resolved to a commit, or the commits cannot be read.
+	#[inline]
+	pub fn find_applied_upstream<F>(&self, upstream: &str, hashes: &[String], is_cancelled: F) -> Result<Option<Vec<String>>>
+	where F: Fn() -> bool {
+		find_applied_upstream(&self.repository, upstream, hashes, is_cancelled)
+	}
+
+	/// Find the commits of `hashes` that are reachable from a remote-tracking branch under `refs/remotes`.
+	///
+	/// # Errors
+	/// Will result in an error if the remote-tracking branches or the commits cannot be read.
+	#[inline]
+	pub fn find_published_commits(&self, hashes: &[String]) -> Result<Vec<String>> {
+		find_published_commits(&self.repository, hashes)
+	}
+
+	/// Find the leading commits of `hashes` that are kept, rather than rewritten, when picked in order onto `base`.
+	#[inline]
+	#[must_use]
+	pub fn find_unchanged_commits(&self, base: &str, hashes: &[String]) -> Vec<String> {
+		find_unchanged_commits(&self.repository, base, hashes)
+	}
+
+	/// Find the commits of `hashes` that carry a GPG or SSH signature.
+	#[inline]
+	#[must_use]
+	pub fn find_signed_commits(&self, hashes: &[String]) -> Vec<String> {
+		find_signed_commits(&self.repository, hashes)
+	}
+
+	/// Find the name of a branch that points to the commit of `hash`, preferring a local branch, such as `main`, over
+	/// a remote-tracking branch, such as `origin/main`.
+	#[inline]
+	#[must_use]
+	pub fn find_branch_name(&self, hash: &str) -> Option<String> {
+		find_branch_name(&self.repository, hash)
+	}
 }
 
 impl From<git2::Repository> for Repository {
@@ -129,6 +219,21 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn get_git_directory() {
+		let temp_repository_directory = tempdir().unwrap();
+		let path = temp_repository_directory.into_path().canonicalize().unwrap();
+		let repo = create_bare_repository(&path);
+		assert_eq!(repo.get_git_directory(), path.join(""));
+	}
+
+	#[test]
+	fn create_rebase_todo_error() {
+		with_temp_bare_repository(|repo| {
+			assert!(repo.create_rebase_todo("main", false).is_err());
+		});
+	}
+
 	#[test]
 	fn from_git2_repository() {
 		let temp_repository_directory = tempdir().unwrap();
@@ -19,10 +19,18 @@ pub struct KeyBindings {
 	pub action_reword: Vec<Event>,
 	/// Key bindings for the squash action.
 	pub action_squash: Vec<Event>,
+	/// Key bindings for applying fixup and squash commits.
+	pub autosquash: Vec<Event>,
 	/// Key bindings for positive confirmation.
 	pub confirm_yes: Vec<Event>,
+	/// Key bindings for cycling the fixup message option.
+	pub cycle_fixup_option: Vec<Event>,
+	/// Key bindings for dropping the commits that are already applied upstream.
+	pub drop_applied_upstream: Vec<Event>,
 	/// Key bindings for editing.
 	pub edit: Vec<Event>,
+	/// Key bindings for filtering the list.
+	pub filter: Vec<Event>,
 	/// Key bindings for forcing an abort.
 	pub force_abort: Vec<Event>,
 	/// Key bindings for forcing a rebase.
@@ -51,18 +59,42 @@ pub struct KeyBindings {
 	pub move_up: Vec<Event>,
 	/// Key bindings for moving up a step.
 	pub move_up_step: Vec<Event>,
+	/// Key bindings for moving to the next file of a diff.
+	pub next_file: Vec<Event>,
+	/// Key bindings for moving to the next hunk of a diff.
+	pub next_hunk: Vec<Event>,
 	/// Key bindings for opening the external editor.
 	pub open_in_external_editor: Vec<Event>,
+	/// Key bindings for moving to the previous file of a diff.
+	pub previous_file: Vec<Event>,
+	/// Key bindings for moving to the previous hunk of a diff.
+	pub previous_hunk: Vec<Event>,
 	/// Key bindings for rebasing.
 	pub rebase: Vec<Event>,
 	/// Key bindings for redoing a change.
 	pub redo: Vec<Event>,
 	/// Key bindings for removing a line.
 	pub remove_line: Vec<Event>,
+	/// Key bindings for starting a search.
+	pub search: Vec<Event>,
+	/// Key bindings for moving to the next search match.
+	pub search_next: Vec<Event>,
+	/// Key bindings for moving to the previous search match.
+	pub search_previous: Vec<Event>,
+	/// Key bindings for showing the combined diff of a squash or fixup group.
+	pub show_combined_diff: Vec<Event>,
 	/// Key bindings for showing a commit.
 	pub show_commit: Vec<Event>,
 	/// Key bindings for showing a diff.
 	pub show_diff: Vec<Event>,
+	/// Key bindings for showing the list of files of a diff.
+	pub show_file_list: Vec<Event>,
+	/// Key bindings for collapsing or expanding a file of a diff.
+	pub toggle_fold: Vec<Event>,
+	/// Key bindings for toggling the graph view.
+	pub toggle_graph: Vec<Event>,
+	/// Key bindings for toggling a side by side diff.
+	pub toggle_side_by_side: Vec<Event>,
 	/// Key bindings for toggling visual mode.
 	pub toggle_visual_mode: Vec<Event>,
 	/// Key bindings for undoing a change.
@@ -133,7 +165,11 @@ impl KeyBindings {
 			action_pick: map_keybindings(&key_bindings.action_pick),
 			action_reword: map_keybindings(&key_bindings.action_reword),
 			action_squash: map_keybindings(&key_bindings.action_squash),
+			autosquash: map_keybindings(&key_bindings.autosquash),
+			cycle_fixup_option: map_keybindings(&key_bindings.cycle_fixup_option),
+			drop_applied_upstream: map_keybindings(&key_bindings.drop_applied_upstream),
 			edit: map_keybindings(&key_bindings.edit),
+			filter: map_keybindings(&key_bindings.filter),
 			force_abort: map_keybindings(&key_bindings.force_abort),
 			force_rebase: map_keybindings(&key_bindings.force_rebase),
 			help: map_keybindings(&key_bindings.help),
@@ -148,12 +184,24 @@ impl KeyBindings {
 			move_selection_up: map_keybindings(&key_bindings.move_selection_up),
 			move_up: map_keybindings(&key_bindings.move_up),
 			move_up_step: map_keybindings(&key_bindings.move_up_step),
+			next_file: map_keybindings(&key_bindings.next_file),
+			next_hunk: map_keybindings(&key_bindings.next_hunk),
 			open_in_external_editor: map_keybindings(&key_bindings.open_in_external_editor),
+			previous_file: map_keybindings(&key_bindings.previous_file),
+			previous_hunk: map_keybindings(&key_bindings.previous_hunk),
 			rebase: map_keybindings(&key_bindings.rebase),
 			redo: map_keybindings(&key_bindings.redo),
 			remove_line: map_keybindings(&key_bindings.remove_line),
+			search: map_keybindings(&key_bindings.search),
+			search_next: map_keybindings(&key_bindings.search_next),
+			search_previous: map_keybindings(&key_bindings.search_previous),
+			show_combined_diff: map_keybindings(&key_bindings.show_combined_diff),
 			show_commit: map_keybindings(&key_bindings.show_commit),
 			show_diff: map_keybindings(&key_bindings.show_diff),
+			show_file_list: map_keybindings(&key_bindings.show_file_list),
+			toggle_fold: map_keybindings(&key_bindings.toggle_fold),
+			toggle_graph: map_keybindings(&key_bindings.toggle_graph),
+			toggle_side_by_side: map_keybindings(&key_bindings.toggle_side_by_side),
 			toggle_visual_mode: map_keybindings(&key_bindings.toggle_visual_mode),
 			undo: map_keybindings(&key_bindings.undo),
 			confirm_yes: map_keybindings(&key_bindings.confirm_yes),
@@ -18,12 +18,20 @@ pub enum MetaEvent {
 	ActionReword,
 	/// The squash action meta event.
 	ActionSquash,
+	/// The autosquash meta event.
+	Autosquash,
+	/// The cycle fixup option meta event.
+	CycleFixupOption,
+	/// The drop applied upstream meta event.
+	DropAppliedUpstream,
 	/// The edit meta event.
 	Edit,
 	/// The exit meta event.
 	Exit,
 	/// The delete meta event.
 	Delete,
+	/// The filter meta event.
+	Filter,
 	/// The force abort meta event.
 	ForceAbort,
 	/// The force rebase meta event.
@@ -50,10 +58,18 @@ pub enum MetaEvent {
 	MoveCursorRight,
 	/// The move cursor up meta event.
 	MoveCursorUp,
+	/// The next file meta event.
+	NextFile,
+	/// The next hunk meta event.
+	NextHunk,
 	/// The no meta event.
 	No,
 	/// The open in editor meta event.
 	OpenInEditor,
+	/// The previous file meta event.
+	PreviousFile,
+	/// The previous hunk meta event.
+	PreviousHunk,
 	/// The rebase meta event.
 	Rebase,
 	/// The redo meta event.
@@ -74,14 +90,30 @@ pub enum MetaEvent {
 	ScrollTop,
 	/// The scroll to top meta event.
 	ScrollUp,
+	/// The search next meta event.
+	SearchNext,
+	/// The search previous meta event.
+	SearchPrevious,
+	/// The search start meta event.
+	SearchStart,
+	/// The show combined diff meta event.
+	ShowCombinedDiff,
 	/// The show commit meta event.
 	ShowCommit,
 	/// The show diff meta event.
 	ShowDiff,
+	/// The show file list meta event.
+	ShowFileList,
 	/// The swap selection down meta event.
 	SwapSelectedDown,
 	/// The swap selection up meta event.
 	SwapSelectedUp,
+	/// The toggle fold meta event.
+	ToggleFold,
+	/// The toggle graph meta event.
+	ToggleGraph,
+	/// The toggle side by side diff meta event.
+	ToggleSideBySide,
 	/// The toggle visual mode meta event.
 	ToggleVisualMode,
 	/// The undo meta event.
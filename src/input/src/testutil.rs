@@ -16,7 +16,10 @@ fn map_event_to_crossterm(event: Event) -> crossterm::event::Event {
 				MetaEvent::ActionPick => KeyEvent::from(KeyCode::Char('p')),
 				MetaEvent::ActionReword => KeyEvent::from(KeyCode::Char('r')),
 				MetaEvent::ActionSquash => KeyEvent::from(KeyCode::Char('s')),
+				MetaEvent::Autosquash => KeyEvent::from(KeyCode::Char('A')),
+				MetaEvent::CycleFixupOption => KeyEvent::from(KeyCode::Char('u')),
 				MetaEvent::Delete => KeyEvent::from(KeyCode::Delete),
+				MetaEvent::DropAppliedUpstream => KeyEvent::from(KeyCode::Char('D')),
 				MetaEvent::Edit => KeyEvent::from(KeyCode::Char('E')),
 				MetaEvent::Exit => {
 					KeyEvent {
@@ -24,6 +27,7 @@ fn map_event_to_crossterm(event: Event) -> crossterm::event::Event {
 						modifiers: KeyModifiers::CONTROL,
 					}
 				},
+				MetaEvent::Filter => KeyEvent::from(KeyCode::Char('F')),
 				MetaEvent::ForceAbort => KeyEvent::from(KeyCode::Char('Q')),
 				MetaEvent::ForceRebase => KeyEvent::from(KeyCode::Char('W')),
 				MetaEvent::Help => KeyEvent::from(KeyCode::Char('?')),
@@ -42,8 +46,12 @@ fn map_event_to_crossterm(event: Event) -> crossterm::event::Event {
 				MetaEvent::MoveCursorPageUp => KeyEvent::from(KeyCode::PageUp),
 				MetaEvent::MoveCursorRight => KeyEvent::from(KeyCode::Right),
 				MetaEvent::MoveCursorUp => KeyEvent::from(KeyCode::Up),
+				MetaEvent::NextFile => KeyEvent::from(KeyCode::Char(']')),
+				MetaEvent::NextHunk => KeyEvent::from(KeyCode::Char('}')),
 				MetaEvent::No => KeyEvent::from(KeyCode::Char('n')),
 				MetaEvent::OpenInEditor => KeyEvent::from(KeyCode::Char('!')),
+				MetaEvent::PreviousFile => KeyEvent::from(KeyCode::Char('[')),
+				MetaEvent::PreviousHunk => KeyEvent::from(KeyCode::Char('{')),
 				MetaEvent::Rebase => KeyEvent::from(KeyCode::Char('w')),
 				MetaEvent::Redo => {
 					KeyEvent {
@@ -59,10 +67,18 @@ fn map_event_to_crossterm(event: Event) -> crossterm::event::Event {
 				MetaEvent::ScrollRight => KeyEvent::from(KeyCode::Right),
 				MetaEvent::ScrollTop => KeyEvent::from(KeyCode::Home),
 				MetaEvent::ScrollUp => KeyEvent::from(KeyCode::Up),
+				MetaEvent::SearchNext => KeyEvent::from(KeyCode::Char('n')),
+				MetaEvent::SearchPrevious => KeyEvent::from(KeyCode::Char('N')),
+				MetaEvent::SearchStart => KeyEvent::from(KeyCode::Char('/')),
+				MetaEvent::ShowCombinedDiff => KeyEvent::from(KeyCode::Char('s')),
 				MetaEvent::ShowCommit => KeyEvent::from(KeyCode::Char('c')),
 				MetaEvent::ShowDiff => KeyEvent::from(KeyCode::Char('d')),
+				MetaEvent::ShowFileList => KeyEvent::from(KeyCode::Char('f')),
 				MetaEvent::SwapSelectedDown => KeyEvent::from(KeyCode::Char('j')),
 				MetaEvent::SwapSelectedUp => KeyEvent::from(KeyCode::Char('k')),
+				MetaEvent::ToggleFold => KeyEvent::from(KeyCode::Char('z')),
+				MetaEvent::ToggleGraph => KeyEvent::from(KeyCode::Char('G')),
+				MetaEvent::ToggleSideBySide => KeyEvent::from(KeyCode::Char('S')),
 				MetaEvent::ToggleVisualMode => KeyEvent::from(KeyCode::Char('v')),
 				MetaEvent::Undo => {
 					KeyEvent {
@@ -96,8 +112,12 @@ pub fn create_test_keybindings() -> KeyBindings {
 		action_pick: vec![Event::from(KeyCode::Char('p'))],
 		action_reword: vec![Event::from(KeyCode::Char('r'))],
 		action_squash: vec![Event::from(KeyCode::Char('s'))],
+		autosquash: vec![Event::from(KeyCode::Char('A'))],
 		confirm_yes: vec![Event::from(KeyCode::Char('y'))],
+		cycle_fixup_option: vec![Event::from(KeyCode::Char('u'))],
+		drop_applied_upstream: vec![Event::from(KeyCode::Char('D'))],
 		edit: vec![Event::from(KeyCode::Char('E'))],
+		filter: vec![Event::from(KeyCode::Char('F'))],
 		force_abort: vec![Event::from(KeyCode::Char('Q'))],
 		force_rebase: vec![Event::from(KeyCode::Char('W'))],
 		help: vec![Event::from(KeyCode::Char('?'))],
@@ -112,7 +132,11 @@ pub fn create_test_keybindings() -> KeyBindings {
 		move_selection_up: vec![Event::from(KeyCode::Char('k'))],
 		move_up: vec![Event::from(KeyCode::Up)],
 		move_up_step: vec![Event::from(KeyCode::PageUp)],
+		next_file: vec![Event::from(KeyCode::Char(']'))],
+		next_hunk: vec![Event::from(KeyCode::Char('}'))],
 		open_in_external_editor: vec![Event::from(KeyCode::Char('!'))],
+		previous_file: vec![Event::from(KeyCode::Char('['))],
+		previous_hunk: vec![Event::from(KeyCode::Char('{'))],
 		rebase: vec![Event::from(KeyCode::Char('w'))],
 		redo: vec![Event::Key({
 			KeyEvent {
@@ -121,8 +145,16 @@ pub fn create_test_keybindings() -> KeyBindings {
 			}
 		})],
 		remove_line: vec![Event::from(KeyCode::Delete)],
+		search: vec![Event::from(KeyCode::Char('/'))],
+		search_next: vec![Event::from(KeyCode::Char('n'))],
+		search_previous: vec![Event::from(KeyCode::Char('N'))],
+		show_combined_diff: vec![Event::from(KeyCode::Char('s'))],
 		show_commit: vec![Event::from(KeyCode::Char('c'))],
 		show_diff: vec![Event::from(KeyCode::Char('d'))],
+		show_file_list: vec![Event::from(KeyCode::Char('f'))],
+		toggle_fold: vec![Event::from(KeyCode::Char('z'))],
+		toggle_graph: vec![Event::from(KeyCode::Char('G'))],
+		toggle_side_by_side: vec![Event::from(KeyCode::Char('S'))],
 		toggle_visual_mode: vec![Event::from(KeyCode::Char('v'))],
 		undo: vec![Event::Key({
 			KeyEvent {
@@ -18,6 +18,8 @@ pub enum Action {
 	Noop,
 	/// A pick action.
 	Pick,
+	/// A revert action, only used by the sequencer of `git revert`.
+	Revert,
 	/// A reword action.
 	Reword,
 	/// A squash action.
@@ -28,6 +30,8 @@ pub enum Action {
 	Reset,
 	/// A merge action.
 	Merge,
+	/// An update-ref action.
+	UpdateRef,
 }
 
 impl Action {
@@ -45,8 +49,10 @@ impl Action {
 			Self::Noop => "noop",
 			Self::Pick => "pick",
 			Self::Reset => "reset",
+			Self::Revert => "revert",
 			Self::Reword => "reword",
 			Self::Squash => "squash",
+			Self::UpdateRef => "update-ref",
 		})
 	}
 
@@ -64,8 +70,10 @@ impl Action {
 			Self::Noop => "n",
 			Self::Pick => "p",
 			Self::Reset => "t",
+			Self::Revert => "v",
 			Self::Reword => "r",
 			Self::Squash => "s",
+			Self::UpdateRef => "u",
 		})
 	}
 
@@ -73,7 +81,14 @@ impl Action {
 	#[must_use]
 	pub const fn is_static(self) -> bool {
 		match self {
-			Self::Break | Self::Exec | Self::Noop | Self::Reset | Self::Label | Self::Merge => true,
+			Self::Break
+			| Self::Exec
+			| Self::Noop
+			| Self::Reset
+			| Self::Label
+			| Self::Merge
+			| Self::Revert
+			| Self::UpdateRef => true,
 			Self::Drop | Self::Edit | Self::Fixup | Self::Pick | Self::Reword | Self::Squash => false,
 		}
 	}
@@ -91,11 +106,14 @@ impl TryFrom<&str> for Action {
 			"fixup" | "f" => Ok(Self::Fixup),
 			"noop" | "n" => Ok(Self::Noop),
 			"pick" | "p" => Ok(Self::Pick),
+			// like git, revert has no abbreviation
+			"revert" => Ok(Self::Revert),
 			"reword" | "r" => Ok(Self::Reword),
 			"squash" | "s" => Ok(Self::Squash),
 			"label" | "l" => Ok(Self::Label),
 			"reset" | "t" => Ok(Self::Reset),
 			"merge" | "m" => Ok(Self::Merge),
+			"update-ref" | "u" => Ok(Self::UpdateRef),
 			_ => Err(anyhow!("Invalid action: {}", s)),
 		}
 	}
@@ -115,11 +133,13 @@ mod tests {
 	#[case::fixup(Action::Fixup, "fixup")]
 	#[case::noop(Action::Noop, "noop")]
 	#[case::pick(Action::Pick, "pick")]
+	#[case::revert(Action::Revert, "revert")]
 	#[case::reword(Action::Reword, "reword")]
 	#[case::squash(Action::Squash, "squash")]
 	#[case::label(Action::Label, "label")]
 	#[case::reset(Action::Reset, "reset")]
 	#[case::merge(Action::Merge, "merge")]
+	#[case::update_ref(Action::UpdateRef, "update-ref")]
 	fn to_string(#[case] action: Action, #[case] expected: &str) {
 		assert_eq!(action.as_string(), expected);
 	}
@@ -139,6 +159,7 @@ mod tests {
 	#[case::noop("noop", Action::Noop)]
 	#[case::p("p", Action::Pick)]
 	#[case::pick("pick", Action::Pick)]
+	#[case::revert("revert", Action::Revert)]
 	#[case::r("r", Action::Reword)]
 	#[case::reword("reword", Action::Reword)]
 	#[case::s("s", Action::Squash)]
@@ -149,10 +170,17 @@ mod tests {
 	#[case::reset("reset", Action::Reset)]
 	#[case::m("m", Action::Merge)]
 	#[case::merge("merge", Action::Merge)]
+	#[case::u("u", Action::UpdateRef)]
+	#[case::update_ref("update-ref", Action::UpdateRef)]
 	fn try_from(#[case] action_str: &str, #[case] expected: Action) {
 		assert_eq!(Action::try_from(action_str).unwrap(), expected);
 	}
 
+	#[test]
+	fn action_try_from_revert_abbreviation() {
+		assert!(Action::try_from("v").is_err());
+	}
+
 	#[test]
 	fn action_try_from_invalid() {
 		assert_eq!(
@@ -169,11 +197,13 @@ mod tests {
 	#[case::f(Action::Fixup, "f")]
 	#[case::n(Action::Noop, "n")]
 	#[case::p(Action::Pick, "p")]
+	#[case::v(Action::Revert, "v")]
 	#[case::r(Action::Reword, "r")]
 	#[case::s(Action::Squash, "s")]
 	#[case::l(Action::Label, "l")]
 	#[case::t(Action::Reset, "t")]
 	#[case::m(Action::Merge, "m")]
+	#[case::u(Action::UpdateRef, "u")]
 	fn to_abbreviation(#[case] action: Action, #[case] expected: &str) {
 		assert_eq!(action.to_abbreviation(), expected);
 	}
@@ -191,6 +221,8 @@ mod tests {
 	#[case::squash(Action::Label, true)]
 	#[case::squash(Action::Reset, true)]
 	#[case::squash(Action::Merge, true)]
+	#[case::update_ref(Action::UpdateRef, true)]
+	#[case::revert(Action::Revert, true)]
 	fn module_lifecycle(#[case] action: Action, #[case] expected: bool) {
 		assert_eq!(action.is_static(), expected);
 	}
@@ -0,0 +1,179 @@
+use super::{action::Action, line::Line};
+
+const FIXUP_PREFIX: &str = "fixup! ";
+const SQUASH_PREFIX: &str = "squash! ";
+const AMEND_PREFIX: &str = "amend! ";
+
+const fn is_commit_action(action: Action) -> bool {
+	match action {
+		Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash => true,
+		Action::Break
+		| Action::Drop
+		| Action::Exec
+		| Action::Label
+		| Action::Merge
+		| Action::Noop
+		| Action::Reset
+		| Action::Revert
+		| Action::UpdateRef => false,
+	}
+}
+
+fn strip_prefix(summary: &str) -> Option<&str> {
+	summary
+		.strip_prefix(FIXUP_PREFIX)
+		.or_else(|| summary.strip_prefix(SQUASH_PREFIX))
+		.or_else(|| summary.strip_prefix(AMEND_PREFIX))
+}
+
+// like git, a summary of "fixup! fixup! Foo" targets the commit "Foo"
+fn get_target_summary(summary: &str) -> Option<&str> {
+	let mut target = strip_prefix(summary)?;
+	while let Some(stripped) = strip_prefix(target) {
+		target = stripped;
+	}
+	Some(target)
+}
+
+fn find_target(lines: &[Line], index: usize, target: &str) -> Option<usize> {
+	let candidates = || (0..index).filter(|&i| is_commit_action(*lines[i].get_action()));
+	candidates()
+		.find(|&i| lines[i].get_content() == target)
+		.or_else(|| {
+			if target.contains(' ') {
+				None
+			}
+			else {
+				candidates().find(|&i| lines[i].get_hash().starts_with(target))
+			}
+		})
+		.or_else(|| candidates().find(|&i| lines[i].get_content().starts_with(target)))
+}
+
+fn push_with_fixups(fixups: &[Vec<usize>], index: usize, order: &mut Vec<usize>) {
+	order.push(index);
+	for &fixup_index in &fixups[index] {
+		push_with_fixups(fixups, fixup_index, order);
+	}
+}
+
+/// Get the lines rearranged the way `git rebase --autosquash` would, with every `fixup!`, `squash!` and `amend!`
+/// commit moved directly below the commit it targets and its action set to match.
+pub(crate) fn autosquash(lines: &[Line]) -> Vec<Line> {
+	let mut fixups: Vec<Vec<usize>> = vec![vec![]; lines.len()];
+	let mut is_fixup = vec![false; lines.len()];
+
+	for (index, line) in lines.iter().enumerate() {
+		if !is_commit_action(*line.get_action()) {
+			continue;
+		}
+		if let Some(target_index) =
+			get_target_summary(line.get_content()).and_then(|target| find_target(lines, index, target))
+		{
+			fixups[target_index].push(index);
+			is_fixup[index] = true;
+		}
+	}
+
+	let mut order = vec![];
+	for index in 0..lines.len() {
+		if !is_fixup[index] {
+			push_with_fixups(&fixups, index, &mut order);
+		}
+	}
+
+	// comments keep their position in the file, so they are taken from the line that was in the same position
+	order
+		.into_iter()
+		.enumerate()
+		.map(|(position, index)| {
+			let mut line = lines[index].clone();
+			line.set_comments(lines[position].get_comments().to_vec());
+			if is_fixup[index] {
+				let summary = line.get_content();
+				if summary.starts_with(SQUASH_PREFIX) {
+					line.set_action(Action::Squash);
+				}
+				else {
+					let is_amend = summary.starts_with(AMEND_PREFIX);
+					line.set_action(Action::Fixup);
+					if is_amend {
+						line.set_option(Some("-C"));
+					}
+				}
+			}
+			line
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+
+	fn autosquash_lines(lines: &[&str]) -> Vec<String> {
+		let lines = lines.iter().map(|l| Line::new(l).unwrap()).collect::<Vec<Line>>();
+		autosquash(&lines).iter().map(Line::to_text).collect()
+	}
+
+	#[rstest]
+	#[case::empty(&[], &[])]
+	#[case::no_fixups(&["pick aaa c1", "pick bbb c2"], &["pick aaa c1", "pick bbb c2"])]
+	#[case::fixup(
+		&["pick aaa c1", "pick bbb c2", "pick ccc fixup! c1"],
+		&["pick aaa c1", "fixup ccc fixup! c1", "pick bbb c2"]
+	)]
+	#[case::squash(
+		&["pick aaa c1", "pick bbb c2", "pick ccc squash! c1"],
+		&["pick aaa c1", "squash ccc squash! c1", "pick bbb c2"]
+	)]
+	#[case::amend(
+		&["pick aaa c1", "pick bbb c2", "pick ccc amend! c1"],
+		&["pick aaa c1", "fixup -C ccc amend! c1", "pick bbb c2"]
+	)]
+	#[case::multiple_fixups_keep_order(
+		&["pick aaa c1", "pick bbb c2", "pick ccc fixup! c1", "pick ddd squash! c1"],
+		&["pick aaa c1", "fixup ccc fixup! c1", "squash ddd squash! c1", "pick bbb c2"]
+	)]
+	#[case::nested_prefix(
+		&["pick aaa c1", "pick bbb c2", "pick ccc fixup! fixup! c1"],
+		&["pick aaa c1", "fixup ccc fixup! fixup! c1", "pick bbb c2"]
+	)]
+	#[case::fixup_of_fixup(
+		&["pick aaa c1", "pick bbb fixup! c1", "pick ccc c2", "pick ddd fixup! bbb"],
+		&["pick aaa c1", "fixup bbb fixup! c1", "fixup ddd fixup! bbb", "pick ccc c2"]
+	)]
+	#[case::by_hash(
+		&["pick aaa1111 c1", "pick bbb c2", "pick ccc fixup! aaa1"],
+		&["pick aaa1111 c1", "fixup ccc fixup! aaa1", "pick bbb c2"]
+	)]
+	#[case::by_summary_prefix(
+		&["pick aaa long summary", "pick bbb c2", "pick ccc fixup! long sum"],
+		&["pick aaa long summary", "fixup ccc fixup! long sum", "pick bbb c2"]
+	)]
+	#[case::target_after_fixup(
+		&["pick ccc fixup! c1", "pick aaa c1"],
+		&["pick ccc fixup! c1", "pick aaa c1"]
+	)]
+	#[case::unknown_target(
+		&["pick aaa c1", "pick ccc fixup! c9"],
+		&["pick aaa c1", "pick ccc fixup! c9"]
+	)]
+	#[case::dropped_target(
+		&["drop aaa c1", "pick ccc fixup! c1"],
+		&["drop aaa c1", "pick ccc fixup! c1"]
+	)]
+	#[case::dropped_fixup(
+		&["pick aaa c1", "pick bbb c2", "drop ccc fixup! c1"],
+		&["pick aaa c1", "pick bbb c2", "drop ccc fixup! c1"]
+	)]
+	#[case::non_commit_lines_stay(
+		&["pick aaa c1", "exec make", "pick ccc fixup! c1", "break"],
+		&["pick aaa c1", "fixup ccc fixup! c1", "exec make", "break"]
+	)]
+	fn rearrange(#[case] lines: &[&str], #[case] expected: &[&str]) {
+		assert_eq!(autosquash_lines(lines), expected);
+	}
+}
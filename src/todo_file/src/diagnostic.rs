@@ -0,0 +1,280 @@
+use std::{
+	collections::HashSet,
+	fmt::{Display, Formatter},
+};
+
+use super::{action::Action, line::Line};
+
+/// Describes a problem found with a line in the rebase file.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::exhaustive_enums)]
+pub enum DiagnosticKind {
+	/// A squash or fixup line that has no previous commit to be combined into.
+	NoPreviousCommit(Action),
+	/// A reset line that targets a label that is not defined before it.
+	UndefinedResetLabel(String),
+	/// A merge line that references a label that is not defined before it.
+	UnknownMergeLabel(String),
+	/// A commit that already appears on an earlier line.
+	DuplicateCommit(String),
+	/// An action that git does not support for the kind of todo file, such as a revert during a rebase.
+	UnsupportedAction(Action, String),
+}
+
+impl Display for DiagnosticKind {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		match *self {
+			Self::NoPreviousCommit(action) => write!(f, "Cannot {} without a previous commit", action.as_string()),
+			Self::UndefinedResetLabel(ref label) => write!(f, "Reset to undefined label: {}", label),
+			Self::UnknownMergeLabel(ref label) => write!(f, "Merge of unknown label: {}", label),
+			Self::DuplicateCommit(ref hash) => write!(f, "Commit {} appears more than once", hash),
+			Self::UnsupportedAction(action, ref operation) => {
+				write!(f, "Cannot {} during a {}", action.as_string(), operation)
+			},
+		}
+	}
+}
+
+/// Describes a problem found on a single line of the rebase file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+	line_index: usize,
+	kind: DiagnosticKind,
+}
+
+impl Diagnostic {
+	const fn new(line_index: usize, kind: DiagnosticKind) -> Self {
+		Self { line_index, kind }
+	}
+
+	/// Get the index of the line with the problem.
+	#[must_use]
+	pub const fn get_line_index(&self) -> usize {
+		self.line_index
+	}
+
+	/// Get the kind of problem.
+	#[must_use]
+	pub const fn get_kind(&self) -> &DiagnosticKind {
+		&self.kind
+	}
+}
+
+impl Display for Diagnostic {
+	#[inline]
+	fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.kind)
+	}
+}
+
+// a reset or merge can also target a commit, which git will accept without a matching label
+fn is_commit_like(reference: &str) -> bool {
+	(4..=40).contains(&reference.len()) && reference.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn is_same_commit(a: &str, b: &str) -> bool {
+	a.starts_with(b) || b.starts_with(a)
+}
+
+// like git, a sequencer todo file only supports the action of the first line, which is a pick for a cherry-pick, and
+// a revert for a revert
+fn get_supported_action(lines: &[Line], is_sequencer: bool) -> (Option<Action>, &'static str) {
+	if !is_sequencer {
+		return (None, "rebase");
+	}
+	match lines.first().map(Line::get_action) {
+		Some(&Action::Revert) => (Some(Action::Revert), "revert"),
+		_ => (Some(Action::Pick), "cherry-pick"),
+	}
+}
+
+/// Check the lines of a rebase file for problems that would cause the rebase to fail, or produce unexpected results.
+/// The lines that have already been applied are not checked, but the labels and commits they create are available.
+/// The lines of a sequencer todo file, as used by `git cherry-pick` and `git revert`, must all share one action.
+pub(crate) fn lint(done_lines: &[Line], lines: &[Line], is_sequencer: bool) -> Vec<Diagnostic> {
+	let mut diagnostics = vec![];
+	let (supported_action, operation) = get_supported_action(lines, is_sequencer);
+	let mut labels = done_lines
+		.iter()
+		.filter(|line| line.get_action() == &Action::Label)
+		.map(Line::get_content)
+		.collect::<HashSet<&str>>();
+	let mut hashes: Vec<&str> = vec![];
+	let mut has_previous_commit = done_lines
+		.iter()
+		.any(|line| line.get_action() != &Action::Drop && line.get_action() != &Action::Noop);
+
+	for (index, line) in lines.iter().enumerate() {
+		let action = *line.get_action();
+		let is_supported = supported_action.map_or(action != Action::Revert, |supported| supported == action);
+		if !is_supported {
+			diagnostics.push(Diagnostic::new(
+				index,
+				DiagnosticKind::UnsupportedAction(action, String::from(operation)),
+			));
+			continue;
+		}
+
+		match action {
+			Action::Fixup | Action::Squash => {
+				if !has_previous_commit {
+					diagnostics.push(Diagnostic::new(index, DiagnosticKind::NoPreviousCommit(action)));
+				}
+			},
+			Action::Label => {
+				let _new = labels.insert(line.get_content());
+			},
+			Action::Reset => {
+				let content = line.get_content();
+				let label = content.split(' ').next().unwrap_or("");
+				if !content.starts_with("[new root]") && !labels.contains(label) && !is_commit_like(label) {
+					diagnostics.push(Diagnostic::new(
+						index,
+						DiagnosticKind::UndefinedResetLabel(String::from(label)),
+					));
+				}
+			},
+			Action::Merge => {
+				for label in line.get_labels() {
+					if !labels.contains(label) && !is_commit_like(label) {
+						diagnostics.push(Diagnostic::new(
+							index,
+							DiagnosticKind::UnknownMergeLabel(String::from(label)),
+						));
+					}
+				}
+			},
+			Action::Break
+			| Action::Drop
+			| Action::Edit
+			| Action::Exec
+			| Action::Noop
+			| Action::Pick
+			| Action::Revert
+			| Action::Reword
+			| Action::UpdateRef => {},
+		}
+
+		match action {
+			Action::Edit | Action::Fixup | Action::Pick | Action::Revert | Action::Reword | Action::Squash => {
+				let hash = line.get_hash();
+				if hashes.iter().any(|h| is_same_commit(h, hash)) {
+					diagnostics.push(Diagnostic::new(
+						index,
+						DiagnosticKind::DuplicateCommit(String::from(hash)),
+					));
+				}
+				else {
+					hashes.push(hash);
+				}
+			},
+			Action::Break
+			| Action::Drop
+			| Action::Exec
+			| Action::Label
+			| Action::Merge
+			| Action::Noop
+			| Action::Reset
+			| Action::UpdateRef => {},
+		}
+
+		// matches git, which only rejects a fixup when every line before it is dropped
+		if action != Action::Drop && action != Action::Noop {
+			has_previous_commit = true;
+		}
+	}
+	diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+	use rstest::rstest;
+
+	use super::*;
+
+	fn lint_lines(lines: &[&str]) -> Vec<String> {
+		let lines = lines.iter().map(|l| Line::new(l).unwrap()).collect::<Vec<Line>>();
+		lint(&[], &lines, false)
+			.iter()
+			.map(|d| format!("{}: {}", d.get_line_index(), d))
+			.collect()
+	}
+
+	#[rstest]
+	#[case::empty(&[])]
+	#[case::picks(&["pick aaa c1", "pick bbb c2"])]
+	#[case::fixup_after_pick(&["pick aaa c1", "fixup bbb c2", "squash ccc c3"])]
+	#[case::fixup_after_exec_and_pick(&["pick aaa c1", "exec make", "fixup bbb c2"])]
+	#[case::fixup_after_reset(&["reset [new root]", "fixup bbb c2"])]
+	#[case::fixup_after_label(&["label onto", "fixup bbb c2"])]
+	#[case::reset_defined_label(&["label onto", "reset onto"])]
+	#[case::reset_new_root(&["reset [new root]"])]
+	#[case::reset_commit(&["reset abc123 # subject"])]
+	#[case::merge_defined_labels(&["label a", "label b", "merge -C aaa a b # Merge"])]
+	#[case::merge_commit(&["merge -C aaa 1234abcd"])]
+	#[case::drop_duplicate(&["pick aaa c1", "drop aaa c1"])]
+	fn lint_valid(#[case] lines: &[&str]) {
+		assert!(lint_lines(lines).is_empty());
+	}
+
+	#[rstest]
+	#[case::fixup_first(&["fixup aaa c1", "pick bbb c2"], "0: Cannot fixup without a previous commit")]
+	#[case::squash_first(&["squash aaa c1"], "0: Cannot squash without a previous commit")]
+	#[case::fixup_after_drop(&["drop aaa c1", "fixup bbb c2"], "1: Cannot fixup without a previous commit")]
+	#[case::reset_undefined(&["reset onto"], "0: Reset to undefined label: onto")]
+	#[case::reset_label_defined_after(&["reset onto", "label onto"], "0: Reset to undefined label: onto")]
+	#[case::merge_unknown(&["label a", "merge -C aaa a b"], "1: Merge of unknown label: b")]
+	#[case::duplicate(&["pick aaa c1", "pick bbb c2", "pick aaa c1"], "2: Commit aaa appears more than once")]
+	#[case::duplicate_prefix(&["pick aaabbb c1", "reword aaa c1"], "1: Commit aaa appears more than once")]
+	fn lint_invalid(#[case] lines: &[&str], #[case] expected: &str) {
+		assert_eq!(lint_lines(lines), vec![expected]);
+	}
+
+	#[test]
+	fn lint_multiple_problems() {
+		assert_eq!(lint_lines(&["squash aaa c1", "reset foo", "pick aaa c1"]), vec![
+			"0: Cannot squash without a previous commit",
+			"1: Reset to undefined label: foo",
+			"2: Commit aaa appears more than once",
+		]);
+	}
+
+	#[test]
+	fn lint_with_done_lines() {
+		let done_lines = vec![Line::new("label onto").unwrap(), Line::new("pick aaa c1").unwrap()];
+		let lines = vec![Line::new("fixup bbb c2").unwrap(), Line::new("reset onto").unwrap()];
+		assert!(lint(&done_lines, &lines, false).is_empty());
+	}
+
+	#[test]
+	fn lint_revert_in_rebase() {
+		assert_eq!(lint_lines(&["pick aaa c1", "revert bbb c2"]), vec![
+			"1: Cannot revert during a rebase"
+		]);
+	}
+
+	fn lint_sequencer_lines(lines: &[&str]) -> Vec<String> {
+		let lines = lines.iter().map(|l| Line::new(l).unwrap()).collect::<Vec<Line>>();
+		lint(&[], &lines, true)
+			.iter()
+			.map(|d| format!("{}: {}", d.get_line_index(), d))
+			.collect()
+	}
+
+	#[rstest]
+	#[case::cherry_pick(&["pick aaa c1", "pick bbb c2"])]
+	#[case::revert(&["revert aaa c1", "revert bbb c2"])]
+	fn lint_sequencer_valid(#[case] lines: &[&str]) {
+		assert!(lint_sequencer_lines(lines).is_empty());
+	}
+
+	#[rstest]
+	#[case::revert_during_cherry_pick(&["pick aaa c1", "revert bbb c2"], "1: Cannot revert during a cherry-pick")]
+	#[case::pick_during_revert(&["revert aaa c1", "pick bbb c2"], "1: Cannot pick during a revert")]
+	#[case::squash(&["pick aaa c1", "squash bbb c2"], "1: Cannot squash during a cherry-pick")]
+	#[case::exec_first(&["exec make", "pick aaa c1"], "0: Cannot exec during a cherry-pick")]
+	fn lint_sequencer_invalid(#[case] lines: &[&str], #[case] expected: &str) {
+		assert_eq!(lint_sequencer_lines(lines), vec![expected]);
+	}
+}
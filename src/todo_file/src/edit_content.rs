@@ -5,6 +5,8 @@ use super::action::Action;
 pub struct EditContext {
 	action: Option<Action>,
 	content: Option<String>,
+	option: Option<String>,
+	remove_option: bool,
 }
 
 impl EditContext {
@@ -14,6 +16,8 @@ impl EditContext {
 		Self {
 			action: None,
 			content: None,
+			option: None,
+			remove_option: false,
 		}
 	}
 
@@ -31,6 +35,22 @@ impl EditContext {
 		self
 	}
 
+	/// Set the option.
+	#[must_use]
+	pub fn option(mut self, option: &str) -> Self {
+		self.option = Some(option.to_owned());
+		self.remove_option = false;
+		self
+	}
+
+	/// Remove the option.
+	#[must_use]
+	pub fn without_option(mut self) -> Self {
+		self.option = None;
+		self.remove_option = true;
+		self
+	}
+
 	/// Get the action.
 	#[must_use]
 	pub const fn get_action(&self) -> &Option<Action> {
@@ -42,6 +62,18 @@ impl EditContext {
 	pub const fn get_content(&self) -> &Option<String> {
 		&self.content
 	}
+
+	/// Get the option.
+	#[must_use]
+	pub const fn get_option(&self) -> &Option<String> {
+		&self.option
+	}
+
+	/// Get if the option should be removed.
+	#[must_use]
+	pub const fn is_removing_option(&self) -> bool {
+		self.remove_option
+	}
 }
 
 #[cfg(test)]
@@ -53,6 +85,8 @@ mod tests {
 		let edit_context = EditContext::new();
 		assert_eq!(edit_context.get_action(), &None);
 		assert_eq!(edit_context.get_content(), &None);
+		assert_eq!(edit_context.get_option(), &None);
+		assert!(!edit_context.is_removing_option());
 	}
 
 	#[test]
@@ -75,4 +109,20 @@ mod tests {
 		assert_eq!(edit_context.get_action(), &Some(Action::Edit));
 		assert_eq!(edit_context.get_content(), &Some(String::from("test content")));
 	}
+
+	#[test]
+	fn with_option() {
+		let edit_context = EditContext::new().option("-C");
+		assert_eq!(edit_context.get_action(), &None);
+		assert_eq!(edit_context.get_content(), &None);
+		assert_eq!(edit_context.get_option(), &Some(String::from("-C")));
+		assert!(!edit_context.is_removing_option());
+	}
+
+	#[test]
+	fn without_option() {
+		let edit_context = EditContext::new().option("-C").without_option();
+		assert_eq!(edit_context.get_option(), &None);
+		assert!(edit_context.is_removing_option());
+	}
 }
@@ -6,6 +6,7 @@ pub(crate) struct HistoryItem {
 	pub(crate) end_index: usize,
 	pub(crate) operation: Operation,
 	pub(crate) lines: Vec<Line>,
+	pub(crate) trailing_comments: Vec<String>,
 }
 
 impl HistoryItem {
@@ -15,6 +16,7 @@ impl HistoryItem {
 			start_index,
 			end_index,
 			lines,
+			trailing_comments: vec![],
 		}
 	}
 
@@ -24,6 +26,7 @@ impl HistoryItem {
 			start_index,
 			end_index,
 			lines: vec![],
+			trailing_comments: vec![],
 		}
 	}
 
@@ -33,6 +36,17 @@ impl HistoryItem {
 			start_index,
 			end_index,
 			lines,
+			trailing_comments: vec![],
+		}
+	}
+
+	pub(crate) fn new_replace(lines: Vec<Line>, trailing_comments: Vec<String>) -> Self {
+		Self {
+			operation: Operation::Replace,
+			start_index: 0,
+			end_index: 0,
+			lines,
+			trailing_comments,
 		}
 	}
 
@@ -42,6 +56,7 @@ impl HistoryItem {
 			start_index,
 			end_index,
 			lines: vec![],
+			trailing_comments: vec![],
 		}
 	}
 
@@ -51,6 +66,7 @@ impl HistoryItem {
 			start_index,
 			end_index,
 			lines: vec![],
+			trailing_comments: vec![],
 		}
 	}
 }
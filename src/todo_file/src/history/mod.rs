@@ -4,7 +4,7 @@ mod operation;
 #[cfg(test)]
 mod tests;
 
-use std::{cmp::min, collections::VecDeque};
+use std::{cmp::min, collections::VecDeque, mem};
 
 pub(crate) use super::history::{history_item::HistoryItem, operation::Operation};
 use super::{
@@ -28,7 +28,11 @@ impl History {
 		}
 	}
 
-	pub(crate) fn apply_operation(lines: &mut Vec<Line>, operation: &HistoryItem) -> HistoryItem {
+	pub(crate) fn apply_operation(
+		lines: &mut Vec<Line>,
+		trailing_comments: &mut Vec<String>,
+		operation: &HistoryItem,
+	) -> HistoryItem {
 		match operation.operation {
 			Operation::Modify => {
 				let range = if operation.end_index <= operation.start_index {
@@ -46,11 +50,17 @@ impl History {
 				HistoryItem::new_modify(operation.start_index, operation.end_index, changed_lines)
 			},
 			Operation::Add => {
-				let removed_lines = remove_range(lines, operation.start_index, operation.end_index);
+				let removed_lines = remove_range(lines, trailing_comments, operation.start_index, operation.end_index);
 				HistoryItem::new_remove(operation.start_index, operation.end_index, removed_lines)
 			},
 			Operation::Remove => {
-				add_range(lines, &operation.lines, operation.start_index, operation.end_index);
+				add_range(
+					lines,
+					trailing_comments,
+					&operation.lines,
+					operation.start_index,
+					operation.end_index,
+				);
 				HistoryItem::new_add(operation.start_index, operation.end_index)
 			},
 			Operation::SwapUp => {
@@ -61,6 +71,12 @@ impl History {
 				swap_range_up(lines, operation.start_index + 1, operation.end_index + 1);
 				HistoryItem::new_swap_up(operation.start_index + 1, operation.end_index + 1)
 			},
+			Operation::Replace => {
+				HistoryItem::new_replace(
+					mem::replace(lines, operation.lines.clone()),
+					mem::replace(trailing_comments, operation.trailing_comments.clone()),
+				)
+			},
 		}
 	}
 
@@ -73,18 +89,26 @@ impl History {
 		}
 	}
 
-	pub(crate) fn undo(&mut self, current: &mut Vec<Line>) -> Option<(usize, usize)> {
+	pub(crate) fn undo(
+		&mut self,
+		current: &mut Vec<Line>,
+		trailing_comments: &mut Vec<String>,
+	) -> Option<(usize, usize)> {
 		self.undo_history.pop_back().map(|operation| {
-			let history = Self::apply_operation(current, &operation);
+			let history = Self::apply_operation(current, trailing_comments, &operation);
 			let update_range = Self::get_last_index_range(&history, current.len());
 			self.redo_history.push_back(history);
 			update_range
 		})
 	}
 
-	pub(crate) fn redo(&mut self, current: &mut Vec<Line>) -> Option<(usize, usize)> {
+	pub(crate) fn redo(
+		&mut self,
+		current: &mut Vec<Line>,
+		trailing_comments: &mut Vec<String>,
+	) -> Option<(usize, usize)> {
 		self.redo_history.pop_back().map(|operation| {
-			let history = Self::apply_operation(current, &operation);
+			let history = Self::apply_operation(current, trailing_comments, &operation);
 			let update_range = Self::get_last_index_range(&history, current.len());
 			self.undo_history.push_back(history);
 			update_range
@@ -98,7 +122,9 @@ impl History {
 
 	fn get_last_index_range(history_item: &HistoryItem, list_length: usize) -> (usize, usize) {
 		match history_item.operation {
-			Operation::Add | Operation::Modify => (history_item.start_index, history_item.end_index),
+			Operation::Add | Operation::Modify | Operation::Replace => {
+				(history_item.start_index, history_item.end_index)
+			},
 			Operation::Remove => {
 				let index = min(history_item.start_index, history_item.end_index);
 				if index == 0 || list_length == 0 {
@@ -124,9 +124,9 @@ fn undo_redo_add_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(0, 0));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(lines, "pick bbb c2", "pick ccc c3", "pick ddd c4", "pick eee c5");
-	assert_eq!(history.redo(&mut lines), Some((0, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -142,9 +142,9 @@ fn undo_redo_add_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(4, 4));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((3, 3)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((3, 3)));
 	assert_todo_lines!(lines, "pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4");
-	assert_eq!(history.redo(&mut lines), Some((4, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -160,9 +160,9 @@ fn undo_redo_add_middle() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(2, 2));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(lines, "pick aaa c1", "pick bbb c2", "pick ddd c4", "pick eee c5");
-	assert_eq!(history.redo(&mut lines), Some((2, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -178,9 +178,9 @@ fn undo_redo_add_range_start_index_at_top() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(0, 1));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(lines, "pick ccc c3", "pick ddd c4", "pick eee c5");
-	assert_eq!(history.redo(&mut lines), Some((0, 1)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -196,9 +196,9 @@ fn undo_redo_add_range_end_index_at_top() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(1, 0));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(lines, "pick ccc c3", "pick ddd c4", "pick eee c5");
-	assert_eq!(history.redo(&mut lines), Some((1, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((1, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -214,9 +214,9 @@ fn undo_redo_add_range_start_index_at_bottom() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(4, 3));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(lines, "pick aaa c1", "pick bbb c2", "pick ccc c3");
-	assert_eq!(history.redo(&mut lines), Some((4, 3)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 3)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -232,9 +232,9 @@ fn undo_redo_add_range_end_index_at_bottom() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_add(3, 4));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(lines, "pick aaa c1", "pick bbb c2", "pick ccc c3");
-	assert_eq!(history.redo(&mut lines), Some((3, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((3, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -250,7 +250,7 @@ fn undo_redo_remove_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_remove(0, 0, vec![Line::new("drop xxx cx").unwrap()]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"drop xxx cx",
@@ -260,7 +260,7 @@ fn undo_redo_remove_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -276,7 +276,7 @@ fn undo_redo_remove_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_remove(5, 5, vec![Line::new("drop xxx cx").unwrap()]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((5, 5)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((5, 5)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -286,7 +286,7 @@ fn undo_redo_remove_end() {
 		"pick eee c5",
 		"drop xxx cx"
 	);
-	assert_eq!(history.redo(&mut lines), Some((4, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -302,7 +302,7 @@ fn undo_redo_remove_middle() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_remove(2, 2, vec![Line::new("drop xxx cx").unwrap()]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -312,7 +312,7 @@ fn undo_redo_remove_middle() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((2, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -331,7 +331,7 @@ fn undo_redo_remove_range_start_index_top() {
 		Line::new("drop yyy cy").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 1)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 1)));
 	assert_todo_lines!(
 		lines,
 		"drop xxx cx",
@@ -342,7 +342,7 @@ fn undo_redo_remove_range_start_index_top() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -361,7 +361,7 @@ fn undo_redo_remove_range_start_index_bottom() {
 		Line::new("drop yyy cy").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((6, 5)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((6, 5)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -372,7 +372,7 @@ fn undo_redo_remove_range_start_index_bottom() {
 		"drop xxx cx",
 		"drop yyy cy"
 	);
-	assert_eq!(history.redo(&mut lines), Some((4, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -391,7 +391,7 @@ fn undo_redo_remove_range_end_index_top() {
 		Line::new("drop yyy cy").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((1, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((1, 0)));
 	assert_todo_lines!(
 		lines,
 		"drop xxx cx",
@@ -402,7 +402,7 @@ fn undo_redo_remove_range_end_index_top() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -421,7 +421,7 @@ fn undo_redo_remove_range_end_index_bottom() {
 		Line::new("drop yyy cy").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((5, 6)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((5, 6)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -432,7 +432,7 @@ fn undo_redo_remove_range_end_index_bottom() {
 		"drop xxx cx",
 		"drop yyy cy"
 	);
-	assert_eq!(history.redo(&mut lines), Some((4, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -448,7 +448,7 @@ fn undo_redo_swap_up_single_index_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(1, 1));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((1, 1)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((1, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick bbb c2",
@@ -457,7 +457,7 @@ fn undo_redo_swap_up_single_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -473,7 +473,7 @@ fn undo_redo_swap_up_single_index_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(4, 4));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((4, 4)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -482,7 +482,7 @@ fn undo_redo_swap_up_single_index_end() {
 		"pick eee c5",
 		"pick ddd c4"
 	);
-	assert_eq!(history.redo(&mut lines), Some((3, 3)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((3, 3)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -498,7 +498,7 @@ fn undo_redo_swap_up_single_index_middle() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(2, 2));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -507,7 +507,7 @@ fn undo_redo_swap_up_single_index_middle() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((1, 1)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((1, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -523,7 +523,7 @@ fn undo_redo_swap_up_range_down_index_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(1, 2));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((1, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((1, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick ccc c3",
@@ -532,7 +532,7 @@ fn undo_redo_swap_up_range_down_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 1)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -548,7 +548,7 @@ fn undo_redo_swap_up_range_down_index_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(3, 4));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((3, 4)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((3, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -557,7 +557,7 @@ fn undo_redo_swap_up_range_down_index_end() {
 		"pick ccc c3",
 		"pick ddd c4"
 	);
-	assert_eq!(history.redo(&mut lines), Some((2, 3)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 3)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -573,7 +573,7 @@ fn undo_redo_swap_up_range_up_index_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(2, 1));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 1)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick ccc c3",
@@ -582,7 +582,7 @@ fn undo_redo_swap_up_range_up_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((1, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((1, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -598,7 +598,7 @@ fn undo_redo_swap_up_range_up_index_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_up(4, 3));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((4, 3)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((4, 3)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -607,7 +607,7 @@ fn undo_redo_swap_up_range_up_index_end() {
 		"pick ccc c3",
 		"pick ddd c4"
 	);
-	assert_eq!(history.redo(&mut lines), Some((3, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((3, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -623,7 +623,7 @@ fn undo_redo_swap_down_range_down_index_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_down(0, 1));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 1)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick bbb c2",
@@ -632,7 +632,7 @@ fn undo_redo_swap_down_range_down_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((1, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((1, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -648,7 +648,7 @@ fn undo_redo_swap_down_range_down_index_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_down(2, 3));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 3)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 3)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -657,7 +657,7 @@ fn undo_redo_swap_down_range_down_index_end() {
 		"pick eee c5",
 		"pick ccc c3"
 	);
-	assert_eq!(history.redo(&mut lines), Some((3, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((3, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -673,7 +673,7 @@ fn undo_redo_swap_down_range_up_index_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_down(1, 0));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((1, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((1, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick bbb c2",
@@ -682,7 +682,7 @@ fn undo_redo_swap_down_range_up_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((2, 1)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 1)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -698,7 +698,7 @@ fn undo_redo_swap_down_range_up_index_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_swap_down(3, 2));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((3, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((3, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -707,7 +707,7 @@ fn undo_redo_swap_down_range_up_index_end() {
 		"pick eee c5",
 		"pick ccc c3"
 	);
-	assert_eq!(history.redo(&mut lines), Some((4, 3)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 3)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -723,7 +723,7 @@ fn undo_redo_modify_single_index_start() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_modify(0, 0, vec![Line::new("drop xxx cx").unwrap()]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"drop xxx cx",
@@ -732,7 +732,7 @@ fn undo_redo_modify_single_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -748,7 +748,7 @@ fn undo_redo_modify_single_index_end() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_modify(4, 4, vec![Line::new("drop xxx cx").unwrap()]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((4, 4)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -757,7 +757,7 @@ fn undo_redo_modify_single_index_end() {
 		"pick ddd c4",
 		"drop xxx cx"
 	);
-	assert_eq!(history.redo(&mut lines), Some((4, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -773,7 +773,7 @@ fn undo_redo_modify_single_index_middle() {
 	let mut history = History::new(10);
 	history.record(HistoryItem::new_modify(2, 2, vec![Line::new("drop xxx cx").unwrap()]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -782,7 +782,7 @@ fn undo_redo_modify_single_index_middle() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((2, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -802,7 +802,7 @@ fn undo_redo_modify_range_down_index_start() {
 		Line::new("drop xx3 c3").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((0, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 2)));
 	assert_todo_lines!(
 		lines,
 		"drop xx1 c1",
@@ -811,7 +811,7 @@ fn undo_redo_modify_range_down_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((0, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -831,7 +831,7 @@ fn undo_redo_modify_range_down_index_end() {
 		Line::new("drop xx3 c3").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 4)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -840,7 +840,7 @@ fn undo_redo_modify_range_down_index_end() {
 		"drop xx2 c2",
 		"drop xx3 c3"
 	);
-	assert_eq!(history.redo(&mut lines), Some((2, 4)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 4)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -860,7 +860,7 @@ fn undo_redo_modify_range_up_index_start() {
 		Line::new("drop xx3 c3").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((2, 0)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((2, 0)));
 	assert_todo_lines!(
 		lines,
 		"drop xx1 c1",
@@ -869,7 +869,7 @@ fn undo_redo_modify_range_up_index_start() {
 		"pick ddd c4",
 		"pick eee c5"
 	);
-	assert_eq!(history.redo(&mut lines), Some((2, 0)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((2, 0)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -889,7 +889,7 @@ fn undo_redo_modify_range_up_index_end() {
 		Line::new("drop xx3 c3").unwrap(),
 	]));
 	let mut lines = create_lines();
-	assert_eq!(history.undo(&mut lines), Some((4, 2)));
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((4, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -898,7 +898,7 @@ fn undo_redo_modify_range_up_index_end() {
 		"drop xx2 c2",
 		"drop xx3 c3"
 	);
-	assert_eq!(history.redo(&mut lines), Some((4, 2)));
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((4, 2)));
 	assert_todo_lines!(
 		lines,
 		"pick aaa c1",
@@ -909,6 +909,41 @@ fn undo_redo_modify_range_up_index_end() {
 	);
 }
 
+#[test]
+fn undo_redo_replace() {
+	let mut history = History::new(10);
+	history.record(HistoryItem::new_replace(
+		vec![Line::new("drop xx1 c1").unwrap(), Line::new("drop xx2 c2").unwrap()],
+		vec![],
+	));
+	let mut lines = create_lines();
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
+	assert_todo_lines!(lines, "drop xx1 c1", "drop xx2 c2");
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
+	assert_todo_lines!(
+		lines,
+		"pick aaa c1",
+		"pick bbb c2",
+		"pick ccc c3",
+		"pick ddd c4",
+		"pick eee c5"
+	);
+}
+
+#[test]
+fn undo_redo_replace_empty() {
+	let mut history = History::new(10);
+	history.record(HistoryItem::new_replace(
+		vec![Line::new("pick aaa c1").unwrap()],
+		vec![],
+	));
+	let mut lines = vec![];
+	assert_eq!(history.undo(&mut lines, &mut vec![]), Some((0, 0)));
+	assert_todo_lines!(lines, "pick aaa c1");
+	assert_eq!(history.redo(&mut lines, &mut vec![]), Some((0, 0)));
+	assert!(lines.is_empty());
+}
+
 #[test]
 fn reset() {
 	let mut history = History::new(3);
@@ -83,35 +83,56 @@
 //! This module is used to handle working with the rebase todo file.
 
 mod action;
+mod autosquash;
+mod diagnostic;
 mod edit_content;
 mod history;
 mod line;
+mod progress;
 mod utils;
 
 use std::{
+	cmp::{max, min},
 	fs::{read_to_string, File},
 	io::Write,
+	mem,
 	path::Path,
 	slice::Iter,
 };
 
 use anyhow::{anyhow, Result};
 
-pub use self::{action::Action, edit_content::EditContext, line::Line};
+pub use self::{
+	action::Action,
+	diagnostic::{Diagnostic, DiagnosticKind},
+	edit_content::EditContext,
+	line::Line,
+	progress::RebaseProgress,
+};
 use self::{
 	history::{History, HistoryItem},
-	utils::{remove_range, swap_range_down, swap_range_up},
+	utils::{move_lines, remove_range, swap_range_down, swap_range_up},
 };
 
 /// Represents a rebase file.
 #[derive(Debug)]
 pub struct TodoFile {
+	applied_upstream: Vec<String>,
 	comment_char: String,
+	conflicts: Option<(usize, Vec<Vec<String>>)>,
 	filepath: String,
 	history: History,
 	is_noop: bool,
 	lines: Vec<Line>,
+	noop_comments: Vec<String>,
+	progress: Option<RebaseProgress>,
+	published: Vec<String>,
 	selected_line_index: usize,
+	sequencer: bool,
+	signed: Vec<String>,
+	trailing_comments: Vec<String>,
+	unchanged: Vec<String>,
+	version: usize,
 }
 
 impl TodoFile {
@@ -119,59 +140,153 @@ impl TodoFile {
 	#[must_use]
 	pub fn new(path: &str, undo_limit: u32, comment_char: &str) -> Self {
 		Self {
+			applied_upstream: vec![],
 			comment_char: String::from(comment_char),
+			conflicts: None,
 			filepath: path.to_owned(),
 			history: History::new(undo_limit),
 			lines: vec![],
 			is_noop: false,
+			noop_comments: vec![],
+			progress: None,
+			published: vec![],
 			selected_line_index: 0,
+			sequencer: false,
+			signed: vec![],
+			trailing_comments: vec![],
+			unchanged: vec![],
+			version: 0,
 		}
 	}
 
 	/// Set the rebase lines.
 	pub fn set_lines(&mut self, lines: Vec<Line>) {
+		self.assign_lines(lines);
+		self.history.reset();
+	}
+
+	/// Replace the rebase lines, recording the change as a single undo step that restores every line.
+	pub fn replace_lines(&mut self, lines: Vec<Line>) {
+		let trailing_comments = self.trailing_comments.clone();
+		let _ = self.replace(lines, trailing_comments);
+	}
+
+	fn replace(&mut self, lines: Vec<Line>, trailing_comments: Vec<String>) -> bool {
+		let original_lines = self.lines.clone();
+		let original_trailing_comments = mem::replace(&mut self.trailing_comments, trailing_comments);
+		self.assign_lines(lines);
+		let changed = self.lines != original_lines || self.trailing_comments != original_trailing_comments;
+		if changed {
+			self.record(HistoryItem::new_replace(original_lines, original_trailing_comments));
+		}
+		changed
+	}
+
+	// every change to the lines, or to how the lines are checked, is recorded by changing the version
+	fn increment_version(&mut self) {
+		self.version = self.version.wrapping_add(1);
+	}
+
+	fn record(&mut self, history_item: HistoryItem) {
+		self.increment_version();
+		self.history.record(history_item);
+	}
+
+	// noop lines are dropped, but their comments are kept in position, on the line that follows them, the trailing
+	// comments, or before the noop of a noop file
+	fn assign_lines(&mut self, lines: Vec<Line>) {
+		self.increment_version();
 		self.is_noop = !lines.is_empty() && lines[0].get_action() == &Action::Noop;
-		self.lines = if self.is_noop {
-			vec![]
+		self.noop_comments.clear();
+		if self.is_noop {
+			self.noop_comments = lines[0].get_comments().to_vec();
+			self.lines = vec![];
 		}
 		else {
-			lines.into_iter().filter(|l| l.get_action() != &Action::Noop).collect()
-		};
+			let mut comments = vec![];
+			self.lines = vec![];
+			for mut line in lines {
+				if line.get_action() == &Action::Noop {
+					comments.extend(mem::take(line.get_comments_mut()));
+				}
+				else {
+					if !comments.is_empty() {
+						comments.append(line.get_comments_mut());
+						line.set_comments(mem::take(&mut comments));
+					}
+					self.lines.push(line);
+				}
+			}
+			if !comments.is_empty() {
+				comments.append(&mut self.trailing_comments);
+				self.trailing_comments = comments;
+			}
+		}
+		self.clamp_selected_line_index();
+	}
+
+	fn clamp_selected_line_index(&mut self) {
 		if self.selected_line_index >= self.lines.len() {
 			self.selected_line_index = if self.lines.is_empty() { 0 } else { self.lines.len() - 1 };
 		}
-		self.history.reset();
 	}
 
 	/// Load the rebase file from disk.
+	///
+	/// Comment and empty lines are attached to the line that follows them, and any after the last line are kept as
+	/// trailing comments, so that they can be written back in the same position.
 	pub fn load_file(&mut self) -> Result<()> {
-		let lines = read_to_string(Path::new(&self.filepath))
-			.map_err(|err| anyhow!("Error reading file: {}", self.filepath).context(err))?
-			.lines()
-			.filter_map(|l| {
-				if l.starts_with(self.comment_char.as_str()) || l.is_empty() {
-					None
-				}
-				else {
-					Some(Line::new(l).map_err(|err| anyhow!("Error reading file: {}", self.filepath).context(err)))
-				}
-			})
-			.collect::<Result<Vec<Line>>>()?;
+		let (lines, trailing_comments) = self.read_file()?;
+		self.trailing_comments = trailing_comments;
+		self.progress = RebaseProgress::load(Path::new(&self.filepath), self.comment_char.as_str());
 		self.set_lines(lines);
 		Ok(())
 	}
 
+	/// Load the rebase file from disk, like `load_file`, but keep the undo history and record the change as a
+	/// single undo step, so that edits made outside of the tool can be undone. Returns if an undo step was recorded,
+	/// which is only the case when the file changed.
+	pub fn reload_file(&mut self) -> Result<bool> {
+		let (lines, trailing_comments) = self.read_file()?;
+		Ok(self.replace(lines, trailing_comments))
+	}
+
+	fn read_file(&self) -> Result<(Vec<Line>, Vec<String>)> {
+		let file_contents = read_to_string(Path::new(&self.filepath))
+			.map_err(|err| anyhow!("Error reading file: {}", self.filepath).context(err))?;
+		let mut comments = vec![];
+		let mut lines = vec![];
+		for l in file_contents.lines() {
+			if l.starts_with(self.comment_char.as_str()) || l.is_empty() {
+				comments.push(String::from(l));
+			}
+			else {
+				let mut line =
+					Line::new(l).map_err(|err| anyhow!("Error reading file: {}", self.filepath).context(err))?;
+				line.set_comments(mem::take(&mut comments));
+				lines.push(line);
+			}
+		}
+		Ok((lines, comments))
+	}
+
 	/// Write the rebase file to disk.
 	pub fn write_file(&self) -> Result<()> {
 		let mut file = File::create(&self.filepath)
 			.map_err(|err| anyhow!(err).context(anyhow!("Error opening file: {}", self.filepath)))?;
-		let file_contents = if self.is_noop {
-			String::from("noop")
+		let mut file_lines = vec![];
+		if self.is_noop {
+			file_lines.extend(self.noop_comments.iter().cloned());
+			file_lines.push(String::from("noop"));
 		}
 		else {
-			self.lines.iter().map(Line::to_text).collect::<Vec<String>>().join("\n")
-		};
-		writeln!(file, "{}", file_contents)
+			for line in &self.lines {
+				file_lines.extend(line.get_comments().iter().cloned());
+				file_lines.push(line.to_text());
+			}
+		}
+		file_lines.extend(self.trailing_comments.iter().cloned());
+		writeln!(file, "{}", file_lines.join("\n"))
 			.map_err(|err| anyhow!(err).context(anyhow!("Error writing file: {}", self.filepath)))?;
 		Ok(())
 	}
@@ -189,10 +304,29 @@ impl TodoFile {
 		}
 	}
 
-	/// Swap a range of lines up.
-	pub fn swap_range_up(&mut self, start_index: usize, end_index: usize) -> bool {
+	/// Swap a range of lines up, returning the new range of the lines if they were moved.
+	///
+	/// Update ref lines are attached to the commit above them, so they are moved along with that commit, and a
+	/// commit with attached update refs is moved past as a single block. Comment lines keep their position in the file.
+	pub fn swap_range_up(&mut self, start_index: usize, end_index: usize) -> Option<(usize, usize)> {
+		self.move_range_up(start_index, end_index, None)
+	}
+
+	/// Swap a range of lines up past the line at `index`, and any lines between, as a single change, returning the new
+	/// range of the lines if they were moved. This allows moving lines past lines that are not shown, such as lines
+	/// hidden by a filter.
+	pub fn swap_range_up_past(&mut self, start_index: usize, end_index: usize, index: usize) -> Option<(usize, usize)> {
+		self.move_range_up(start_index, end_index, Some(index))
+	}
+
+	fn move_range_up(
+		&mut self,
+		start_index: usize,
+		end_index: usize,
+		above_index: Option<usize>,
+	) -> Option<(usize, usize)> {
 		if end_index == 0 || start_index == 0 || self.lines.is_empty() {
-			return false;
+			return None;
 		}
 
 		let max_index = self.lines.len() - 1;
@@ -204,23 +338,98 @@ impl TodoFile {
 			start_index
 		};
 
-		swap_range_up(&mut self.lines, start, end);
-		self.history.record(HistoryItem::new_swap_up(start, end));
-		true
-	}
+		let range_start = min(start, end);
+		let range_end = self.get_attached_update_ref_end(range_start, max(start, end));
+		let above = above_index.map_or(range_start - 1, |index| min(index, range_start - 1));
+		let above_start = self.get_block_start(range_start, above);
+		let distance = range_start - above_start;
 
-	/// Swap a range of lines down.
-	pub fn swap_range_down(&mut self, start_index: usize, end_index: usize) -> bool {
+		if distance == 1 && range_end == max(start, end) {
+			swap_range_up(&mut self.lines, start, end);
+			self.record(HistoryItem::new_swap_up(start, end));
+		}
+		else {
+			let original_lines = self.lines[above_start..=range_end].to_vec();
+			move_lines(&mut self.lines[above_start..=range_end], |moved_lines| {
+				moved_lines.rotate_left(distance);
+			});
+			self.record(HistoryItem::new_modify(above_start, range_end, original_lines));
+		}
+		Some((start - distance, end - distance))
+	}
+
+	/// Swap a range of lines down, returning the new range of the lines if they were moved.
+	///
+	/// Update ref lines are attached to the commit above them, so they are moved along with that commit, and a
+	/// commit with attached update refs is moved past as a single block. Comment lines keep their position in the file.
+	pub fn swap_range_down(&mut self, start_index: usize, end_index: usize) -> Option<(usize, usize)> {
+		self.move_range_down(start_index, end_index, None)
+	}
+
+	/// Swap a range of lines down past the line at `index`, and any lines between, as a single change, returning the
+	/// new range of the lines if they were moved. This allows moving lines past lines that are not shown, such as
+	/// lines hidden by a filter.
+	pub fn swap_range_down_past(
+		&mut self,
+		start_index: usize,
+		end_index: usize,
+		index: usize,
+	) -> Option<(usize, usize)> {
+		self.move_range_down(start_index, end_index, Some(index))
+	}
+
+	fn move_range_down(
+		&mut self,
+		start_index: usize,
+		end_index: usize,
+		below_index: Option<usize>,
+	) -> Option<(usize, usize)> {
 		let len = self.lines.len();
 		let max_index = if len == 0 { 0 } else { len - 1 };
 
 		if end_index == max_index || start_index == max_index {
-			return false;
+			return None;
+		}
+
+		let range_start = min(start_index, end_index);
+		let range_end = self.get_attached_update_ref_end(range_start, max(start_index, end_index));
+		if range_end == max_index {
+			return None;
 		}
+		let below = below_index.map_or(range_end + 1, |index| min(max(index, range_end + 1), max_index));
+		let below_end = self.get_attached_update_ref_end(below, below);
+		let distance = below_end - range_end;
 
-		swap_range_down(&mut self.lines, start_index, end_index);
-		self.history.record(HistoryItem::new_swap_down(start_index, end_index));
-		true
+		if distance == 1 && range_end == max(start_index, end_index) {
+			swap_range_down(&mut self.lines, start_index, end_index);
+			self.record(HistoryItem::new_swap_down(start_index, end_index));
+		}
+		else {
+			let original_lines = self.lines[range_start..=below_end].to_vec();
+			move_lines(&mut self.lines[range_start..=below_end], |moved_lines| {
+				moved_lines.rotate_right(distance);
+			});
+			self.record(HistoryItem::new_modify(range_start, below_end, original_lines));
+		}
+		Some((start_index + distance, end_index + distance))
+	}
+
+	/// Move every `fixup!`, `squash!` and `amend!` commit directly below the commit it targets, setting the matching
+	/// action, as a single change. Comment lines keep their position in the file. Returns the range of changed lines,
+	/// if any lines were changed.
+	pub fn autosquash(&mut self) -> Option<(usize, usize)> {
+		let lines = autosquash::autosquash(&self.lines);
+		let start = self.lines.iter().zip(&lines).position(|(a, b)| a != b)?;
+		let unchanged_end = self
+			.lines
+			.iter()
+			.rev()
+			.zip(lines.iter().rev())
+			.position(|(a, b)| a != b)?;
+		let end = self.lines.len() - 1 - unchanged_end;
+		let original_lines = mem::replace(&mut self.lines, lines)[start..=end].to_vec();
+		self.record(HistoryItem::new_modify(start, end, original_lines));
+		Some((start, end))
 	}
 
 	/// Add a new line.
@@ -232,10 +441,10 @@ impl TodoFile {
 			index
 		};
 		self.lines.insert(i, line);
-		self.history.record(HistoryItem::new_add(i, i));
+		self.record(HistoryItem::new_add(i, i));
 	}
 
-	/// Remove a range of lines.
+	/// Remove a range of lines. Comment lines keep their position in the file.
 	pub fn remove_lines(&mut self, start_index: usize, end_index: usize) {
 		if self.lines.is_empty() {
 			return;
@@ -250,8 +459,27 @@ impl TodoFile {
 			start_index
 		};
 
-		let removed_lines = remove_range(&mut self.lines, start, end);
-		self.history.record(HistoryItem::new_remove(start, end, removed_lines));
+		let removed_lines = remove_range(&mut self.lines, &mut self.trailing_comments, start, end);
+		self.record(HistoryItem::new_remove(start, end, removed_lines));
+	}
+
+	/// Remove a set of lines, that need not be contiguous, as a single change. Comment lines keep their position in the
+	/// file. Indices past the end of the file are ignored.
+	pub fn remove_lines_at(&mut self, indices: &[usize]) {
+		let mut removed_indices = indices
+			.iter()
+			.copied()
+			.filter(|&index| index < self.lines.len())
+			.collect::<Vec<usize>>();
+		removed_indices.sort_unstable();
+		removed_indices.dedup();
+
+		let mut lines = self.lines.clone();
+		let mut trailing_comments = self.trailing_comments.clone();
+		for &index in removed_indices.iter().rev() {
+			let _ = remove_range(&mut lines, &mut trailing_comments, index, index);
+		}
+		let _ = self.replace(lines, trailing_comments);
 	}
 
 	/// Update a range of lines.
@@ -275,25 +503,93 @@ impl TodoFile {
 		for index in range {
 			let line = &mut self.lines[index];
 			lines.push(line.clone());
-			if let Some(action) = edit_context.get_action().as_ref() {
-				line.set_action(*action);
-			}
+			Self::apply_edit(line, edit_context);
+		}
+		self.record(HistoryItem::new_modify(start, end, lines));
+	}
+
+	/// Update a set of lines, that need not be contiguous, as a single change. Indices past the end of the file are
+	/// ignored.
+	pub fn update_lines(&mut self, indices: &[usize], edit_context: &EditContext) {
+		let indices = indices
+			.iter()
+			.copied()
+			.filter(|&index| index < self.lines.len())
+			.collect::<Vec<usize>>();
+		let (start, end) = match (indices.iter().min(), indices.iter().max()) {
+			(Some(&start), Some(&end)) => (start, end),
+			_ => return,
+		};
 
-			if let Some(content) = edit_context.get_content().as_ref() {
-				line.edit_content(content);
-			}
+		let lines = self.lines[start..=end].to_vec();
+		for index in indices {
+			Self::apply_edit(&mut self.lines[index], edit_context);
+		}
+		self.record(HistoryItem::new_modify(start, end, lines));
+	}
+
+	fn apply_edit(line: &mut Line, edit_context: &EditContext) {
+		if let Some(action) = edit_context.get_action().as_ref() {
+			line.set_action(*action);
+		}
+
+		if let Some(content) = edit_context.get_content().as_ref() {
+			line.edit_content(content);
+		}
+
+		let option = edit_context.get_option().as_deref();
+		if option.is_some() || edit_context.is_removing_option() {
+			line.set_option(option);
 		}
-		self.history.record(HistoryItem::new_modify(start, end, lines));
+	}
+
+	/// Get the index of the last update ref line attached to the block starting at `block_start`, when the block
+	/// starts with a commit, otherwise return `index`.
+	fn get_attached_update_ref_end(&self, block_start: usize, index: usize) -> usize {
+		if self.lines[block_start].get_action() == &Action::UpdateRef {
+			return index;
+		}
+		let mut end = index;
+		while end + 1 < self.lines.len() && self.lines[end + 1].get_action() == &Action::UpdateRef {
+			end += 1;
+		}
+		end
+	}
+
+	/// Get the start index of the block, including any attached update ref lines, containing the line at `index`
+	/// that is directly above the block starting at `block_start`.
+	fn get_block_start(&self, block_start: usize, index: usize) -> usize {
+		if self.lines[block_start].get_action() == &Action::UpdateRef {
+			return index;
+		}
+		let mut start = index;
+		while start > 0 && self.lines[start].get_action() == &Action::UpdateRef {
+			start -= 1;
+		}
+		start
 	}
 
 	/// Undo the last modification.
 	pub fn undo(&mut self) -> Option<(usize, usize)> {
-		self.history.undo(&mut self.lines)
+		let range = self.history.undo(&mut self.lines, &mut self.trailing_comments);
+		self.sync_after_history_change();
+		range
 	}
 
 	/// Redo the last undone modification.
 	pub fn redo(&mut self) -> Option<(usize, usize)> {
-		self.history.redo(&mut self.lines)
+		let range = self.history.redo(&mut self.lines, &mut self.trailing_comments);
+		self.sync_after_history_change();
+		range
+	}
+
+	// replacing every line can restore lines to a noop file, or shrink the file past the selected line
+	fn sync_after_history_change(&mut self) {
+		self.increment_version();
+		if !self.lines.is_empty() {
+			self.is_noop = false;
+		}
+		self.clamp_selected_line_index();
 	}
 
 	/// Get the selected line.
@@ -332,6 +628,12 @@ impl TodoFile {
 		self.lines.get(index)
 	}
 
+	/// Get the comment and empty lines that follow the last line of the rebase file.
+	#[must_use]
+	pub fn get_trailing_comments(&self) -> &[String] {
+		self.trailing_comments.as_slice()
+	}
+
 	/// Get an owned copy of the lines.
 	#[must_use]
 	pub fn get_lines_owned(&self) -> Vec<Line> {
@@ -350,6 +652,138 @@ impl TodoFile {
 		self.lines.iter()
 	}
 
+	/// Get the progress of the rebase, when the todo file is being edited after the rebase has started.
+	#[must_use]
+	pub const fn get_progress(&self) -> Option<&RebaseProgress> {
+		self.progress.as_ref()
+	}
+
+	/// Set the name of the branch that points to the commit the rebase is applied onto. Does nothing if the rebase has
+	/// not started.
+	pub fn set_onto_name(&mut self, name: String) {
+		if let Some(progress) = self.progress.as_mut() {
+			progress.set_onto_name(name);
+		}
+	}
+
+	/// Set if the todo file is the todo file of the sequencer used by `git cherry-pick` and `git revert`, rather than
+	/// the todo file of a rebase.
+	pub fn set_sequencer(&mut self, sequencer: bool) {
+		self.increment_version();
+		self.sequencer = sequencer;
+	}
+
+	/// Is the todo file the todo file of the sequencer used by `git cherry-pick` and `git revert`.
+	#[must_use]
+	pub const fn is_sequencer(&self) -> bool {
+		self.sequencer
+	}
+
+	/// Set the paths that are predicted to conflict for each line, for the `version` of the todo file that was
+	/// current when the prediction was started.
+	pub fn set_conflicts(&mut self, version: usize, conflicts: Vec<Vec<String>>) {
+		self.conflicts = Some((version, conflicts));
+	}
+
+	/// Get the paths that are predicted to conflict for each line, if the prediction was made for the current lines.
+	#[must_use]
+	pub fn get_conflicts(&self) -> Option<&[Vec<String>]> {
+		self.conflicts
+			.as_ref()
+			.filter(|&&(version, _)| version == self.version)
+			.map(|&(_, ref conflicts)| conflicts.as_slice())
+	}
+
+	/// Set the hashes of the commits whose changes are already applied upstream.
+	pub fn set_applied_upstream(&mut self, hashes: Vec<String>) {
+		self.applied_upstream = hashes;
+	}
+
+	/// Is the commit of the line already applied upstream. A dropped line is never considered applied upstream.
+	#[must_use]
+	pub fn is_applied_upstream(&self, line: &Line) -> bool {
+		line.get_action() != &Action::Drop
+			&& line.has_reference()
+			&& self.applied_upstream.iter().any(|hash| hash == line.get_hash())
+	}
+
+	/// Drop every line with a commit that is already applied upstream, recording the change as a single undo step.
+	pub fn drop_applied_upstream(&mut self) {
+		let indices = self
+			.lines
+			.iter()
+			.enumerate()
+			.filter(|&(_, line)| self.is_applied_upstream(line))
+			.map(|(index, _)| index)
+			.collect::<Vec<usize>>();
+		self.update_lines(indices.as_slice(), &EditContext::new().action(Action::Drop));
+	}
+
+	/// Set the hashes of the commits that have been published to a remote-tracking branch.
+	pub fn set_published(&mut self, hashes: Vec<String>) {
+		self.published = hashes;
+	}
+
+	/// Is the commit of the line published to a remote-tracking branch.
+	#[must_use]
+	pub fn is_published(&self, line: &Line) -> bool {
+		line.has_reference() && self.published.iter().any(|hash| hash == line.get_hash())
+	}
+
+	/// Set the hashes of the commits that carry a signature.
+	pub fn set_signed(&mut self, hashes: Vec<String>) {
+		self.signed = hashes;
+	}
+
+	/// Does the commit of the line carry a signature.
+	#[must_use]
+	pub fn is_signed(&self, line: &Line) -> bool {
+		line.has_reference() && self.signed.iter().any(|hash| hash == line.get_hash())
+	}
+
+	/// Set the hashes of the commits that are kept, rather than rewritten, when picked in order at the start of the
+	/// rebase, since each is already on top of the commit before it.
+	pub fn set_unchanged(&mut self, hashes: Vec<String>) {
+		self.unchanged = hashes;
+	}
+
+	/// Is the commit of the line at `index` rewritten by the rebase. A dropped commit is not rewritten, and neither is
+	/// a commit that is kept by a pick at the start of the rebase.
+	#[must_use]
+	pub fn is_rewritten(&self, index: usize) -> bool {
+		let line = match self.lines.get(index) {
+			Some(line) => line,
+			None => return false,
+		};
+		if !line.has_reference() || line.get_action() == &Action::Drop {
+			return false;
+		}
+		let unchanged_count = self
+			.lines
+			.iter()
+			.zip(self.unchanged.iter())
+			.take_while(|&(line, hash)| line.get_action() == &Action::Pick && line.get_hash() == hash)
+			.count();
+		index >= unchanged_count
+	}
+
+	/// Check the lines for problems that would cause the rebase to fail, such as a fixup without a previous commit.
+	#[must_use]
+	pub fn lint(&self) -> Vec<Diagnostic> {
+		diagnostic::lint(
+			self.progress.as_ref().map_or(&[], RebaseProgress::get_done_lines),
+			&self.lines,
+			self.sequencer,
+		)
+	}
+
+	/// Get the version of the lines, which changes with every change to the lines, so that a change can be found
+	/// without comparing every line.
+	#[must_use]
+	pub const fn get_version(&self) -> usize {
+		self.version
+	}
+
 	/// Does the rebase file contain no lines.
 	#[must_use]
 	pub fn is_empty(&self) -> bool {
@@ -359,6 +793,7 @@ impl TodoFile {
 
 #[cfg(test)]
 mod tests {
+	use rstest::rstest;
 	use tempfile::{Builder, NamedTempFile};
 
 	use super::*;
@@ -401,6 +836,43 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa foobar");
 	}
 
+	#[test]
+	fn load_file_with_progress() {
+		let state_directory = tempfile::tempdir().unwrap();
+		let todo_file_path = state_directory.path().join("git-rebase-todo");
+		std::fs::write(&todo_file_path, "fixup bbb comment\n").unwrap();
+		std::fs::write(state_directory.path().join("done"), "pick aaa comment\n").unwrap();
+		let mut todo_file = TodoFile::new(todo_file_path.to_str().unwrap(), 1, "#");
+		todo_file.load_file().unwrap();
+		assert_eq!(todo_file.get_progress().unwrap().get_applied_count(), 1);
+		assert!(todo_file.lint().is_empty());
+	}
+
+	#[test]
+	fn set_onto_name_with_progress() {
+		let state_directory = tempfile::tempdir().unwrap();
+		let todo_file_path = state_directory.path().join("git-rebase-todo");
+		std::fs::write(&todo_file_path, "fixup bbb comment\n").unwrap();
+		std::fs::write(state_directory.path().join("done"), "pick aaa comment\n").unwrap();
+		let mut todo_file = TodoFile::new(todo_file_path.to_str().unwrap(), 1, "#");
+		todo_file.load_file().unwrap();
+		todo_file.set_onto_name(String::from("main"));
+		assert_eq!(todo_file.get_progress().unwrap().get_onto_name(), Some("main"));
+	}
+
+	#[test]
+	fn set_onto_name_without_progress() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		todo_file.set_onto_name(String::from("main"));
+		assert!(todo_file.get_progress().is_none());
+	}
+
+	#[test]
+	fn load_file_without_progress() {
+		let (todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		assert!(todo_file.get_progress().is_none());
+	}
+
 	#[test]
 	fn load_noop_file() {
 		let (todo_file, _) = create_and_load_todo_file(&["noop"]);
@@ -420,6 +892,169 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa foobar");
 	}
 
+	#[test]
+	fn load_comments_attached_to_following_line() {
+		let (todo_file, _) = create_and_load_todo_file(&["# comment", "", "pick aaa foo", "pick bbb bar"]);
+		assert_eq!(todo_file.get_line(0).unwrap().get_comments(), &["# comment", ""]);
+		assert!(todo_file.get_line(1).unwrap().get_comments().is_empty());
+	}
+
+	#[test]
+	fn load_trailing_comments() {
+		let (todo_file, _) = create_and_load_todo_file(&["pick aaa foo", "", "# comment"]);
+		assert_eq!(todo_file.get_trailing_comments(), &["", "# comment"]);
+	}
+
+	#[rstest]
+	#[case::no_comments("pick aaa foo\npick bbb bar\n")]
+	#[case::help_block(
+		"pick aaa foo\npick bbb bar\n\n# Rebase a..b onto c (2 commands)\n#\n# Commands:\n# p, pick <commit> = use \
+		 commit\n#\n"
+	)]
+	#[case::leading_comments("# Rebase a..b onto c\npick aaa foo\n")]
+	#[case::inline_comments("label onto\n\n# Branch foo\nreset onto\npick aaa foo\n")]
+	#[case::noop("noop\n\n# Rebase a..b onto c (1 command)\n")]
+	#[case::noop_leading_comments("# comment\nnoop\n\n# Rebase a..b onto c (1 command)\n")]
+	#[case::only_comments("# comment\n")]
+	fn write_file_round_trip(#[case] contents: &str) {
+		let todo_file_path = Builder::new()
+			.prefix("git-rebase-todo-scratch")
+			.suffix("")
+			.tempfile()
+			.unwrap();
+		write!(todo_file_path.as_file(), "{}", contents).unwrap();
+		let mut todo_file = TodoFile::new(todo_file_path.path().to_str().unwrap(), 1, "#");
+		todo_file.load_file().unwrap();
+		todo_file.write_file().unwrap();
+		assert_eq!(read_to_string(todo_file_path.path()).unwrap(), contents);
+	}
+
+	#[rstest]
+	#[case::swap_up(
+		|todo_file: &mut TodoFile| { let _ = todo_file.swap_range_up(3, 3); },
+		&["pick aaa foo", "update-ref refs/heads/aaa", "# comment", "pick ccc baz", "pick bbb bar", "# trailing"]
+	)]
+	#[case::swap_down(
+		|todo_file: &mut TodoFile| { let _ = todo_file.swap_range_down(2, 2); },
+		&["pick aaa foo", "update-ref refs/heads/aaa", "# comment", "pick ccc baz", "pick bbb bar", "# trailing"]
+	)]
+	#[case::swap_up_past_update_ref(
+		|todo_file: &mut TodoFile| { let _ = todo_file.swap_range_up(2, 2); },
+		&["pick bbb bar", "pick aaa foo", "# comment", "update-ref refs/heads/aaa", "pick ccc baz", "# trailing"]
+	)]
+	#[case::swap_range_up_past_update_ref(
+		|todo_file: &mut TodoFile| { let _ = todo_file.swap_range_up(2, 3); },
+		&["pick bbb bar", "pick ccc baz", "# comment", "pick aaa foo", "update-ref refs/heads/aaa", "# trailing"]
+	)]
+	fn swap_lines_comments_keep_position(#[case] action: fn(&mut TodoFile), #[case] expected: &[&str]) {
+		let lines = [
+			"pick aaa foo",
+			"update-ref refs/heads/aaa",
+			"# comment",
+			"pick bbb bar",
+			"pick ccc baz",
+			"# trailing",
+		];
+		let (mut todo_file, todo_file_path) = create_and_load_todo_file(&lines);
+		action(&mut todo_file);
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			read_to_string(todo_file_path.path()).unwrap(),
+			format!("{}\n", expected.join("\n"))
+		);
+		let _ = todo_file.undo();
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			read_to_string(todo_file_path.path()).unwrap(),
+			format!("{}\n", lines.join("\n"))
+		);
+	}
+
+	#[test]
+	fn autosquash_comments_keep_position() {
+		let (mut todo_file, todo_file_path) = create_and_load_todo_file(&[
+			"# Rebase a..b onto c",
+			"pick aaa foo",
+			"pick bbb bar",
+			"# comment",
+			"pick ccc fixup! foo",
+		]);
+		let _ = todo_file.autosquash();
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			read_to_string(todo_file_path.path()).unwrap(),
+			"# Rebase a..b onto c\npick aaa foo\nfixup ccc fixup! foo\n# comment\npick bbb bar\n"
+		);
+	}
+
+	#[rstest]
+	#[case::first(0, 0, &["# comment", "", "pick bbb bar", "# other", "pick ccc baz", "# trailing"])]
+	#[case::middle(1, 1, &["# comment", "pick aaa foo", "", "# other", "pick ccc baz", "# trailing"])]
+	#[case::last(2, 2, &["# comment", "pick aaa foo", "", "pick bbb bar", "# other", "# trailing"])]
+	#[case::range(1, 2, &["# comment", "pick aaa foo", "", "# other", "# trailing"])]
+	fn remove_lines_comments_keep_position(#[case] start: usize, #[case] end: usize, #[case] expected: &[&str]) {
+		let lines = [
+			"# comment",
+			"pick aaa foo",
+			"",
+			"pick bbb bar",
+			"# other",
+			"pick ccc baz",
+			"# trailing",
+		];
+		let (mut todo_file, todo_file_path) = create_and_load_todo_file(&lines);
+		todo_file.remove_lines(start, end);
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			read_to_string(todo_file_path.path()).unwrap(),
+			format!("{}\n", expected.join("\n"))
+		);
+		let _ = todo_file.undo();
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			read_to_string(todo_file_path.path()).unwrap(),
+			format!("{}\n", lines.join("\n"))
+		);
+		let _ = todo_file.redo();
+		todo_file.write_file().unwrap();
+		assert_eq!(
+			read_to_string(todo_file_path.path()).unwrap(),
+			format!("{}\n", expected.join("\n"))
+		);
+	}
+
+	#[test]
+	fn load_noop_line_comments_keep_position() {
+		let (todo_file, _) = create_and_load_todo_file(&["pick aaa foo", "# comment", "noop", "pick bbb bar", "# end"]);
+		todo_file.write_file().unwrap();
+		assert_read_todo_file!(
+			todo_file.get_filepath(),
+			"pick aaa foo",
+			"# comment",
+			"pick bbb bar",
+			"# end"
+		);
+	}
+
+	#[test]
+	fn load_last_noop_line_comments_keep_position() {
+		let (todo_file, _) = create_and_load_todo_file(&["pick aaa foo", "# comment", "noop", "# end"]);
+		todo_file.write_file().unwrap();
+		assert_read_todo_file!(todo_file.get_filepath(), "pick aaa foo", "# comment", "# end");
+	}
+
+	#[test]
+	fn reload_file_undo_restores_trailing_comments() {
+		let (mut todo_file, todo_file_path) = create_and_load_todo_file(&["pick aaa foo", "", "# trailing"]);
+		std::fs::write(todo_file_path.path(), "pick aaa foo\n# edited\n").unwrap();
+		todo_file.reload_file().unwrap();
+		assert_eq!(todo_file.get_trailing_comments(), &["# edited"]);
+		let _ = todo_file.undo();
+		assert_eq!(todo_file.get_trailing_comments(), &["", "# trailing"]);
+		let _ = todo_file.redo();
+		assert_eq!(todo_file.get_trailing_comments(), &["# edited"]);
+	}
+
 	#[test]
 	fn set_lines() {
 		let (mut todo_file, _) = create_and_load_todo_file(&[]);
@@ -451,6 +1086,71 @@ mod tests {
 		assert_eq!(todo_file.selected_line_index, 0);
 	}
 
+	#[test]
+	fn replace_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		todo_file.replace_lines(vec![Line::new("drop ccc comment").unwrap()]);
+		assert_todo_lines!(todo_file, "drop ccc comment");
+		assert_eq!(todo_file.undo(), Some((0, 0)));
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment");
+		assert_eq!(todo_file.redo(), Some((0, 0)));
+		assert_todo_lines!(todo_file, "drop ccc comment");
+	}
+
+	#[test]
+	fn replace_lines_unchanged() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		todo_file.replace_lines(vec![Line::new("pick aaa comment").unwrap()]);
+		assert!(todo_file.undo().is_none());
+	}
+
+	#[test]
+	fn replace_lines_reset_selected_index() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick a a", "pick b b", "pick c c"]);
+		todo_file.selected_line_index = 2;
+		todo_file.replace_lines(vec![Line::new("pick a a").unwrap()]);
+		assert_eq!(todo_file.selected_line_index, 0);
+	}
+
+	#[test]
+	fn replace_lines_undo_noop() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		todo_file.replace_lines(vec![Line::new("noop").unwrap()]);
+		assert!(todo_file.is_noop());
+		let _ = todo_file.undo();
+		assert!(!todo_file.is_noop());
+	}
+
+	#[test]
+	fn reload_file_keeps_history() {
+		let (_, todo_file_path) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		let mut todo_file = TodoFile::new(todo_file_path.path().to_str().unwrap(), 10, "#");
+		todo_file.load_file().unwrap();
+		todo_file.update_range(0, 0, &EditContext::new().action(Action::Drop));
+		std::fs::write(todo_file_path.path(), "pick bbb comment\nreword ccc comment\n").unwrap();
+		assert!(todo_file.reload_file().unwrap());
+		assert_todo_lines!(todo_file, "pick bbb comment", "reword ccc comment");
+		let _ = todo_file.undo();
+		assert_todo_lines!(todo_file, "drop aaa comment", "pick bbb comment");
+		let _ = todo_file.undo();
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment");
+	}
+
+	#[test]
+	fn reload_file_unchanged() {
+		let (mut todo_file, _todo_file_path) = create_and_load_todo_file(&["pick aaa comment"]);
+		assert!(!todo_file.reload_file().unwrap());
+		assert!(todo_file.undo().is_none());
+	}
+
+	#[test]
+	fn reload_file_error() {
+		let (mut todo_file, todo_file_path) = create_and_load_todo_file(&["pick aaa comment"]);
+		std::fs::write(todo_file_path.path(), "invalid aaa comment\n").unwrap();
+		assert!(todo_file.reload_file().is_err());
+		assert_todo_lines!(todo_file, "pick aaa comment");
+	}
+
 	#[test]
 	fn write_file() {
 		let (mut todo_file, _) = create_and_load_todo_file(&[]);
@@ -549,6 +1249,29 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa comment", "edit ccc comment");
 	}
 
+	#[test]
+	fn remove_lines_at() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"# comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		todo_file.remove_lines_at(&[2, 1, 9]);
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick ddd comment");
+		assert_eq!(todo_file.get_line(1).unwrap().get_comments(), &["# comment"]);
+	}
+
+	#[test]
+	fn remove_lines_at_record_history() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		todo_file.remove_lines_at(&[0, 2]);
+		let _undo_result = todo_file.undo();
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
+	}
+
 	#[test]
 	fn update_range_full_set_action() {
 		let (mut todo_file, _) =
@@ -569,6 +1292,20 @@ mod tests {
 		assert_todo_lines!(todo_file, "exec echo", "exec echo", "exec echo");
 	}
 
+	#[test]
+	fn update_range_set_option() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["fixup aaa comment", "fixup -c bbb comment"]);
+		todo_file.update_range(0, 1, &EditContext::new().option("-c"));
+		assert_todo_lines!(todo_file, "fixup -c aaa comment", "fixup -c bbb comment");
+	}
+
+	#[test]
+	fn update_range_remove_option() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["fixup aaa comment", "fixup -C bbb comment"]);
+		todo_file.update_range(0, 1, &EditContext::new().without_option());
+		assert_todo_lines!(todo_file, "fixup aaa comment", "fixup bbb comment");
+	}
+
 	#[test]
 	fn update_range_edit_action() {
 		let (mut todo_file, _) =
@@ -610,6 +1347,40 @@ mod tests {
 		assert_todo_lines!(todo_file, "reword aaa comment", "reword bbb comment");
 	}
 
+	#[test]
+	fn update_lines() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		todo_file.update_lines(&[0, 2], &EditContext::new().action(Action::Drop));
+		assert_todo_lines!(todo_file, "drop aaa comment", "pick bbb comment", "drop ccc comment");
+	}
+
+	#[test]
+	fn update_lines_empty() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment"]);
+		todo_file.update_lines(&[], &EditContext::new().action(Action::Drop));
+		assert_todo_lines!(todo_file, "pick aaa comment");
+		assert!(todo_file.undo().is_none());
+	}
+
+	#[test]
+	fn update_lines_index_overflow() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		todo_file.update_lines(&[1, 2], &EditContext::new().action(Action::Reword));
+		assert_todo_lines!(todo_file, "pick aaa comment", "reword bbb comment");
+	}
+
+	#[test]
+	fn update_lines_undo_redo() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		todo_file.update_lines(&[0, 2], &EditContext::new().action(Action::Drop));
+		assert_eq!(todo_file.undo(), Some((0, 2)));
+		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
+		assert_eq!(todo_file.redo(), Some((0, 2)));
+		assert_todo_lines!(todo_file, "drop aaa comment", "pick bbb comment", "drop ccc comment");
+	}
+
 	#[test]
 	fn history_undo_redo() {
 		let (mut todo_file, _) =
@@ -625,7 +1396,7 @@ mod tests {
 	fn swap_up() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_up(1, 2));
+		assert!(todo_file.swap_range_up(1, 2).is_some());
 		assert_todo_lines!(todo_file, "pick bbb comment", "pick ccc comment", "pick aaa comment");
 	}
 
@@ -642,7 +1413,7 @@ mod tests {
 	fn swap_up_reverse_index() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_up(2, 1));
+		assert!(todo_file.swap_range_up(2, 1).is_some());
 		assert_todo_lines!(todo_file, "pick bbb comment", "pick ccc comment", "pick aaa comment");
 	}
 
@@ -650,7 +1421,7 @@ mod tests {
 	fn swap_up_single_line() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_up(1, 1));
+		assert!(todo_file.swap_range_up(1, 1).is_some());
 		assert_todo_lines!(todo_file, "pick bbb comment", "pick aaa comment", "pick ccc comment");
 	}
 
@@ -658,7 +1429,7 @@ mod tests {
 	fn swap_up_at_top_start_index() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(!todo_file.swap_range_up(0, 1));
+		assert!(todo_file.swap_range_up(0, 1).is_none());
 		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
 	}
 
@@ -666,7 +1437,7 @@ mod tests {
 	fn swap_up_at_top_end_index() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(!todo_file.swap_range_up(1, 0));
+		assert!(todo_file.swap_range_up(1, 0).is_none());
 		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
 	}
 
@@ -674,7 +1445,7 @@ mod tests {
 	fn swap_up_start_index_overflow() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_up(3, 1));
+		assert!(todo_file.swap_range_up(3, 1).is_some());
 		assert_todo_lines!(todo_file, "pick bbb comment", "pick ccc comment", "pick aaa comment");
 	}
 
@@ -682,21 +1453,21 @@ mod tests {
 	fn swap_up_end_index_overflow() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_up(3, 1));
+		assert!(todo_file.swap_range_up(3, 1).is_some());
 		assert_todo_lines!(todo_file, "pick bbb comment", "pick ccc comment", "pick aaa comment");
 	}
 
 	#[test]
 	fn swap_up_empty_list_index_out_of_bounds() {
 		let (mut todo_file, _) = create_and_load_todo_file(&[]);
-		assert!(!todo_file.swap_range_up(1, 1));
+		assert!(todo_file.swap_range_up(1, 1).is_none());
 	}
 
 	#[test]
 	fn swap_down() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_down(0, 1));
+		assert!(todo_file.swap_range_down(0, 1).is_some());
 		assert_todo_lines!(todo_file, "pick ccc comment", "pick aaa comment", "pick bbb comment");
 	}
 
@@ -709,11 +1480,81 @@ mod tests {
 		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
 	}
 
+	#[test]
+	fn swap_up_past() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert_eq!(todo_file.swap_range_up_past(3, 3, 1), Some((1, 1)));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick ddd comment",
+			"pick bbb comment",
+			"pick ccc comment"
+		);
+		let _undo_result = todo_file.undo();
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn swap_down_past() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment",
+		]);
+		assert_eq!(todo_file.swap_range_down_past(0, 1, 3), Some((2, 3)));
+		assert_todo_lines!(
+			todo_file,
+			"pick ccc comment",
+			"pick ddd comment",
+			"pick aaa comment",
+			"pick bbb comment"
+		);
+		let _undo_result = todo_file.undo();
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"pick ddd comment"
+		);
+	}
+
+	#[test]
+	fn swap_down_past_attached_update_ref() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"pick ccc comment",
+			"update-ref refs/heads/ccc",
+		]);
+		assert_eq!(todo_file.swap_range_down_past(0, 0, 2), Some((3, 3)));
+		assert_todo_lines!(
+			todo_file,
+			"pick bbb comment",
+			"pick ccc comment",
+			"update-ref refs/heads/ccc",
+			"pick aaa comment"
+		);
+	}
+
 	#[test]
 	fn swap_down_reverse_index() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_down(1, 0));
+		assert!(todo_file.swap_range_down(1, 0).is_some());
 		assert_todo_lines!(todo_file, "pick ccc comment", "pick aaa comment", "pick bbb comment");
 	}
 
@@ -721,7 +1562,7 @@ mod tests {
 	fn swap_down_single_line() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(todo_file.swap_range_down(0, 0));
+		assert!(todo_file.swap_range_down(0, 0).is_some());
 		assert_todo_lines!(todo_file, "pick bbb comment", "pick aaa comment", "pick ccc comment");
 	}
 
@@ -729,7 +1570,7 @@ mod tests {
 	fn swap_down_at_bottom_end_index() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(!todo_file.swap_range_down(1, 2));
+		assert!(todo_file.swap_range_down(1, 2).is_none());
 		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
 	}
 
@@ -737,10 +1578,138 @@ mod tests {
 	fn swap_down_at_bottom_start_index() {
 		let (mut todo_file, _) =
 			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
-		assert!(!todo_file.swap_range_down(2, 1));
+		assert!(todo_file.swap_range_down(2, 1).is_none());
 		assert_todo_lines!(todo_file, "pick aaa comment", "pick bbb comment", "pick ccc comment");
 	}
 
+	#[test]
+	fn swap_up_returns_new_range() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		assert_eq!(todo_file.swap_range_up(2, 1), Some((1, 0)));
+	}
+
+	#[test]
+	fn swap_up_with_attached_update_ref() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "update-ref refs/heads/bbb"]);
+		assert_eq!(todo_file.swap_range_up(1, 1), Some((0, 0)));
+		assert_todo_lines!(
+			todo_file,
+			"pick bbb comment",
+			"update-ref refs/heads/bbb",
+			"pick aaa comment"
+		);
+	}
+
+	#[test]
+	fn swap_up_past_update_ref_block() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"update-ref refs/heads/aaa",
+			"update-ref refs/heads/aaa2",
+			"pick bbb comment",
+		]);
+		assert_eq!(todo_file.swap_range_up(3, 3), Some((0, 0)));
+		assert_todo_lines!(
+			todo_file,
+			"pick bbb comment",
+			"pick aaa comment",
+			"update-ref refs/heads/aaa",
+			"update-ref refs/heads/aaa2"
+		);
+	}
+
+	#[test]
+	fn swap_up_update_ref_line() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "update-ref refs/heads/bbb"]);
+		assert_eq!(todo_file.swap_range_up(2, 2), Some((1, 1)));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"update-ref refs/heads/bbb",
+			"pick bbb comment"
+		);
+	}
+
+	#[test]
+	fn swap_up_with_update_ref_records_history() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"update-ref refs/heads/aaa",
+			"pick bbb comment",
+			"update-ref refs/heads/bbb",
+		]);
+		let _ = todo_file.swap_range_up(2, 2);
+		let _undo_result = todo_file.undo();
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"update-ref refs/heads/aaa",
+			"pick bbb comment",
+			"update-ref refs/heads/bbb"
+		);
+	}
+
+	#[test]
+	fn swap_down_returns_new_range() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "pick ccc comment"]);
+		assert_eq!(todo_file.swap_range_down(1, 0), Some((2, 1)));
+	}
+
+	#[test]
+	fn swap_down_with_attached_update_ref() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "update-ref refs/heads/aaa", "pick bbb comment"]);
+		assert_eq!(todo_file.swap_range_down(0, 0), Some((1, 1)));
+		assert_todo_lines!(
+			todo_file,
+			"pick bbb comment",
+			"pick aaa comment",
+			"update-ref refs/heads/aaa"
+		);
+	}
+
+	#[test]
+	fn swap_down_past_update_ref_block() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"update-ref refs/heads/bbb",
+			"update-ref refs/heads/bbb2",
+		]);
+		assert_eq!(todo_file.swap_range_down(0, 0), Some((3, 3)));
+		assert_todo_lines!(
+			todo_file,
+			"pick bbb comment",
+			"update-ref refs/heads/bbb",
+			"update-ref refs/heads/bbb2",
+			"pick aaa comment"
+		);
+	}
+
+	#[test]
+	fn swap_down_with_attached_update_ref_at_bottom() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "update-ref refs/heads/bbb"]);
+		assert!(todo_file.swap_range_down(1, 1).is_none());
+	}
+
+	#[test]
+	fn swap_down_update_ref_line() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "update-ref refs/heads/aaa", "pick bbb comment"]);
+		assert_eq!(todo_file.swap_range_down(1, 1), Some((2, 2)));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"update-ref refs/heads/aaa"
+		);
+	}
+
 	#[test]
 	fn selected_line_index() {
 		let (mut todo_file, _) = create_and_load_todo_file(&["exec foo", "exec bar", "exec foobar"]);
@@ -808,6 +1777,191 @@ mod tests {
 		);
 	}
 
+	#[test]
+	fn autosquash() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment 1",
+			"pick bbb comment 2",
+			"pick ccc fixup! comment 1",
+			"pick ddd comment 3",
+		]);
+		assert_eq!(todo_file.autosquash(), Some((1, 2)));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment 1",
+			"fixup ccc fixup! comment 1",
+			"pick bbb comment 2",
+			"pick ddd comment 3"
+		);
+	}
+
+	#[test]
+	fn autosquash_no_change() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment 1", "pick bbb comment 2"]);
+		assert_eq!(todo_file.autosquash(), None);
+		assert!(todo_file.undo().is_none());
+	}
+
+	#[test]
+	fn autosquash_undo_redo() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment 1",
+			"pick bbb comment 2",
+			"pick ccc squash! comment 1",
+			"pick ddd amend! comment 2",
+		]);
+		let _ = todo_file.autosquash();
+		assert_eq!(todo_file.undo(), Some((1, 3)));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment 1",
+			"pick bbb comment 2",
+			"pick ccc squash! comment 1",
+			"pick ddd amend! comment 2"
+		);
+		assert_eq!(todo_file.redo(), Some((1, 3)));
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment 1",
+			"squash ccc squash! comment 1",
+			"pick bbb comment 2",
+			"fixup -C ddd amend! comment 2"
+		);
+	}
+
+	#[test]
+	fn lint() {
+		let (todo_file, _) = create_and_load_todo_file(&["fixup aaa comment", "pick bbb comment"]);
+		let diagnostics = todo_file.lint();
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].get_line_index(), 0);
+		assert_eq!(
+			diagnostics[0].get_kind(),
+			&DiagnosticKind::NoPreviousCommit(Action::Fixup)
+		);
+	}
+
+	#[test]
+	fn lint_sequencer() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["revert aaa comment", "pick bbb comment"]);
+		todo_file.set_sequencer(true);
+		assert!(todo_file.is_sequencer());
+		let diagnostics = todo_file.lint();
+		assert_eq!(diagnostics.len(), 1);
+		assert_eq!(diagnostics[0].get_line_index(), 1);
+		assert_eq!(
+			diagnostics[0].get_kind(),
+			&DiagnosticKind::UnsupportedAction(Action::Pick, String::from("revert"))
+		);
+	}
+
+	#[test]
+	fn get_version_changes_with_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		let mut version = todo_file.get_version();
+		todo_file.update_range(0, 0, &EditContext::new().action(Action::Drop));
+		assert_ne!(todo_file.get_version(), version);
+		version = todo_file.get_version();
+		let _ = todo_file.undo();
+		assert_ne!(todo_file.get_version(), version);
+		version = todo_file.get_version();
+		todo_file.set_selected_line_index(1);
+		assert_eq!(todo_file.get_version(), version);
+	}
+
+	#[test]
+	fn get_conflicts_current_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		todo_file.set_conflicts(todo_file.get_version(), vec![vec![], vec![String::from("file.txt")]]);
+		assert_eq!(
+			todo_file.get_conflicts(),
+			Some(&[vec![], vec![String::from("file.txt")]][..])
+		);
+	}
+
+	#[test]
+	fn get_conflicts_changed_lines() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment"]);
+		todo_file.set_conflicts(todo_file.get_version(), vec![vec![], vec![String::from("file.txt")]]);
+		let _ = todo_file.swap_range_down(0, 0);
+		assert_eq!(todo_file.get_conflicts(), None);
+	}
+
+	#[test]
+	fn is_applied_upstream() {
+		let (mut todo_file, _) =
+			create_and_load_todo_file(&["pick aaa comment", "drop bbb comment", "pick ccc comment"]);
+		todo_file.set_applied_upstream(vec![String::from("aaa"), String::from("bbb")]);
+		assert!(todo_file.is_applied_upstream(todo_file.get_line(0).unwrap()));
+		assert!(!todo_file.is_applied_upstream(todo_file.get_line(1).unwrap()));
+		assert!(!todo_file.is_applied_upstream(todo_file.get_line(2).unwrap()));
+	}
+
+	#[test]
+	fn is_published() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "drop bbb comment", "exec aaa"]);
+		todo_file.set_published(vec![String::from("aaa"), String::from("bbb")]);
+		assert!(todo_file.is_published(todo_file.get_line(0).unwrap()));
+		assert!(todo_file.is_published(todo_file.get_line(1).unwrap()));
+		assert!(!todo_file.is_published(todo_file.get_line(2).unwrap()));
+		assert!(!todo_file.is_signed(todo_file.get_line(0).unwrap()));
+	}
+
+	#[rstest]
+	#[case::leading_picks(&["pick aaa c1", "pick bbb c2", "pick ccc c3"], &[false, false, true])]
+	#[case::reordered(&["pick bbb c2", "pick aaa c1", "pick ccc c3"], &[true, true, true])]
+	#[case::reworded(&["reword aaa c1", "pick bbb c2", "pick ccc c3"], &[true, true, true])]
+	#[case::dropped(&["pick aaa c1", "drop bbb c2", "pick ccc c3"], &[false, false, true])]
+	#[case::exec(&["exec make", "pick aaa c1", "pick ccc c3"], &[false, true, true])]
+	fn is_rewritten(#[case] lines: &[&str], #[case] expected: &[bool]) {
+		let (mut todo_file, _) = create_and_load_todo_file(lines);
+		todo_file.set_unchanged(vec![String::from("aaa"), String::from("bbb")]);
+		assert_eq!(
+			(0..lines.len())
+				.map(|index| todo_file.is_rewritten(index))
+				.collect::<Vec<bool>>(),
+			expected
+		);
+		assert!(!todo_file.is_rewritten(lines.len()));
+	}
+
+	#[test]
+	fn is_signed() {
+		let (mut todo_file, _) = create_and_load_todo_file(&["pick aaa comment", "pick bbb comment", "exec aaa"]);
+		todo_file.set_signed(vec![String::from("aaa")]);
+		assert!(todo_file.is_signed(todo_file.get_line(0).unwrap()));
+		assert!(!todo_file.is_signed(todo_file.get_line(1).unwrap()));
+		assert!(!todo_file.is_signed(todo_file.get_line(2).unwrap()));
+		assert!(!todo_file.is_published(todo_file.get_line(0).unwrap()));
+	}
+
+	#[test]
+	fn drop_applied_upstream() {
+		let (mut todo_file, _) = create_and_load_todo_file(&[
+			"pick aaa comment",
+			"pick bbb comment",
+			"exec make",
+			"reword ccc comment",
+		]);
+		todo_file.set_applied_upstream(vec![String::from("aaa"), String::from("ccc")]);
+		todo_file.drop_applied_upstream();
+		assert_todo_lines!(
+			todo_file,
+			"drop aaa comment",
+			"pick bbb comment",
+			"exec make",
+			"drop ccc comment"
+		);
+		let _ = todo_file.undo();
+		assert_todo_lines!(
+			todo_file,
+			"pick aaa comment",
+			"pick bbb comment",
+			"exec make",
+			"reword ccc comment"
+		);
+	}
+
 	#[test]
 	fn is_empty_true() {
 		let (todo_file, _) = create_and_load_todo_file(&[]);
@@ -6,9 +6,12 @@ use super::action::Action;
 #[derive(Clone, Debug, PartialEq)]
 pub struct Line {
 	action: Action,
+	comments: Vec<String>,
 	content: String,
 	hash: String,
+	message: Option<String>,
 	mutated: bool,
+	option: Option<String>,
 }
 
 impl Line {
@@ -20,6 +23,9 @@ impl Line {
 			content: String::from(""),
 			hash: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		}
 	}
 
@@ -31,6 +37,9 @@ impl Line {
 			content: String::from(""),
 			hash: String::from(hash),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		}
 	}
 
@@ -42,6 +51,9 @@ impl Line {
 			content: String::from(""),
 			hash: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		}
 	}
 
@@ -53,18 +65,26 @@ impl Line {
 			content: String::from(command),
 			hash: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		}
 	}
 
-	/// Create a new merge line.
+	/// Create a new merge line, from the arguments of the merge command.
 	#[must_use]
 	pub fn new_merge(command: &str) -> Self {
-		Self {
-			action: Action::Merge,
-			content: String::from(command),
-			hash: String::from(""),
-			mutated: false,
-		}
+		Self::parse_merge(command).unwrap_or_else(|_| {
+			Self {
+				action: Action::Merge,
+				content: String::from(command),
+				hash: String::from(""),
+				mutated: false,
+				option: None,
+				comments: vec![],
+				message: None,
+			}
+		})
 	}
 
 	/// Create a new label line.
@@ -75,6 +95,9 @@ impl Line {
 			content: String::from(label),
 			hash: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		}
 	}
 
@@ -86,6 +109,23 @@ impl Line {
 			content: String::from(label),
 			hash: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
+		}
+	}
+
+	/// Create a new update-ref line.
+	#[must_use]
+	pub fn new_update_ref(reference: &str) -> Self {
+		Self {
+			action: Action::UpdateRef,
+			content: String::from(reference),
+			hash: String::from(""),
+			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		}
 	}
 
@@ -97,14 +137,20 @@ impl Line {
 		else if input_line.starts_with("break") || input_line.starts_with('b') {
 			return Ok(Self::new_break());
 		}
+		else if input_line.starts_with("merge") || input_line.starts_with('m') {
+			let input: Vec<&str> = input_line.splitn(2, ' ').collect();
+			if input.len() == 2 && Action::try_from(input[0])? == Action::Merge {
+				return Self::parse_merge(input[1]).map_err(|err| err.context(format!("Invalid line: {}", input_line)));
+			}
+		}
 		else if input_line.starts_with("exec")
 			|| input_line.starts_with('x')
-			|| input_line.starts_with("merge")
-			|| input_line.starts_with('m')
 			|| input_line.starts_with("label")
 			|| input_line.starts_with('l')
 			|| input_line.starts_with("reset")
 			|| input_line.starts_with('t')
+			|| input_line.starts_with("update-ref")
+			|| input_line.starts_with('u')
 		{
 			let input: Vec<&str> = input_line.splitn(2, ' ').collect();
 			if input.len() == 2 {
@@ -113,40 +159,129 @@ impl Line {
 					hash: String::from(""),
 					content: String::from(input[1]),
 					mutated: false,
+					option: None,
+					comments: vec![],
+					message: None,
 				});
 			}
 		}
 		else {
 			let input: Vec<&str> = input_line.splitn(3, ' ').collect();
 			if input.len() >= 2 {
-				return Ok(Self {
-					action: Action::try_from(input[0])?,
-					hash: String::from(input[1]),
-					content: if input.len() == 3 {
-						String::from(input[2])
+				let action = Action::try_from(input[0])?;
+				if action == Action::Fixup && (input[1] == "-C" || input[1] == "-c") {
+					let fixup_input: Vec<&str> = input_line.splitn(4, ' ').collect();
+					if fixup_input.len() >= 3 {
+						return Ok(Self {
+							action,
+							hash: String::from(fixup_input[2]),
+							content: if fixup_input.len() == 4 {
+								String::from(fixup_input[3])
+							}
+							else {
+								String::from("")
+							},
+							mutated: false,
+							option: Some(String::from(fixup_input[1])),
+							comments: vec![],
+							message: None,
+						});
 					}
-					else {
-						String::from("")
-					},
-					mutated: false,
-				});
+				}
+				else {
+					return Ok(Self {
+						action,
+						hash: String::from(input[1]),
+						content: if input.len() == 3 {
+							String::from(input[2])
+						}
+						else {
+							String::from("")
+						},
+						mutated: false,
+						option: None,
+						comments: vec![],
+						message: None,
+					});
+				}
 			}
 		}
 
 		Err(anyhow!("Invalid line: {}", input_line))
 	}
 
+	/// Parse the arguments of a merge line, in the form `[-C <commit> | -c <commit>] <label>... [# <oneline>]`.
+	fn parse_merge(arguments: &str) -> Result<Self> {
+		let (arguments, message) = match arguments.find(" # ") {
+			Some(index) => (&arguments[..index], Some(String::from(&arguments[index + 3..]))),
+			None => (arguments, None),
+		};
+
+		let mut tokens = arguments.split_whitespace().peekable();
+		let (option, hash) = match tokens.peek() {
+			Some(&token) if token == "-C" || token == "-c" => {
+				let _ = tokens.next();
+				let hash = tokens
+					.next()
+					.ok_or_else(|| anyhow!("Missing commit for merge option: {}", token))?;
+				(Some(String::from(token)), String::from(hash))
+			},
+			_ => (None, String::from("")),
+		};
+
+		let labels = tokens.collect::<Vec<&str>>();
+		if labels.is_empty() {
+			return Err(anyhow!("Missing label for merge"));
+		}
+		if let Some(label) = labels.iter().find(|l| l.starts_with('-') || l.starts_with('#')) {
+			return Err(anyhow!("Invalid merge label: {}", label));
+		}
+
+		Ok(Self {
+			action: Action::Merge,
+			content: labels.join(" "),
+			hash,
+			mutated: false,
+			option,
+			comments: vec![],
+			message,
+		})
+	}
+
+	/// Validate the arguments of a merge line.
+	pub fn validate_merge(arguments: &str) -> Result<()> {
+		Self::parse_merge(arguments).map(|_| ())
+	}
+
 	/// Set the action of the line.
 	pub fn set_action(&mut self, action: Action) {
 		if !self.action.is_static() && self.action != action {
 			self.mutated = true;
 			self.action = action;
+			self.option = None;
 		}
 	}
 
-	/// Edit the content of the line, if it is editable.
+	/// Set the option of the line, or remove it when `None`. Only fixup lines support an option.
+	pub fn set_option(&mut self, option: Option<&str>) {
+		if self.action == Action::Fixup && self.option.as_deref() != option {
+			self.mutated = true;
+			self.option = option.map(String::from);
+		}
+	}
+
+	/// Edit the content of the line, if it is editable. The content of a merge line is its full arguments, and is
+	/// ignored if it is not a valid merge.
 	pub fn edit_content(&mut self, content: &str) {
-		if self.is_editable() {
+		if self.action == Action::Merge {
+			if let Ok(line) = Self::parse_merge(content) {
+				self.content = line.content;
+				self.hash = line.hash;
+				self.message = line.message;
+				self.option = line.option;
+			}
+		}
+		else if self.is_editable() {
 			self.content = String::from(content);
 		}
 	}
@@ -169,6 +304,55 @@ impl Line {
 		self.hash.as_str()
 	}
 
+	/// Get the content of the line as it is edited, which for a merge line includes the option, commit and message.
+	#[must_use]
+	pub fn get_edit_content(&self) -> String {
+		if self.action == Action::Merge {
+			self.get_merge_arguments()
+		}
+		else {
+			String::from(self.get_content())
+		}
+	}
+
+	/// Get the labels of a merge line.
+	#[must_use]
+	pub fn get_labels(&self) -> Vec<&str> {
+		if self.action == Action::Merge {
+			self.content.split(' ').collect()
+		}
+		else {
+			vec![]
+		}
+	}
+
+	/// Get the trailing message, the oneline after the `#`, of a merge line.
+	#[must_use]
+	pub fn get_message(&self) -> Option<&str> {
+		self.message.as_deref()
+	}
+
+	/// Get the option of the line, such as the `-C` or `-c` of a fixup or merge.
+	#[must_use]
+	pub fn get_option(&self) -> Option<&str> {
+		self.option.as_deref()
+	}
+
+	/// Set the comment and empty lines that directly precede the line in the rebase file.
+	pub fn set_comments(&mut self, comments: Vec<String>) {
+		self.comments = comments;
+	}
+
+	/// Get the comment and empty lines that directly precede the line in the rebase file.
+	#[must_use]
+	pub fn get_comments(&self) -> &[String] {
+		self.comments.as_slice()
+	}
+
+	pub(crate) fn get_comments_mut(&mut self) -> &mut Vec<String> {
+		&mut self.comments
+	}
+
 	/// Does this line contain a commit reference.
 	#[must_use]
 	pub fn has_reference(&self) -> bool {
@@ -179,13 +363,14 @@ impl Line {
 	#[must_use]
 	pub const fn is_editable(&self) -> bool {
 		match self.action {
-			Action::Exec | Action::Label | Action::Reset | Action::Merge => true,
+			Action::Exec | Action::Label | Action::Reset | Action::Merge | Action::UpdateRef => true,
 			Action::Break
 			| Action::Drop
 			| Action::Edit
 			| Action::Fixup
 			| Action::Noop
 			| Action::Pick
+			| Action::Revert
 			| Action::Reword
 			| Action::Squash => false,
 		}
@@ -195,15 +380,41 @@ impl Line {
 	#[must_use]
 	pub fn to_text(&self) -> String {
 		match self.action {
-			Action::Drop | Action::Edit | Action::Fixup | Action::Pick | Action::Reword | Action::Squash => {
-				format!("{} {} {}", self.action.as_string(), self.hash, self.content)
+			Action::Drop
+			| Action::Edit
+			| Action::Fixup
+			| Action::Pick
+			| Action::Revert
+			| Action::Reword
+			| Action::Squash => {
+				if let Some(option) = self.option.as_ref() {
+					format!("{} {} {} {}", self.action.as_string(), option, self.hash, self.content)
+				}
+				else {
+					format!("{} {} {}", self.action.as_string(), self.hash, self.content)
+				}
 			},
-			Action::Exec | Action::Label | Action::Reset | Action::Merge => {
+			Action::Exec | Action::Label | Action::Reset | Action::UpdateRef => {
 				format!("{} {}", self.action.as_string(), self.content)
 			},
+			Action::Merge => format!("{} {}", self.action.as_string(), self.get_merge_arguments()),
 			Action::Noop | Action::Break => self.action.as_string(),
 		}
 	}
+
+	fn get_merge_arguments(&self) -> String {
+		let mut arguments = vec![];
+		if let Some(option) = self.option.as_ref() {
+			arguments.push(option.as_str());
+			arguments.push(self.hash.as_str());
+		}
+		arguments.push(self.content.as_str());
+		if let Some(message) = self.message.as_ref() {
+			arguments.push("#");
+			arguments.push(message.as_str());
+		}
+		arguments.join(" ")
+	}
 }
 
 #[cfg(test)]
@@ -218,78 +429,189 @@ mod tests {
 		hash: String::from("aaa"),
 		content: String::from("comment"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::reword_action("reword aaa comment", &Line {
 		action: Action::Reword,
 		hash: String::from("aaa"),
 		content: String::from("comment"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::edit_action("edit aaa comment", &Line {
 		action: Action::Edit,
 		hash: String::from("aaa"),
 		content: String::from("comment"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::squash_action("squash aaa comment", &Line {
 		action: Action::Squash,
 		hash: String::from("aaa"),
 		content: String::from("comment"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
+	})]
+	#[case::revert_action("revert aaa comment", &Line {
+		action: Action::Revert,
+		hash: String::from("aaa"),
+		content: String::from("comment"),
+		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::fixup_action("fixup aaa comment", &Line {
 		action: Action::Fixup,
 		hash: String::from("aaa"),
 		content: String::from("comment"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
+	})]
+	#[case::fixup_with_message_option_action("fixup -C aaa comment", &Line {
+		action: Action::Fixup,
+		hash: String::from("aaa"),
+		content: String::from("comment"),
+		mutated: false,
+		option: Some(String::from("-C")),
+		comments: vec![],
+		message: None,
+	})]
+	#[case::fixup_with_edit_message_option_action("fixup -c aaa comment", &Line {
+		action: Action::Fixup,
+		hash: String::from("aaa"),
+		content: String::from("comment"),
+		mutated: false,
+		option: Some(String::from("-c")),
+		comments: vec![],
+		message: None,
+	})]
+	#[case::fixup_with_option_without_comment("fixup -C aaa", &Line {
+		action: Action::Fixup,
+		hash: String::from("aaa"),
+		content: String::from(""),
+		mutated: false,
+		option: Some(String::from("-C")),
+		comments: vec![],
+		message: None,
 	})]
 	#[case::drop_action("drop aaa comment", &Line {
 		action: Action::Drop,
 		hash: String::from("aaa"),
 		content: String::from("comment"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::action_without_comment("pick aaa", &Line {
 		action: Action::Pick,
 		hash: String::from("aaa"),
 		content: String::from(""),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::exec_action("exec command", &Line {
 		action: Action::Exec,
 		hash: String::from(""),
 		content: String::from("command"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::label_action("label ref", &Line {
 		action: Action::Label,
 		hash: String::from(""),
 		content: String::from("ref"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::reset_action("reset ref", &Line {
 		action: Action::Reset,
 		hash: String::from(""),
 		content: String::from("ref"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::reset_action("merge command", &Line {
 		action: Action::Merge,
 		hash: String::from(""),
 		content: String::from("command"),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
+	})]
+	#[case::merge_with_commit("merge -C aaa ref # Merge 'ref'", &Line {
+		action: Action::Merge,
+		hash: String::from("aaa"),
+		content: String::from("ref"),
+		mutated: false,
+		option: Some(String::from("-C")),
+		comments: vec![],
+		message: Some(String::from("Merge 'ref'")),
+	})]
+	#[case::merge_with_commit_edit_message("merge -c aaa ref", &Line {
+		action: Action::Merge,
+		hash: String::from("aaa"),
+		content: String::from("ref"),
+		mutated: false,
+		option: Some(String::from("-c")),
+		comments: vec![],
+		message: None,
+	})]
+	#[case::merge_octopus("m ref1 ref2 # Merge", &Line {
+		action: Action::Merge,
+		hash: String::from(""),
+		content: String::from("ref1 ref2"),
+		mutated: false,
+		option: None,
+		comments: vec![],
+		message: Some(String::from("Merge")),
+	})]
+	#[case::update_ref_action("update-ref refs/heads/foo", &Line {
+		action: Action::UpdateRef,
+		hash: String::from(""),
+		content: String::from("refs/heads/foo"),
+		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::break_action("break", &Line {
 		action: Action::Break,
 		hash: String::from(""),
 		content: String::from(""),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	#[case::nnop( "noop", &Line {
 		action: Action::Noop,
 		hash: String::from(""),
 		content: String::from(""),
 		mutated: false,
+		option: None,
+		comments: vec![],
+		message: None,
 	})]
 	fn new(#[case] line: &str, #[case] expected: &Line) {
 		assert_eq!(&Line::new(line).unwrap(), expected);
@@ -302,6 +624,9 @@ mod tests {
 			hash: String::from("abc123"),
 			content: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		});
 	}
 
@@ -312,6 +637,9 @@ mod tests {
 			hash: String::from(""),
 			content: String::from(""),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		});
 	}
 
@@ -322,6 +650,9 @@ mod tests {
 			hash: String::from(""),
 			content: String::from("command"),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		});
 	}
 
@@ -332,6 +663,9 @@ mod tests {
 			hash: String::from(""),
 			content: String::from("command"),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		});
 	}
 
@@ -342,6 +676,22 @@ mod tests {
 			hash: String::from(""),
 			content: String::from("label"),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
+		});
+	}
+
+	#[test]
+	fn line_new_update_ref() {
+		assert_eq!(Line::new_update_ref("refs/heads/foo"), Line {
+			action: Action::UpdateRef,
+			hash: String::from(""),
+			content: String::from("refs/heads/foo"),
+			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		});
 	}
 
@@ -352,6 +702,9 @@ mod tests {
 			hash: String::from(""),
 			content: String::from("label"),
 			mutated: false,
+			option: None,
+			comments: vec![],
+			message: None,
 		});
 	}
 
@@ -368,10 +721,34 @@ mod tests {
 	#[case::label_line_only("label", "Invalid line: label")]
 	#[case::reset_line_only("reset", "Invalid line: reset")]
 	#[case::merge_line_only("merge", "Invalid line: merge")]
+	#[case::update_ref_line_only("update-ref", "Invalid line: update-ref")]
+	#[case::merge_option_only("merge -C", "Invalid line: merge -C")]
+	#[case::merge_commit_only("merge -C aaa", "Invalid line: merge -C aaa")]
+	#[case::merge_message_only("merge -C aaa # comment", "Invalid line: merge -C aaa # comment")]
+	#[case::fixup_option_only("fixup -C", "Invalid line: fixup -C")]
 	fn new_err(#[case] line: &str, #[case] expected_err: &str) {
 		assert_eq!(Line::new(line).unwrap_err().to_string(), expected_err);
 	}
 
+	#[rstest]
+	#[case::option_only("-C", "Missing commit for merge option: -C")]
+	#[case::missing_label("-c aaa", "Missing label for merge")]
+	#[case::empty("", "Missing label for merge")]
+	#[case::invalid_option("-x aaa ref", "Invalid merge label: -x")]
+	#[case::invalid_label("ref #", "Invalid merge label: #")]
+	fn validate_merge_err(#[case] arguments: &str, #[case] expected_err: &str) {
+		assert_eq!(Line::validate_merge(arguments).unwrap_err().to_string(), expected_err);
+	}
+
+	#[rstest]
+	#[case::label("ref")]
+	#[case::octopus("ref1 ref2")]
+	#[case::commit("-C aaa ref")]
+	#[case::commit_and_message("-c aaa ref # Merge 'ref'")]
+	fn validate_merge(#[case] arguments: &str) {
+		assert!(Line::validate_merge(arguments).is_ok());
+	}
+
 	#[rstest]
 	#[case::drop(Action::Drop, Action::Fixup)]
 	#[case::edit(Action::Edit, Action::Fixup)]
@@ -393,6 +770,8 @@ mod tests {
 	#[case::merge_action(Action::Merge, Action::Fixup)]
 	#[case::exec(Action::Exec, Action::Fixup)]
 	#[case::noop(Action::Noop, Action::Fixup)]
+	#[case::update_ref(Action::UpdateRef, Action::Fixup)]
+	#[case::revert(Action::Revert, Action::Pick)]
 	fn set_action_static(#[case] from: Action, #[case] to: Action) {
 		let mut line = Line::new(format!("{} comment", from.as_string()).as_str()).unwrap();
 		line.set_action(to);
@@ -400,6 +779,32 @@ mod tests {
 		assert!(!line.mutated);
 	}
 
+	#[test]
+	fn set_action_removes_option() {
+		let mut line = Line::new("fixup -C aaa comment").unwrap();
+		line.set_action(Action::Pick);
+		assert_eq!(line.get_option(), None);
+	}
+
+	#[rstest]
+	#[case::add_option("fixup aaa comment", Some("-C"), Some("-C"), true)]
+	#[case::change_option("fixup -C aaa comment", Some("-c"), Some("-c"), true)]
+	#[case::remove_option("fixup -c aaa comment", None, None, true)]
+	#[case::unchanged_option("fixup -c aaa comment", Some("-c"), Some("-c"), false)]
+	#[case::unchanged_no_option("fixup aaa comment", None, None, false)]
+	#[case::non_fixup("pick aaa comment", Some("-C"), None, false)]
+	fn set_option(
+		#[case] line: &str,
+		#[case] option: Option<&str>,
+		#[case] expected: Option<&str>,
+		#[case] mutated: bool,
+	) {
+		let mut line = Line::new(line).unwrap();
+		line.set_option(option);
+		assert_eq!(line.get_option(), expected);
+		assert_eq!(line.mutated, mutated);
+	}
+
 	#[test]
 	fn set_to_new_action_with_changed_action() {
 		let mut line = Line::new("pick aaa comment").unwrap();
@@ -428,12 +833,42 @@ mod tests {
 	#[case::label("label ref", "new")]
 	#[case::reset("reset ref", "new")]
 	#[case::merge("merge command", "new")]
+	#[case::update_ref("update-ref refs/heads/foo", "new")]
 	fn edit_content(#[case] line: &str, #[case] expected: &str) {
 		let mut line = Line::new(line).unwrap();
 		line.edit_content("new");
 		assert_eq!(line.get_content(), expected);
 	}
 
+	#[rstest]
+	#[case::label("-C aaa ref # comment", "new", "merge new")]
+	#[case::add_commit("ref", "-c bbb new # comment", "merge -c bbb new # comment")]
+	#[case::invalid("-C aaa ref # comment", "-C", "merge -C aaa ref # comment")]
+	fn edit_content_merge(#[case] arguments: &str, #[case] content: &str, #[case] expected: &str) {
+		let mut line = Line::new_merge(arguments);
+		line.edit_content(content);
+		assert_eq!(line.to_text(), expected);
+	}
+
+	#[rstest]
+	#[case::exec("exec git commit --amend 'foo'", "git commit --amend 'foo'")]
+	#[case::label("label ref", "ref")]
+	#[case::merge("merge ref", "ref")]
+	#[case::merge_with_commit("merge -C aaa ref # Merge 'ref'", "-C aaa ref # Merge 'ref'")]
+	fn get_edit_content(#[case] line: &str, #[case] expected: &str) {
+		assert_eq!(Line::new(line).unwrap().get_edit_content(), expected);
+	}
+
+	#[rstest]
+	#[case::merge("merge ref", &["ref"], None)]
+	#[case::merge_octopus("merge -C aaa ref1 ref2 # Merge", &["ref1", "ref2"], Some("Merge"))]
+	#[case::pick("pick aaa comment", &[], None)]
+	fn get_labels_and_message(#[case] line: &str, #[case] labels: &[&str], #[case] message: Option<&str>) {
+		let line = Line::new(line).unwrap();
+		assert_eq!(line.get_labels(), labels);
+		assert_eq!(line.get_message(), message);
+	}
+
 	#[rstest]
 	#[case::break_action("break", "")]
 	#[case::drop("drop aaa comment", "comment")]
@@ -485,6 +920,8 @@ mod tests {
 	#[case::label("label ref", false)]
 	#[case::reset("reset ref", false)]
 	#[case::merge("merge command", false)]
+	#[case::merge_with_commit("merge -C aaa ref", true)]
+	#[case::update_ref("update-ref refs/heads/foo", false)]
 	fn has_reference(#[case] line: &str, #[case] expected: bool) {
 		assert_eq!(Line::new(line).unwrap().has_reference(), expected);
 	}
@@ -502,6 +939,8 @@ mod tests {
 	#[case::squash(Action::Label, true)]
 	#[case::squash(Action::Reset, true)]
 	#[case::squash(Action::Merge, true)]
+	#[case::update_ref(Action::UpdateRef, true)]
+	#[case::revert(Action::Revert, false)]
 	fn is_editable(#[case] from: Action, #[case] editable: bool) {
 		let line = Line::new(format!("{} aaa bbb", from.as_string()).as_str()).unwrap();
 		assert_eq!(line.is_editable(), editable);
@@ -513,9 +952,17 @@ mod tests {
 	#[case::edit("edit aaa comment")]
 	#[case::exec("exec git commit --amend 'foo'")]
 	#[case::fixup("fixup aaa comment")]
+	#[case::fixup_with_message_option("fixup -C aaa comment")]
+	#[case::fixup_with_edit_message_option("fixup -c aaa comment")]
 	#[case::pick("pick aaa comment")]
 	#[case::reword("reword aaa comment")]
 	#[case::squash("squash aaa comment")]
+	#[case::revert("revert aaa comment")]
+	#[case::merge("merge ref")]
+	#[case::merge_octopus("merge ref1 ref2")]
+	#[case::merge_with_commit("merge -C aaa ref # Merge 'ref'")]
+	#[case::merge_with_commit_edit_message("merge -c aaa ref")]
+	#[case::update_ref("update-ref refs/heads/foo")]
 	fn to_text(#[case] line: &str) {
 		assert_eq!(Line::new(line).unwrap().to_text(), line);
 	}
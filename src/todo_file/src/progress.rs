@@ -0,0 +1,173 @@
+use std::{fs::read_to_string, path::Path};
+
+use super::line::Line;
+
+const TODO_FILE_NAME: &str = "git-rebase-todo";
+const HEAD_BRANCH_PREFIX: &str = "refs/heads/";
+
+fn read_state_file(state_directory: &Path, name: &str) -> Option<String> {
+	read_to_string(state_directory.join(name))
+		.ok()
+		.map(|contents| String::from(contents.trim()))
+		.filter(|contents| !contents.is_empty())
+}
+
+/// Describes the progress of a rebase that has already started, as recorded by git in the rebase state directory.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RebaseProgress {
+	done_lines: Vec<Line>,
+	head_name: Option<String>,
+	msgnum: Option<usize>,
+	onto: Option<String>,
+	onto_name: Option<String>,
+}
+
+impl RebaseProgress {
+	/// Load the progress from the rebase state directory that contains the todo file. Only a todo file named
+	/// `git-rebase-todo` is considered part of a rebase, and `None` is returned when no commands have been applied.
+	pub(crate) fn load(todo_file_path: &Path, comment_char: &str) -> Option<Self> {
+		if todo_file_path.file_name().map_or(true, |name| name != TODO_FILE_NAME) {
+			return None;
+		}
+		let state_directory = todo_file_path.parent()?;
+
+		let done_lines = read_state_file(state_directory, "done").map_or_else(Vec::new, |contents| {
+			contents
+				.lines()
+				.filter(|l| !l.is_empty() && !l.starts_with(comment_char))
+				.filter_map(|l| Line::new(l).ok())
+				.collect()
+		});
+		let msgnum = read_state_file(state_directory, "msgnum").and_then(|msgnum| msgnum.parse::<usize>().ok());
+
+		if done_lines.is_empty() && msgnum.map_or(true, |msgnum| msgnum == 0) {
+			return None;
+		}
+
+		Some(Self {
+			done_lines,
+			head_name: read_state_file(state_directory, "head-name"),
+			msgnum,
+			onto: read_state_file(state_directory, "onto"),
+			onto_name: None,
+		})
+	}
+
+	/// Get the lines that have already been applied.
+	#[must_use]
+	pub fn get_done_lines(&self) -> &[Line] {
+		&self.done_lines
+	}
+
+	/// Get the number of commands that have already been applied.
+	#[must_use]
+	pub fn get_applied_count(&self) -> usize {
+		self.msgnum.unwrap_or(self.done_lines.len())
+	}
+
+	/// Get the name of the branch being rebased, if the rebase is not of a detached head.
+	#[must_use]
+	pub fn get_branch_name(&self) -> Option<&str> {
+		self.head_name
+			.as_deref()
+			.and_then(|head_name| head_name.strip_prefix(HEAD_BRANCH_PREFIX))
+	}
+
+	/// Get the commit that the rebase is applied onto.
+	#[must_use]
+	pub fn get_onto(&self) -> Option<&str> {
+		self.onto.as_deref()
+	}
+
+	/// Set the name of the branch that points to the commit the rebase is applied onto.
+	pub(crate) fn set_onto_name(&mut self, name: String) {
+		self.onto_name = Some(name);
+	}
+
+	/// Get the name of the branch that points to the commit the rebase is applied onto, if one is known.
+	#[must_use]
+	pub fn get_onto_name(&self) -> Option<&str> {
+		self.onto_name.as_deref()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::fs::write;
+
+	use tempfile::{tempdir, TempDir};
+
+	use super::*;
+
+	fn create_state_directory(files: &[(&str, &str)]) -> TempDir {
+		let state_directory = tempdir().unwrap();
+		write(state_directory.path().join(TODO_FILE_NAME), "pick ccc c3\n").unwrap();
+		for &(name, contents) in files {
+			write(state_directory.path().join(name), contents).unwrap();
+		}
+		state_directory
+	}
+
+	fn load(state_directory: &TempDir) -> Option<RebaseProgress> {
+		RebaseProgress::load(&state_directory.path().join(TODO_FILE_NAME), "#")
+	}
+
+	#[test]
+	fn load_full() {
+		let state_directory = create_state_directory(&[
+			("done", "pick aaa c1\n# comment\n\nexec make\n"),
+			("msgnum", "2\n"),
+			("head-name", "refs/heads/feature\n"),
+			("onto", "1234567890abcdef\n"),
+		]);
+		let progress = load(&state_directory).unwrap();
+		assert_eq!(
+			progress
+				.get_done_lines()
+				.iter()
+				.map(Line::to_text)
+				.collect::<Vec<String>>(),
+			vec!["pick aaa c1", "exec make"]
+		);
+		assert_eq!(progress.get_applied_count(), 2);
+		assert_eq!(progress.get_branch_name(), Some("feature"));
+		assert_eq!(progress.get_onto(), Some("1234567890abcdef"));
+		assert_eq!(progress.get_onto_name(), None);
+	}
+
+	#[test]
+	fn set_onto_name() {
+		let state_directory = create_state_directory(&[("done", "pick aaa c1\n"), ("onto", "1234567890abcdef\n")]);
+		let mut progress = load(&state_directory).unwrap();
+		progress.set_onto_name(String::from("main"));
+		assert_eq!(progress.get_onto_name(), Some("main"));
+	}
+
+	#[test]
+	fn load_without_msgnum() {
+		let state_directory = create_state_directory(&[("done", "pick aaa c1\npick bbb c2\n")]);
+		let progress = load(&state_directory).unwrap();
+		assert_eq!(progress.get_applied_count(), 2);
+		assert_eq!(progress.get_branch_name(), None);
+		assert_eq!(progress.get_onto(), None);
+	}
+
+	#[test]
+	fn load_detached_head() {
+		let state_directory = create_state_directory(&[("done", "pick aaa c1\n"), ("head-name", "detached HEAD\n")]);
+		assert_eq!(load(&state_directory).unwrap().get_branch_name(), None);
+	}
+
+	#[test]
+	fn load_not_started() {
+		let state_directory = create_state_directory(&[("head-name", "refs/heads/feature\n"), ("onto", "abc\n")]);
+		assert!(load(&state_directory).is_none());
+	}
+
+	#[test]
+	fn load_other_file_name() {
+		let state_directory = create_state_directory(&[("done", "pick aaa c1\n")]);
+		let todo_file_path = state_directory.path().join("other");
+		assert!(RebaseProgress::load(&todo_file_path, "#").is_none());
+	}
+}
@@ -1,5 +1,23 @@
+use std::{
+	cmp::{max, min},
+	mem,
+};
+
 use super::line::Line;
 
+// comments keep their position in the file when lines are moved, so the comments are taken from the lines before they
+// are moved, and given to the lines that end up in the same positions
+pub(crate) fn move_lines<F: FnOnce(&mut [Line])>(lines: &mut [Line], move_fn: F) {
+	let comments = lines
+		.iter_mut()
+		.map(|line| mem::take(line.get_comments_mut()))
+		.collect::<Vec<Vec<String>>>();
+	move_fn(lines);
+	for (line, line_comments) in lines.iter_mut().zip(comments) {
+		line.set_comments(line_comments);
+	}
+}
+
 pub(crate) fn swap_range_up(lines: &mut Vec<Line>, start_index: usize, end_index: usize) {
 	let range = if end_index <= start_index {
 		(end_index - 1)..start_index
@@ -7,9 +25,9 @@ pub(crate) fn swap_range_up(lines: &mut Vec<Line>, start_index: usize, end_index
 	else {
 		(start_index - 1)..end_index
 	};
-	for index in range {
-		lines.swap(index, index + 1);
-	}
+	move_lines(&mut lines[range.start..=range.end], |moved_lines| {
+		moved_lines.rotate_left(1);
+	});
 }
 
 pub(crate) fn swap_range_down(lines: &mut Vec<Line>, start_index: usize, end_index: usize) {
@@ -20,28 +38,47 @@ pub(crate) fn swap_range_down(lines: &mut Vec<Line>, start_index: usize, end_ind
 		start_index..=end_index
 	};
 
-	for index in range.rev() {
-		lines.swap(index, index + 1);
-	}
+	move_lines(&mut lines[*range.start()..=(*range.end() + 1)], |moved_lines| {
+		moved_lines.rotate_right(1);
+	});
 }
 
-pub(crate) fn remove_range(lines: &mut Vec<Line>, start_index: usize, end_index: usize) -> Vec<Line> {
+// the comments of removed lines keep their position in the file, by moving them to the line that follows the removed
+// lines, or to the trailing comments when no line follows them
+pub(crate) fn remove_range(
+	lines: &mut Vec<Line>,
+	trailing_comments: &mut Vec<String>,
+	start_index: usize,
+	end_index: usize,
+) -> Vec<Line> {
 	let mut removed_lines = vec![];
-	if end_index <= start_index {
-		for _ in end_index..=start_index {
-			removed_lines.push(lines.remove(end_index));
-		}
+	let index = min(start_index, end_index);
+	for _ in index..=max(start_index, end_index) {
+		removed_lines.push(lines.remove(index));
+	}
+
+	let mut comments = removed_lines
+		.iter()
+		.flat_map(|line| line.get_comments().iter().cloned())
+		.collect::<Vec<String>>();
+	if !comments.is_empty() {
+		let following_comments = lines.get_mut(index).map_or(trailing_comments, Line::get_comments_mut);
+		comments.append(following_comments);
+		*following_comments = comments;
 	}
-	else {
-		for _ in start_index..=end_index {
-			removed_lines.push(lines.remove(start_index));
-		}
-	};
 
 	removed_lines
 }
 
-pub(crate) fn add_range(lines: &mut Vec<Line>, new_lines: &[Line], start_index: usize, end_index: usize) {
+// the inverse of `remove_range`, the comments of the added lines are taken back from the line that follows the added
+// lines, or from the trailing comments when no line follows them
+pub(crate) fn add_range(
+	lines: &mut Vec<Line>,
+	trailing_comments: &mut Vec<String>,
+	new_lines: &[Line],
+	start_index: usize,
+	end_index: usize,
+) {
 	let range = if end_index <= start_index {
 		end_index..=start_index
 	}
@@ -49,7 +86,16 @@ pub(crate) fn add_range(lines: &mut Vec<Line>, new_lines: &[Line], start_index:
 		start_index..=end_index
 	};
 
+	let following_index = range.end() + 1;
 	for (add_index, index) in range.enumerate() {
 		lines.insert(index, new_lines[add_index].clone());
 	}
+
+	let comments_length = new_lines.iter().map(|line| line.get_comments().len()).sum::<usize>();
+	if comments_length > 0 {
+		let following_comments = lines
+			.get_mut(following_index)
+			.map_or(trailing_comments, Line::get_comments_mut);
+		*following_comments = following_comments.split_off(min(comments_length, following_comments.len()));
+	}
 }
@@ -197,7 +197,7 @@ impl<C: Tui> View<C> {
 		self.display.ensure_at_line_start()?;
 		if render_slice.show_title() {
 			self.display.ensure_at_line_start()?;
-			self.draw_title(render_slice.show_help())?;
+			self.draw_title(render_slice.show_help(), render_slice.get_title_info())?;
 			self.display.next_line()?;
 		}
 
@@ -267,19 +267,23 @@ impl<C: Tui> View<C> {
 		Ok(())
 	}
 
-	fn draw_title(&mut self, show_help: bool) -> Result<()> {
+	fn draw_title(&mut self, show_help: bool, info: Option<&str>) -> Result<()> {
 		self.display.color(DisplayColor::Normal, false)?;
 		self.display.set_style(false, true, false)?;
 		let window_width = self.display.get_window_size().width();
 
 		let title_help_indicator_total_length = TITLE_HELP_INDICATOR_LABEL.len() + self.help_indicator_key.len();
+		let (title, short_title) = match info {
+			Some(info) => (format!("{} ({})", TITLE, info), format!("{} ({})", TITLE_SHORT, info)),
+			None => (String::from(TITLE), String::from(TITLE_SHORT)),
+		};
 
-		if window_width >= TITLE.len() {
-			self.display.draw_str(TITLE)?;
+		if window_width >= title.len() {
+			self.display.draw_str(title.as_str())?;
 			// only draw help if there is room
-			if window_width > TITLE.len() + title_help_indicator_total_length {
-				if (window_width - TITLE.len() - title_help_indicator_total_length) > 0 {
-					let padding = " ".repeat(window_width - TITLE.len() - title_help_indicator_total_length);
+			if window_width > title.len() + title_help_indicator_total_length {
+				if (window_width - title.len() - title_help_indicator_total_length) > 0 {
+					let padding = " ".repeat(window_width - title.len() - title_help_indicator_total_length);
 					self.display.draw_str(padding.as_str())?;
 				}
 				if show_help {
@@ -291,15 +295,22 @@ impl<C: Tui> View<C> {
 					self.display.draw_str(padding.as_str())?;
 				}
 			}
-			else if (window_width - TITLE.len()) > 0 {
-				let padding = " ".repeat(window_width - TITLE.len());
+			else if (window_width - title.len()) > 0 {
+				let padding = " ".repeat(window_width - title.len());
 				self.display.draw_str(padding.as_str())?;
 			}
 		}
 		else {
-			self.display.draw_str(TITLE_SHORT)?;
-			if (window_width - TITLE_SHORT.len()) > 0 {
-				let padding = " ".repeat(window_width - TITLE_SHORT.len());
+			// drop the information before dropping the short title
+			let short_title = if window_width >= short_title.len() {
+				short_title.as_str()
+			}
+			else {
+				TITLE_SHORT
+			};
+			self.display.draw_str(short_title)?;
+			if (window_width - short_title.len()) > 0 {
+				let padding = " ".repeat(window_width - short_title.len());
 				self.display.draw_str(padding.as_str())?;
 			}
 		}
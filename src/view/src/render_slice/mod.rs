@@ -26,6 +26,7 @@ pub struct RenderSlice {
 	should_show_scrollbar: bool,
 	show_help: bool,
 	show_title: bool,
+	title_info: Option<String>,
 	version: u32,
 	view_data_name: String,
 	view_data_version: u32,
@@ -47,6 +48,7 @@ impl RenderSlice {
 			should_show_scrollbar: false,
 			show_help: false,
 			show_title: false,
+			title_info: None,
 			version: 0,
 			view_data_name: String::from(""),
 			view_data_version: 0,
@@ -78,6 +80,10 @@ impl RenderSlice {
 		self.actions.push_back(RenderAction::ScrollRight);
 	}
 
+	pub fn record_scroll_to(&mut self, row: usize) {
+		self.actions.push_back(RenderAction::ScrollTo(row));
+	}
+
 	pub fn record_resize(&mut self, width: usize, height: usize) {
 		self.actions.push_back(RenderAction::Resize(width, height));
 	}
@@ -90,6 +96,9 @@ impl RenderSlice {
 		}
 		self.set_active_scroll_position(view_data);
 		let has_actions = !self.actions.is_empty();
+		// the visible line and column are only applied when the view data changes or the view is resized, so that
+		// scrolling away from them is not undone
+		let mut ensure_visible = cache_expired;
 		while let Some(action) = self.actions.pop_front() {
 			match action {
 				RenderAction::ScrollDown => self.scroll_position.scroll_down(),
@@ -98,11 +107,19 @@ impl RenderSlice {
 				RenderAction::ScrollLeft => self.scroll_position.scroll_left(),
 				RenderAction::PageUp => self.scroll_position.page_up(),
 				RenderAction::PageDown => self.scroll_position.page_down(),
-				RenderAction::Resize(width, height) => self.set_size(width, height),
+				RenderAction::ScrollTo(row) => {
+					// the row is of the lines of the view data, which can be longer than the lines of the last render
+					self.scroll_position.set_lines_length(view_data.get_lines().len());
+					self.scroll_position.scroll_to(row);
+				},
+				RenderAction::Resize(width, height) => {
+					self.set_size(width, height);
+					ensure_visible = true;
+				},
 			}
 		}
 		if has_actions || cache_expired {
-			self.rebuild(view_data);
+			self.rebuild(view_data, ensure_visible);
 		}
 	}
 
@@ -169,6 +186,10 @@ impl RenderSlice {
 		self.show_help
 	}
 
+	pub(crate) fn get_title_info(&self) -> Option<&str> {
+		self.title_info.as_deref()
+	}
+
 	pub(crate) const fn get_leading_lines_count(&self) -> usize {
 		self.lines_leading_count
 	}
@@ -250,7 +271,7 @@ impl RenderSlice {
 	}
 
 	#[allow(clippy::cognitive_complexity)]
-	fn rebuild(&mut self, view_data: &ViewData) {
+	fn rebuild(&mut self, view_data: &ViewData, ensure_visible: bool) {
 		let leading_lines_length = view_data.get_leading_lines().len();
 		let trailing_lines_length = view_data.get_trailing_lines().len();
 		let lines_length = view_data.get_lines().len();
@@ -260,11 +281,12 @@ impl RenderSlice {
 		self.view_data_version = view_data.get_version();
 		self.show_title = view_data.show_title();
 		self.show_help = view_data.show_help();
+		self.title_info = view_data.get_title_info().map(String::from);
 		self.should_show_scrollbar =
 			self.padding_height < self.height && lines_length > (self.height - self.padding_height);
 
 		self.scroll_position.set_lines_length(lines_length);
-		if let Some(row) = view_data.get_visible_row().as_ref() {
+		if let Some(row) = view_data.get_visible_row().as_ref().filter(|_| ensure_visible) {
 			self.scroll_position.ensure_line_visible(*row);
 		}
 
@@ -356,7 +378,7 @@ impl RenderSlice {
 				.max(max_leading_line_length)
 				.max(max_trailing_line_length),
 		);
-		if let Some(column) = view_data.get_visible_column().as_ref() {
+		if let Some(column) = view_data.get_visible_column().as_ref().filter(|_| ensure_visible) {
 			self.scroll_position.ensure_column_visible(*column);
 		}
 
@@ -6,5 +6,6 @@ pub(crate) enum RenderAction {
 	ScrollLeft,
 	PageUp,
 	PageDown,
+	ScrollTo(usize),
 	Resize(usize, usize),
 }
@@ -174,6 +174,49 @@ fn page_down_action() {
 	]);
 }
 
+#[test]
+fn scroll_to_action() {
+	let view_data = create_view_data(2, 10, 2);
+	let mut render_slice = create_render_slice(100, 8, &view_data);
+	render_slice.record_scroll_to(3);
+	render_slice.sync_view_data(&view_data);
+	assert_rendered(&render_slice, &[
+		"{LEADING}",
+		"{Normal}L(1)",
+		"{Normal}L(2)",
+		"{BODY}",
+		"{Normal}B(4)",
+		"{Normal}B(5)",
+		"{Normal}B(6)",
+		"{Normal}B(7)",
+		"{TRAILING}",
+		"{Normal}T(1)",
+		"{Normal}T(2)",
+	]);
+}
+
+#[test]
+fn scroll_to_action_with_new_view_data() {
+	let view_data = create_view_data(2, 2, 2);
+	let mut render_slice = create_render_slice(100, 8, &view_data);
+	let view_data = create_view_data(2, 10, 2);
+	render_slice.record_scroll_to(20);
+	render_slice.sync_view_data(&view_data);
+	assert_rendered(&render_slice, &[
+		"{LEADING}",
+		"{Normal}L(1)",
+		"{Normal}L(2)",
+		"{BODY}",
+		"{Normal}B(7)",
+		"{Normal}B(8)",
+		"{Normal}B(9)",
+		"{Normal}B(10)",
+		"{TRAILING}",
+		"{Normal}T(1)",
+		"{Normal}T(2)",
+	]);
+}
+
 #[test]
 fn scroll_left_action() {
 	let view_data = ViewData::new(|updater| updater.push_line(ViewLine::from("1234567890")));
@@ -485,6 +528,52 @@ fn ensure_row_visible() {
 	]);
 }
 
+#[test]
+fn ensure_row_visible_after_scroll_action() {
+	let mut view_data = create_view_data(2, 10, 2);
+	view_data.update_view_data(|updater| updater.ensure_line_visible(4));
+	let mut render_slice = create_render_slice(100, 8, &view_data);
+	for _ in 0..3 {
+		render_slice.record_scroll_down();
+	}
+	render_slice.sync_view_data(&view_data);
+	assert_rendered(&render_slice, &[
+		"{LEADING}",
+		"{Normal}L(1)",
+		"{Normal}L(2)",
+		"{BODY}",
+		"{Normal}B(5)",
+		"{Normal}B(6)",
+		"{Normal}B(7)",
+		"{Normal}B(8)",
+		"{TRAILING}",
+		"{Normal}T(1)",
+		"{Normal}T(2)",
+	]);
+}
+
+#[test]
+fn ensure_row_visible_after_resize_action() {
+	let mut view_data = create_view_data(2, 10, 2);
+	view_data.update_view_data(|updater| updater.ensure_line_visible(4));
+	let mut render_slice = create_render_slice(100, 8, &view_data);
+	render_slice.scroll_position.reset();
+	render_slice.record_resize(100, 7);
+	render_slice.sync_view_data(&view_data);
+	assert_rendered(&render_slice, &[
+		"{LEADING}",
+		"{Normal}L(1)",
+		"{Normal}L(2)",
+		"{BODY}",
+		"{Normal}B(3)",
+		"{Normal}B(4)",
+		"{Normal}B(5)",
+		"{TRAILING}",
+		"{Normal}T(1)",
+		"{Normal}T(2)",
+	]);
+}
+
 #[test]
 fn ensure_column_visible() {
 	let view_data = ViewData::new(|updater| {
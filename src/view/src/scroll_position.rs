@@ -53,6 +53,15 @@ impl ScrollPosition {
 		self.update_top(self.view_height / 2, ScrollDirection::Down);
 	}
 
+	pub(crate) fn scroll_to(&mut self, line_index: usize) {
+		self.top_value = if self.view_height >= self.lines_length {
+			0
+		}
+		else {
+			line_index.min(self.lines_length - self.view_height)
+		};
+	}
+
 	pub(crate) fn scroll_left(&mut self) {
 		self.update_left(1, ScrollDirection::Left);
 	}
@@ -441,6 +450,33 @@ mod tests {
 		assert_eq!(scroll_position.get_top_position(), 5);
 	}
 
+	#[test]
+	fn scroll_position_scroll_to() {
+		let mut scroll_position = ScrollPosition::new();
+		scroll_position.lines_length = 100;
+		scroll_position.view_height = 10;
+		scroll_position.scroll_to(42);
+		assert_eq!(scroll_position.get_top_position(), 42);
+	}
+
+	#[test]
+	fn scroll_position_scroll_to_past_bottom_of_list() {
+		let mut scroll_position = ScrollPosition::new();
+		scroll_position.lines_length = 100;
+		scroll_position.view_height = 10;
+		scroll_position.scroll_to(95);
+		assert_eq!(scroll_position.get_top_position(), 90);
+	}
+
+	#[test]
+	fn scroll_position_scroll_to_when_view_size_greater_than_list() {
+		let mut scroll_position = ScrollPosition::new();
+		scroll_position.lines_length = 10;
+		scroll_position.view_height = 11;
+		scroll_position.scroll_to(5);
+		assert_eq!(scroll_position.get_top_position(), 0);
+	}
+
 	#[test]
 	fn scroll_position_page_down_from_bottom_of_list() {
 		let mut scroll_position = ScrollPosition::new();
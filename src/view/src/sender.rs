@@ -141,6 +141,16 @@ impl Sender {
 			.record_page_down();
 	}
 
+	/// Queue a scroll action that shows the row at the top of the view.
+	#[inline]
+	pub fn scroll_to(&self, row: usize) {
+		self.render_slice
+			.lock()
+			.expect("Unable to lock render slice")
+			.borrow_mut()
+			.record_scroll_to(row);
+	}
+
 	/// Queue a resize action.
 	#[inline]
 	pub fn resize(&self, width: u16, height: u16) {
@@ -248,6 +258,14 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn scroll_to() {
+		with_view_sender(|context| {
+			context.sender.scroll_to(5);
+			context.assert_render_action(&["ScrollTo(5)"]);
+		});
+	}
+
 	#[test]
 	fn scroll_left() {
 		with_view_sender(|context| {
@@ -77,6 +77,52 @@ fn render_title_full_width_with_help_enabled_but_not_enough_length() {
 	);
 }
 
+#[test]
+fn render_title_full_width_with_info_and_help() {
+	let mut expected = vec!["Git Interactive Rebase Tool (2 of 5) Help: ?"];
+	expected.extend(vec!["~"; 9]);
+	assert_render(
+		44,
+		10,
+		&ViewData::new(|updater| {
+			updater.set_show_title(true);
+			updater.set_show_help(true);
+			updater.set_title_info(Some("2 of 5"));
+		}),
+		&expected,
+	);
+}
+
+#[test]
+fn render_title_short_title_with_info() {
+	let mut expected = vec!["Git Rebase (2 of 5)       "];
+	expected.extend(vec!["~"; 9]);
+	assert_render(
+		26,
+		10,
+		&ViewData::new(|updater| {
+			updater.set_show_title(true);
+			updater.set_title_info(Some("2 of 5"));
+		}),
+		&expected,
+	);
+}
+
+#[test]
+fn render_title_short_title_without_room_for_info() {
+	let mut expected = vec!["Git Rebase    "];
+	expected.extend(vec!["~"; 9]);
+	assert_render(
+		14,
+		10,
+		&ViewData::new(|updater| {
+			updater.set_show_title(true);
+			updater.set_title_info(Some("2 of 5"));
+		}),
+		&expected,
+	);
+}
+
 #[test]
 fn render_leading_lines() {
 	let mut expected = vec!["This is a leading line"];
@@ -39,11 +39,20 @@ fn render_style(color: DisplayColor, dimmed: bool, underline: bool, reversed: bo
 		DisplayColor::DiffRemoveColor => String::from("DiffRemoveColor"),
 		DisplayColor::DiffContextColor => String::from("DiffContextColor"),
 		DisplayColor::DiffWhitespaceColor => String::from("DiffWhitespaceColor"),
+		DisplayColor::DiffAddHighlightColor => String::from("DiffAddHighlightColor"),
+		DisplayColor::DiffRemoveHighlightColor => String::from("DiffRemoveHighlightColor"),
+		DisplayColor::DiffSyntaxCommentColor => String::from("DiffSyntaxCommentColor"),
+		DisplayColor::DiffSyntaxKeywordColor => String::from("DiffSyntaxKeywordColor"),
+		DisplayColor::DiffSyntaxNumberColor => String::from("DiffSyntaxNumberColor"),
+		DisplayColor::DiffSyntaxStringColor => String::from("DiffSyntaxStringColor"),
 		DisplayColor::IndicatorColor => String::from("IndicatorColor"),
 		DisplayColor::Normal => String::from("Normal"),
+		DisplayColor::WarningColor => String::from("WarningColor"),
 		DisplayColor::ActionLabel => String::from("ActionLabel"),
 		DisplayColor::ActionReset => String::from("ActionReset"),
 		DisplayColor::ActionMerge => String::from("ActionMerge"),
+		DisplayColor::ActionUpdateRef => String::from("ActionUpdateRef"),
+		DisplayColor::ActionRevert => String::from("ActionRevert"),
 	};
 
 	let mut style = vec![];
@@ -105,12 +114,14 @@ pub fn render_view_line(view_line: &ViewLine) -> String {
 fn render_view_data(view_data: &ViewData) -> Vec<String> {
 	let mut lines = vec![];
 	if view_data.show_title() {
-		if view_data.show_help() {
-			lines.push(String::from("{TITLE}{HELP}"));
+		let mut title = String::from("{TITLE}");
+		if let Some(info) = view_data.get_title_info() {
+			title.push_str(format!("({})", info).as_str());
 		}
-		else {
-			lines.push(String::from("{TITLE}"));
+		if view_data.show_help() {
+			title.push_str("{HELP}");
 		}
+		lines.push(title);
 	}
 
 	if view_data.is_empty() {
@@ -236,6 +247,7 @@ fn assert_view_sender_actions(view_sender: &ViewSender, expected_actions: &[Stri
 				RenderAction::ScrollLeft => String::from("ScrollLeft"),
 				RenderAction::PageUp => String::from("PageUp"),
 				RenderAction::PageDown => String::from("PageDown"),
+				RenderAction::ScrollTo(row) => format!("ScrollTo({})", row),
 				RenderAction::Resize(width, height) => format!("Resize({}, {})", width, height),
 			}
 		})
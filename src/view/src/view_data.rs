@@ -13,6 +13,7 @@ pub struct ViewData {
 	scroll_version: u32,
 	show_help: bool,
 	show_title: bool,
+	title_info: Option<String>,
 	version: u32,
 	visible_column: Option<usize>,
 	visible_row: Option<usize>,
@@ -32,6 +33,7 @@ impl ViewData {
 			scroll_version: 0,
 			show_help: false,
 			show_title: false,
+			title_info: None,
 			version: 0,
 			visible_column: None,
 			visible_row: None,
@@ -66,6 +68,8 @@ impl ViewData {
 		self.lines_leading.clear();
 		self.lines.clear();
 		self.lines_trailing.clear();
+		self.visible_column = None;
+		self.visible_row = None;
 	}
 
 	pub(crate) fn clear_body(&mut self) {
@@ -88,6 +92,10 @@ impl ViewData {
 		self.show_help = show;
 	}
 
+	pub(crate) fn set_title_info(&mut self, info: Option<&str>) {
+		self.title_info = info.map(String::from);
+	}
+
 	pub(crate) fn push_leading_line(&mut self, view_line: ViewLine) {
 		self.lines_leading.push(view_line);
 	}
@@ -116,6 +124,10 @@ impl ViewData {
 		self.show_help
 	}
 
+	pub(crate) fn get_title_info(&self) -> Option<&str> {
+		self.title_info.as_deref()
+	}
+
 	pub(crate) const fn get_leading_lines(&self) -> &Vec<ViewLine> {
 		&self.lines_leading
 	}
@@ -191,10 +203,14 @@ mod tests {
 		view_data.push_line(ViewLine::new_empty_line());
 		view_data.push_leading_line(ViewLine::new_empty_line());
 		view_data.push_trailing_line(ViewLine::new_empty_line());
+		view_data.ensure_line_visible(1);
+		view_data.ensure_column_visible(2);
 		view_data.clear();
 		assert!(view_data.get_leading_lines().is_empty());
 		assert!(view_data.get_lines().is_empty());
 		assert!(view_data.get_trailing_lines().is_empty());
+		assert!(view_data.get_visible_row().is_none());
+		assert!(view_data.get_visible_column().is_none());
 	}
 
 	#[test]
@@ -237,6 +253,13 @@ mod tests {
 		assert!(!view_data.show_help());
 	}
 
+	#[test]
+	fn set_title_info() {
+		let mut view_data = ViewData::new(|_| {});
+		view_data.set_title_info(Some("info"));
+		assert_eq!(view_data.get_title_info(), Some("info"));
+	}
+
 	#[test]
 	fn push_leading_line() {
 		let mut view_data = ViewData::new(|_| {});
@@ -57,6 +57,13 @@ impl<'v> ViewDataUpdater<'v> {
 		self.view_data.set_show_help(show);
 	}
 
+	/// Set the additional information shown after the title, or `None` to show only the title.
+	#[inline]
+	pub fn set_title_info(&mut self, info: Option<&str>) {
+		self.modified = true;
+		self.view_data.set_title_info(info);
+	}
+
 	/// Push a new leading line to the view data.
 	#[inline]
 	pub fn push_leading_line(&mut self, view_line: ViewLine) {
@@ -78,6 +85,13 @@ impl<'v> ViewDataUpdater<'v> {
 		self.view_data.push_trailing_line(view_line);
 	}
 
+	/// Get the number of body lines of the view data.
+	#[must_use]
+	#[inline]
+	pub fn get_lines_length(&self) -> usize {
+		self.view_data.get_lines().len()
+	}
+
 	/// Set the scroll position retain value of the view data.
 	#[inline]
 	pub fn set_retain_scroll_position(&mut self, value: bool) {
@@ -124,6 +138,17 @@ mod tests {
 		assert_eq!(view_data.get_leading_lines().len(), 1);
 	}
 
+	#[test]
+	fn get_lines_length() {
+		let mut view_data = ViewData::new(|_| {});
+		view_data.push_leading_line(ViewLine::new_empty_line());
+		view_data.push_line(ViewLine::new_empty_line());
+		view_data.push_line(ViewLine::new_empty_line());
+		let updater = ViewDataUpdater::new(&mut view_data);
+		assert_eq!(updater.get_lines_length(), 2);
+		assert!(!updater.is_modified());
+	}
+
 	#[test]
 	fn ensure_line_visible() {
 		let mut view_data = ViewData::new(|_| {});
@@ -160,6 +185,15 @@ mod tests {
 		assert!(view_data.show_help());
 	}
 
+	#[test]
+	fn set_title_info() {
+		let mut view_data = ViewData::new(|_| {});
+		let mut updater = ViewDataUpdater::new(&mut view_data);
+		updater.set_title_info(Some("info"));
+		assert!(updater.is_modified());
+		assert_eq!(view_data.get_title_info(), Some("info"));
+	}
+
 	#[test]
 	fn push_leading_line() {
 		let mut view_data = ViewData::new(|_| {});